@@ -39,6 +39,8 @@ impl BenchProvider {
             responses: Mutex::new(vec![ChatResponse {
                 text: Some(text.into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             }]),
         }
     }
@@ -53,10 +55,14 @@ impl BenchProvider {
                         name: "noop".into(),
                         arguments: "{}".into(),
                     }],
+                    reasoning: None,
+                    usage: None,
                 },
                 ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    reasoning: None,
+                    usage: None,
                 },
             ]),
         }
@@ -86,6 +92,8 @@ impl Provider for BenchProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -151,6 +159,8 @@ Let me know if you need more."#
                 .into(),
         ),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     };
 
     let multi_tool = ChatResponse {
@@ -167,6 +177,8 @@ Let me know if you need more."#
                 .into(),
         ),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     };
 
     c.bench_function("xml_parse_single_tool_call", |b| {
@@ -199,6 +211,8 @@ fn bench_native_parsing(c: &mut Criterion) {
                 arguments: r#"{"path": "src/main.rs"}"#.into(),
             },
         ],
+        reasoning: None,
+        usage: None,
     };
 
     c.bench_function("native_parse_tool_calls", |b| {
@@ -233,6 +233,41 @@ impl Agent {
     pub fn from_config(config: &Config) -> Result<Self> {
         let observer: Arc<dyn Observer> =
             Arc::from(observability::create_observer(&config.observability));
+
+        let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+
+        let model_name = config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4-20250514")
+            .to_string();
+
+        let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
+            provider_name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            &config.reliability,
+            &config.model_routes,
+            &model_name,
+            &providers::ProviderRuntimeOptions {
+                auth_profile_override: None,
+                zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+                secrets_encrypt: config.secrets.encrypt,
+                reasoning_enabled: config.runtime.reasoning_enabled,
+                cassette: config.runtime.provider_cassette.clone(),
+            },
+        )?;
+
+        Self::from_config_with(config, provider, observer)
+    }
+
+    /// Build an agent from config around an already-constructed provider and
+    /// observer (used by `zeroclaw eval` to meter and trace each task).
+    pub fn from_config_with(
+        config: &Config,
+        provider: Box<dyn Provider>,
+        observer: Arc<dyn Observer>,
+    ) -> Result<Self> {
         let runtime: Arc<dyn runtime::RuntimeAdapter> =
             Arc::from(runtime::create_runtime(&config.runtime)?);
         let security = Arc::new(SecurityPolicy::from_config(
@@ -274,23 +309,12 @@ impl Agent {
             config,
         );
 
-        let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
-
         let model_name = config
             .default_model
             .as_deref()
             .unwrap_or("anthropic/claude-sonnet-4-20250514")
             .to_string();
 
-        let provider: Box<dyn Provider> = providers::create_routed_provider(
-            provider_name,
-            config.api_key.as_deref(),
-            config.api_url.as_deref(),
-            &config.reliability,
-            &config.model_routes,
-            &model_name,
-        )?;
-
         let dispatcher_choice = config.agent.tool_dispatcher.as_str();
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
             "native" => Box::new(NativeToolDispatcher),
@@ -479,6 +503,8 @@ impl Agent {
                         } else {
                             None
                         },
+                        response_schema: None,
+                        reasoning: None,
                     },
                     &effective_model,
                     self.temperature,
@@ -518,6 +544,7 @@ impl Agent {
             self.history.push(ConversationMessage::AssistantToolCalls {
                 text: response.text.clone(),
                 tool_calls: response.tool_calls.clone(),
+                reasoning: response.reasoning.clone(),
             });
 
             let results = self.execute_tools(&calls).await;
@@ -650,6 +677,8 @@ mod tests {
                 return Ok(crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    reasoning: None,
+                    usage: None,
                 });
             }
             Ok(guard.remove(0))
@@ -687,6 +716,8 @@ mod tests {
             responses: Mutex::new(vec![crate::providers::ChatResponse {
                 text: Some("hello".into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             }]),
         });
 
@@ -725,10 +756,14 @@ mod tests {
                         name: "echo".into(),
                         arguments: "{}".into(),
                     }],
+                    reasoning: None,
+                    usage: None,
                 },
                 crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    reasoning: None,
+                    usage: None,
                 },
             ]),
         });
@@ -4,10 +4,11 @@ use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, Observer, ObserverEvent};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, Reasoning, ToolCall,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sessions::{self, SessionMessage, SessionStore};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -50,7 +51,7 @@ static SENSITIVE_KV_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 /// Scrub credentials from tool output to prevent accidental exfiltration.
 /// Replaces known credential patterns with a redacted placeholder while preserving
 /// a small prefix for context.
-fn scrub_credentials(input: &str) -> String {
+pub(crate) fn scrub_credentials(input: &str) -> String {
     SENSITIVE_KV_REGEX
         .replace_all(input, |caps: &regex::Captures| {
             let full_match = &caps[0];
@@ -914,7 +915,11 @@ fn parse_structured_tool_calls(tool_calls: &[ToolCall]) -> Vec<ParsedToolCall> {
 /// Build assistant history entry in JSON format for native tool-call APIs.
 /// `convert_messages` in the OpenRouter provider parses this JSON to reconstruct
 /// the proper `NativeMessage` with structured `tool_calls`.
-fn build_native_assistant_history(text: &str, tool_calls: &[ToolCall]) -> String {
+pub(crate) fn build_native_assistant_history(
+    text: &str,
+    tool_calls: &[ToolCall],
+    reasoning: Option<&Reasoning>,
+) -> String {
     let calls_json: Vec<serde_json::Value> = tool_calls
         .iter()
         .map(|tc| {
@@ -932,14 +937,22 @@ fn build_native_assistant_history(text: &str, tool_calls: &[ToolCall]) -> String
         serde_json::Value::String(text.trim().to_string())
     };
 
-    serde_json::json!({
+    let mut payload = serde_json::json!({
         "content": content,
         "tool_calls": calls_json,
-    })
-    .to_string()
+    });
+    // Providers that sign reasoning (Anthropic thinking blocks) reject tool
+    // follow-ups unless the original blocks are replayed verbatim.
+    if let Some(reasoning) = reasoning.filter(|r| !r.blocks.is_empty()) {
+        payload["reasoning"] = serde_json::json!(reasoning);
+    }
+    payload.to_string()
 }
 
-fn build_assistant_history_with_tool_calls(text: &str, tool_calls: &[ToolCall]) -> String {
+pub(crate) fn build_assistant_history_with_tool_calls(
+    text: &str,
+    tool_calls: &[ToolCall],
+) -> String {
     let mut parts = Vec::new();
 
     if !text.trim().is_empty() {
@@ -1026,6 +1039,11 @@ async fn execute_one_tool(
     let Some(tool) = find_tool(tools_registry, call_name) else {
         return Ok(format!("Unknown tool: {call_name}"));
     };
+    if let Some(role) = crate::security::rbac::current() {
+        if let Err(reason) = role.check_tool_call(call_name, &call_arguments) {
+            return Ok(format!("Error: {reason}"));
+        }
+    }
 
     observer.record_event(&ObserverEvent::ToolCallStart {
         tool: call_name.to_string(),
@@ -1129,7 +1147,7 @@ async fn execute_tools_sequential(
                 let decision = if channel_name == "cli" {
                     mgr.prompt_cli(&request)
                 } else {
-                    ApprovalResponse::No
+                    mgr.request_remote(&request, channel_name).await
                 };
 
                 mgr.record_decision(&call.name, &call.arguments, decision, channel_name);
@@ -1192,8 +1210,16 @@ pub(crate) async fn run_tool_call_loop(
         max_tool_iterations
     };
 
-    let tool_specs: Vec<crate::tools::ToolSpec> =
-        tools_registry.iter().map(|tool| tool.spec()).collect();
+    // An active gateway role only sees the tools it may call.
+    let role = crate::security::rbac::current();
+    let tool_specs: Vec<crate::tools::ToolSpec> = tools_registry
+        .iter()
+        .filter(|tool| {
+            role.as_ref()
+                .is_none_or(|role| role.permissions.allows_tool(tool.name()))
+        })
+        .map(|tool| tool.spec())
+        .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
 
     for _iteration in 0..max_iterations {
@@ -1239,6 +1265,8 @@ pub(crate) async fn run_tool_call_loop(
             ChatRequest {
                 messages: &prepared_messages.messages,
                 tools: request_tools,
+                response_schema: None,
+                reasoning: None,
             },
             model,
             temperature,
@@ -1263,6 +1291,13 @@ pub(crate) async fn run_tool_call_loop(
                         success: true,
                         error_message: None,
                     });
+                    if let Some(reasoning) = resp.reasoning.as_ref() {
+                        observer.record_event(&ObserverEvent::LlmReasoning {
+                            provider: provider_name.to_string(),
+                            model: model.to_string(),
+                            content: reasoning.text.clone(),
+                        });
+                    }
 
                     let response_text = resp.text_or_empty().to_string();
                     // First try native structured tool calls (OpenAI-format).
@@ -1285,7 +1320,11 @@ pub(crate) async fn run_tool_call_loop(
                     let assistant_history_content = if resp.tool_calls.is_empty() {
                         response_text.clone()
                     } else {
-                        build_native_assistant_history(&response_text, &resp.tool_calls)
+                        build_native_assistant_history(
+                            &response_text,
+                            &resp.tool_calls,
+                            resp.reasoning.as_ref(),
+                        )
                     };
 
                     let native_calls = resp.tool_calls;
@@ -1451,6 +1490,7 @@ pub async fn run(
     model_override: Option<String>,
     temperature: f64,
     peripheral_overrides: Vec<String>,
+    session_id: Option<String>,
 ) -> Result<String> {
     // ── Wire up agnostic subsystems ──────────────────────────────
     let base_observer = observability::create_observer(&config.observability);
@@ -1526,6 +1566,7 @@ pub async fn run(
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        cassette: config.runtime.provider_cassette.clone(),
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -1578,6 +1619,10 @@ pub async fn run(
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file by exact replacement, line range or unified diff. Use when: changing part of a file without rewriting it. Don't use when: creating a new file.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -1633,10 +1678,16 @@ pub async fn run(
         "schedule",
         "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
     ));
+    if config.send_message.enabled {
+        tool_descs.push((
+            "send_message",
+            "Send a message to a named contact or recipient on any configured channel. Use when: notifying people proactively (e.g. post to #ops on Slack, DM on Telegram). Don't use when: replying in the current conversation.",
+        ));
+    }
     if !config.agents.is_empty() {
         tool_descs.push((
             "delegate",
-            "Delegate a sub-task to a specialized agent. Use when: task needs different model/capability, or to parallelize work.",
+            "Delegate a sub-task to a specialized agent. Use when: task needs different model/capability, or to parallelize work (agents/tasks fan out; background=true returns an id for delegate_status/delegate_result/delegate_cancel).",
         ));
     }
     if config.peripherals.enabled && !config.peripherals.boards.is_empty() {
@@ -1699,6 +1750,21 @@ pub async fn run(
 
     let mut final_output = String::new();
 
+    // ── Session recording ────────────────────────────────────────
+    // An explicit session id is resumed and recorded; otherwise interactive
+    // runs record into a fresh session when `agent.persist_sessions` is on.
+    let session_store = SessionStore::new(&config.workspace_dir);
+    let mut session_id = session_id.or_else(|| {
+        (message.is_none() && config.agent.persist_sessions)
+            .then(|| sessions::new_session_id("cli"))
+    });
+    let prior_history = match &session_id {
+        Some(id) if session_store.exists(id) => {
+            sessions::to_history(&session_store.load(id)?, native_tools)
+        }
+        _ => Vec::new(),
+    };
+
     if let Some(msg) = message {
         // Auto-save user message to memory (skip short/trivial messages)
         if config.memory.auto_save && msg.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
@@ -1723,10 +1789,10 @@ pub async fn run(
             format!("{context}{msg}")
         };
 
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
+        let mut history = vec![ChatMessage::system(&system_prompt)];
+        history.extend(prior_history);
+        history.push(ChatMessage::user(&enriched));
+        let turn_start = history.len();
 
         let response = run_tool_call_loop(
             provider.as_ref(),
@@ -1744,7 +1810,14 @@ pub async fn run(
             None,
             None,
         )
-        .await?;
+        .await;
+        record_session_turn(
+            &session_store,
+            session_id.as_deref(),
+            &msg,
+            &history[turn_start..],
+        );
+        let response = response?;
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
@@ -1753,8 +1826,18 @@ pub async fn run(
         println!("Type /help for commands.\n");
         let cli = crate::channels::CliChannel::new();
 
+        if let Some(id) = &session_id {
+            let verb = if prior_history.is_empty() {
+                "Recording"
+            } else {
+                "Resuming"
+            };
+            println!("{verb} session {id}\n");
+        }
+
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
+        history.extend(prior_history);
 
         loop {
             print!("> ");
@@ -1802,6 +1885,9 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    if session_id.is_some() {
+                        session_id = Some(sessions::new_session_id("cli"));
+                    }
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -1846,8 +1932,9 @@ pub async fn run(
             };
 
             history.push(ChatMessage::user(&enriched));
+            let turn_start = history.len();
 
-            let response = match run_tool_call_loop(
+            let response = run_tool_call_loop(
                 provider.as_ref(),
                 &mut history,
                 &tools_registry,
@@ -1863,8 +1950,14 @@ pub async fn run(
                 None,
                 None,
             )
-            .await
-            {
+            .await;
+            record_session_turn(
+                &session_store,
+                session_id.as_deref(),
+                &user_input,
+                &history[turn_start..],
+            );
+            let response = match response {
                 Ok(resp) => resp,
                 Err(e) => {
                     eprintln!("\nError: {e}\n");
@@ -1913,6 +2006,24 @@ pub async fn run(
     Ok(final_output)
 }
 
+/// Append one user turn and the messages the agent loop added for it.
+/// The raw user input is recorded, not the memory-enriched prompt.
+fn record_session_turn(
+    store: &SessionStore,
+    session_id: Option<&str>,
+    user_input: &str,
+    turn: &[ChatMessage],
+) {
+    let Some(id) = session_id else {
+        return;
+    };
+    let mut messages = vec![SessionMessage::new("user", user_input)];
+    messages.extend(turn.iter().map(SessionMessage::from_history));
+    if let Err(e) = store.append(id, &messages) {
+        tracing::warn!("Failed to record session {id}: {e}");
+    }
+}
+
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
@@ -1967,6 +2078,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        cassette: config.runtime.provider_cassette.clone(),
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -1998,6 +2110,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ("shell", "Execute terminal commands."),
         ("file_read", "Read file contents."),
         ("file_write", "Write file contents."),
+        ("file_edit", "Edit part of an existing file."),
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
@@ -2152,6 +2265,7 @@ mod tests {
             ProviderCapabilities {
                 native_tool_calling: false,
                 vision: true,
+                structured_output: false,
             }
         }
 
@@ -2185,6 +2299,8 @@ mod tests {
             Ok(ChatResponse {
                 text: Some("vision-ok".to_string()),
                 tool_calls: Vec::new(),
+                reasoning: None,
+                usage: None,
             })
         }
     }
@@ -2200,6 +2316,8 @@ mod tests {
                 .map(|text| ChatResponse {
                     text: Some(text.to_string()),
                     tool_calls: Vec::new(),
+                    reasoning: None,
+                    usage: None,
                 })
                 .collect();
             Self {
@@ -3,13 +3,16 @@
 //! Provides a pre-execution hook that prompts the user before tool calls,
 //! with session-scoped "Always" allowlists and audit logging.
 
-use crate::config::AutonomyConfig;
+use crate::config::{AutonomyConfig, Config};
 use crate::security::AutonomyLevel;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, Write};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::oneshot;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -60,6 +63,8 @@ pub struct ApprovalManager {
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// How long non-CLI requests wait in the dashboard queue; `None` = deny.
+    remote_timeout: Option<Duration>,
 }
 
 impl ApprovalManager {
@@ -71,9 +76,28 @@ impl ApprovalManager {
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            remote_timeout: None,
         }
     }
 
+    /// Manager for non-CLI channels when `[gateway.admin]` routes approvals to
+    /// the dashboard; `None` keeps the existing no-prompt behavior.
+    pub fn for_dashboard(config: &Config) -> Option<Self> {
+        let admin = &config.gateway.admin;
+        (admin.enabled && admin.approvals).then(|| {
+            Self::from_config(&config.autonomy)
+                .with_remote_approvals(Duration::from_secs(admin.approval_timeout_secs))
+        })
+    }
+
+    /// Queue non-CLI approval requests for the admin dashboard, denying
+    /// them if no decision arrives within `timeout`.
+    #[must_use]
+    pub fn with_remote_approvals(mut self, timeout: Duration) -> Self {
+        self.remote_timeout = Some(timeout);
+        self
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -152,6 +176,89 @@ impl ApprovalManager {
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Ask the admin dashboard for a decision on a non-CLI channel.
+    ///
+    /// Returns `No` when remote approvals are off or the request times out.
+    pub async fn request_remote(
+        &self,
+        request: &ApprovalRequest,
+        channel: &str,
+    ) -> ApprovalResponse {
+        let Some(timeout) = self.remote_timeout else {
+            return ApprovalResponse::No;
+        };
+        let (id, reply) = enqueue_pending(request, channel);
+        match tokio::time::timeout(timeout, reply).await {
+            Ok(Ok(decision)) => decision,
+            _ => {
+                pending_queue().lock().remove(&id);
+                ApprovalResponse::No
+            }
+        }
+    }
+}
+
+// ── Remote (dashboard) approvals ─────────────────────────────────
+
+/// A tool call waiting for a decision in the admin dashboard.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub tool_name: String,
+    pub arguments_summary: String,
+    pub channel: String,
+    pub requested_at: String,
+}
+
+struct PendingEntry {
+    info: PendingApproval,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+static PENDING: OnceLock<Mutex<HashMap<String, PendingEntry>>> = OnceLock::new();
+
+fn pending_queue() -> &'static Mutex<HashMap<String, PendingEntry>> {
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn enqueue_pending(
+    request: &ApprovalRequest,
+    channel: &str,
+) -> (String, oneshot::Receiver<ApprovalResponse>) {
+    let id = uuid::Uuid::new_v4().to_string();
+    let (reply, receiver) = oneshot::channel();
+    let info = PendingApproval {
+        id: id.clone(),
+        tool_name: request.tool_name.clone(),
+        arguments_summary: summarize_args(&request.arguments),
+        channel: channel.to_string(),
+        requested_at: Utc::now().to_rfc3339(),
+    };
+    pending_queue()
+        .lock()
+        .insert(id.clone(), PendingEntry { info, reply });
+    (id, receiver)
+}
+
+/// Requests currently waiting for a decision, oldest first.
+pub fn pending_approvals() -> Vec<PendingApproval> {
+    let mut pending: Vec<PendingApproval> = pending_queue()
+        .lock()
+        .values()
+        .map(|entry| entry.info.clone())
+        .collect();
+    pending.sort_by(|a, b| a.requested_at.cmp(&b.requested_at));
+    pending
+}
+
+/// Deliver a decision to a waiting request. Returns `false` if `id` is not
+/// pending (already decided or timed out).
+pub fn resolve_pending(id: &str, decision: ApprovalResponse) -> bool {
+    let Some(entry) = pending_queue().lock().remove(id) else {
+        return false;
+    };
+    entry.reply.send(decision).is_ok()
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -423,4 +530,56 @@ mod tests {
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
     }
+
+    // ── remote approvals ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn remote_requests_wait_for_a_dashboard_decision() {
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_remote_approvals(Duration::from_secs(5));
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "echo remote-approval-test"}),
+        };
+
+        let decision = tokio::spawn(async move { mgr.request_remote(&request, "telegram").await });
+        let pending = loop {
+            let found = pending_approvals()
+                .into_iter()
+                .find(|p| p.arguments_summary.contains("remote-approval-test"));
+            if let Some(found) = found {
+                break found;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert_eq!(pending.tool_name, "shell");
+        assert_eq!(pending.channel, "telegram");
+
+        assert!(resolve_pending(&pending.id, ApprovalResponse::Yes));
+        assert_eq!(decision.await.unwrap(), ApprovalResponse::Yes);
+        assert!(!resolve_pending(&pending.id, ApprovalResponse::No));
+    }
+
+    #[tokio::test]
+    async fn remote_requests_are_denied_when_disabled_or_timed_out() {
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "echo remote-timeout-test"}),
+        };
+        let disabled = ApprovalManager::from_config(&supervised_config());
+        assert_eq!(
+            disabled.request_remote(&request, "slack").await,
+            ApprovalResponse::No
+        );
+
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_remote_approvals(Duration::from_millis(20));
+        assert_eq!(
+            mgr.request_remote(&request, "slack").await,
+            ApprovalResponse::No
+        );
+        assert!(!pending_approvals()
+            .iter()
+            .any(|p| p.arguments_summary.contains("remote-timeout-test")));
+    }
 }
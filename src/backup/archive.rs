@@ -0,0 +1,524 @@
+//! Building and unpacking backup archives.
+//!
+//! An archive is a gzipped tar with two top-level directories, `config/`
+//! (the directory holding `config.toml`) and `workspace/`, plus a
+//! `manifest.json` listing every file with its SHA-256. SQLite databases are
+//! copied with the online backup API, so a running daemon never leaves a
+//! half-written page in the archive.
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+pub const MANIFEST_NAME: &str = "manifest.json";
+const MANIFEST_FORMAT: u32 = 1;
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+/// Files SQLite rebuilds or discards; the online backup already has their data.
+const SQLITE_SIDECARS: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub created_at: String,
+    pub version: String,
+    pub host: String,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// The directories an archive covers, by archive prefix.
+pub fn roots(config_dir: &Path, workspace_dir: &Path) -> [(&'static str, PathBuf); 2] {
+    [
+        ("config", config_dir.to_path_buf()),
+        ("workspace", workspace_dir.to_path_buf()),
+    ]
+}
+
+fn is_sqlite(path: &Path) -> bool {
+    let mut magic = [0u8; 16];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| &magic == SQLITE_MAGIC)
+}
+
+fn is_sqlite_sidecar(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    SQLITE_SIDECARS.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Forward-slash archive path for `rel` under `prefix`.
+fn archive_path(prefix: &str, rel: &Path) -> String {
+    let mut path = prefix.to_string();
+    for component in rel.components() {
+        path.push('/');
+        path.push_str(&component.as_os_str().to_string_lossy());
+    }
+    path
+}
+
+/// Regular files under `root`, as `(absolute, relative)` pairs. Symlinks and
+/// anything under `skip` are left out.
+fn walk(root: &Path, skip: &[PathBuf]) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if skip.iter().any(|skipped| path.starts_with(skipped)) {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                let rel = path.strip_prefix(root)?.to_path_buf();
+                files.push((path, rel));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+/// Reader that hashes and counts what passes through it.
+struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+    count: u64,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.count += n as u64;
+        Ok(n)
+    }
+}
+
+fn copy_sqlite(src: &Path, dst: &Path) -> Result<()> {
+    let conn = rusqlite::Connection::open_with_flags(
+        src,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open {}", src.display()))?;
+    conn.backup(rusqlite::MAIN_DB, dst, None)
+        .with_context(|| format!("Failed to back up {}", src.display()))
+}
+
+/// Write an archive of `roots` to `out`, using `staging` for database copies.
+/// `skip` lists directories to leave out (nested roots are skipped
+/// automatically); `exclude` holds glob patterns matched against archive
+/// paths.
+pub fn build(
+    roots: &[(&'static str, PathBuf)],
+    skip: &[PathBuf],
+    exclude: &[glob::Pattern],
+    staging: &Path,
+    out: &Path,
+) -> Result<Manifest> {
+    let file = File::create(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mut manifest = Manifest {
+        format: MANIFEST_FORMAT,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        host: hostname::get()
+            .map(|h| h.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        files: Vec::new(),
+    };
+
+    let mut copies = 0usize;
+    for (index, (prefix, root)) in roots.iter().enumerate() {
+        let mut skip_here: Vec<PathBuf> = skip.to_vec();
+        skip_here.extend(
+            roots
+                .iter()
+                .enumerate()
+                .filter(|(other, (_, dir))| *other != index && dir.starts_with(root))
+                .map(|(_, (_, dir))| dir.clone()),
+        );
+        for (path, rel) in walk(root, &skip_here)? {
+            let name = archive_path(prefix, &rel);
+            if is_sqlite_sidecar(&path) || exclude.iter().any(|pattern| pattern.matches(&name)) {
+                continue;
+            }
+            let source = if is_sqlite(&path) {
+                copies += 1;
+                let copy = staging.join(format!("db-{copies}"));
+                copy_sqlite(&path, &copy)?;
+                copy
+            } else {
+                path.clone()
+            };
+
+            let size = fs::metadata(&source)?.len();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size);
+            header.set_mode(0o600);
+            header.set_mtime(
+                fs::metadata(&path)?
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs()),
+            );
+            let mut reader = HashingReader {
+                inner: File::open(&source)
+                    .with_context(|| format!("Failed to open {}", source.display()))?
+                    .take(size),
+                hasher: Sha256::new(),
+                count: 0,
+            };
+            builder
+                .append_data(&mut header, &name, &mut reader)
+                .with_context(|| format!("Failed to archive {}", path.display()))?;
+            if reader.count != size {
+                bail!("{} changed while it was being archived", path.display());
+            }
+            manifest.files.push(ManifestFile {
+                path: name,
+                size,
+                sha256: hex::encode(reader.hasher.finalize()),
+            });
+        }
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(chrono::Utc::now().timestamp().try_into().unwrap_or(0));
+    builder.append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())?;
+    builder.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// A verified archive file: the root it belongs to and its path below it.
+#[derive(Debug, Clone)]
+pub struct UnpackedFile {
+    pub prefix: &'static str,
+    pub rel: PathBuf,
+}
+
+/// Result of [`unpack`]: the manifest and the files that were checked
+/// against it.
+#[derive(Debug)]
+pub struct Unpacked {
+    pub manifest: Manifest,
+    pub files: Vec<UnpackedFile>,
+}
+
+/// Split an archive path into its root prefix and relative path. Only plain
+/// `config/...` and `workspace/...` names are accepted: no `..`, `.`, root
+/// or drive components, and nothing that is not UTF-8.
+fn parse_entry_path(path: &Path) -> Option<(&'static str, PathBuf)> {
+    let mut components = path.components();
+    let prefix = match components.next()? {
+        Component::Normal(first) if first == "config" => "config",
+        Component::Normal(first) if first == "workspace" => "workspace",
+        _ => return None,
+    };
+    let mut rel = PathBuf::new();
+    for component in components {
+        match component {
+            Component::Normal(part) if part.to_str().is_some() => rel.push(part),
+            _ => return None,
+        }
+    }
+    (!rel.as_os_str().is_empty()).then_some((prefix, rel))
+}
+
+/// Unpack `archive` into `staging` and check every file against the
+/// manifest. Only `config/` and `workspace/` files are accepted, each at most
+/// once, and the manifest must list exactly the files in the archive.
+pub fn unpack(archive: &Path, staging: &Path) -> Result<Unpacked> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    fs::create_dir_all(staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest: Option<Manifest> = None;
+    let mut unpacked: BTreeMap<String, UnpackedFile> = BTreeMap::new();
+    for entry in tar.entries().context("Not a ZeroClaw backup archive")? {
+        let mut entry = entry.context("Backup archive is corrupted")?;
+        let entry_path = entry.path()?.into_owned();
+        let path = entry_path.to_string_lossy().into_owned();
+        if entry_path == Path::new(MANIFEST_NAME) {
+            if manifest.is_some() {
+                bail!("Duplicate entry in backup archive: {path}");
+            }
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+            manifest = Some(serde_json::from_str(&json).context("Invalid backup manifest")?);
+            continue;
+        }
+        if !entry.header().entry_type().is_file() {
+            bail!("Unexpected entry in backup archive: {path}");
+        }
+        let (prefix, rel) = parse_entry_path(&entry_path)
+            .with_context(|| format!("Unsafe path in backup archive: {path}"))?;
+        let name = archive_path(prefix, &rel);
+        if unpacked.contains_key(&name) {
+            bail!("Duplicate entry in backup archive: {name}");
+        }
+        if !entry.unpack_in(staging)? {
+            bail!("Unsafe path in backup archive: {path}");
+        }
+        unpacked.insert(name, UnpackedFile { prefix, rel });
+    }
+
+    let manifest = manifest.context("Backup archive has no manifest")?;
+    if manifest.format != MANIFEST_FORMAT {
+        bail!("Unsupported backup format {}", manifest.format);
+    }
+    let mut expected: BTreeMap<&str, &ManifestFile> = BTreeMap::new();
+    for file in &manifest.files {
+        if expected.insert(file.path.as_str(), file).is_some() {
+            bail!("Backup manifest lists {} twice", file.path);
+        }
+    }
+    if unpacked.len() != expected.len() {
+        bail!(
+            "Backup archive has {} files but its manifest lists {}",
+            unpacked.len(),
+            expected.len()
+        );
+    }
+    for (path, file) in &unpacked {
+        let listed = expected
+            .get(path.as_str())
+            .with_context(|| format!("{path} is not in the backup manifest"))?;
+        let staged = staging.join(file.prefix).join(&file.rel);
+        if fs::metadata(&staged)?.len() != listed.size || sha256_file(&staged)? != listed.sha256 {
+            bail!("Checksum mismatch for {path}; the archive is corrupted");
+        }
+    }
+    Ok(Unpacked {
+        manifest,
+        files: unpacked.into_values().collect(),
+    })
+}
+
+/// Copy the verified files in `staging` over `roots`. Each file is written
+/// next to its destination and renamed into place; stale SQLite sidecars are
+/// removed so they are not replayed over a restored database.
+pub fn install(
+    unpacked: &Unpacked,
+    staging: &Path,
+    roots: &[(&'static str, PathBuf)],
+) -> Result<()> {
+    for file in &unpacked.files {
+        let (_, root) = roots
+            .iter()
+            .find(|(name, _)| *name == file.prefix)
+            .with_context(|| format!("No restore root for {}", file.prefix))?;
+        let dest = root.join(&file.rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut tmp_name = dest.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".restore-tmp");
+        let tmp = dest.with_file_name(tmp_name);
+        fs::copy(staging.join(file.prefix).join(&file.rel), &tmp)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        if is_sqlite(&tmp) {
+            for suffix in SQLITE_SIDECARS {
+                let mut sidecar = dest.as_os_str().to_os_string();
+                sidecar.push(suffix);
+                let _ = fs::remove_file(PathBuf::from(sidecar));
+            }
+        }
+        fs::rename(&tmp, &dest).with_context(|| format!("Failed to replace {}", dest.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sqlite_copies_include_uncheckpointed_wal_pages() {
+        let tmp = TempDir::new().unwrap();
+        let config_dir = tmp.path().join("home");
+        let workspace = config_dir.join("workspace");
+        fs::create_dir_all(workspace.join("memory")).unwrap();
+        fs::write(
+            config_dir.join("config.toml"),
+            "default_temperature = 0.7\n",
+        )
+        .unwrap();
+        fs::write(workspace.join("SOUL.md"), "soul").unwrap();
+
+        let db = workspace.join("memory").join("brain.db");
+        let live = rusqlite::Connection::open(&db).unwrap();
+        live.execute_batch(
+            "PRAGMA journal_mode = WAL; PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('kept');",
+        )
+        .unwrap();
+
+        let roots = roots(&config_dir, &workspace);
+        let staging = tmp.path().join("staging");
+        fs::create_dir_all(&staging).unwrap();
+        let out = tmp.path().join("backup.tar.gz");
+        let exclude = [glob::Pattern::new("workspace/*.md").unwrap()];
+        let manifest = build(&roots, &[], &exclude, &staging, &out).unwrap();
+        drop(live);
+
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["config/config.toml", "workspace/memory/brain.db"]);
+
+        let unpacked = tmp.path().join("unpacked");
+        let read_back = unpack(&out, &unpacked).unwrap();
+        assert_eq!(read_back.manifest.files.len(), 2);
+        assert_eq!(read_back.files.len(), 2);
+        let conn = rusqlite::Connection::open(unpacked.join("workspace/memory/brain.db")).unwrap();
+        let value: String = conn.query_row("SELECT v FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(value, "kept");
+    }
+
+    /// Write `entries` with their names stored verbatim, so tests can build
+    /// archives the `tar` builder would refuse to create.
+    fn write_raw_archive(out: &Path, entries: &[(&str, Vec<u8>)]) {
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(out).unwrap(),
+            Compression::default(),
+        ));
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_mode(0o600);
+            header.set_cksum();
+            builder.append(&header, data.as_slice()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn manifest_for(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let manifest = Manifest {
+            format: MANIFEST_FORMAT,
+            created_at: String::new(),
+            version: String::new(),
+            host: String::new(),
+            files: files
+                .iter()
+                .map(|(path, data)| ManifestFile {
+                    path: (*path).to_string(),
+                    size: data.len() as u64,
+                    sha256: hex::encode(Sha256::digest(data)),
+                })
+                .collect(),
+        };
+        serde_json::to_vec(&manifest).unwrap()
+    }
+
+    #[test]
+    fn unpack_rejects_tampered_files() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("bad.tar.gz");
+        write_raw_archive(
+            &out,
+            &[
+                ("config/config.toml", b"abc".to_vec()),
+                (
+                    MANIFEST_NAME,
+                    manifest_for(&[("config/config.toml", b"abd")]),
+                ),
+            ],
+        );
+
+        let err = unpack(&out, &tmp.path().join("staging")).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn unpack_rejects_duplicates_and_unsafe_paths() {
+        let tmp = TempDir::new().unwrap();
+        type Entries<'a> = Vec<(&'a str, Vec<u8>)>;
+        let cases: [(&str, Entries, &str); 4] = [
+            (
+                "duplicate entry",
+                vec![
+                    ("config/a", b"1".to_vec()),
+                    ("config/a", b"1".to_vec()),
+                    (MANIFEST_NAME, manifest_for(&[("config/a", b"1")])),
+                ],
+                "Duplicate entry",
+            ),
+            (
+                "duplicate manifest path",
+                vec![
+                    ("config/a", b"1".to_vec()),
+                    (
+                        MANIFEST_NAME,
+                        manifest_for(&[("config/a", b"1"), ("config/a", b"1")]),
+                    ),
+                ],
+                "lists config/a twice",
+            ),
+            (
+                "parent component",
+                vec![
+                    ("workspace/../escape", b"1".to_vec()),
+                    (
+                        MANIFEST_NAME,
+                        manifest_for(&[("workspace/../escape", b"1")]),
+                    ),
+                ],
+                "Unsafe path",
+            ),
+            (
+                "absolute path",
+                vec![
+                    ("/config/a", b"1".to_vec()),
+                    (MANIFEST_NAME, manifest_for(&[("/config/a", b"1")])),
+                ],
+                "Unsafe path",
+            ),
+        ];
+        for (case, entries, message) in cases {
+            let out = tmp.path().join(format!("{case}.tar.gz"));
+            write_raw_archive(&out, &entries);
+            let err = unpack(&out, &tmp.path().join(case)).unwrap_err();
+            assert!(err.to_string().contains(message), "{case}: {err}");
+        }
+        assert!(!tmp.path().join("escape").exists());
+    }
+}
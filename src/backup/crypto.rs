@@ -0,0 +1,256 @@
+//! Passphrase encryption for backup archives.
+//!
+//! The key is derived with PBKDF2-HMAC-SHA256. The archive is encrypted with
+//! ChaCha20-Poly1305 in 64 KiB chunks so large databases never have to fit in
+//! memory. Each chunk nonce is a random prefix, the chunk counter and a
+//! last-chunk flag, which makes reordered, dropped or truncated chunks fail
+//! authentication.
+//!
+//! Layout: `ZCBACKUP`, version byte, PBKDF2 iterations (u32 BE), 16-byte
+//! salt, 7-byte nonce prefix, then `[len u32 BE][ciphertext]` frames. The
+//! header is bound to every chunk as associated data.
+
+use anyhow::{bail, Context, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"ZCBACKUP";
+const VERSION: u8 = 1;
+const ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const PREFIX_LEN: usize = 7;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + SALT_LEN + PREFIX_LEN;
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+/// Whether `path` starts with the encrypted archive header.
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    let mut magic = [0u8; MAGIC.len()];
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    Ok(file.read_exact(&mut magic).is_ok() && &magic == MAGIC)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<Key> {
+    let iterations = NonZeroU32::new(iterations).context("Invalid backup header")?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(Key::from(key))
+}
+
+fn chunk_nonce(prefix: &[u8], counter: u32, last: bool) -> Nonce {
+    let mut nonce = [0u8; 12];
+    nonce[..PREFIX_LEN].copy_from_slice(prefix);
+    nonce[PREFIX_LEN..PREFIX_LEN + 4].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = u8::from(last);
+    Nonce::from(nonce)
+}
+
+/// Fill `buf` from `reader`, returning how many bytes were read (short only
+/// at end of input).
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Encrypt `src` into `dst` under `passphrase`.
+pub fn encrypt_file(src: &Path, dst: &Path, passphrase: &str) -> Result<()> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut prefix = [0u8; PREFIX_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut prefix);
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.push(VERSION);
+    header.extend_from_slice(&ITERATIONS.to_be_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&prefix);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, ITERATIONS)?);
+    let mut reader = BufReader::new(
+        File::open(src).with_context(|| format!("Failed to open {}", src.display()))?,
+    );
+    let mut writer = BufWriter::new(
+        File::create(dst).with_context(|| format!("Failed to create {}", dst.display()))?,
+    );
+    writer.write_all(&header)?;
+
+    let mut current = vec![0u8; CHUNK_LEN];
+    let mut next = vec![0u8; CHUNK_LEN];
+    let mut current_len = read_full(&mut reader, &mut current)?;
+    let mut counter: u32 = 0;
+    loop {
+        let next_len = if current_len == CHUNK_LEN {
+            read_full(&mut reader, &mut next)?
+        } else {
+            0
+        };
+        let last = next_len == 0;
+        let ciphertext = cipher
+            .encrypt(
+                &chunk_nonce(&prefix, counter, last),
+                Payload {
+                    msg: &current[..current_len],
+                    aad: &header,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Backup encryption failed"))?;
+        let frame_len = u32::try_from(ciphertext.len()).context("Backup chunk too large")?;
+        writer.write_all(&frame_len.to_be_bytes())?;
+        writer.write_all(&ciphertext)?;
+        if last {
+            break;
+        }
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+        counter = counter
+            .checked_add(1)
+            .context("Backup too large to encrypt")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Decrypt `src` (written by [`encrypt_file`]) into `dst`.
+pub fn decrypt_file(src: &Path, dst: &Path, passphrase: &str) -> Result<()> {
+    let mut reader = BufReader::new(
+        File::open(src).with_context(|| format!("Failed to open {}", src.display()))?,
+    );
+    let mut header = [0u8; HEADER_LEN];
+    reader
+        .read_exact(&mut header)
+        .context("Backup archive is truncated")?;
+    if &header[..MAGIC.len()] != MAGIC {
+        bail!("Not an encrypted ZeroClaw backup");
+    }
+    if header[MAGIC.len()] != VERSION {
+        bail!(
+            "Unsupported encrypted backup version {}",
+            header[MAGIC.len()]
+        );
+    }
+    let mut offset = MAGIC.len() + 1;
+    let iterations = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let salt = &header[offset..offset + SALT_LEN];
+    let prefix = &header[offset + SALT_LEN..];
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt, iterations)?);
+    let mut writer = BufWriter::new(
+        File::create(dst).with_context(|| format!("Failed to create {}", dst.display()))?,
+    );
+
+    let read_frame = |reader: &mut BufReader<File>| -> Result<Option<Vec<u8>>> {
+        let mut len = [0u8; 4];
+        match read_full(reader, &mut len)? {
+            0 => return Ok(None),
+            4 => {}
+            _ => bail!("Backup archive is truncated"),
+        }
+        let len = u32::from_be_bytes(len) as usize;
+        if !(TAG_LEN..=CHUNK_LEN + TAG_LEN).contains(&len) {
+            bail!("Backup archive is corrupted");
+        }
+        let mut frame = vec![0u8; len];
+        reader
+            .read_exact(&mut frame)
+            .context("Backup archive is truncated")?;
+        Ok(Some(frame))
+    };
+
+    let mut current = read_frame(&mut reader)?.context("Backup archive is truncated")?;
+    let mut counter: u32 = 0;
+    loop {
+        let next = read_frame(&mut reader)?;
+        let last = next.is_none();
+        let plaintext = cipher
+            .decrypt(
+                &chunk_nonce(prefix, counter, last),
+                Payload {
+                    msg: &current,
+                    aad: &header,
+                },
+            )
+            .map_err(|_| {
+                anyhow::anyhow!("Failed to decrypt backup: wrong passphrase or corrupted archive")
+            })?;
+        writer.write_all(&plaintext)?;
+        match next {
+            Some(frame) => current = frame,
+            None => break,
+        }
+        counter = counter
+            .checked_add(1)
+            .context("Backup archive is corrupted")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn encrypt_roundtrip_and_tamper_detection() {
+        let tmp = TempDir::new().unwrap();
+        let plain = tmp.path().join("plain");
+        let sealed = tmp.path().join("sealed");
+        let opened = tmp.path().join("opened");
+        // Two full chunks plus a partial one.
+        let data: Vec<u8> = (0..CHUNK_LEN * 2 + 100)
+            .map(|i| u8::try_from(i % 251).unwrap())
+            .collect();
+        std::fs::write(&plain, &data).unwrap();
+
+        encrypt_file(&plain, &sealed, "correct horse").unwrap();
+        assert!(is_encrypted(&sealed).unwrap());
+        assert!(!is_encrypted(&plain).unwrap());
+
+        decrypt_file(&sealed, &opened, "correct horse").unwrap();
+        assert_eq!(std::fs::read(&opened).unwrap(), data);
+
+        let err = decrypt_file(&sealed, &opened, "wrong").unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+
+        // Dropping the final chunk must not decrypt to a shorter archive.
+        let bytes = std::fs::read(&sealed).unwrap();
+        let truncated = tmp.path().join("truncated");
+        std::fs::write(
+            &truncated,
+            &bytes[..HEADER_LEN + 2 * (4 + CHUNK_LEN + TAG_LEN)],
+        )
+        .unwrap();
+        assert!(decrypt_file(&truncated, &opened, "correct horse").is_err());
+    }
+
+    #[test]
+    fn empty_input_roundtrips() {
+        let tmp = TempDir::new().unwrap();
+        let plain = tmp.path().join("plain");
+        let sealed = tmp.path().join("sealed");
+        std::fs::write(&plain, b"").unwrap();
+        encrypt_file(&plain, &sealed, "p").unwrap();
+        decrypt_file(&sealed, &plain, "p").unwrap();
+        assert!(std::fs::read(&plain).unwrap().is_empty());
+    }
+}
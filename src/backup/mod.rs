@@ -0,0 +1,453 @@
+//! `zeroclaw backup`: consistent, optionally encrypted archives of the config
+//! directory (config, `.secret_key`, auth profiles) and the workspace
+//! (memory, cron store, sessions, skills, identity files), stored in a local
+//! directory or an S3-compatible bucket.
+
+mod archive;
+mod crypto;
+mod s3;
+mod store;
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use store::{Store, Stored};
+
+/// Overrides `backup.passphrase`.
+pub const PASSPHRASE_ENV: &str = "ZEROCLAW_BACKUP_PASSPHRASE";
+const NAME_PREFIX: &str = "zeroclaw-backup-";
+const PLAIN_SUFFIX: &str = ".tar.gz";
+const ENCRYPTED_SUFFIX: &str = ".tar.gz.enc";
+
+/// Result of [`create`].
+#[derive(Debug)]
+pub struct Created {
+    pub name: String,
+    pub location: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub encrypted: bool,
+    pub pruned: Vec<String>,
+}
+
+/// Result of [`restore`].
+#[derive(Debug)]
+pub struct Restored {
+    pub name: String,
+    pub created_at: String,
+    pub files: usize,
+}
+
+fn config_dir(config: &Config) -> PathBuf {
+    crate::auth::state_dir_from_config(config)
+}
+
+fn passphrase(config: &Config) -> Option<String> {
+    std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .or_else(|| config.backup.passphrase.clone())
+        .filter(|p| !p.is_empty())
+}
+
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(NAME_PREFIX)
+        && (name.ends_with(PLAIN_SUFFIX) || name.ends_with(ENCRYPTED_SUFFIX))
+}
+
+/// Scratch directory next to `config.toml` (so unencrypted copies never land
+/// in a shared temp dir), removed on drop.
+struct Staging(PathBuf);
+
+impl Staging {
+    fn new(config: &Config) -> Result<Self> {
+        let dir = config_dir(config).join(format!(".backup-staging-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Archive the config directory and workspace into the target store, then
+/// apply `backup.keep`. Archives are encrypted when a passphrase is set,
+/// unless `plain` is true.
+pub async fn create(config: &Config, target: Option<&str>, plain: bool) -> Result<Created> {
+    create_at(config, target, plain, chrono::Utc::now()).await
+}
+
+/// [`create`] with the timestamp that names the archive.
+async fn create_at(
+    config: &Config,
+    target: Option<&str>,
+    plain: bool,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Created> {
+    let store = Store::open(config, target)?;
+    let passphrase = if plain { None } else { passphrase(config) };
+    let exclude = config
+        .backup
+        .exclude
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .with_context(|| format!("Invalid backup.exclude pattern {pattern:?}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The random suffix keeps two backups started in the same millisecond
+    // from replacing each other; names still sort by creation time.
+    let mut suffix = uuid::Uuid::new_v4().simple().to_string();
+    suffix.truncate(8);
+    let name = format!(
+        "{NAME_PREFIX}{}-{suffix}{}",
+        now.format("%Y%m%dT%H%M%S%3fZ"),
+        if passphrase.is_some() {
+            ENCRYPTED_SUFFIX
+        } else {
+            PLAIN_SUFFIX
+        }
+    );
+    let staging = Staging::new(config)?;
+    let mut skip = vec![staging.0.clone()];
+    skip.extend(store.local_dir().map(Path::to_path_buf));
+    let roots = archive::roots(&config_dir(config), &config.workspace_dir);
+    let workspace_dir = config.workspace_dir.clone();
+    let staging_dir = staging.0.clone();
+    let encrypted = passphrase.is_some();
+
+    let (manifest, archive_path) = tokio::task::spawn_blocking(move || -> Result<_> {
+        if let Err(e) = crate::memory::snapshot::export_snapshot(&workspace_dir) {
+            tracing::warn!("Backup: memory snapshot export failed: {e}");
+        }
+        let plain_path = staging_dir.join("archive.tar.gz");
+        let manifest = archive::build(&roots, &skip, &exclude, &staging_dir, &plain_path)?;
+        let Some(passphrase) = passphrase else {
+            return Ok((manifest, plain_path));
+        };
+        let sealed = staging_dir.join("archive.tar.gz.enc");
+        crypto::encrypt_file(&plain_path, &sealed, &passphrase)?;
+        Ok((manifest, sealed))
+    })
+    .await??;
+
+    store.put(&name, &archive_path).await?;
+    let pruned = prune(&store, config.backup.keep).await?;
+    Ok(Created {
+        name,
+        location: store.describe(),
+        files: manifest.files.len(),
+        bytes: manifest.total_bytes(),
+        encrypted,
+        pruned,
+    })
+}
+
+/// Backups in the target store, oldest first.
+pub async fn list(config: &Config, target: Option<&str>) -> Result<Vec<Stored>> {
+    list_in(&Store::open(config, target)?).await
+}
+
+async fn list_in(store: &Store) -> Result<Vec<Stored>> {
+    let mut backups: Vec<Stored> = store
+        .list()
+        .await?
+        .into_iter()
+        .filter(|stored| is_backup_name(&stored.name))
+        .collect();
+    backups.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` backups. `0` keeps everything.
+async fn prune(store: &Store, keep: u32) -> Result<Vec<String>> {
+    let keep = keep as usize;
+    if keep == 0 {
+        return Ok(Vec::new());
+    }
+    let backups = list_in(store).await?;
+    let excess = backups.len().saturating_sub(keep);
+    let mut pruned = Vec::with_capacity(excess);
+    for stored in backups.into_iter().take(excess) {
+        store.delete(&stored.name).await?;
+        pruned.push(stored.name);
+    }
+    Ok(pruned)
+}
+
+/// Restore backup `name` (or `latest`). Files are written over the config
+/// directory and workspace, or under `into/config` and `into/workspace` when
+/// `into` is set. Every file is checked against the manifest before anything
+/// is written.
+pub async fn restore(
+    config: &Config,
+    name: &str,
+    target: Option<&str>,
+    into: Option<&Path>,
+) -> Result<Restored> {
+    let store = Store::open(config, target)?;
+    let name = if name == "latest" {
+        list_in(&store)
+            .await?
+            .pop()
+            .with_context(|| format!("No backups in {}", store.describe()))?
+            .name
+    } else if is_backup_name(name) {
+        name.to_string()
+    } else {
+        bail!("{name} is not a backup name (see `zeroclaw backup list`)");
+    };
+
+    let staging = Staging::new(config)?;
+    let download = staging.0.join("download");
+    store.get(&name, &download).await?;
+
+    let roots = match into {
+        Some(dir) => archive::roots(&dir.join("config"), &dir.join("workspace")),
+        None => archive::roots(&config_dir(config), &config.workspace_dir),
+    };
+    let passphrase = passphrase(config);
+    let staging_dir = staging.0.clone();
+    let unpacked = tokio::task::spawn_blocking(move || -> Result<_> {
+        let archive_path = if crypto::is_encrypted(&download)? {
+            let passphrase = passphrase.with_context(|| {
+                format!("Backup is encrypted; set {PASSPHRASE_ENV} or backup.passphrase")
+            })?;
+            let plain = staging_dir.join("archive.tar.gz");
+            crypto::decrypt_file(&download, &plain, &passphrase)?;
+            plain
+        } else {
+            download
+        };
+        let files = staging_dir.join("files");
+        let unpacked = archive::unpack(&archive_path, &files)?;
+        archive::install(&unpacked, &files, &roots)?;
+        Ok(unpacked)
+    })
+    .await??;
+
+    Ok(Restored {
+        name,
+        created_at: unpacked.manifest.created_at,
+        files: unpacked.files.len(),
+    })
+}
+
+/// Run a backup for the built-in cron job. Returns `(success, output)`.
+pub async fn run_scheduled(config: &Config) -> (bool, String) {
+    match create(config, None, false).await {
+        Ok(created) => (true, describe_created(&created)),
+        Err(e) => (false, format!("backup failed: {e:#}")),
+    }
+}
+
+fn describe_created(created: &Created) -> String {
+    let mut text = format!(
+        "{} ({} files, {} bytes{}) -> {}",
+        created.name,
+        created.files,
+        created.bytes,
+        if created.encrypted { ", encrypted" } else { "" },
+        created.location
+    );
+    if !created.pruned.is_empty() {
+        let _ = write!(text, "; pruned {}", created.pruned.join(", "));
+    }
+    text
+}
+
+/// Handle `zeroclaw backup <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::BackupCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::BackupCommands::Create { target, plain } => {
+            let created = create(config, target.as_deref(), plain).await?;
+            if !created.encrypted {
+                println!(
+                    "⚠️  Archive is not encrypted; it contains .secret_key and auth profiles."
+                );
+            }
+            println!("✅ Backup {}", describe_created(&created));
+            Ok(())
+        }
+        crate::BackupCommands::List { target } => {
+            let store = Store::open(config, target.as_deref())?;
+            let backups = list_in(&store).await?;
+            if backups.is_empty() {
+                println!("No backups in {}.", store.describe());
+                return Ok(());
+            }
+            println!("Backups in {} ({}):", store.describe(), backups.len());
+            for stored in backups {
+                println!("  {:<50} {:>12} bytes", stored.name, stored.size);
+            }
+            Ok(())
+        }
+        crate::BackupCommands::Restore {
+            name,
+            target,
+            into,
+            yes,
+        } => {
+            if into.is_none() && !yes {
+                println!(
+                    "This overwrites files in {} and {}. Stop the daemon first.",
+                    config_dir(config).display(),
+                    config.workspace_dir.display()
+                );
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt(format!("  Restore {name}?"))
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+            let restored = restore(config, &name, target.as_deref(), into.as_deref()).await?;
+            println!(
+                "✅ Restored {} files from {} (created {})",
+                restored.files, restored.name, restored.created_at
+            );
+            Ok(())
+        }
+        crate::BackupCommands::Schedule { expression, tz } => {
+            let job = crate::cron::upsert_backup_job(
+                config,
+                crate::cron::Schedule::Cron {
+                    expr: expression,
+                    tz,
+                },
+            )?;
+            println!("✅ Backup scheduled");
+            println!("  ID  : {}", job.id);
+            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Keep: {} (backup.keep)", config.backup.keep);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let home = tmp.path().join("home");
+        let workspace = home.join("workspace");
+        std::fs::create_dir_all(workspace.join("memory")).unwrap();
+        let config = Config {
+            config_path: home.join("config.toml"),
+            workspace_dir: workspace,
+            ..Config::default()
+        };
+        std::fs::write(&config.config_path, "default_temperature = 0.7\n").unwrap();
+        std::fs::write(home.join(".secret_key"), "key").unwrap();
+        config
+    }
+
+    #[tokio::test]
+    async fn create_list_restore_and_prune_locally() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.backup.keep = 2;
+        config.backup.passphrase = Some("hunter2".into());
+        let soul = config.workspace_dir.join("SOUL.md");
+        std::fs::write(&soul, "v1").unwrap();
+
+        let start = chrono::Utc::now();
+        let at = |seconds| start + chrono::Duration::seconds(seconds);
+        let first = create_at(&config, None, false, at(0)).await.unwrap();
+        assert!(first.encrypted);
+        assert!(first.name.ends_with(ENCRYPTED_SUFFIX));
+        assert_eq!(first.files, 3);
+        let backups_dir = config_dir(&config).join("backups");
+        assert_eq!(first.location, backups_dir.display().to_string());
+
+        std::fs::write(&soul, "v2").unwrap();
+        create_at(&config, None, true, at(1)).await.unwrap();
+        let third = create_at(&config, None, true, at(2)).await.unwrap();
+        assert_eq!(third.pruned, vec![first.name.clone()]);
+        let names: Vec<String> = list(&config, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.iter().all(|n| n.ends_with(PLAIN_SUFFIX)));
+
+        // Backups and staging dirs are never archived into later backups.
+        let into = tmp.path().join("restored");
+        let restored = restore(&config, "latest", None, Some(&into)).await.unwrap();
+        assert_eq!(restored.name, third.name);
+        assert_eq!(restored.files, 3);
+        assert_eq!(
+            std::fs::read_to_string(into.join("workspace/SOUL.md")).unwrap(),
+            "v2"
+        );
+        assert!(into.join("config/.secret_key").exists());
+        assert!(!into.join("config/backups").exists());
+
+        std::fs::write(&soul, "v3").unwrap();
+        restore(&config, &third.name, None, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&soul).unwrap(), "v2");
+        let leftovers: Vec<_> = std::fs::read_dir(config_dir(&config))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with(".backup-staging")
+            })
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn backups_started_together_keep_distinct_names() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let now = chrono::Utc::now();
+        let first = create_at(&config, None, true, now).await.unwrap();
+        let second = create_at(&config, None, true, now).await.unwrap();
+        assert_ne!(first.name, second.name);
+        assert_eq!(list(&config, None).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn encrypted_restore_requires_the_passphrase() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.backup.passphrase = Some("hunter2".into());
+        let created = create(&config, None, false).await.unwrap();
+
+        config.backup.passphrase = None;
+        let into = tmp.path().join("restored");
+        let err = restore(&config, &created.name, None, Some(&into))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(PASSPHRASE_ENV));
+
+        config.backup.passphrase = Some("wrong".into());
+        let err = restore(&config, &created.name, None, Some(&into))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+        assert!(!into.exists());
+
+        assert!(restore(&config, "config.toml", None, Some(&into))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! Where backup archives live: a local directory or an S3 bucket.
+
+use super::s3::Bucket;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// A stored archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stored {
+    pub name: String,
+    pub size: u64,
+}
+
+pub enum Store {
+    Local(PathBuf),
+    S3(Box<Bucket>),
+}
+
+impl Store {
+    /// The store for `target`, falling back to `backup.target` and then to
+    /// `backups/` next to `config.toml`.
+    pub fn open(config: &Config, target: Option<&str>) -> Result<Self> {
+        let target = target
+            .map(str::to_string)
+            .or_else(|| config.backup.target.clone())
+            .filter(|t| !t.trim().is_empty());
+        match target {
+            Some(target) if target.starts_with("s3://") => Ok(Self::S3(Box::new(Bucket::parse(
+                &target,
+                &config.backup.s3,
+            )?))),
+            Some(target) => Ok(Self::Local(PathBuf::from(
+                shellexpand::tilde(&target).into_owned(),
+            ))),
+            None => Ok(Self::Local(super::config_dir(config).join("backups"))),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Local(dir) => dir.display().to_string(),
+            Self::S3(bucket) => bucket.describe(),
+        }
+    }
+
+    /// The local directory, which must be left out of the archive itself.
+    pub fn local_dir(&self) -> Option<&Path> {
+        match self {
+            Self::Local(dir) => Some(dir),
+            Self::S3(_) => None,
+        }
+    }
+
+    pub async fn put(&self, name: &str, file: &Path) -> Result<()> {
+        match self {
+            Self::Local(dir) => {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+                let tmp = dir.join(format!(".{name}.partial"));
+                tokio::fs::copy(file, &tmp)
+                    .await
+                    .with_context(|| format!("Failed to write {}", tmp.display()))?;
+                tokio::fs::rename(&tmp, dir.join(name)).await?;
+                Ok(())
+            }
+            Self::S3(bucket) => bucket.put(name, file).await,
+        }
+    }
+
+    pub async fn get(&self, name: &str, dest: &Path) -> Result<()> {
+        match self {
+            Self::Local(dir) => {
+                let src = dir.join(name);
+                tokio::fs::copy(&src, dest)
+                    .await
+                    .with_context(|| format!("Backup {} not found", src.display()))?;
+                Ok(())
+            }
+            Self::S3(bucket) => bucket.get(name, dest).await,
+        }
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        match self {
+            Self::Local(dir) => Ok(tokio::fs::remove_file(dir.join(name)).await?),
+            Self::S3(bucket) => bucket.delete(name).await,
+        }
+    }
+
+    /// Every object in the store, backups or not.
+    pub async fn list(&self) -> Result<Vec<Stored>> {
+        match self {
+            Self::Local(dir) => {
+                let mut entries = match tokio::fs::read_dir(dir).await {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                    Err(e) => {
+                        return Err(e).with_context(|| format!("Failed to read {}", dir.display()))
+                    }
+                };
+                let mut stored = Vec::new();
+                while let Some(entry) = entries.next_entry().await? {
+                    let metadata = entry.metadata().await?;
+                    if metadata.is_file() {
+                        stored.push(Stored {
+                            name: entry.file_name().to_string_lossy().into_owned(),
+                            size: metadata.len(),
+                        });
+                    }
+                }
+                Ok(stored)
+            }
+            Self::S3(bucket) => Ok(bucket
+                .list()
+                .await?
+                .into_iter()
+                .map(|object| Stored {
+                    name: object.name,
+                    size: object.size,
+                })
+                .collect()),
+        }
+    }
+}
@@ -27,10 +27,12 @@ pub mod linq;
 pub mod matrix;
 pub mod mattermost;
 pub mod nextcloud_talk;
+pub(crate) mod personas;
 pub mod qq;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
+pub(crate) mod tenancy;
 pub mod traits;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
@@ -190,6 +192,46 @@ fn runtime_config_store() -> &'static Mutex<HashMap<PathBuf, RuntimeConfigState>
     STORE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+type InboundSender = tokio::sync::mpsc::Sender<traits::ChannelMessage>;
+
+/// Message bus of the channel pipeline running in this process, if any.
+fn inbound_bus() -> &'static Mutex<Option<InboundSender>> {
+    static BUS: OnceLock<Mutex<Option<InboundSender>>> = OnceLock::new();
+    BUS.get_or_init(|| Mutex::new(None))
+}
+
+/// Hand a message received outside a channel listener (e.g. a gateway
+/// webhook) to the running channel pipeline, so it gets the same personas,
+/// tenancy, sessions and streaming as listener traffic.
+///
+/// Returns the message back when no pipeline runs in this process.
+pub async fn dispatch_inbound(msg: traits::ChannelMessage) -> Result<(), traits::ChannelMessage> {
+    let tx = inbound_bus()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    match tx {
+        Some(tx) => tx.send(msg).await.map_err(|e| e.0),
+        None => Err(msg),
+    }
+}
+
+/// Channels served by the channel pipeline running in this process.
+fn running_registry() -> &'static Mutex<Vec<Arc<dyn Channel>>> {
+    static RUNNING: OnceLock<Mutex<Vec<Arc<dyn Channel>>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// The live channel handles of the pipeline running in this process, so
+/// tools outside it (e.g. `send_message` in gateway requests) reuse its
+/// connections instead of opening their own. Empty when no pipeline runs.
+pub fn running_channels() -> Vec<Arc<dyn Channel>> {
+    running_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
 const SYSTEMD_STATUS_ARGS: [&str; 3] = ["--user", "is-active", "zeroclaw.service"];
 const SYSTEMD_RESTART_ARGS: [&str; 3] = ["--user", "restart", "zeroclaw.service"];
 const OPENRC_STATUS_ARGS: [&str; 2] = ["zeroclaw", "status"];
@@ -220,6 +262,16 @@ struct ChannelRuntimeContext {
     message_timeout_secs: u64,
     interrupt_on_new_message: bool,
     multimodal: crate::config::MultimodalConfig,
+    /// Set when cron is enabled so inbound messages can fire channel-triggered jobs.
+    cron_config: Option<Arc<Config>>,
+    /// Set when `agent.persist_sessions` is on; turns are mirrored to disk.
+    session_store: Option<Arc<crate::sessions::SessionStore>>,
+    /// Named personas and the routes that select them.
+    personas: Arc<personas::PersonaRouter>,
+    /// Registered users and their memory, workspace and quota scopes.
+    users: Arc<tenancy::UserRegistry>,
+    /// Set when `[gateway.admin]` queues tool approvals for the dashboard.
+    approval: Option<Arc<crate::approval::ApprovalManager>>,
 }
 
 #[derive(Clone)]
@@ -259,8 +311,16 @@ fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
     format!("{}_{}_{}", msg.channel, msg.sender, msg.id)
 }
 
-fn conversation_history_key(msg: &traits::ChannelMessage) -> String {
-    format!("{}_{}", msg.channel, msg.sender)
+/// Per-sender history and route key. Persona conversations are kept apart
+/// from the default identity's and from each other.
+fn conversation_history_key(
+    msg: &traits::ChannelMessage,
+    persona: Option<&personas::Persona>,
+) -> String {
+    match persona {
+        Some(persona) => format!("{}_{}_{}", persona.name, msg.channel, msg.sender),
+        None => format!("{}_{}", msg.channel, msg.sender),
+    }
 }
 
 fn interruption_scope_key(msg: &traits::ChannelMessage) -> String {
@@ -465,12 +525,9 @@ fn decrypt_optional_secret_for_runtime_reload(
 }
 
 async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRuntimeDefaults> {
-    let contents = tokio::fs::read_to_string(path)
+    let mut parsed = Config::parse_file(path)
         .await
-        .with_context(|| format!("Failed to read {}", path.display()))?;
-    let mut parsed: Config =
-        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
-    parsed.config_path = path.to_path_buf();
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
 
     if let Some(zeroclaw_dir) = path.parent() {
         let store = crate::security::SecretStore::new(zeroclaw_dir, parsed.secrets.encrypt);
@@ -551,25 +608,41 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
     Ok(())
 }
 
-fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection {
+fn default_route_selection(
+    ctx: &ChannelRuntimeContext,
+    persona: Option<&personas::Persona>,
+) -> ChannelRouteSelection {
     let defaults = runtime_defaults_snapshot(ctx);
     ChannelRouteSelection {
-        provider: defaults.default_provider,
-        model: defaults.model,
+        provider: persona
+            .and_then(|p| p.provider.clone())
+            .unwrap_or(defaults.default_provider),
+        model: persona
+            .and_then(|p| p.model.clone())
+            .unwrap_or(defaults.model),
     }
 }
 
-fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> ChannelRouteSelection {
+fn get_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    persona: Option<&personas::Persona>,
+) -> ChannelRouteSelection {
     ctx.route_overrides
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .get(sender_key)
         .cloned()
-        .unwrap_or_else(|| default_route_selection(ctx))
+        .unwrap_or_else(|| default_route_selection(ctx, persona))
 }
 
-fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: ChannelRouteSelection) {
-    let default_route = default_route_selection(ctx);
+fn set_route_selection(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    next: ChannelRouteSelection,
+    persona: Option<&personas::Persona>,
+) {
+    let default_route = default_route_selection(ctx, persona);
     let mut routes = ctx
         .route_overrides
         .lock()
@@ -623,6 +696,21 @@ fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool
     true
 }
 
+/// Mirror messages to the sender's recorded session, when recording is on.
+fn record_session(ctx: &ChannelRuntimeContext, sender_key: &str, messages: &[ChatMessage]) {
+    let Some(store) = &ctx.session_store else {
+        return;
+    };
+    let session_id = crate::sessions::channel_session_id(sender_key);
+    let messages: Vec<_> = messages
+        .iter()
+        .map(crate::sessions::SessionMessage::from_history)
+        .collect();
+    if let Err(e) = store.append(&session_id, &messages) {
+        tracing::warn!("Failed to record session {session_id}: {e}");
+    }
+}
+
 fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatMessage) {
     let mut histories = ctx
         .conversation_histories
@@ -821,8 +909,9 @@ async fn handle_runtime_command_if_needed(
         return true;
     };
 
-    let sender_key = conversation_history_key(msg);
-    let mut current = get_route_selection(ctx, &sender_key);
+    let persona = ctx.personas.resolve(msg);
+    let sender_key = conversation_history_key(msg, persona.as_deref());
+    let mut current = get_route_selection(ctx, &sender_key, persona.as_deref());
 
     let response = match command {
         ChannelRuntimeCommand::ShowProviders => build_providers_help_response(&current),
@@ -832,7 +921,12 @@ async fn handle_runtime_command_if_needed(
                     Ok(_) => {
                         if provider_name != current.provider {
                             current.provider = provider_name.clone();
-                            set_route_selection(ctx, &sender_key, current.clone());
+                            set_route_selection(
+                                ctx,
+                                &sender_key,
+                                current.clone(),
+                                persona.as_deref(),
+                            );
                             clear_sender_history(ctx, &sender_key);
                         }
 
@@ -862,7 +956,7 @@ async fn handle_runtime_command_if_needed(
                 "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
             } else {
                 current.model = model.clone();
-                set_route_selection(ctx, &sender_key, current.clone());
+                set_route_selection(ctx, &sender_key, current.clone(), persona.as_deref());
                 clear_sender_history(ctx, &sender_key);
 
                 format!(
@@ -1241,6 +1335,9 @@ fn spawn_supervised_listener_with_health_interval(
                             crate::health::mark_component_ok(&component);
                         }
                         result = &mut listen_future => break result,
+                        // The dispatcher is gone (e.g. the daemon restarted the
+                        // channels component); stop instead of listening forever.
+                        () = tx.closed() => break Ok(()),
                     }
                 }
             };
@@ -1338,10 +1435,72 @@ async fn process_channel_message(
     if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
         return;
     }
+    if let Some(config) = ctx.cron_config.as_deref() {
+        crate::cron::triggers::dispatch_channel_message(config, &msg.channel, &msg.content);
+    }
 
-    let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let tenant = ctx.users.resolve(&msg.channel, &msg.sender);
+    let refusal = match tenant.as_ref() {
+        None if ctx.users.rejects_unknown() => {
+            Some("⚠️ This assistant only serves registered users.")
+        }
+        Some(user) if user.quota.cost_exhausted() => {
+            Some("⚠️ Your daily usage budget is spent. Please try again tomorrow.")
+        }
+        _ => None,
+    };
+    if let Some(refusal) = refusal {
+        tracing::info!(
+            channel = %msg.channel,
+            sender = %msg.sender,
+            "Refused channel message: {refusal}"
+        );
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(
+                    &SendMessage::new(refusal, &msg.reply_target).in_thread(msg.thread_ts.clone()),
+                )
+                .await;
+        }
+        return;
+    }
+
+    let persona = ctx.personas.resolve(&msg);
+    let history_key = conversation_history_key(&msg, persona.as_deref());
+    let route = get_route_selection(ctx.as_ref(), &history_key, persona.as_deref());
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
+    let mut memory = persona
+        .as_ref()
+        .map_or_else(|| Arc::clone(&ctx.memory), |p| Arc::clone(&p.memory));
+    let mut tools_registry = persona.as_ref().map_or_else(
+        || Arc::clone(&ctx.tools_registry),
+        |p| Arc::clone(&p.tools_registry),
+    );
+    if let Some(user) = tenant.as_ref() {
+        memory = user.memory(&memory);
+        match ctx.users.tools_for(user, persona.as_deref(), &memory) {
+            Ok(registry) => tools_registry = registry,
+            Err(err) => {
+                tracing::warn!(user = %user.name, "Failed to build user tool registry: {err}");
+                if let Some(channel) = target_channel.as_ref() {
+                    let _ = channel
+                        .send(
+                            &SendMessage::new(
+                                "⚠️ Failed to prepare your workspace. Please try again later.",
+                                &msg.reply_target,
+                            )
+                            .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
+                }
+                return;
+            }
+        }
+    }
+    let temperature = persona
+        .as_ref()
+        .and_then(|p| p.temperature)
+        .unwrap_or(runtime_defaults.temperature);
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
         Ok(provider) => provider,
         Err(err) => {
@@ -1363,8 +1522,7 @@ async fn process_channel_message(
     };
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
-        let _ = ctx
-            .memory
+        let _ = memory
             .store(
                 &autosave_key,
                 &msg.content,
@@ -1385,7 +1543,9 @@ async fn process_channel_message(
         .is_some_and(|turns| !turns.is_empty());
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
-    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    let user_turn = ChatMessage::user(&msg.content);
+    record_session(ctx.as_ref(), &history_key, std::slice::from_ref(&user_turn));
+    append_sender_turn(ctx.as_ref(), &history_key, user_turn);
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -1401,7 +1561,7 @@ async fn process_channel_message(
     // history. Follow-up turns already include context from previous messages.
     if !had_prior_history {
         let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+            build_memory_context(memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
                 last_turn.content = format!("{memory_context}{}", msg.content);
@@ -1409,7 +1569,13 @@ async fn process_channel_message(
         }
     }
 
-    let system_prompt = build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel);
+    let base_prompt = persona
+        .as_ref()
+        .map_or(ctx.system_prompt.as_str(), |p| p.system_prompt.as_str());
+    let mut system_prompt = build_channel_system_prompt(base_prompt, &msg.channel);
+    if let Some(user) = tenant.as_ref() {
+        system_prompt.push_str(&user.prompt_note());
+    }
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
     let use_streaming = target_channel
@@ -1481,6 +1647,15 @@ async fn process_channel_message(
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
 
+    // Tenant spend is charged from the usage the provider reports.
+    let metered_provider = tenant
+        .as_ref()
+        .map(|_| providers::metered::MeteredProvider::new(Arc::clone(&active_provider)));
+    let loop_provider: &dyn Provider = match metered_provider.as_ref() {
+        Some(metered) => metered,
+        None => active_provider.as_ref(),
+    };
+
     enum LlmExecutionResult {
         Completed(Result<Result<String, anyhow::Error>, tokio::time::error::Elapsed>),
         Cancelled,
@@ -1492,21 +1667,24 @@ async fn process_channel_message(
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                None,
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
+            crate::sessions::scope(
+                crate::sessions::channel_session_id(&history_key),
+                run_tool_call_loop(
+                    loop_provider,
+                    &mut history,
+                    tools_registry.as_ref(),
+                    ctx.observer.as_ref(),
+                    route.provider.as_str(),
+                    route.model.as_str(),
+                    temperature,
+                    true,
+                    ctx.approval.as_deref(),
+                    msg.channel.as_str(),
+                    &ctx.multimodal,
+                    ctx.max_tool_iterations,
+                    Some(cancellation_token.clone()),
+                    delta_tx,
+                ),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -1538,8 +1716,25 @@ async fn process_channel_message(
             }
         }
         LlmExecutionResult::Completed(Ok(Ok(response))) => {
-            let sanitized_response =
-                sanitize_channel_response(&response, ctx.tools_registry.as_ref());
+            if let (Some(user), Some(metered)) = (tenant.as_ref(), metered_provider.as_ref()) {
+                let totals = metered.totals();
+                if totals.unmetered_calls > 0 {
+                    tracing::warn!(
+                        user = %user.name,
+                        provider = %route.provider,
+                        "{} of {} LLM calls reported no token usage and were not charged",
+                        totals.unmetered_calls,
+                        totals.calls
+                    );
+                }
+                if let Some(cost) =
+                    ctx.users
+                        .estimate_cost_usd(&route.provider, &route.model, totals.tokens)
+                {
+                    user.quota.record_cost(cost);
+                }
+            }
+            let sanitized_response = sanitize_channel_response(&response, tools_registry.as_ref());
             let delivered_response = if sanitized_response.is_empty() && !response.trim().is_empty()
             {
                 "I encountered malformed tool-call output and could not produce a safe reply. Please try again.".to_string()
@@ -1557,6 +1752,15 @@ async fn process_channel_message(
                 format!("{tool_summary}\n{delivered_response}")
             };
 
+            // The session keeps the loop's tool calls and results in full,
+            // followed by the reply as delivered.
+            let mut recorded = history[history_len_before_tools..].to_vec();
+            if recorded.last().is_some_and(|last| last.role == "assistant") {
+                recorded.pop();
+            }
+            recorded.push(ChatMessage::assistant(&delivered_response));
+            record_session(ctx.as_ref(), &history_key, &recorded);
+
             append_sender_turn(
                 ctx.as_ref(),
                 &history_key,
@@ -1839,6 +2043,33 @@ pub fn build_system_prompt_with_mode(
     bootstrap_max_chars: Option<usize>,
     native_tools: bool,
     skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+) -> String {
+    build_system_prompt_with_identity_dir(
+        workspace_dir,
+        workspace_dir,
+        model_name,
+        tools,
+        skills,
+        identity_config,
+        bootstrap_max_chars,
+        native_tools,
+        skills_prompt_mode,
+    )
+}
+
+/// Like [`build_system_prompt_with_mode`], but reads identity files (and
+/// resolves AIEOS paths) from `identity_dir` instead of the workspace root.
+/// Used for personas that keep their own SOUL.md/IDENTITY.md.
+pub(crate) fn build_system_prompt_with_identity_dir(
+    workspace_dir: &std::path::Path,
+    identity_dir: &std::path::Path,
+    model_name: &str,
+    tools: &[(&str, &str)],
+    skills: &[crate::skills::Skill],
+    identity_config: Option<&crate::config::IdentityConfig>,
+    bootstrap_max_chars: Option<usize>,
+    native_tools: bool,
+    skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
 ) -> String {
     use std::fmt::Write;
     let mut prompt = String::with_capacity(8192);
@@ -1925,7 +2156,7 @@ pub fn build_system_prompt_with_mode(
     if let Some(config) = identity_config {
         if identity::is_aieos_configured(config) {
             // Load AIEOS identity
-            match identity::load_aieos_identity(config, workspace_dir) {
+            match identity::load_aieos_identity(config, identity_dir) {
                 Ok(Some(aieos_identity)) => {
                     let aieos_prompt = identity::aieos_to_system_prompt(&aieos_identity);
                     if !aieos_prompt.is_empty() {
@@ -1937,7 +2168,7 @@ pub fn build_system_prompt_with_mode(
                     // No AIEOS identity loaded (shouldn't happen if is_aieos_configured returned true)
                     // Fall back to OpenClaw bootstrap files
                     let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
+                    load_openclaw_bootstrap_files(&mut prompt, identity_dir, max_chars);
                 }
                 Err(e) => {
                     // Log error but don't fail - fall back to OpenClaw
@@ -1945,18 +2176,18 @@ pub fn build_system_prompt_with_mode(
                         "Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format."
                     );
                     let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
+                    load_openclaw_bootstrap_files(&mut prompt, identity_dir, max_chars);
                 }
             }
         } else {
             // OpenClaw format
             let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-            load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
+            load_openclaw_bootstrap_files(&mut prompt, identity_dir, max_chars);
         }
     } else {
         // No identity config - use OpenClaw format
         let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-        load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
+        load_openclaw_bootstrap_files(&mut prompt, identity_dir, max_chars);
     }
 
     // ── 6. Date & Time ──────────────────────────────────────────
@@ -2307,11 +2538,15 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     if let Some(ref sl) = config.channels_config.slack {
         channels.push((
             "Slack",
-            Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_app_token(sl.app_token.clone())
+                .with_streaming(sl.stream_mode, sl.draft_update_interval_ms),
+            ),
         ));
     }
 
@@ -2528,6 +2763,200 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Build every configured channel. Used by the channel server and by the
+/// `send_message` tool.
+fn collect_channels(config: &Config) -> Vec<Arc<dyn Channel>> {
+    let mut channels: Vec<Arc<dyn Channel>> = Vec::new();
+
+    if let Some(ref tg) = config.channels_config.telegram {
+        channels.push(Arc::new(
+            TelegramChannel::new(
+                tg.bot_token.clone(),
+                tg.allowed_users.clone(),
+                tg.mention_only,
+            )
+            .with_streaming(tg.stream_mode, tg.draft_update_interval_ms),
+        ));
+    }
+
+    if let Some(ref dc) = config.channels_config.discord {
+        channels.push(Arc::new(DiscordChannel::new(
+            dc.bot_token.clone(),
+            dc.guild_id.clone(),
+            dc.allowed_users.clone(),
+            dc.listen_to_bots,
+            dc.mention_only,
+        )));
+    }
+
+    if let Some(ref sl) = config.channels_config.slack {
+        channels.push(Arc::new(
+            SlackChannel::new(
+                sl.bot_token.clone(),
+                sl.channel_id.clone(),
+                sl.allowed_users.clone(),
+            )
+            .with_app_token(sl.app_token.clone())
+            .with_streaming(sl.stream_mode, sl.draft_update_interval_ms),
+        ));
+    }
+
+    if let Some(ref mm) = config.channels_config.mattermost {
+        channels.push(Arc::new(MattermostChannel::new(
+            mm.url.clone(),
+            mm.bot_token.clone(),
+            mm.channel_id.clone(),
+            mm.allowed_users.clone(),
+            mm.thread_replies.unwrap_or(true),
+            mm.mention_only.unwrap_or(false),
+        )));
+    }
+
+    if let Some(ref im) = config.channels_config.imessage {
+        channels.push(Arc::new(IMessageChannel::new(im.allowed_contacts.clone())));
+    }
+
+    #[cfg(feature = "channel-matrix")]
+    if let Some(ref mx) = config.channels_config.matrix {
+        channels.push(Arc::new(MatrixChannel::new_with_session_hint(
+            mx.homeserver.clone(),
+            mx.access_token.clone(),
+            mx.room_id.clone(),
+            mx.allowed_users.clone(),
+            mx.user_id.clone(),
+            mx.device_id.clone(),
+        )));
+    }
+
+    #[cfg(not(feature = "channel-matrix"))]
+    if config.channels_config.matrix.is_some() {
+        tracing::warn!(
+            "Matrix channel is configured but this build was compiled without `channel-matrix`; skipping Matrix."
+        );
+    }
+
+    if let Some(ref sig) = config.channels_config.signal {
+        channels.push(Arc::new(SignalChannel::new(
+            sig.http_url.clone(),
+            sig.account.clone(),
+            sig.group_id.clone(),
+            sig.allowed_from.clone(),
+            sig.ignore_attachments,
+            sig.ignore_stories,
+        )));
+    }
+
+    if let Some(ref wa) = config.channels_config.whatsapp {
+        if wa.is_ambiguous_config() {
+            tracing::warn!(
+                "WhatsApp config has both phone_number_id and session_path set; preferring Cloud API mode. Remove one selector to avoid ambiguity."
+            );
+        }
+        // Runtime negotiation: detect backend type from config
+        match wa.backend_type() {
+            "cloud" => {
+                // Cloud API mode: requires phone_number_id, access_token, verify_token
+                if wa.is_cloud_config() {
+                    channels.push(Arc::new(WhatsAppChannel::new(
+                        wa.access_token.clone().unwrap_or_default(),
+                        wa.phone_number_id.clone().unwrap_or_default(),
+                        wa.verify_token.clone().unwrap_or_default(),
+                        wa.allowed_numbers.clone(),
+                    )));
+                } else {
+                    tracing::warn!("WhatsApp Cloud API configured but missing required fields (phone_number_id, access_token, verify_token)");
+                }
+            }
+            "web" => {
+                // Web mode: requires session_path
+                #[cfg(feature = "whatsapp-web")]
+                if wa.is_web_config() {
+                    channels.push(Arc::new(WhatsAppWebChannel::new(
+                        wa.session_path.clone().unwrap_or_default(),
+                        wa.pair_phone.clone(),
+                        wa.pair_code.clone(),
+                        wa.allowed_numbers.clone(),
+                    )));
+                } else {
+                    tracing::warn!("WhatsApp Web configured but session_path not set");
+                }
+                #[cfg(not(feature = "whatsapp-web"))]
+                {
+                    tracing::warn!("WhatsApp Web backend requires 'whatsapp-web' feature. Enable with: cargo build --features whatsapp-web");
+                }
+            }
+            _ => {
+                tracing::warn!("WhatsApp config invalid: neither phone_number_id (Cloud API) nor session_path (Web) is set");
+            }
+        }
+    }
+
+    if let Some(ref lq) = config.channels_config.linq {
+        channels.push(Arc::new(LinqChannel::new(
+            lq.api_token.clone(),
+            lq.from_phone.clone(),
+            lq.allowed_senders.clone(),
+        )));
+    }
+
+    if let Some(ref nc) = config.channels_config.nextcloud_talk {
+        channels.push(Arc::new(NextcloudTalkChannel::new(
+            nc.base_url.clone(),
+            nc.app_token.clone(),
+            nc.allowed_users.clone(),
+        )));
+    }
+
+    if let Some(ref email_cfg) = config.channels_config.email {
+        channels.push(Arc::new(EmailChannel::new(email_cfg.clone())));
+    }
+
+    if let Some(ref irc) = config.channels_config.irc {
+        channels.push(Arc::new(IrcChannel::new(irc::IrcChannelConfig {
+            server: irc.server.clone(),
+            port: irc.port,
+            nickname: irc.nickname.clone(),
+            username: irc.username.clone(),
+            channels: irc.channels.clone(),
+            allowed_users: irc.allowed_users.clone(),
+            server_password: irc.server_password.clone(),
+            nickserv_password: irc.nickserv_password.clone(),
+            sasl_password: irc.sasl_password.clone(),
+            verify_tls: irc.verify_tls.unwrap_or(true),
+        })));
+    }
+
+    #[cfg(feature = "channel-lark")]
+    if let Some(ref lk) = config.channels_config.lark {
+        channels.push(Arc::new(LarkChannel::from_config(lk)));
+    }
+
+    #[cfg(not(feature = "channel-lark"))]
+    if config.channels_config.lark.is_some() {
+        tracing::warn!(
+            "Lark channel is configured but this build was compiled without `channel-lark`; skipping Lark."
+        );
+    }
+
+    if let Some(ref dt) = config.channels_config.dingtalk {
+        channels.push(Arc::new(DingTalkChannel::new(
+            dt.client_id.clone(),
+            dt.client_secret.clone(),
+            dt.allowed_users.clone(),
+        )));
+    }
+
+    if let Some(ref qq) = config.channels_config.qq {
+        channels.push(Arc::new(QQChannel::new(
+            qq.app_id.clone(),
+            qq.app_secret.clone(),
+            qq.allowed_users.clone(),
+        )));
+    }
+
+    channels
+}
+
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
@@ -2537,6 +2966,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        cassette: config.runtime.provider_cassette.clone(),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -2593,6 +3023,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
     } else {
         (None, None)
     };
+    let channels = collect_channels(&config);
+
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
     let mut all_tools = tools::all_tools_with_runtime(
@@ -2638,6 +3070,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file by exact replacement, line range or unified diff. Use when: changing part of a file without rewriting it. Don't use when: creating a new file.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -2653,246 +3089,78 @@ pub async fn start_channels(config: Config) -> Result<()> {
     ];
 
     if config.browser.enabled {
-        tool_descs.push((
-            "browser_open",
-            "Open approved HTTPS URLs in Brave Browser (allowlist-only, no scraping)",
-        ));
-    }
-    if config.composio.enabled {
-        tool_descs.push((
-            "composio",
-            "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). Use action='list' to discover actions, 'list_accounts' to retrieve connected account IDs, 'execute' to run (optionally with connected_account_id), and 'connect' for OAuth.",
-        ));
-    }
-    tool_descs.push((
-        "schedule",
-        "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
-    ));
-    tool_descs.push((
-        "pushover",
-        "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
-    ));
-    if !config.agents.is_empty() {
-        tool_descs.push((
-            "delegate",
-            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single prompt and returns its response.",
-        ));
-    }
-
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
-    let native_tools = provider.supports_native_tools();
-    let mut system_prompt = build_system_prompt_with_mode(
-        &workspace,
-        &model,
-        &tool_descs,
-        &skills,
-        Some(&config.identity),
-        bootstrap_max_chars,
-        native_tools,
-        config.skills.prompt_injection_mode,
-    );
-    if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
-    }
-
-    if !skills.is_empty() {
-        println!(
-            "  🧩 Skills:   {}",
-            skills
-                .iter()
-                .map(|s| s.name.as_str())
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
-    }
-
-    // Collect active channels
-    let mut channels: Vec<Arc<dyn Channel>> = Vec::new();
-
-    if let Some(ref tg) = config.channels_config.telegram {
-        channels.push(Arc::new(
-            TelegramChannel::new(
-                tg.bot_token.clone(),
-                tg.allowed_users.clone(),
-                tg.mention_only,
-            )
-            .with_streaming(tg.stream_mode, tg.draft_update_interval_ms),
-        ));
-    }
-
-    if let Some(ref dc) = config.channels_config.discord {
-        channels.push(Arc::new(DiscordChannel::new(
-            dc.bot_token.clone(),
-            dc.guild_id.clone(),
-            dc.allowed_users.clone(),
-            dc.listen_to_bots,
-            dc.mention_only,
-        )));
-    }
-
-    if let Some(ref sl) = config.channels_config.slack {
-        channels.push(Arc::new(SlackChannel::new(
-            sl.bot_token.clone(),
-            sl.channel_id.clone(),
-            sl.allowed_users.clone(),
-        )));
-    }
-
-    if let Some(ref mm) = config.channels_config.mattermost {
-        channels.push(Arc::new(MattermostChannel::new(
-            mm.url.clone(),
-            mm.bot_token.clone(),
-            mm.channel_id.clone(),
-            mm.allowed_users.clone(),
-            mm.thread_replies.unwrap_or(true),
-            mm.mention_only.unwrap_or(false),
-        )));
-    }
-
-    if let Some(ref im) = config.channels_config.imessage {
-        channels.push(Arc::new(IMessageChannel::new(im.allowed_contacts.clone())));
-    }
-
-    #[cfg(feature = "channel-matrix")]
-    if let Some(ref mx) = config.channels_config.matrix {
-        channels.push(Arc::new(MatrixChannel::new_with_session_hint(
-            mx.homeserver.clone(),
-            mx.access_token.clone(),
-            mx.room_id.clone(),
-            mx.allowed_users.clone(),
-            mx.user_id.clone(),
-            mx.device_id.clone(),
-        )));
-    }
-
-    #[cfg(not(feature = "channel-matrix"))]
-    if config.channels_config.matrix.is_some() {
-        tracing::warn!(
-            "Matrix channel is configured but this build was compiled without `channel-matrix`; skipping Matrix runtime startup."
-        );
-    }
-
-    if let Some(ref sig) = config.channels_config.signal {
-        channels.push(Arc::new(SignalChannel::new(
-            sig.http_url.clone(),
-            sig.account.clone(),
-            sig.group_id.clone(),
-            sig.allowed_from.clone(),
-            sig.ignore_attachments,
-            sig.ignore_stories,
-        )));
-    }
-
-    if let Some(ref wa) = config.channels_config.whatsapp {
-        if wa.is_ambiguous_config() {
-            tracing::warn!(
-                "WhatsApp config has both phone_number_id and session_path set; preferring Cloud API mode. Remove one selector to avoid ambiguity."
-            );
-        }
-        // Runtime negotiation: detect backend type from config
-        match wa.backend_type() {
-            "cloud" => {
-                // Cloud API mode: requires phone_number_id, access_token, verify_token
-                if wa.is_cloud_config() {
-                    channels.push(Arc::new(WhatsAppChannel::new(
-                        wa.access_token.clone().unwrap_or_default(),
-                        wa.phone_number_id.clone().unwrap_or_default(),
-                        wa.verify_token.clone().unwrap_or_default(),
-                        wa.allowed_numbers.clone(),
-                    )));
-                } else {
-                    tracing::warn!("WhatsApp Cloud API configured but missing required fields (phone_number_id, access_token, verify_token)");
-                }
-            }
-            "web" => {
-                // Web mode: requires session_path
-                #[cfg(feature = "whatsapp-web")]
-                if wa.is_web_config() {
-                    channels.push(Arc::new(WhatsAppWebChannel::new(
-                        wa.session_path.clone().unwrap_or_default(),
-                        wa.pair_phone.clone(),
-                        wa.pair_code.clone(),
-                        wa.allowed_numbers.clone(),
-                    )));
-                } else {
-                    tracing::warn!("WhatsApp Web configured but session_path not set");
-                }
-                #[cfg(not(feature = "whatsapp-web"))]
-                {
-                    tracing::warn!("WhatsApp Web backend requires 'whatsapp-web' feature. Enable with: cargo build --features whatsapp-web");
-                }
-            }
-            _ => {
-                tracing::warn!("WhatsApp config invalid: neither phone_number_id (Cloud API) nor session_path (Web) is set");
-            }
-        }
-    }
-
-    if let Some(ref lq) = config.channels_config.linq {
-        channels.push(Arc::new(LinqChannel::new(
-            lq.api_token.clone(),
-            lq.from_phone.clone(),
-            lq.allowed_senders.clone(),
-        )));
+        tool_descs.push((
+            "browser_open",
+            "Open approved HTTPS URLs in Brave Browser (allowlist-only, no scraping)",
+        ));
     }
-
-    if let Some(ref nc) = config.channels_config.nextcloud_talk {
-        channels.push(Arc::new(NextcloudTalkChannel::new(
-            nc.base_url.clone(),
-            nc.app_token.clone(),
-            nc.allowed_users.clone(),
-        )));
+    if config.composio.enabled {
+        tool_descs.push((
+            "composio",
+            "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). Use action='list' to discover actions, 'list_accounts' to retrieve connected account IDs, 'execute' to run (optionally with connected_account_id), and 'connect' for OAuth.",
+        ));
     }
-
-    if let Some(ref email_cfg) = config.channels_config.email {
-        channels.push(Arc::new(EmailChannel::new(email_cfg.clone())));
+    tool_descs.push((
+        "schedule",
+        "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
+    ));
+    if config.send_message.enabled {
+        tool_descs.push((
+            "send_message",
+            "Send a message to a named contact or recipient on any configured channel. Use when: notifying people proactively (e.g. post to #ops on Slack, DM on Telegram). Don't use when: replying in the current conversation.",
+        ));
     }
-
-    if let Some(ref irc) = config.channels_config.irc {
-        channels.push(Arc::new(IrcChannel::new(irc::IrcChannelConfig {
-            server: irc.server.clone(),
-            port: irc.port,
-            nickname: irc.nickname.clone(),
-            username: irc.username.clone(),
-            channels: irc.channels.clone(),
-            allowed_users: irc.allowed_users.clone(),
-            server_password: irc.server_password.clone(),
-            nickserv_password: irc.nickserv_password.clone(),
-            sasl_password: irc.sasl_password.clone(),
-            verify_tls: irc.verify_tls.unwrap_or(true),
-        })));
+    tool_descs.push((
+        "pushover",
+        "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
+    ));
+    if !config.agents.is_empty() {
+        tool_descs.push((
+            "delegate",
+            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). Pass agents or tasks to fan out concurrently; background=true returns an id to check with delegate_status/delegate_result or stop with delegate_cancel.",
+        ));
     }
 
-    #[cfg(feature = "channel-lark")]
-    if let Some(ref lk) = config.channels_config.lark {
-        channels.push(Arc::new(LarkChannel::from_config(lk)));
+    let bootstrap_max_chars = if config.agent.compact_context {
+        Some(6000)
+    } else {
+        None
+    };
+    let native_tools = provider.supports_native_tools();
+    let mut system_prompt = build_system_prompt_with_mode(
+        &workspace,
+        &model,
+        &tool_descs,
+        &skills,
+        Some(&config.identity),
+        bootstrap_max_chars,
+        native_tools,
+        config.skills.prompt_injection_mode,
+    );
+    if !native_tools {
+        system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
     }
 
-    #[cfg(not(feature = "channel-lark"))]
-    if config.channels_config.lark.is_some() {
-        tracing::warn!(
-            "Lark channel is configured but this build was compiled without `channel-lark`; skipping Lark runtime startup."
-        );
-    }
+    let persona_router = Arc::new(personas::build_personas(
+        &config,
+        &mem,
+        &tool_descs,
+        &skills,
+        native_tools,
+        bootstrap_max_chars,
+    )?);
 
-    if let Some(ref dt) = config.channels_config.dingtalk {
-        channels.push(Arc::new(DingTalkChannel::new(
-            dt.client_id.clone(),
-            dt.client_secret.clone(),
-            dt.allowed_users.clone(),
-        )));
-    }
+    let user_registry = Arc::new(tenancy::UserRegistry::from_config(&config)?);
 
-    if let Some(ref qq) = config.channels_config.qq {
-        channels.push(Arc::new(QQChannel::new(
-            qq.app_id.clone(),
-            qq.app_secret.clone(),
-            qq.allowed_users.clone(),
-        )));
+    if !skills.is_empty() {
+        println!(
+            "  🧩 Skills:   {}",
+            skills
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
     }
 
     if channels.is_empty() {
@@ -2902,6 +3170,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
     println!("🦀 ZeroClaw Channel Server");
     println!("  🤖 Model:    {model}");
+    let persona_names = persona_router.names();
+    if !persona_names.is_empty() {
+        println!("  🎭 Personas: {}", persona_names.join(", "));
+    }
+    let user_names = user_registry.names();
+    if !user_names.is_empty() {
+        println!("  👥 Users:    {}", user_names.join(", "));
+    }
     let effective_backend = memory::effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
@@ -2937,9 +3213,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
     // Single message bus — all channels send messages here
     let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
 
+    // Webhook-fed channels (e.g. Slack over the Events API) enter the bus
+    // through `dispatch_inbound` rather than a listener.
+    *inbound_bus().lock().unwrap_or_else(|e| e.into_inner()) = Some(tx.clone());
+    running_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone_from(&channels);
+
     // Spawn a listener for each channel
     let mut handles = Vec::new();
-    for ch in &channels {
+    for ch in channels.iter().filter(|ch| ch.needs_listener()) {
         handles.push(spawn_supervised_listener(
             ch.clone(),
             tx.clone(),
@@ -2947,7 +3231,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             max_backoff_secs,
         ));
     }
-    drop(tx); // Drop our copy so rx closes when all channels stop
+    drop(tx); // The registered bus sender keeps rx open for webhook-fed channels
 
     let channels_by_name = Arc::new(
         channels
@@ -2993,9 +3277,25 @@ pub async fn start_channels(config: Config) -> Result<()> {
         message_timeout_secs,
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
+        cron_config: config.cron.enabled.then(|| Arc::new(config.clone())),
+        session_store: config
+            .agent
+            .persist_sessions
+            .then(|| Arc::new(crate::sessions::SessionStore::new(&config.workspace_dir))),
+        personas: persona_router,
+        users: user_registry,
+        approval: crate::approval::ApprovalManager::for_dashboard(&config).map(Arc::new),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    inbound_bus()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    running_registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
 
     // Wait for all channel tasks
     for h in handles {
@@ -3038,6 +3338,28 @@ mod tests {
         tmp
     }
 
+    #[tokio::test]
+    async fn dispatch_inbound_reaches_registered_pipeline() {
+        let msg = traits::ChannelMessage {
+            id: "slack_C1_1.0".to_string(),
+            sender: "U1".to_string(),
+            reply_target: "C1".to_string(),
+            content: "hello".to_string(),
+            channel: "slack".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+        };
+        let msg = dispatch_inbound(msg).await.unwrap_err();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        *inbound_bus().lock().unwrap() = Some(tx);
+        let dispatched = dispatch_inbound(msg).await;
+        inbound_bus().lock().unwrap().take();
+
+        assert!(dispatched.is_ok());
+        assert_eq!(rx.recv().await.unwrap().content, "hello");
+    }
+
     #[test]
     fn effective_channel_message_timeout_secs_clamps_to_minimum() {
         assert_eq!(
@@ -3165,6 +3487,11 @@ mod tests {
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
@@ -3615,6 +3942,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -3640,6 +3972,151 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!sent_messages[0].contains("mock_price"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_records_tool_exchanges_in_session() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Arc::new(crate::sessions::SessionStore::new(tmp.path()));
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(ToolCallingProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(tmp.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: Some(store.clone()),
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-42".to_string(),
+                content: "What is the BTC price now?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sessions = store.list().unwrap();
+        assert_eq!(sessions.len(), 1);
+        let recorded = store.load(&sessions[0].id).unwrap();
+        assert_eq!(recorded.first().unwrap().role, "user");
+        assert!(recorded
+            .iter()
+            .any(|m| m.role == "assistant" && m.content.contains("mock_price")));
+        assert!(recorded
+            .iter()
+            .any(|m| m.content.contains("[Tool results]")));
+        let last = recorded.last().unwrap();
+        assert_eq!(last.role, "assistant");
+        assert!(last.content.starts_with("BTC is currently around"));
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_rejects_unregistered_senders_when_configured() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.tenancy.unknown_users = crate::config::UnknownUserPolicy::Reject;
+        config.users.insert(
+            "alice".into(),
+            crate::config::UserConfig {
+                identities: vec!["test-channel:alice".into()],
+                ..crate::config::UserConfig::default()
+            },
+        );
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(ToolCallingProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::from_config(&config).unwrap()),
+            approval: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "mallory".to_string(),
+                reply_target: "chat-42".to_string(),
+                content: "What is the BTC price now?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent_messages = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent_messages.len(), 1);
+        assert!(sent_messages[0].contains("only serves registered users"));
+        assert!(!sent_messages[0].contains("BTC is currently around"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_strips_unexecuted_tool_json_artifacts_from_reply() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -3672,6 +4149,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -3729,6 +4211,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -3795,6 +4282,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -3882,6 +4374,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -3951,6 +4448,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -4035,6 +4537,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -4104,6 +4611,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -4162,6 +4674,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -4331,6 +4848,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4409,6 +4931,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4499,6 +5026,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4571,6 +5103,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -5073,6 +5610,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -5156,6 +5698,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -5239,6 +5786,11 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cron_config: None,
+            session_store: None,
+            personas: Arc::new(personas::PersonaRouter::default()),
+            users: Arc::new(tenancy::UserRegistry::default()),
+            approval: None,
         });
 
         process_channel_message(
@@ -5571,6 +6123,28 @@ This is an example JSON object for profile settings."#;
         }
     }
 
+    struct NeverReturnChannel {
+        name: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for NeverReturnChannel {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            std::future::pending().await
+        }
+    }
+
     #[async_trait::async_trait]
     impl Channel for BlockUntilClosedChannel {
         fn name(&self) -> &str {
@@ -5618,6 +6192,23 @@ This is an example JSON object for profile settings."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    #[tokio::test]
+    async fn supervised_listener_stops_when_dispatcher_drops_receiver() {
+        let channel: Arc<dyn Channel> = Arc::new(NeverReturnChannel {
+            name: format!("test-supervised-stop-{}", uuid::Uuid::new_v4()),
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(rx);
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("listener should exit once the receiver is dropped")
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn supervised_listener_refreshes_health_while_running() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -0,0 +1,317 @@
+//! Named agent personas for the channel daemon.
+//!
+//! Each `[personas.<name>]` entry gets its own system prompt (identity files
+//! from `personas/<name>/` by default), model route, skill set, tool allowlist
+//! and memory namespace. `[[persona_routes]]` bind inbound messages to a
+//! persona by channel, chat id or sender; anything unmatched is served by the
+//! default identity.
+
+use super::{build_system_prompt_with_identity_dir, build_tool_instructions, traits};
+use crate::config::{Config, DelegateMemoryMode, PersonaConfig, PersonaRouteConfig};
+use crate::memory::{Memory, ScopedMemory};
+use crate::runtime;
+use crate::security::SecurityPolicy;
+use crate::skills::Skill;
+use crate::tools::{self, Tool};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Runtime pieces for one persona, built once at channel startup.
+pub(crate) struct Persona {
+    pub name: String,
+    pub system_prompt: String,
+    pub tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    pub memory: Arc<dyn Memory>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub temperature: Option<f64>,
+    /// Tool allowlist, reapplied when a per-user registry is built.
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// Picks the persona for an inbound message.
+#[derive(Default)]
+pub(crate) struct PersonaRouter {
+    routes: Vec<PersonaRouteConfig>,
+    personas: HashMap<String, Arc<Persona>>,
+}
+
+impl PersonaRouter {
+    pub fn new(routes: Vec<PersonaRouteConfig>, personas: Vec<Persona>) -> Self {
+        Self {
+            routes,
+            personas: personas
+                .into_iter()
+                .map(|persona| (persona.name.clone(), Arc::new(persona)))
+                .collect(),
+        }
+    }
+
+    /// First matching route wins; `None` means the default identity.
+    pub fn resolve(&self, msg: &traits::ChannelMessage) -> Option<Arc<Persona>> {
+        self.routes
+            .iter()
+            .find(|route| route_matches(route, msg))
+            .and_then(|route| self.personas.get(&route.persona).cloned())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.personas.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+fn route_matches(route: &PersonaRouteConfig, msg: &traits::ChannelMessage) -> bool {
+    route.channel.as_deref().is_none_or(|c| c == msg.channel)
+        && route.chat.as_deref().is_none_or(|c| c == msg.reply_target)
+        && route.sender.as_deref().is_none_or(|s| s == msg.sender)
+}
+
+/// Build every configured persona. `tool_descs` and `skills` are the default
+/// identity's; each persona sees the subset its allowlists permit.
+pub(crate) fn build_personas(
+    config: &Config,
+    base_memory: &Arc<dyn Memory>,
+    tool_descs: &[(&str, &str)],
+    skills: &[Skill],
+    native_tools: bool,
+    bootstrap_max_chars: Option<usize>,
+) -> Result<PersonaRouter> {
+    let mut personas = Vec::with_capacity(config.personas.len());
+    for (name, persona) in &config.personas {
+        personas.push(build_persona(
+            config,
+            name,
+            persona,
+            base_memory,
+            tool_descs,
+            skills,
+            native_tools,
+            bootstrap_max_chars,
+        )?);
+    }
+    Ok(PersonaRouter::new(config.persona_routes.clone(), personas))
+}
+
+fn build_persona(
+    config: &Config,
+    name: &str,
+    persona: &PersonaConfig,
+    base_memory: &Arc<dyn Memory>,
+    tool_descs: &[(&str, &str)],
+    skills: &[Skill],
+    native_tools: bool,
+    bootstrap_max_chars: Option<usize>,
+) -> Result<Persona> {
+    let workspace = &config.workspace_dir;
+    let identity_dir = workspace.join(
+        persona
+            .identity_dir
+            .clone()
+            .unwrap_or_else(|| format!("personas/{name}")),
+    );
+    if !identity_dir.is_dir() {
+        tracing::warn!(
+            persona = name,
+            "Persona identity directory {} does not exist; identity files will be reported missing",
+            identity_dir.display()
+        );
+    }
+
+    let (provider, model) = match persona
+        .model
+        .as_deref()
+        .and_then(|m| m.strip_prefix("hint:"))
+    {
+        Some(hint) => {
+            let route = config
+                .model_routes
+                .iter()
+                .find(|route| route.hint == hint)
+                .ok_or_else(|| anyhow::anyhow!("Persona '{name}': unknown model route '{hint}'"))?;
+            (Some(route.provider.clone()), Some(route.model.clone()))
+        }
+        None => (persona.provider.clone(), persona.model.clone()),
+    };
+
+    let memory: Arc<dyn Memory> = match persona.memory {
+        DelegateMemoryMode::Shared => Arc::clone(base_memory),
+        DelegateMemoryMode::Isolated => Arc::new(ScopedMemory::isolated(
+            Arc::clone(base_memory),
+            persona.memory_namespace.as_deref().unwrap_or(name),
+        )),
+        DelegateMemoryMode::ReadOnly => Arc::new(ScopedMemory::read_only(Arc::clone(base_memory))),
+    };
+
+    let security = Arc::new(SecurityPolicy::from_config(&config.autonomy, workspace));
+    let registry = build_tools_registry(
+        config,
+        &security,
+        &memory,
+        workspace,
+        persona.allowed_tools.as_deref(),
+    )?;
+
+    let allowed_descs: Vec<(&str, &str)> = tool_descs
+        .iter()
+        .filter(|(tool, _)| registry.iter().any(|t| t.name() == *tool))
+        .copied()
+        .collect();
+    let persona_skills: Vec<Skill> = match &persona.skills {
+        Some(names) => skills
+            .iter()
+            .filter(|skill| names.contains(&skill.name))
+            .cloned()
+            .collect(),
+        None => skills.to_vec(),
+    };
+
+    let model_name = model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_default();
+    let mut system_prompt = build_system_prompt_with_identity_dir(
+        workspace,
+        &identity_dir,
+        &model_name,
+        &allowed_descs,
+        &persona_skills,
+        Some(persona.identity.as_ref().unwrap_or(&config.identity)),
+        bootstrap_max_chars,
+        native_tools,
+        config.skills.prompt_injection_mode,
+    );
+    if !native_tools {
+        system_prompt.push_str(&build_tool_instructions(&registry));
+    }
+
+    Ok(Persona {
+        name: name.to_string(),
+        system_prompt,
+        tools_registry: Arc::new(registry),
+        memory,
+        provider,
+        model,
+        temperature: persona.temperature,
+        allowed_tools: persona.allowed_tools.clone(),
+    })
+}
+
+/// Full tool registry bound to `security`, `memory` and `workspace`, cut down
+/// to `allowed_tools` when set.
+pub(crate) fn build_tools_registry(
+    config: &Config,
+    security: &Arc<SecurityPolicy>,
+    memory: &Arc<dyn Memory>,
+    workspace: &Path,
+    allowed_tools: Option<&[String]>,
+) -> Result<Vec<Box<dyn Tool>>> {
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    let mut registry = tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        security,
+        Arc::from(runtime::create_runtime(&config.runtime)?),
+        Arc::clone(memory),
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        workspace,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    );
+    if let Some(allowed) = allowed_tools {
+        registry.retain(|tool| allowed.iter().any(|name| name == tool.name()));
+    }
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::NoneMemory;
+
+    fn message(channel: &str, chat: &str, sender: &str) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            reply_target: chat.into(),
+            content: "hi".into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+        }
+    }
+
+    fn route(persona: &str, channel: Option<&str>, chat: Option<&str>) -> PersonaRouteConfig {
+        PersonaRouteConfig {
+            persona: persona.into(),
+            channel: channel.map(Into::into),
+            chat: chat.map(Into::into),
+            sender: None,
+        }
+    }
+
+    #[test]
+    fn routes_by_chat_then_channel() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        for name in ["helpdesk", "sre"] {
+            config.personas.insert(
+                name.into(),
+                PersonaConfig {
+                    memory: DelegateMemoryMode::Isolated,
+                    allowed_tools: Some(vec!["memory_recall".into()]),
+                    ..PersonaConfig::default()
+                },
+            );
+        }
+        config.persona_routes = vec![
+            route("helpdesk", Some("slack"), Some("C-SUPPORT")),
+            route("sre", Some("telegram"), None),
+        ];
+        std::fs::create_dir_all(tmp.path().join("personas/helpdesk")).unwrap();
+        std::fs::write(
+            tmp.path().join("personas/helpdesk/SOUL.md"),
+            "You are the helpdesk agent.",
+        )
+        .unwrap();
+
+        let memory: Arc<dyn Memory> = Arc::new(NoneMemory::new());
+        let router = build_personas(&config, &memory, &[], &[], true, None).unwrap();
+        assert_eq!(router.names(), vec!["helpdesk", "sre"]);
+
+        let helpdesk = router
+            .resolve(&message("slack", "C-SUPPORT", "U1"))
+            .unwrap();
+        assert_eq!(helpdesk.name, "helpdesk");
+        assert!(helpdesk
+            .system_prompt
+            .contains("You are the helpdesk agent."));
+        assert_eq!(helpdesk.tools_registry.len(), 1);
+        assert_eq!(helpdesk.tools_registry[0].name(), "memory_recall");
+
+        assert_eq!(
+            router
+                .resolve(&message("telegram", "-100", "U2"))
+                .unwrap()
+                .name,
+            "sre"
+        );
+        assert!(router.resolve(&message("slack", "C-OTHER", "U1")).is_none());
+    }
+}
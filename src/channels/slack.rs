@@ -1,22 +1,64 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
-
-/// Slack channel — polls conversations.history via Web API
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Threads the bot has joined; forgotten wholesale once this many pile up.
+const MAX_ACTIVE_THREADS: usize = 1024;
+
+/// Slack channel — Socket Mode over WebSocket when an app-level token is set,
+/// otherwise polls `conversations.history` for one channel. The gateway's
+/// `/slack/events` endpoint feeds Events API deliveries through the same
+/// event parser.
 pub struct SlackChannel {
     bot_token: String,
+    app_token: Option<String>,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
+    bot_user_id: tokio::sync::OnceCell<String>,
+    /// `channel:thread_ts` pairs whose follow-up replies reach the bot without a mention.
+    active_threads: Mutex<HashSet<String>>,
 }
 
 impl SlackChannel {
     pub fn new(bot_token: String, channel_id: Option<String>, allowed_users: Vec<String>) -> Self {
         Self {
             bot_token,
+            app_token: None,
             channel_id,
             allowed_users,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
+            bot_user_id: tokio::sync::OnceCell::new(),
+            active_threads: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Receive events over Socket Mode using an app-level token (`xapp-...`).
+    pub fn with_app_token(mut self, app_token: Option<String>) -> Self {
+        self.app_token = app_token.filter(|token| !token.trim().is_empty());
+        self
+    }
+
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
@@ -46,6 +88,14 @@ impl SlackChannel {
             .map(String::from)
     }
 
+    /// Bot user ID, looked up once. Empty when `auth.test` fails.
+    pub async fn bot_user_id(&self) -> String {
+        self.bot_user_id
+            .get_or_init(|| async { self.get_bot_user_id().await.unwrap_or_default() })
+            .await
+            .clone()
+    }
+
     /// Resolve the thread identifier for inbound Slack messages.
     /// Replies carry `thread_ts` (root thread id); top-level messages only have `ts`.
     fn inbound_thread_ts(msg: &serde_json::Value, ts: &str) -> Option<String> {
@@ -54,29 +104,126 @@ impl SlackChannel {
             .or(if ts.is_empty() { None } else { Some(ts) })
             .map(str::to_string)
     }
-}
 
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
+    fn remember_thread(&self, channel: &str, thread_ts: &str) {
+        let mut threads = self.active_threads.lock();
+        if threads.len() >= MAX_ACTIVE_THREADS {
+            threads.clear();
+        }
+        threads.insert(format!("{channel}:{thread_ts}"));
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let mut body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
+    fn is_active_thread(&self, channel: &str, thread_ts: &str) -> bool {
+        self.active_threads
+            .lock()
+            .contains(&format!("{channel}:{thread_ts}"))
+    }
 
-        if let Some(ref ts) = message.thread_ts {
-            body["thread_ts"] = serde_json::json!(ts);
+    /// Turn an Events API `event` object into a channel message.
+    ///
+    /// Handles direct messages (`message.im`), `app_mention` and replies in
+    /// threads the bot already takes part in. With `channel_id` set, every
+    /// top-level message in that channel is handled and other channels are
+    /// ignored apart from DMs.
+    pub fn parse_event(
+        &self,
+        event: &serde_json::Value,
+        bot_user_id: &str,
+    ) -> Option<ChannelMessage> {
+        let kind = event.get("type").and_then(|t| t.as_str())?;
+        if kind != "message" && kind != "app_mention" {
+            return None;
+        }
+        // Edits, deletions, joins and bot posts arrive as subtyped messages.
+        if let Some(subtype) = event.get("subtype").and_then(|s| s.as_str()) {
+            if subtype != "thread_broadcast" && subtype != "file_share" {
+                return None;
+            }
+        }
+        if event.get("bot_id").is_some() {
+            return None;
+        }
+
+        let user = event.get("user").and_then(|u| u.as_str())?;
+        if !bot_user_id.is_empty() && user == bot_user_id {
+            return None;
+        }
+        let channel = event.get("channel").and_then(|c| c.as_str())?;
+        let ts = event.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+        let text = event.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        let thread_ts = event.get("thread_ts").and_then(|t| t.as_str());
+        let is_dm = event.get("channel_type").and_then(|c| c.as_str()) == Some("im")
+            || channel.starts_with('D');
+        let mention = format!("<@{bot_user_id}>");
+
+        if let Some(only) = self.channel_id.as_deref() {
+            if !is_dm && only != channel {
+                return None;
+            }
+        }
+        if kind == "message" && !is_dm {
+            // Mentions are delivered a second time as `app_mention`; answer those instead.
+            if !bot_user_id.is_empty() && text.contains(&mention) {
+                return None;
+            }
+            let pinned = self.channel_id.as_deref() == Some(channel);
+            let followed = thread_ts.is_some_and(|t| self.is_active_thread(channel, t));
+            if !pinned && !followed {
+                return None;
+            }
+        }
+
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+            return None;
         }
 
+        let content = if bot_user_id.is_empty() {
+            text.trim().to_string()
+        } else {
+            text.replace(&mention, "").trim().to_string()
+        };
+        if content.is_empty() {
+            return None;
+        }
+
+        // DMs stay flat unless the user replied in a thread.
+        let reply_thread = if is_dm {
+            thread_ts.map(str::to_string)
+        } else {
+            Self::inbound_thread_ts(event, ts)
+        };
+        if let Some(thread) = reply_thread.as_deref() {
+            if !is_dm {
+                self.remember_thread(channel, thread);
+            }
+        }
+
+        Some(ChannelMessage {
+            id: format!("slack_{channel}_{ts}"),
+            sender: user.to_string(),
+            reply_target: channel.to_string(),
+            content,
+            channel: "slack".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: reply_thread,
+        })
+    }
+
+    /// POST a Web API method with the bot token and return the parsed body.
+    async fn api_call(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(format!("https://slack.com/api/{method}"))
             .bearer_auth(&self.bot_token)
-            .json(&body)
+            .json(body)
             .send()
             .await?;
 
@@ -87,7 +234,7 @@ impl Channel for SlackChannel {
             .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
 
         if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
         }
 
         // Slack returns 200 for most app-level errors; check JSON "ok" field
@@ -97,19 +244,123 @@ impl Channel for SlackChannel {
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack {method} failed: {err}");
         }
 
-        Ok(())
+        Ok(parsed)
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        let channel_id = self
-            .channel_id
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("Slack channel_id required for listening"))?;
+    /// Ask Slack for a fresh Socket Mode WebSocket URL.
+    async fn open_socket_url(&self, app_token: &str) -> anyhow::Result<String> {
+        let resp: serde_json::Value = self
+            .http_client()
+            .post("https://slack.com/api/apps.connections.open")
+            .bearer_auth(app_token)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if resp.get("ok") != Some(&serde_json::Value::Bool(true)) {
+            let err = resp
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack apps.connections.open failed: {err}");
+        }
+        resp.get("url")
+            .and_then(|u| u.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Slack apps.connections.open returned no url"))
+    }
+
+    async fn listen_socket_mode(
+        &self,
+        app_token: &str,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let bot_user_id = self.bot_user_id().await;
+
+        loop {
+            let url = match self.open_socket_url(app_token).await {
+                Ok(url) => url,
+                Err(e) => {
+                    tracing::warn!("Slack Socket Mode connect error: {e}");
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("Slack Socket Mode connect error: {e}");
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            tracing::info!("Slack channel listening via Socket Mode...");
+            let (mut write, mut read) = ws_stream.split();
+
+            while let Some(frame) = read.next().await {
+                let text = match frame {
+                    Ok(Message::Text(t)) => t,
+                    Ok(Message::Ping(payload)) => {
+                        let _ = write.send(Message::Pong(payload)).await;
+                        continue;
+                    }
+                    Ok(Message::Close(_)) => break,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!("Slack Socket Mode stream error: {e}");
+                        break;
+                    }
+                };
+                let Ok(envelope) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    tracing::warn!("Slack parse error: invalid Socket Mode envelope");
+                    continue;
+                };
+
+                // Every envelope must be acknowledged or Slack redelivers it.
+                if let Some(envelope_id) = envelope.get("envelope_id").and_then(|e| e.as_str()) {
+                    let ack = serde_json::json!({ "envelope_id": envelope_id });
+                    if write
+                        .send(Message::Text(ack.to_string().into()))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
 
-        let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
+                match envelope.get("type").and_then(|t| t.as_str()) {
+                    Some("disconnect") => {
+                        tracing::info!("Slack Socket Mode: server requested reconnect");
+                        break;
+                    }
+                    Some("events_api") => {
+                        let Some(event) = envelope.pointer("/payload/event") else {
+                            continue;
+                        };
+                        if let Some(msg) = self.parse_event(event, &bot_user_id) {
+                            if tx.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    async fn listen_polling(
+        &self,
+        channel_id: String,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let bot_user_id = self.bot_user_id().await;
         let mut last_ts = String::new();
 
         tracing::info!("Slack channel listening on #{channel_id}...");
@@ -193,6 +444,81 @@ impl Channel for SlackChannel {
             }
         }
     }
+}
+
+/// Verify a Slack request signature (`X-Slack-Signature`).
+///
+/// Slack signs `"v0:{timestamp}:{body}"` with HMAC-SHA256 using the app's
+/// signing secret and sends `v0=<hex>`. Requests older than 300s are rejected.
+/// See: <https://api.slack.com/authentication/verifying-requests-from-slack>
+pub fn verify_slack_signature(secret: &str, timestamp: &str, body: &str, signature: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Ok(ts) = timestamp.parse::<i64>() else {
+        tracing::warn!("Slack: invalid request timestamp: {timestamp}");
+        return false;
+    };
+    let now = chrono::Utc::now().timestamp();
+    if (now - ts).unsigned_abs() > 300 {
+        tracing::warn!("Slack: rejecting stale request timestamp ({ts}, now={now})");
+        return false;
+    }
+
+    let Some(hex_sig) = signature.trim().strip_prefix("v0=") else {
+        return false;
+    };
+    let Ok(provided) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{timestamp}:{body}").as_bytes());
+
+    // Constant-time comparison
+    mac.verify_slice(&provided).is_ok()
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let mut body = serde_json::json!({
+            "channel": message.recipient,
+            "text": message.content
+        });
+
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        self.api_call("chat.postMessage", &body).await?;
+        Ok(())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if let Some(app_token) = self.app_token.as_deref() {
+            return self.listen_socket_mode(app_token, tx).await;
+        }
+
+        let channel_id = self.channel_id.clone().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Slack listening requires app_token (Socket Mode) or channel_id (polling); \
+                 for the Events API set signing_secret and point Slack at the gateway's /slack/events"
+            )
+        })?;
+        self.listen_polling(channel_id, tx).await
+    }
+
+    /// Without an app token or channel id the bot only receives events the
+    /// gateway's `/slack/events` endpoint hands to the channel pipeline.
+    fn needs_listener(&self) -> bool {
+        self.app_token.is_some() || self.channel_id.is_some()
+    }
 
     async fn health_check(&self) -> bool {
         self.http_client()
@@ -203,6 +529,91 @@ impl Channel for SlackChannel {
             .map(|r| r.status().is_success())
             .unwrap_or(false)
     }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        let mut body = serde_json::json!({
+            "channel": message.recipient,
+            "text": message.content
+        });
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        let resp = self.api_call("chat.postMessage", &body).await?;
+        let ts = resp
+            .get("ts")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Slack chat.postMessage returned no ts"))?
+            .to_string();
+
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+        Ok(Some(ts))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per channel; chat.update is a Tier 3 method.
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text
+        });
+        if let Err(e) = self.api_call("chat.update", &body).await {
+            tracing::debug!("Slack draft update failed: {e}");
+            return Ok(());
+        }
+
+        self.last_draft_edit
+            .lock()
+            .insert(recipient.to_string(), Instant::now());
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text
+        });
+        self.api_call("chat.update", &body).await?;
+        Ok(())
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id
+        });
+        self.api_call("chat.delete", &body).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -221,6 +632,16 @@ mod tests {
         assert_eq!(ch.channel_id, Some("C12345".to_string()));
     }
 
+    #[test]
+    fn events_only_slack_needs_no_listener() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!ch.needs_listener());
+        let ch = ch.with_app_token(Some("xapp-fake".into()));
+        assert!(ch.needs_listener());
+        let ch = SlackChannel::new("xoxb-fake".into(), Some("C12345".into()), vec![]);
+        assert!(ch.needs_listener());
+    }
+
     #[test]
     fn empty_allowlist_denies_everyone() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
@@ -346,4 +767,159 @@ mod tests {
         let thread_ts = SlackChannel::inbound_thread_ts(&msg, "");
         assert_eq!(thread_ts, None);
     }
+    // ── Events API / Socket Mode parsing ─────────────────────────
+
+    fn event_channel(channel_id: Option<&str>) -> SlackChannel {
+        SlackChannel::new(
+            "xoxb-fake".into(),
+            channel_id.map(Into::into),
+            vec!["*".into()],
+        )
+    }
+
+    #[test]
+    fn parse_event_accepts_direct_messages_without_threading() {
+        let ch = event_channel(None);
+        let event = serde_json::json!({
+            "type": "message",
+            "channel_type": "im",
+            "channel": "D123",
+            "user": "U1",
+            "text": "hello",
+            "ts": "100.1"
+        });
+
+        let msg = ch.parse_event(&event, "UBOT").unwrap();
+        assert_eq!(msg.reply_target, "D123");
+        assert_eq!(msg.sender, "U1");
+        assert_eq!(msg.content, "hello");
+        assert_eq!(msg.id, "slack_D123_100.1");
+        assert!(msg.thread_ts.is_none());
+    }
+
+    #[test]
+    fn parse_event_handles_mentions_and_follows_their_threads() {
+        let ch = event_channel(None);
+        let mention = serde_json::json!({
+            "type": "app_mention",
+            "channel": "C9",
+            "user": "U1",
+            "text": "<@UBOT> deploy status?",
+            "ts": "200.1"
+        });
+        // The duplicate `message` event for the same mention is dropped.
+        let mut duplicate = mention.clone();
+        duplicate["type"] = "message".into();
+        assert!(ch.parse_event(&duplicate, "UBOT").is_none());
+
+        let msg = ch.parse_event(&mention, "UBOT").unwrap();
+        assert_eq!(msg.content, "deploy status?");
+        assert_eq!(msg.thread_ts.as_deref(), Some("200.1"));
+
+        let follow_up = serde_json::json!({
+            "type": "message",
+            "channel": "C9",
+            "user": "U2",
+            "text": "and staging?",
+            "ts": "200.5",
+            "thread_ts": "200.1"
+        });
+        let msg = ch.parse_event(&follow_up, "UBOT").unwrap();
+        assert_eq!(msg.thread_ts.as_deref(), Some("200.1"));
+
+        let unrelated = serde_json::json!({
+            "type": "message",
+            "channel": "C9",
+            "user": "U2",
+            "text": "lunch?",
+            "ts": "300.1"
+        });
+        assert!(ch.parse_event(&unrelated, "UBOT").is_none());
+    }
+
+    #[test]
+    fn parse_event_skips_bots_edits_and_unauthorized_users() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["U1".into()]);
+        let base = serde_json::json!({
+            "type": "message",
+            "channel_type": "im",
+            "channel": "D1",
+            "user": "U1",
+            "text": "hi",
+            "ts": "1.0"
+        });
+        assert!(ch.parse_event(&base, "UBOT").is_some());
+
+        let mut edited = base.clone();
+        edited["subtype"] = "message_changed".into();
+        assert!(ch.parse_event(&edited, "UBOT").is_none());
+
+        let mut from_bot = base.clone();
+        from_bot["bot_id"] = "B1".into();
+        assert!(ch.parse_event(&from_bot, "UBOT").is_none());
+
+        let mut own = base.clone();
+        own["user"] = "UBOT".into();
+        assert!(ch.parse_event(&own, "UBOT").is_none());
+
+        let mut stranger = base;
+        stranger["user"] = "U2".into();
+        assert!(ch.parse_event(&stranger, "UBOT").is_none());
+    }
+
+    #[test]
+    fn parse_event_respects_pinned_channel() {
+        let ch = event_channel(Some("C1"));
+        let top_level = serde_json::json!({
+            "type": "message",
+            "channel": "C1",
+            "user": "U1",
+            "text": "status",
+            "ts": "5.0"
+        });
+        assert!(ch.parse_event(&top_level, "UBOT").is_some());
+
+        let elsewhere = serde_json::json!({
+            "type": "app_mention",
+            "channel": "C2",
+            "user": "U1",
+            "text": "<@UBOT> status",
+            "ts": "6.0"
+        });
+        assert!(ch.parse_event(&elsewhere, "UBOT").is_none());
+    }
+
+    #[test]
+    fn slack_signature_verification() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let body = "token=xyz&team_id=T1";
+        let now = chrono::Utc::now().timestamp().to_string();
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("v0:{now}:{body}").as_bytes());
+        let signature = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_slack_signature(secret, &now, body, &signature));
+        assert!(!verify_slack_signature("other", &now, body, &signature));
+        assert!(!verify_slack_signature(
+            secret, &now, "tampered", &signature
+        ));
+        assert!(!verify_slack_signature(secret, "1000", body, &signature));
+        assert!(!verify_slack_signature(
+            secret,
+            &now,
+            body,
+            signature.trim_start_matches("v0=")
+        ));
+    }
+
+    #[test]
+    fn streaming_is_opt_in() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!ch.supports_draft_updates());
+        let ch = ch.with_streaming(StreamMode::Partial, 500);
+        assert!(ch.supports_draft_updates());
+    }
 }
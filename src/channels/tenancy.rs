@@ -0,0 +1,351 @@
+//! Multi-user tenancy for the channel daemon.
+//!
+//! `[users.<name>]` maps channel identities (`telegram:<id>`, `slack:<user>`,
+//! `email:<address>`) onto one ZeroClaw user. Every message from a registered
+//! user runs against that user's memory keyspace, workspace root and daily
+//! quota. Once any user is registered, unregistered senders get a read-only
+//! guest sandbox unless `[tenancy] unknown_users` says otherwise.
+
+use super::personas::{build_tools_registry, Persona};
+use crate::config::schema::ModelPricing;
+use crate::config::{Config, UnknownUserPolicy};
+use crate::memory::{Memory, ScopedMemory};
+use crate::providers::traits::TokenUsage;
+use crate::security::{AutonomyLevel, DailyQuota, SecurityPolicy};
+use crate::tools::Tool;
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+type ToolRegistry = Arc<Vec<Box<dyn Tool>>>;
+
+/// Tools left out of tenant registries: they change daemon-wide settings.
+const TENANT_EXCLUDED_TOOLS: &[&str] = &["proxy_config"];
+
+/// Runtime state for one registered user or sandboxed guest.
+#[derive(Debug)]
+pub(crate) struct TenantUser {
+    pub name: String,
+    pub workspace_dir: PathBuf,
+    pub quota: Arc<DailyQuota>,
+    /// Unregistered sender served under `unknown_users = "sandboxed"`.
+    pub guest: bool,
+}
+
+impl TenantUser {
+    /// Private keyspace for this user inside `base`.
+    pub fn memory(&self, base: &Arc<dyn Memory>) -> Arc<dyn Memory> {
+        Arc::new(ScopedMemory::isolated(
+            Arc::clone(base),
+            &format!("user:{}", self.name),
+        ))
+    }
+
+    /// Appended to the system prompt so the model knows whose workspace it is in.
+    pub fn prompt_note(&self) -> String {
+        if self.guest {
+            return format!(
+                "\n\n## Current User\n\nYou are talking to an unregistered guest. \
+                 Their sandbox is `{}`; tools are read-only and confined to it.\n",
+                self.workspace_dir.display()
+            );
+        }
+        format!(
+            "\n\n## Current User\n\nYou are talking to registered user `{}`. \
+             Their working directory is `{}`; file and shell tools are confined to it.\n",
+            self.name,
+            self.workspace_dir.display()
+        )
+    }
+}
+
+/// Resolves senders to users and hands out per-user tool registries.
+#[derive(Default)]
+pub(crate) struct UserRegistry {
+    config: Option<Arc<Config>>,
+    users: HashMap<String, Arc<TenantUser>>,
+    identities: HashMap<String, String>,
+    /// Policy for unregistered senders; `Shared` when no user is registered.
+    unknown_users: UnknownUserPolicy,
+    guests: Mutex<HashMap<String, Arc<TenantUser>>>,
+    tools: Mutex<HashMap<String, ToolRegistry>>,
+}
+
+impl UserRegistry {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut users = HashMap::with_capacity(config.users.len());
+        let mut identities = HashMap::new();
+        for (name, user) in &config.users {
+            let workspace_dir = config.workspace_dir.join(
+                user.workspace
+                    .clone()
+                    .unwrap_or_else(|| format!("users/{name}")),
+            );
+            std::fs::create_dir_all(&workspace_dir)?;
+            for identity in &user.identities {
+                identities.insert(identity.clone(), name.clone());
+            }
+            users.insert(
+                name.clone(),
+                Arc::new(TenantUser {
+                    name: name.clone(),
+                    workspace_dir,
+                    quota: Arc::new(DailyQuota::persistent(
+                        user.max_actions_per_day,
+                        user.max_cost_per_day_usd,
+                        &quota_path(config, name),
+                    )),
+                    guest: false,
+                }),
+            );
+        }
+        let unknown_users = if users.is_empty() {
+            UnknownUserPolicy::Shared
+        } else {
+            config.tenancy.unknown_users
+        };
+        Ok(Self {
+            config: Some(Arc::new(config.clone())),
+            users,
+            identities,
+            unknown_users,
+            guests: Mutex::new(HashMap::new()),
+            tools: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The registered user behind `sender`, or their guest sandbox under
+    /// `unknown_users = "sandboxed"`. `None` means the shared workspace, or a
+    /// refusal when [`Self::rejects_unknown`].
+    pub fn resolve(&self, channel: &str, sender: &str) -> Option<Arc<TenantUser>> {
+        let identity = format!("{channel}:{sender}");
+        if let Some(user) = self
+            .identities
+            .get(&identity)
+            .and_then(|name| self.users.get(name))
+        {
+            return Some(Arc::clone(user));
+        }
+        if self.unknown_users != UnknownUserPolicy::Sandboxed {
+            return None;
+        }
+        let config = self.config.as_deref()?;
+        let name = format!(
+            "guest-{}",
+            crate::sessions::channel_session_id(&format!("{channel}_{sender}"))
+        );
+        let mut guests = self.guests.lock();
+        if let Some(guest) = guests.get(&name) {
+            return Some(Arc::clone(guest));
+        }
+        let workspace_dir = config.workspace_dir.join("users/guests").join(&name);
+        if let Err(e) = std::fs::create_dir_all(&workspace_dir) {
+            tracing::warn!(
+                "Failed to create guest sandbox {}: {e}",
+                workspace_dir.display()
+            );
+        }
+        let guest = Arc::new(TenantUser {
+            quota: Arc::new(DailyQuota::persistent(
+                None,
+                None,
+                &quota_path(config, &name),
+            )),
+            name: name.clone(),
+            workspace_dir,
+            guest: true,
+        });
+        guests.insert(name, Arc::clone(&guest));
+        Some(guest)
+    }
+
+    pub fn rejects_unknown(&self) -> bool {
+        self.unknown_users == UnknownUserPolicy::Reject
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.users.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Tool registry confined to the user's workspace and charged against
+    /// their quota, built on first use and cached per persona. Tools see a
+    /// config rooted at the user's workspace with cron disabled, since the
+    /// scheduler only runs the daemon's own jobs under the daemon's policy.
+    pub fn tools_for(
+        &self,
+        user: &TenantUser,
+        persona: Option<&Persona>,
+        memory: &Arc<dyn Memory>,
+    ) -> Result<ToolRegistry> {
+        let Some(config) = self.config.as_deref() else {
+            anyhow::bail!("user registry was not built from a config");
+        };
+        let key = format!("{}/{}", persona.map_or("", |p| p.name.as_str()), user.name);
+        if let Some(registry) = self.tools.lock().get(&key) {
+            return Ok(Arc::clone(registry));
+        }
+
+        let mut user_config = config.clone();
+        user_config.workspace_dir = user.workspace_dir.clone();
+        user_config.cron.enabled = false;
+        if user.guest {
+            user_config.autonomy.level = AutonomyLevel::ReadOnly;
+        }
+        let mut security = SecurityPolicy::from_config(&user_config.autonomy, &user.workspace_dir);
+        security.quota = Some(Arc::clone(&user.quota));
+        let mut tools = build_tools_registry(
+            &user_config,
+            &Arc::new(security),
+            memory,
+            &user.workspace_dir,
+            persona.and_then(|p| p.allowed_tools.as_deref()),
+        )?;
+        tools.retain(|tool| !TENANT_EXCLUDED_TOOLS.contains(&tool.name()));
+        let registry = Arc::new(tools);
+        self.tools.lock().insert(key, Arc::clone(&registry));
+        Ok(registry)
+    }
+
+    /// USD cost of provider-reported `usage` using `[cost.prices]`, looked up
+    /// by `<provider>/<model>` then `<model>`. Unpriced models cost nothing.
+    pub fn estimate_cost_usd(&self, provider: &str, model: &str, usage: TokenUsage) -> Option<f64> {
+        let prices = &self.config.as_deref()?.cost.prices;
+        let pricing: &ModelPricing = prices
+            .get(&format!("{provider}/{model}"))
+            .or_else(|| prices.get(model))?;
+        #[allow(clippy::cast_precision_loss)]
+        let millions = |tokens: u64| tokens as f64 / 1_000_000.0;
+        Some(
+            millions(usage.input_tokens) * pricing.input
+                + millions(usage.output_tokens) * pricing.output,
+        )
+    }
+}
+
+/// Where a tenant's quota counters are kept, outside every user workspace.
+fn quota_path(config: &Config, name: &str) -> PathBuf {
+    config
+        .workspace_dir
+        .join("state")
+        .join("quota")
+        .join(format!("{name}.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UserConfig;
+    use crate::memory::NoneMemory;
+
+    #[test]
+    fn resolves_identities_and_builds_confined_tools() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.users.insert(
+            "alice".into(),
+            UserConfig {
+                identities: vec!["telegram:42".into(), "email:alice@example.com".into()],
+                max_actions_per_day: Some(3),
+                ..UserConfig::default()
+            },
+        );
+        config.cost.prices.insert(
+            "test/model".into(),
+            ModelPricing {
+                input: 1.0,
+                output: 2.0,
+            },
+        );
+
+        let registry = UserRegistry::from_config(&config).unwrap();
+        assert!(!registry.rejects_unknown());
+        let alice = registry.resolve("telegram", "42").unwrap();
+        assert_eq!(alice.name, "alice");
+        assert_eq!(
+            registry.resolve("email", "alice@example.com").unwrap().name,
+            "alice"
+        );
+        assert!(registry.resolve("telegram", "43").unwrap().guest);
+        assert!(registry.resolve("slack", "42").unwrap().guest);
+        assert_eq!(alice.workspace_dir, tmp.path().join("users/alice"));
+        assert!(alice.workspace_dir.is_dir());
+
+        let memory: Arc<dyn Memory> = Arc::new(NoneMemory::new());
+        let tools = registry.tools_for(&alice, None, &memory).unwrap();
+        assert!(!tools.is_empty());
+        let cached = registry.tools_for(&alice, None, &memory).unwrap();
+        assert!(Arc::ptr_eq(&tools, &cached));
+
+        assert!(!tools.iter().any(|tool| tool.name() == "proxy_config"));
+
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 500_000,
+        };
+        let cost = registry.estimate_cost_usd("test", "model", usage).unwrap();
+        assert!((cost - 2.0).abs() < f64::EPSILON);
+        assert!(registry.estimate_cost_usd("x", "unpriced", usage).is_none());
+
+        assert!(alice.quota.record_action());
+        let restarted = UserRegistry::from_config(&config).unwrap();
+        let alice = restarted.resolve("telegram", "42").unwrap();
+        assert_eq!(alice.quota.usage().0, 1);
+    }
+
+    #[test]
+    fn unknown_senders_get_private_read_only_sandboxes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        assert!(UserRegistry::from_config(&config)
+            .unwrap()
+            .resolve("telegram", "7")
+            .is_none());
+
+        config.users.insert(
+            "alice".into(),
+            UserConfig {
+                identities: vec!["telegram:42".into()],
+                ..UserConfig::default()
+            },
+        );
+        let registry = UserRegistry::from_config(&config).unwrap();
+        assert!(!registry.rejects_unknown());
+        let guest = registry.resolve("telegram", "7").unwrap();
+        assert!(guest.guest);
+        assert!(guest
+            .workspace_dir
+            .starts_with(tmp.path().join("users/guests")));
+        assert!(guest.workspace_dir.is_dir());
+        let again = registry.resolve("telegram", "7").unwrap();
+        assert!(Arc::ptr_eq(&guest, &again));
+        let other = registry.resolve("telegram", "8").unwrap();
+        assert_ne!(guest.workspace_dir, other.workspace_dir);
+
+        let memory: Arc<dyn Memory> = Arc::new(NoneMemory::new());
+        let tools = registry.tools_for(&guest, None, &memory).unwrap();
+        let write = tools
+            .iter()
+            .find(|tool| tool.name() == "file_write")
+            .unwrap();
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(write.execute(serde_json::json!({"path": "x.txt", "content": "x"})))
+            .unwrap();
+        assert!(!result.success);
+
+        config.tenancy.unknown_users = UnknownUserPolicy::Reject;
+        let registry = UserRegistry::from_config(&config).unwrap();
+        assert!(registry.rejects_unknown());
+        assert!(registry.resolve("telegram", "7").is_none());
+    }
+}
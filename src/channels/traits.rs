@@ -68,6 +68,12 @@ pub trait Channel: Send + Sync {
     /// Start listening for incoming messages (long-running)
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()>;
 
+    /// Whether `listen` must run for this channel to receive messages.
+    /// Channels fed only by gateway webhooks return `false`.
+    fn needs_listener(&self) -> bool {
+        true
+    }
+
     /// Check if channel is healthy
     async fn health_check(&self) -> bool {
         true
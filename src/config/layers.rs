@@ -0,0 +1,867 @@
+//! Config composition applied before `config.toml` is deserialized:
+//! `include = [...]` files, `[profiles.<name>]` / `config.<name>.toml`
+//! overlays, `${ENV}` interpolation and, last, the override file
+//! `Config::save` writes runtime changes of a composed config to.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Selects a profile when `--profile` is not given.
+pub const PROFILE_ENV: &str = "ZEROCLAW_PROFILE";
+
+const MAX_INCLUDE_DEPTH: usize = 8;
+const REDACTED: &str = "***REDACTED***";
+
+/// Where a loaded config came from.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    /// Files read, in merge order (included files before the file listing them).
+    pub files: Vec<PathBuf>,
+    /// Profile overlaid on the base config.
+    pub profile: Option<String>,
+    /// Source of each value, keyed by dotted path. Arrays are single values.
+    pub origins: BTreeMap<String, String>,
+    /// Dotted paths whose value was produced by `${ENV}` interpolation.
+    pub interpolated: Vec<String>,
+}
+
+impl ConfigSources {
+    /// Whether the config is more than one plain file. `Config::save` then
+    /// writes runtime changes to [`override_path`] instead of flattening the
+    /// config back into `config.toml`.
+    pub fn is_composed(&self) -> bool {
+        self.files.len() > 1 || self.profile.is_some() || !self.interpolated.is_empty()
+    }
+}
+
+/// The profile named by `ZEROCLAW_PROFILE`, if any.
+pub fn active_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// A parsed TOML table and the source of each of its values.
+#[derive(Default)]
+struct Layer {
+    table: toml::Table,
+    origins: BTreeMap<String, String>,
+}
+
+/// Where `Config::save` keeps runtime changes to a composed config:
+/// `config.override.toml`, or `config.<profile>.override.toml`.
+pub fn override_path(config_path: &Path, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => config_path.with_file_name(format!("config.{name}.override.toml")),
+        None => config_path.with_file_name("config.override.toml"),
+    }
+}
+
+/// Read `config_path` with its includes, overlay `profile`, expand `${ENV}`
+/// references (looked up with `env`) and apply the override file.
+pub fn compose(
+    config_path: &Path,
+    profile: Option<&str>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Result<(toml::Table, ConfigSources)> {
+    let (table, mut sources) = compose_base(config_path, profile, env)?;
+    let override_path = override_path(config_path, profile);
+    if !override_path.exists() {
+        return Ok((table, sources));
+    }
+    let contents = std::fs::read_to_string(&override_path)
+        .with_context(|| format!("Failed to read config file {}", override_path.display()))?;
+    let overrides: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file {}", override_path.display()))?;
+    let mut origins = BTreeMap::new();
+    record_origins(
+        &toml::Value::Table(overrides.clone()),
+        "",
+        &override_path.display().to_string(),
+        &mut origins,
+    );
+    let mut layer = Layer {
+        table,
+        origins: std::mem::take(&mut sources.origins),
+    };
+    merge(
+        &mut layer,
+        Layer {
+            table: overrides,
+            origins,
+        },
+    );
+    sources.origins = layer.origins;
+    sources.files.push(override_path);
+    Ok((layer.table, sources))
+}
+
+/// [`compose`] without the override file: the config as the user wrote it.
+pub fn compose_base(
+    config_path: &Path,
+    profile: Option<&str>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Result<(toml::Table, ConfigSources)> {
+    let mut sources = ConfigSources::default();
+    let mut layer = load_file(config_path, &mut Vec::new(), &mut sources.files)?;
+    let profiles = layer.table.remove("profiles");
+
+    if let Some(name) = profile {
+        let mut found = false;
+        if let Some(overlay) = profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(name))
+            .and_then(toml::Value::as_table)
+        {
+            let prefix = format!("profiles.{name}.");
+            let origins = layer
+                .origins
+                .iter()
+                .filter_map(|(path, origin)| {
+                    let path = path.strip_prefix(&prefix)?;
+                    Some((path.to_string(), format!("{origin} [profiles.{name}]")))
+                })
+                .collect();
+            merge(
+                &mut layer,
+                Layer {
+                    table: overlay.clone(),
+                    origins,
+                },
+            );
+            found = true;
+        }
+        let profile_file = config_path.with_file_name(format!("config.{name}.toml"));
+        if profile_file.exists() {
+            let overlay = load_file(&profile_file, &mut Vec::new(), &mut sources.files)?;
+            merge(&mut layer, overlay);
+            found = true;
+        }
+        if !found {
+            anyhow::bail!(
+                "Profile '{name}' not found: no [profiles.{name}] table and no {}",
+                profile_file.display()
+            );
+        }
+        sources.profile = Some(name.to_string());
+    }
+    layer
+        .origins
+        .retain(|path, _| path != "profiles" && !path.starts_with("profiles."));
+
+    let mut table = toml::Value::Table(layer.table);
+    let mut schema = None;
+    interpolate(
+        &mut table,
+        &mut Vec::new(),
+        env,
+        &mut schema,
+        &mut layer.origins,
+        &mut sources.interpolated,
+    )?;
+    sources.origins = layer.origins;
+    let toml::Value::Table(table) = table else {
+        unreachable!("root stays a table");
+    };
+    Ok((table, sources))
+}
+
+fn load_file(path: &Path, stack: &mut Vec<PathBuf>, files: &mut Vec<PathBuf>) -> Result<Layer> {
+    let canonical = std::fs::canonicalize(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    if stack.contains(&canonical) {
+        let chain: Vec<String> = stack
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        anyhow::bail!("Config include cycle: {}", chain.join(" -> "));
+    }
+    if stack.len() >= MAX_INCLUDE_DEPTH {
+        anyhow::bail!(
+            "Config includes nested deeper than {MAX_INCLUDE_DEPTH} levels at {}",
+            path.display()
+        );
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    let mut table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+    let includes = match table.remove("include") {
+        None => Vec::new(),
+        Some(toml::Value::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                toml::Value::String(include) => Ok(include),
+                other => anyhow::bail!(
+                    "{}: include entries must be file paths, got {}",
+                    path.display(),
+                    other.type_str()
+                ),
+            })
+            .collect::<Result<_>>()?,
+        Some(other) => anyhow::bail!(
+            "{}: include must be an array of file paths, got {}",
+            path.display(),
+            other.type_str()
+        ),
+    };
+
+    stack.push(canonical);
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let mut layer = Layer::default();
+    for include in includes {
+        let include_path = base_dir.join(shellexpand::tilde(&include).as_ref());
+        let included = load_file(&include_path, stack, files)
+            .with_context(|| format!("Included from {}", path.display()))?;
+        merge(&mut layer, included);
+    }
+    stack.pop();
+
+    let label = path.display().to_string();
+    let mut origins = BTreeMap::new();
+    record_origins(&toml::Value::Table(table.clone()), "", &label, &mut origins);
+    merge(&mut layer, Layer { table, origins });
+    files.push(path.to_path_buf());
+    Ok(layer)
+}
+
+/// Overlay `overlay` onto `base`: tables merge key by key, anything else
+/// (including arrays) replaces the base value.
+fn merge(base: &mut Layer, overlay: Layer) {
+    merge_tables(
+        &mut base.table,
+        overlay.table,
+        "",
+        &mut base.origins,
+        &overlay.origins,
+    );
+}
+
+fn merge_tables(
+    base: &mut toml::Table,
+    overlay: toml::Table,
+    prefix: &str,
+    base_origins: &mut BTreeMap<String, String>,
+    overlay_origins: &BTreeMap<String, String>,
+) {
+    for (key, value) in overlay {
+        let path = join_path(prefix, &key);
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_child)), toml::Value::Table(overlay_child)) => {
+                merge_tables(
+                    base_child,
+                    overlay_child,
+                    &path,
+                    base_origins,
+                    overlay_origins,
+                );
+            }
+            (_, value) => {
+                let nested = format!("{path}.");
+                base_origins.retain(|p, _| *p != path && !p.starts_with(&nested));
+                base_origins.extend(
+                    overlay_origins
+                        .iter()
+                        .filter(|(p, _)| **p == path || p.starts_with(&nested))
+                        .map(|(p, origin)| (p.clone(), origin.clone())),
+                );
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Values of `current` that differ from `base`, taken from `stored` (the
+/// same config as written to disk), as a table to overlay on `base`. Also
+/// returns the dotted paths set in `base` but absent from `current`, which an
+/// overlay cannot express.
+pub fn diff_tables(
+    base: &toml::Table,
+    current: &toml::Table,
+    stored: &toml::Table,
+) -> (toml::Table, Vec<String>) {
+    let mut changed = toml::Table::new();
+    let mut removed = Vec::new();
+    diff_into(base, current, stored, "", &mut changed, &mut removed);
+    (changed, removed)
+}
+
+fn diff_into(
+    base: &toml::Table,
+    current: &toml::Table,
+    stored: &toml::Table,
+    prefix: &str,
+    changed: &mut toml::Table,
+    removed: &mut Vec<String>,
+) {
+    for (key, value) in current {
+        let Some(stored_value) = stored.get(key) else {
+            continue;
+        };
+        match (base.get(key), value, stored_value) {
+            (Some(old), _, _) if old == value => {}
+            (
+                Some(toml::Value::Table(old)),
+                toml::Value::Table(new),
+                toml::Value::Table(stored_child),
+            ) => {
+                let mut child = toml::Table::new();
+                diff_into(
+                    old,
+                    new,
+                    stored_child,
+                    &join_path(prefix, key),
+                    &mut child,
+                    removed,
+                );
+                if !child.is_empty() {
+                    changed.insert(key.clone(), toml::Value::Table(child));
+                }
+            }
+            _ => {
+                changed.insert(key.clone(), stored_value.clone());
+            }
+        }
+    }
+    for key in base.keys().filter(|key| !current.contains_key(*key)) {
+        removed.push(join_path(prefix, key));
+    }
+}
+
+fn record_origins(
+    value: &toml::Value,
+    path: &str,
+    label: &str,
+    origins: &mut BTreeMap<String, String>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, child) in table {
+                record_origins(child, &join_path(path, key), label, origins);
+            }
+        }
+        _ => {
+            origins.insert(path.to_string(), label.to_string());
+        }
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn interpolate(
+    value: &mut toml::Value,
+    path: &mut Vec<String>,
+    env: &dyn Fn(&str) -> Option<String>,
+    schema: &mut Option<serde_json::Value>,
+    origins: &mut BTreeMap<String, String>,
+    interpolated: &mut Vec<String>,
+) -> Result<()> {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, child) in table.iter_mut() {
+                path.push(key.clone());
+                interpolate(child, path, env, schema, origins, interpolated)?;
+                path.pop();
+            }
+        }
+        toml::Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                interpolate(child, path, env, schema, origins, interpolated)?;
+                path.pop();
+            }
+        }
+        toml::Value::String(text) if text.contains('$') => {
+            let dotted = path.join(".");
+            let (expanded, vars) =
+                expand_env(text, env).with_context(|| format!("Config value `{dotted}`"))?;
+            if vars.is_empty() {
+                *text = expanded;
+                return Ok(());
+            }
+            let schema = schema.get_or_insert_with(|| {
+                serde_json::to_value(schemars::schema_for!(super::Config)).unwrap_or_default()
+            });
+            *value = coerce(&expanded, path, schema).with_context(|| {
+                format!(
+                    "Config value `{dotted}` expands to {expanded:?}, which does not match \
+                     the config schema (see `zeroclaw config schema`)"
+                )
+            })?;
+
+            // Values inside arrays are recorded against the array itself.
+            let origin_key = (1..=path.len())
+                .rev()
+                .map(|len| path[..len].join("."))
+                .find(|key| origins.contains_key(key))
+                .unwrap_or_else(|| dotted.clone());
+            let vars = vars
+                .iter()
+                .map(|var| format!("${{{var}}}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if let Some(origin) = origins.get_mut(&origin_key) {
+                let _ = write!(origin, " via {vars}");
+            }
+            interpolated.push(dotted);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Expand `${NAME}` and `${NAME:-default}`; `$${` is a literal `${`.
+/// Returns the expanded text and the variables it used.
+fn expand_env(text: &str, env: &dyn Fn(&str) -> Option<String>) -> Result<(String, Vec<String>)> {
+    let mut out = String::with_capacity(text.len());
+    let mut vars = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+        } else if let Some(body) = tail.strip_prefix("${") {
+            let end = body
+                .find('}')
+                .with_context(|| format!("unterminated `${{` in {text:?}"))?;
+            let (name, default) = match body[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&body[..end], None),
+            };
+            let valid = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                anyhow::bail!("invalid environment variable name {name:?} in {text:?}");
+            }
+            match (env(name), default) {
+                (Some(value), _) => out.push_str(&value),
+                (None, Some(default)) => out.push_str(default),
+                (None, None) => anyhow::bail!("environment variable {name} is not set"),
+            }
+            vars.push(name.to_string());
+            rest = &body[end + 1..];
+        } else {
+            out.push('$');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok((out, vars))
+}
+
+/// Turn an expanded string into the TOML type the schema expects at `path`.
+fn coerce(expanded: &str, path: &[String], schema: &serde_json::Value) -> Result<toml::Value> {
+    let mut types = Vec::new();
+    schema_types_at(schema, schema, path, &mut types);
+    if types.is_empty() || types.iter().any(|t| t == "string") {
+        return Ok(toml::Value::String(expanded.to_string()));
+    }
+    let trimmed = expanded.trim();
+    for kind in &types {
+        match kind.as_str() {
+            "boolean" => {
+                if let Ok(value) = trimmed.parse::<bool>() {
+                    return Ok(toml::Value::Boolean(value));
+                }
+            }
+            "integer" => {
+                if let Ok(value) = trimmed.parse::<i64>() {
+                    return Ok(toml::Value::Integer(value));
+                }
+            }
+            "number" => {
+                if let Ok(value) = trimmed.parse::<f64>() {
+                    return Ok(toml::Value::Float(value));
+                }
+            }
+            _ => {}
+        }
+    }
+    anyhow::bail!("expected {}", types.join(" or "))
+}
+
+/// Collect the JSON types allowed at `path`. Leaves `types` empty when the
+/// path is unknown to the schema.
+fn schema_types_at(
+    root: &serde_json::Value,
+    node: &serde_json::Value,
+    path: &[String],
+    types: &mut Vec<String>,
+) {
+    if let Some(reference) = node.get("$ref").and_then(serde_json::Value::as_str) {
+        if let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            schema_types_at(root, target, path, types);
+        }
+    }
+    for combinator in ["anyOf", "oneOf", "allOf"] {
+        if let Some(branches) = node.get(combinator).and_then(serde_json::Value::as_array) {
+            for branch in branches {
+                schema_types_at(root, branch, path, types);
+            }
+        }
+    }
+
+    let Some((first, rest)) = path.split_first() else {
+        match node.get("type") {
+            Some(serde_json::Value::String(kind)) => types.push(kind.clone()),
+            Some(serde_json::Value::Array(kinds)) => types.extend(
+                kinds
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::to_string),
+            ),
+            _ => {}
+        }
+        if node.get("enum").is_some() || node.get("const").is_some() {
+            types.push("string".into());
+        }
+        return;
+    };
+    if let Some(child) = node.get("properties").and_then(|props| props.get(first)) {
+        schema_types_at(root, child, rest, types);
+    } else if let Some(child) = node
+        .get("additionalProperties")
+        .filter(|child| child.is_object())
+    {
+        schema_types_at(root, child, rest, types);
+    }
+    if first.parse::<usize>().is_ok() {
+        if let Some(items) = node.get("items") {
+            schema_types_at(root, items, rest, types);
+        }
+    }
+}
+
+/// Render the effective config as TOML with secrets redacted and the source
+/// of each value in a trailing comment. `parsed` is the config as read from
+/// disk, before environment overrides; values that differ were overridden.
+pub fn render_resolved(
+    effective: &toml::Table,
+    parsed: &toml::Table,
+    sources: &ConfigSources,
+    secret_refs: &[crate::security::secret_refs::ResolvedSecret],
+) -> String {
+    let refs: BTreeMap<String, &str> = secret_refs
+        .iter()
+        .map(|secret| (secret.path.join("."), secret.reference.as_str()))
+        .collect();
+    let mut out = String::new();
+    render_table(
+        effective,
+        "",
+        "",
+        &mut |path, value| {
+            let redact = refs.contains_key(path) || is_secret_path(path);
+            let source = if let Some(reference) = refs.get(path) {
+                (*reference).to_string()
+            } else if lookup(parsed, path) != Some(value) {
+                "environment override".to_string()
+            } else {
+                sources
+                    .origins
+                    .get(path)
+                    .cloned()
+                    .unwrap_or_else(|| "default".to_string())
+            };
+            (redact, source)
+        },
+        &mut out,
+    );
+    out
+}
+
+/// `path` is the dotted lookup path; `header` the same path with keys quoted
+/// for a TOML table header.
+fn render_table(
+    table: &toml::Table,
+    path: &str,
+    header: &str,
+    annotate: &mut dyn FnMut(&str, &toml::Value) -> (bool, String),
+    out: &mut String,
+) {
+    let (tables, values): (Vec<_>, Vec<_>) = table
+        .iter()
+        .partition(|(_, value)| matches!(value, toml::Value::Table(_)));
+    if !values.is_empty() && !header.is_empty() {
+        let _ = writeln!(out, "\n[{header}]");
+    }
+    for (key, value) in values {
+        let (redact, source) = annotate(&join_path(path, key), value);
+        let shown = if redact {
+            redacted(value)
+        } else {
+            value.clone()
+        };
+        let _ = writeln!(out, "{} = {shown}  # {source}", toml_key(key));
+    }
+    for (key, value) in tables {
+        if let toml::Value::Table(child) = value {
+            render_table(
+                child,
+                &join_path(path, key),
+                &join_path(header, &toml_key(key)),
+                annotate,
+                out,
+            );
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, path: &str) -> Option<&'a toml::Value> {
+    let mut parts = path.split('.');
+    let mut node = table.get(parts.next()?)?;
+    for part in parts {
+        node = node.as_table()?.get(part)?;
+    }
+    Some(node)
+}
+
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Whether the last key of `path` names a credential.
+fn is_secret_path(path: &str) -> bool {
+    let key = path.rsplit('.').next().unwrap_or(path).to_ascii_lowercase();
+    key.ends_with("_key")
+        || key == "key"
+        || key == "db_url"
+        || ["token", "secret", "password", "passphrase", "credential"]
+            .iter()
+            .any(|word| key.contains(word))
+}
+
+fn redacted(value: &toml::Value) -> toml::Value {
+    match value {
+        toml::Value::String(text) if text.is_empty() => value.clone(),
+        toml::Value::String(_) => toml::Value::String(REDACTED.into()),
+        toml::Value::Array(items) => toml::Value::Array(items.iter().map(redacted).collect()),
+        toml::Value::Table(table) => toml::Value::Table(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), redacted(value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    fn write(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn override_file_applies_last_and_diff_keeps_only_changes() {
+        let tmp = TempDir::new().unwrap();
+        write(&tmp, "models.toml", "default_model = \"included\"\n");
+        let config = write(
+            &tmp,
+            "config.toml",
+            "include = [\"models.toml\"]\n[gateway]\nport = 3000\nhost = \"127.0.0.1\"\n",
+        );
+        let (base, _) = compose_base(&config, None, &no_env).unwrap();
+
+        let mut current = base.clone();
+        current["gateway"]
+            .as_table_mut()
+            .unwrap()
+            .insert("port".into(), toml::Value::Integer(4000));
+        current.remove("default_model");
+        let (changed, removed) = diff_tables(&base, &current, &current);
+        assert_eq!(
+            toml::to_string(&changed).unwrap(),
+            "[gateway]\nport = 4000\n"
+        );
+        assert_eq!(removed, vec!["default_model".to_string()]);
+
+        write(
+            &tmp,
+            "config.override.toml",
+            &toml::to_string(&changed).unwrap(),
+        );
+        let (table, sources) = compose(&config, None, &no_env).unwrap();
+        assert_eq!(table["gateway"]["port"].as_integer(), Some(4000));
+        assert_eq!(table["gateway"]["host"].as_str(), Some("127.0.0.1"));
+        assert_eq!(
+            sources.origins["gateway.port"],
+            tmp.path()
+                .join("config.override.toml")
+                .display()
+                .to_string()
+        );
+        assert_eq!(
+            override_path(&config, Some("prod")),
+            tmp.path().join("config.prod.override.toml")
+        );
+    }
+
+    #[test]
+    fn includes_merge_under_the_including_file() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            &tmp,
+            "channels.toml",
+            "[channels_config]\nmessage_timeout_secs = 90\ncli = false\n",
+        );
+        write(&tmp, "models.toml", "default_model = \"from-include\"\n");
+        let config = write(
+            &tmp,
+            "config.toml",
+            "include = [\"channels.toml\", \"models.toml\"]\ndefault_model = \"from-base\"\n\
+             [channels_config]\ncli = true\n",
+        );
+
+        let (table, sources) = compose(&config, None, &no_env).unwrap();
+        assert!(table.get("include").is_none());
+        assert_eq!(table["default_model"].as_str(), Some("from-base"));
+        assert_eq!(table["channels_config"]["cli"].as_bool(), Some(true));
+        assert_eq!(
+            table["channels_config"]["message_timeout_secs"].as_integer(),
+            Some(90)
+        );
+        assert_eq!(sources.files.len(), 3);
+        assert!(sources.origins["channels_config.message_timeout_secs"].ends_with("channels.toml"));
+        assert!(sources.origins["channels_config.cli"].ends_with("config.toml"));
+        assert!(sources.is_composed());
+    }
+
+    #[test]
+    fn include_cycles_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        write(&tmp, "a.toml", "include = [\"config.toml\"]\n");
+        let config = write(&tmp, "config.toml", "include = [\"a.toml\"]\n");
+        let err = compose(&config, None, &no_env).unwrap_err();
+        assert!(format!("{err:#}").contains("include cycle"), "{err:#}");
+    }
+
+    #[test]
+    fn profiles_overlay_from_table_and_file() {
+        let tmp = TempDir::new().unwrap();
+        let config = write(
+            &tmp,
+            "config.toml",
+            "default_model = \"base\"\ndefault_temperature = 0.7\n\
+             [profiles.prod]\ndefault_model = \"prod-model\"\n",
+        );
+        write(&tmp, "config.prod.toml", "default_temperature = 0.1\n");
+
+        let (table, sources) = compose(&config, None, &no_env).unwrap();
+        assert_eq!(table["default_model"].as_str(), Some("base"));
+        assert!(table.get("profiles").is_none());
+        assert!(!sources.is_composed());
+
+        let (table, sources) = compose(&config, Some("prod"), &no_env).unwrap();
+        assert_eq!(table["default_model"].as_str(), Some("prod-model"));
+        assert_eq!(table["default_temperature"].as_float(), Some(0.1));
+        assert_eq!(sources.profile.as_deref(), Some("prod"));
+        assert!(sources.origins["default_model"].ends_with("[profiles.prod]"));
+        assert!(sources.origins["default_temperature"].ends_with("config.prod.toml"));
+        assert!(!sources.origins.keys().any(|k| k.starts_with("profiles")));
+
+        let err = compose(&config, Some("staging"), &no_env).unwrap_err();
+        assert!(err.to_string().contains("Profile 'staging' not found"));
+    }
+
+    #[test]
+    fn env_interpolation_follows_the_schema_types() {
+        let tmp = TempDir::new().unwrap();
+        let config = write(
+            &tmp,
+            "config.toml",
+            "api_url = \"http://${HOST}:${API_PORT:-11434}\"\n\
+             default_temperature = \"${TEMP}\"\n\
+             [gateway]\nport = \"${PORT}\"\nrequire_pairing = \"${PAIRING}\"\n\
+             [agent]\nliteral = \"$${NOT_EXPANDED}\"\n",
+        );
+        let env = |name: &str| match name {
+            "HOST" => Some("10.0.0.1".to_string()),
+            "TEMP" => Some("0.25".to_string()),
+            "PORT" => Some("4100".to_string()),
+            "PAIRING" => Some("false".to_string()),
+            _ => None,
+        };
+
+        let (table, sources) = compose(&config, None, &env).unwrap();
+        assert_eq!(table["api_url"].as_str(), Some("http://10.0.0.1:11434"));
+        assert_eq!(table["default_temperature"].as_float(), Some(0.25));
+        assert_eq!(table["gateway"]["port"].as_integer(), Some(4100));
+        assert_eq!(table["gateway"]["require_pairing"].as_bool(), Some(false));
+        assert_eq!(table["agent"]["literal"].as_str(), Some("${NOT_EXPANDED}"));
+        assert!(sources.origins["gateway.port"].ends_with("via ${PORT}"));
+        assert!(sources.interpolated.contains(&"api_url".to_string()));
+
+        let bad_env = |name: &str| (name == "PORT").then(|| "not-a-port".to_string());
+        write(&tmp, "config.toml", "[gateway]\nport = \"${PORT}\"\n");
+        let err = compose(&config, None, &bad_env).unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("gateway.port"), "{message}");
+        assert!(message.contains("expected integer"), "{message}");
+
+        write(&tmp, "config.toml", "api_url = \"${MISSING}\"\n");
+        let err = compose(&config, None, &no_env).unwrap_err();
+        assert!(format!("{err:#}").contains("MISSING is not set"));
+    }
+
+    #[test]
+    fn resolved_view_redacts_secrets_and_names_sources() {
+        let effective: toml::Table = toml::from_str(
+            "api_key = \"sk-live\"\ndefault_model = \"m\"\ndefault_temperature = 0.2\n\
+             [gateway]\nport = 3000\npaired_tokens = [\"zc_1\"]\n",
+        )
+        .unwrap();
+        let parsed: toml::Table =
+            toml::from_str("api_key = \"sk-live\"\ndefault_model = \"m\"\ndefault_temperature = 0.7\n[gateway]\nport = 3000\npaired_tokens = [\"zc_1\"]\n")
+                .unwrap();
+        let mut sources = ConfigSources::default();
+        sources
+            .origins
+            .insert("default_model".into(), "/etc/zc/config.toml".into());
+
+        let out = render_resolved(&effective, &parsed, &sources, &[]);
+        assert!(!out.contains("sk-live"));
+        assert!(!out.contains("zc_1"));
+        assert!(out.contains("api_key = \"***REDACTED***\"  # default"));
+        assert!(out.contains("default_model = \"m\"  # /etc/zc/config.toml"));
+        assert!(out.contains("default_temperature = 0.2  # environment override"));
+        assert!(out.contains("\n[gateway]\n"));
+        assert!(out.contains("port = 3000  # default"));
+    }
+}
@@ -1,20 +1,28 @@
+pub mod layers;
 pub mod schema;
+pub mod validate;
+
+pub use layers::ConfigSources;
 
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig, CronConfig,
-    DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig, GatewayConfig,
+    AgentConfig, AuditConfig, AutonomyConfig, BackupConfig, BackupS3Config,
+    BrowserComputerUseConfig, BrowserConfig, CassetteMatching, CassetteMode, ChannelsConfig,
+    ClassificationRule, ComposioConfig, Config, CostConfig, CronConfig, DelegateAgentConfig,
+    DelegateMemoryMode, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
+    GatewayAdminConfig, GatewayConfig, GatewayOidcConfig, GatewayRbacConfig, GatewayRoleConfig,
     HardwareConfig, HardwareTransport, HeartbeatConfig, HttpRequestConfig, IMessageConfig,
-    IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig, ModelRouteConfig, MultimodalConfig,
-    NextcloudTalkConfig, ObservabilityConfig, PeripheralBoardConfig, PeripheralsConfig,
-    ProxyConfig, ProxyScope, QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig,
-    RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig,
-    SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TunnelConfig, WebSearchConfig,
-    WebhookConfig,
+    IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig, MessageContactConfig, ModelRouteConfig,
+    MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, OidcIntrospectionConfig,
+    OidcIssuerConfig, PeripheralBoardConfig, PeripheralsConfig, PersonaConfig, PersonaRouteConfig,
+    ProviderCassetteConfig, ProxyConfig, ProxyScope, QueryClassificationConfig, ReliabilityConfig,
+    ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig,
+    SecretsConfig, SecurityConfig, SendMessageConfig, SkillsConfig, SkillsPromptInjectionMode,
+    SlackConfig, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
+    TelegramConfig, TenancyConfig, TunnelConfig, UnknownUserPolicy, UserConfig, VaultConfig,
+    WebSearchConfig, WebhookConfig,
 };
 
 #[cfg(test)]
@@ -37,6 +37,8 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "tool.http_request",
     "tool.pushover",
     "memory.embeddings",
+    "secrets.vault",
+    "backup.s3",
     "tunnel.custom",
 ];
 
@@ -60,6 +62,12 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// `secret://` references resolved at load time; `save` writes them back.
+    #[serde(skip)]
+    pub secret_refs: Vec<crate::security::secret_refs::ResolvedSecret>,
+    /// Files, profile and `${ENV}` references the config was composed from.
+    #[serde(skip)]
+    pub sources: crate::config::layers::ConfigSources,
     /// API key for the selected provider. Overridden by `ZEROCLAW_API_KEY` or `API_KEY` env vars.
     pub api_key: Option<String>,
     /// Base URL override for provider API (e.g. "http://10.0.0.1:11434" for remote Ollama)
@@ -119,6 +127,10 @@ pub struct Config {
     #[serde(default)]
     pub cron: CronConfig,
 
+    /// Backups of the config directory and workspace (`[backup]`).
+    #[serde(default)]
+    pub backup: BackupConfig,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels_config]`).
     #[serde(default)]
     pub channels_config: ChannelsConfig,
@@ -139,6 +151,10 @@ pub struct Config {
     #[serde(default)]
     pub gateway: GatewayConfig,
 
+    /// Sandboxing, resource limits and audit logging (`[security]`).
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     /// Composio managed OAuth tools integration (`[composio]`).
     #[serde(default)]
     pub composio: ComposioConfig,
@@ -163,6 +179,10 @@ pub struct Config {
     #[serde(default)]
     pub web_search: WebSearchConfig,
 
+    /// Cross-channel `send_message` tool configuration (`[send_message]`).
+    #[serde(default)]
+    pub send_message: SendMessageConfig,
+
     /// Proxy configuration for outbound HTTP/HTTPS/SOCKS5 traffic (`[proxy]`).
     #[serde(default)]
     pub proxy: ProxyConfig,
@@ -183,6 +203,22 @@ pub struct Config {
     #[serde(default)]
     pub agents: HashMap<String, DelegateAgentConfig>,
 
+    /// Named agent personas served by the channel daemon (`[personas.<name>]`).
+    #[serde(default)]
+    pub personas: HashMap<String, PersonaConfig>,
+
+    /// Rules binding channels, chats or senders to a persona (`[[persona_routes]]`).
+    #[serde(default)]
+    pub persona_routes: Vec<PersonaRouteConfig>,
+
+    /// Registered users for multi-user deployments (`[users.<name>]`).
+    #[serde(default)]
+    pub users: HashMap<String, UserConfig>,
+
+    /// How the channel daemon treats senders that are not registered users (`[tenancy]`).
+    #[serde(default)]
+    pub tenancy: TenancyConfig,
+
     /// Hardware configuration (wizard-driven physical world setup).
     #[serde(default)]
     pub hardware: HardwareConfig,
@@ -218,6 +254,154 @@ pub struct DelegateAgentConfig {
     /// Maximum tool-call iterations in agentic mode.
     #[serde(default = "default_max_tool_iterations")]
     pub max_iterations: usize,
+    /// How the sub-agent sees the parent's memory.
+    #[serde(default)]
+    pub memory: DelegateMemoryMode,
+    /// Key prefix for `memory = "isolated"`. Defaults to the agent name.
+    #[serde(default)]
+    pub memory_namespace: Option<String>,
+    /// Autonomy level for the sub-agent's tools. Can only lower the parent's
+    /// level, never raise it.
+    #[serde(default)]
+    pub autonomy: Option<AutonomyLevel>,
+    /// Shell command allowlist for the sub-agent. Commands the parent does not
+    /// allow are dropped.
+    #[serde(default)]
+    pub allowed_commands: Option<Vec<String>>,
+    /// Workspace subdirectory (relative to the parent workspace) that the
+    /// sub-agent's file, search, shell and git tools are confined to.
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+impl DelegateAgentConfig {
+    /// Whether the agent overrides any part of the parent's memory, security
+    /// or workspace.
+    pub fn has_scoped_profile(&self) -> bool {
+        self.memory != DelegateMemoryMode::Shared
+            || self.autonomy.is_some()
+            || self.allowed_commands.is_some()
+            || self.workspace.is_some()
+    }
+}
+
+// ── Personas ─────────────────────────────────────────────────────
+
+/// A named agent profile served by the channel daemon alongside the default
+/// identity. Unset fields inherit the top-level configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PersonaConfig {
+    /// Directory (relative to the workspace) holding the persona's identity
+    /// files (SOUL.md, IDENTITY.md, AGENTS.md, ...). Default: `personas/<name>`.
+    #[serde(default)]
+    pub identity_dir: Option<String>,
+    /// Identity format for this persona. AIEOS paths resolve against
+    /// `identity_dir`. Defaults to the top-level `[identity]`.
+    #[serde(default)]
+    pub identity: Option<IdentityConfig>,
+    /// Provider override.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model override, or `hint:<name>` to use a `[[model_routes]]` entry.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Temperature override.
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Skill names to load. Default: all workspace skills.
+    #[serde(default)]
+    pub skills: Option<Vec<String>>,
+    /// Tool names available to the persona. Default: all tools.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Memory visibility. Default: `isolated` (private keyspace).
+    #[serde(default = "default_persona_memory")]
+    pub memory: DelegateMemoryMode,
+    /// Key prefix for `memory = "isolated"`. Defaults to the persona name.
+    #[serde(default)]
+    pub memory_namespace: Option<String>,
+}
+
+fn default_persona_memory() -> DelegateMemoryMode {
+    DelegateMemoryMode::Isolated
+}
+
+/// Binds inbound channel messages to a persona. Every field that is set must
+/// match; rules are checked in order and the first match wins. Messages that
+/// match no rule go to the default identity.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PersonaRouteConfig {
+    /// Persona name (a key of `[personas]`).
+    pub persona: String,
+    /// Channel name (e.g. `"slack"`, `"telegram"`).
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Chat, room or channel id the message arrived in (the reply target).
+    #[serde(default)]
+    pub chat: Option<String>,
+    /// Sender id.
+    #[serde(default)]
+    pub sender: Option<String>,
+}
+
+// ── Users / tenancy ──────────────────────────────────────────────
+
+/// A registered user. Messages from any of the user's channel identities share
+/// one private memory keyspace, workspace root and daily quota.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct UserConfig {
+    /// Channel identities as `<channel>:<sender id>`, e.g. `"telegram:123456789"`,
+    /// `"slack:U012AB3CD"` or `"email:alice@example.com"`.
+    #[serde(default)]
+    pub identities: Vec<String>,
+    /// Workspace root for this user, relative to the main workspace.
+    /// Default: `users/<name>`.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Maximum tool actions per day. `None` = only the global hourly limit applies.
+    #[serde(default)]
+    pub max_actions_per_day: Option<u32>,
+    /// Maximum model spend per day in USD, priced from provider-reported
+    /// token usage and `[cost.prices]`; models without a price entry are not
+    /// counted.
+    #[serde(default)]
+    pub max_cost_per_day_usd: Option<f64>,
+}
+
+/// What to do with messages from senders that match no `[users.<name>]`
+/// identity. Only applies once at least one user is registered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownUserPolicy {
+    /// Give each sender a private read-only guest workspace and memory
+    /// keyspace (default).
+    #[default]
+    Sandboxed,
+    /// Serve them with the shared workspace and memory.
+    Shared,
+    /// Refuse to answer.
+    Reject,
+}
+
+/// Multi-user settings (`[tenancy]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TenancyConfig {
+    /// Policy for senders that are not registered users.
+    #[serde(default)]
+    pub unknown_users: UnknownUserPolicy,
+}
+
+/// Memory visibility for a delegate sub-agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegateMemoryMode {
+    /// Read and write the parent's memory (default).
+    #[default]
+    Shared,
+    /// Private keyspace inside the parent's memory backend.
+    Isolated,
+    /// Recall the parent's memory but never store or forget.
+    ReadOnly,
 }
 
 fn default_max_depth() -> u32 {
@@ -317,6 +501,10 @@ pub struct AgentConfig {
     /// Tool dispatch strategy (e.g. `"auto"`). Default: `"auto"`.
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Record CLI and channel conversations under `<workspace>/sessions/`
+    /// for `zeroclaw sessions export`. Default: `false`.
+    #[serde(default)]
+    pub persist_sessions: bool,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -339,6 +527,7 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            persist_sessions: false,
         }
     }
 }
@@ -698,7 +887,7 @@ pub struct GatewayConfig {
     pub idempotency_max_keys: usize,
 
     /// Keycloak base URL for JWKS fetching (e.g. "https://auth.dev.oluto.app").
-    /// When set, JWT validation is enabled on /webhook.
+    /// Shorthand for one `[[gateway.oidc.issuers]]` entry for that realm.
     #[serde(default)]
     pub keycloak_url: Option<String>,
 
@@ -710,6 +899,287 @@ pub struct GatewayConfig {
     /// If not set, keycloak_url is used for both JWKS fetch and issuer check.
     #[serde(default)]
     pub keycloak_issuer_url: Option<String>,
+
+    /// Role-based access control for JWT-authenticated requests (`[gateway.rbac]`).
+    #[serde(default)]
+    pub rbac: GatewayRbacConfig,
+
+    /// OIDC/JWT authentication applied to every gateway route (`[gateway.oidc]`).
+    #[serde(default)]
+    pub oidc: GatewayOidcConfig,
+
+    /// Embedded admin dashboard and JSON admin API (`[gateway.admin]`).
+    #[serde(default)]
+    pub admin: GatewayAdminConfig,
+}
+
+impl GatewayConfig {
+    /// Configured OIDC issuers plus the one implied by `keycloak_url`, if set.
+    /// Empty = OIDC authentication is off.
+    pub fn oidc_issuers(&self) -> Vec<OidcIssuerConfig> {
+        let mut issuers = self.oidc.issuers.clone();
+        if let Some(url) = self.keycloak_url.as_deref() {
+            let base = url.trim_end_matches('/');
+            let issuer_base = self
+                .keycloak_issuer_url
+                .as_deref()
+                .map_or(base, |issuer| issuer.trim_end_matches('/'));
+            let realm = &self.keycloak_realm;
+            issuers.push(OidcIssuerConfig {
+                issuer: format!("{issuer_base}/realms/{realm}"),
+                jwks_uri: Some(format!(
+                    "{base}/realms/{realm}/protocol/openid-connect/certs"
+                )),
+                algorithms: vec!["RS256".into()],
+                // Keycloak access tokens carry no predictable `aud`; the
+                // legacy keys never checked it.
+                allow_any_audience: true,
+                ..OidcIssuerConfig::default()
+            });
+        }
+        issuers
+    }
+}
+
+/// Standards-based OIDC/JWT authentication for the gateway (`[gateway.oidc]`).
+///
+/// When at least one issuer is configured, every route except `public_paths`
+/// requires `Authorization: Bearer <token>`. JWTs are verified against the
+/// issuer's JWKS; other tokens are checked with RFC 7662 introspection.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayOidcConfig {
+    /// Trusted token issuers (Auth0, Okta, Google, Dex, Keycloak, ...).
+    #[serde(default)]
+    pub issuers: Vec<OidcIssuerConfig>,
+    /// Dot-separated claim path holding the user id. Default: `"sub"`.
+    #[serde(default = "default_oidc_user_claim")]
+    pub user_claim: String,
+    /// Dot-separated claim path holding the tenant (`business_id`). Default: `"business_id"`.
+    #[serde(default = "default_oidc_tenant_claim")]
+    pub tenant_claim: String,
+    /// Routes served without a token. A trailing `/*` matches a prefix.
+    /// Default: `/health` and the platform webhooks that verify their own signatures.
+    #[serde(default = "default_oidc_public_paths")]
+    pub public_paths: Vec<String>,
+    /// Allowed clock skew when checking `exp`/`nbf`, in seconds. Default: `60`.
+    #[serde(default = "default_oidc_leeway_secs")]
+    pub leeway_secs: u64,
+}
+
+/// One trusted token issuer (`[[gateway.oidc.issuers]]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct OidcIssuerConfig {
+    /// Exact `iss` value, e.g. `"https://dev-123.okta.com/oauth2/default"`.
+    pub issuer: String,
+    /// Accepted `aud` values. Required unless `allow_any_audience` is set.
+    #[serde(default)]
+    pub audiences: Vec<String>,
+    /// Accept tokens for any audience, for providers whose `aud` is not
+    /// predictable. The issuer check is then the only trust anchor.
+    #[serde(default)]
+    pub allow_any_audience: bool,
+    /// Discovery document. Default: `<issuer>/.well-known/openid-configuration`.
+    #[serde(default)]
+    pub discovery_url: Option<String>,
+    /// JWKS endpoint; when set, discovery is only used for introspection.
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+    /// Accepted JWS algorithms. Empty = RS256/384/512, PS256/384/512, ES256/384 and EdDSA.
+    #[serde(default)]
+    pub algorithms: Vec<String>,
+    /// Opaque-token introspection (`[gateway.oidc.issuers.introspection]`).
+    #[serde(default)]
+    pub introspection: Option<OidcIntrospectionConfig>,
+}
+
+/// RFC 7662 token introspection client credentials.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct OidcIntrospectionConfig {
+    /// Introspection endpoint. Default: `introspection_endpoint` from discovery.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Client id used for HTTP Basic authentication.
+    pub client_id: String,
+    /// Client secret (use a `secret://` reference rather than a literal).
+    pub client_secret: String,
+}
+
+fn default_oidc_user_claim() -> String {
+    "sub".into()
+}
+
+fn default_oidc_tenant_claim() -> String {
+    "business_id".into()
+}
+
+fn default_oidc_public_paths() -> Vec<String> {
+    [
+        "/health",
+        "/whatsapp",
+        "/linq",
+        "/nextcloud-talk",
+        "/slack/events",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_oidc_leeway_secs() -> u64 {
+    60
+}
+
+impl Default for GatewayOidcConfig {
+    fn default() -> Self {
+        Self {
+            issuers: Vec::new(),
+            user_claim: default_oidc_user_claim(),
+            tenant_claim: default_oidc_tenant_claim(),
+            public_paths: default_oidc_public_paths(),
+            leeway_secs: default_oidc_leeway_secs(),
+        }
+    }
+}
+
+/// Embedded admin dashboard served at `/admin` (`[gateway.admin]`).
+///
+/// The page itself is static; every `/admin/api/*` call needs a paired bearer
+/// token, or an OIDC token whose `[gateway.rbac]` role is `role`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayAdminConfig {
+    /// Serve the dashboard and admin API. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// RBAC role required for OIDC callers. Default: `"admin"`.
+    #[serde(default = "default_admin_role")]
+    pub role: String,
+    /// Queue tool calls that need approval on non-CLI channels for a decision
+    /// in the dashboard instead of denying them. Default: `false`.
+    #[serde(default)]
+    pub approvals: bool,
+    /// How long a queued approval waits before it is denied. Default: `300`.
+    #[serde(default = "default_admin_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+fn default_admin_role() -> String {
+    "admin".into()
+}
+
+fn default_admin_approval_timeout_secs() -> u64 {
+    300
+}
+
+impl Default for GatewayAdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            role: default_admin_role(),
+            approvals: false,
+            approval_timeout_secs: default_admin_approval_timeout_secs(),
+        }
+    }
+}
+
+/// Maps JWT claims to roles and roles to permissions (`[gateway.rbac]`).
+///
+/// Permissions are enforced in code before a tool runs; they do not depend on
+/// the model following instructions.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayRbacConfig {
+    /// Dot-separated path to the roles claim (a string or an array of strings).
+    #[serde(default = "default_rbac_claim")]
+    pub claim: String,
+    /// Role given to tokens that carry none of the configured role values.
+    #[serde(default = "default_rbac_default_role")]
+    pub default_role: String,
+    /// Role definitions, highest priority first. Empty = built-in
+    /// `admin` / `accountant` / `viewer`.
+    #[serde(default)]
+    pub roles: Vec<GatewayRoleConfig>,
+}
+
+/// One gateway role and what it may do (`[[gateway.rbac.roles]]`).
+/// Unset permission lists mean "no extra restriction".
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayRoleConfig {
+    /// Role name reported to skills and audit logs.
+    pub name: String,
+    /// Claim values that grant this role. Empty = the role name itself.
+    #[serde(default)]
+    pub claim_values: Vec<String>,
+    /// Tool allowlist.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Shell command allowlist, applied on top of `autonomy.allowed_commands`.
+    #[serde(default)]
+    pub allowed_commands: Option<Vec<String>>,
+    /// HTTP methods the `http_request` tool may use (e.g. `["GET"]`).
+    #[serde(default)]
+    pub http_methods: Option<Vec<String>>,
+    /// Autonomy ceiling; the lower of this and `autonomy.level` applies.
+    #[serde(default)]
+    pub autonomy: Option<AutonomyLevel>,
+    /// Memory categories the memory tools may read and write.
+    #[serde(default)]
+    pub memory_categories: Option<Vec<String>>,
+}
+
+fn default_rbac_claim() -> String {
+    "realm_access.roles".into()
+}
+
+fn default_rbac_default_role() -> String {
+    "viewer".into()
+}
+
+impl Default for GatewayRbacConfig {
+    fn default() -> Self {
+        Self {
+            claim: default_rbac_claim(),
+            default_role: default_rbac_default_role(),
+            roles: Vec::new(),
+        }
+    }
+}
+
+/// Tools the built-in `viewer` role may call; none of them change state.
+pub const VIEWER_TOOLS: &[&str] = &[
+    "file_read",
+    "glob_search",
+    "content_search",
+    "pdf_read",
+    "image_info",
+    "memory_recall",
+    "http_request",
+    "web_search_tool",
+    "cron_list",
+    "cron_runs",
+];
+
+impl GatewayRbacConfig {
+    /// Configured roles, or the built-in set when none are configured.
+    /// Built-in `viewer` is read-only: it gets [`VIEWER_TOOLS`], `GET`
+    /// requests only and the `read_only` autonomy ceiling.
+    pub fn effective_roles(&self) -> Vec<GatewayRoleConfig> {
+        if !self.roles.is_empty() {
+            return self.roles.clone();
+        }
+        let role = |name: &str| GatewayRoleConfig {
+            name: name.into(),
+            ..GatewayRoleConfig::default()
+        };
+        vec![
+            role("admin"),
+            role("accountant"),
+            GatewayRoleConfig {
+                allowed_tools: Some(VIEWER_TOOLS.iter().map(|t| (*t).to_string()).collect()),
+                http_methods: Some(vec!["GET".into()]),
+                autonomy: Some(AutonomyLevel::ReadOnly),
+                ..role("viewer")
+            },
+        ]
+    }
 }
 
 fn default_gateway_port() -> u16 {
@@ -765,6 +1235,9 @@ impl Default for GatewayConfig {
             keycloak_url: None,
             keycloak_realm: default_keycloak_realm(),
             keycloak_issuer_url: None,
+            rbac: GatewayRbacConfig::default(),
+            oidc: GatewayOidcConfig::default(),
+            admin: GatewayAdminConfig::default(),
         }
     }
 }
@@ -809,11 +1282,49 @@ pub struct SecretsConfig {
     /// Enable encryption for API keys and tokens in config.toml
     #[serde(default = "default_true")]
     pub encrypt: bool,
+
+    /// HashiCorp Vault used by `secret://vault/<path>#field` references (`[secrets.vault]`).
+    #[serde(default)]
+    pub vault: VaultConfig,
 }
 
 impl Default for SecretsConfig {
     fn default() -> Self {
-        Self { encrypt: true }
+        Self {
+            encrypt: true,
+            vault: VaultConfig::default(),
+        }
+    }
+}
+
+/// HashiCorp Vault KV v2 backend (`[secrets.vault]` section).
+///
+/// The token is never read from config: it comes from `VAULT_TOKEN` or the
+/// `~/.vault-token` file written by `vault login`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VaultConfig {
+    /// Vault server address. Falls back to `VAULT_ADDR`.
+    #[serde(default)]
+    pub address: Option<String>,
+    /// KV v2 secrets engine mount. Default: `"secret"`.
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+    /// Vault Enterprise namespace. Falls back to `VAULT_NAMESPACE`.
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+fn default_vault_mount() -> String {
+    "secret".into()
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            address: None,
+            mount: default_vault_mount(),
+            namespace: None,
+        }
     }
 }
 
@@ -998,6 +1509,38 @@ impl Default for WebSearchConfig {
     }
 }
 
+// ── Send message tool ───────────────────────────────────────────
+
+/// Cross-channel `send_message` tool configuration (`[send_message]` section).
+///
+/// Deny-by-default: a recipient must be a named contact, match
+/// `allowed_recipients`, or be listed in the target channel's own allowlist.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct SendMessageConfig {
+    /// Enable the `send_message` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Extra outbound recipients as `channel:recipient`, e.g. `"slack:C0123"`.
+    /// Either side may be `*`.
+    #[serde(default)]
+    pub allowed_recipients: Vec<String>,
+    /// Named recipients the agent can address by name (`[send_message.contacts.<name>]`).
+    #[serde(default)]
+    pub contacts: HashMap<String, MessageContactConfig>,
+}
+
+/// A named `send_message` recipient.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MessageContactConfig {
+    /// Channel to send through, e.g. `"slack"`, `"telegram"`
+    pub channel: String,
+    /// Platform recipient: chat ID, channel ID, phone number or address
+    pub to: String,
+    /// Thread to post into when the call does not name one
+    #[serde(default)]
+    pub thread: Option<String>,
+}
+
 // ── Proxy ───────────────────────────────────────────────────────
 
 /// Proxy application scope — determines which outbound traffic uses the proxy.
@@ -1823,6 +2366,71 @@ pub struct RuntimeConfig {
     /// - `Some(false)`: disable reasoning/thinking when supported
     #[serde(default)]
     pub reasoning_enabled: Option<bool>,
+
+    /// Record or replay provider exchanges through a cassette file
+    /// (`[runtime.provider_cassette]`). Overridden by `ZEROCLAW_PROVIDER_CASSETTE`.
+    #[serde(default)]
+    pub provider_cassette: Option<ProviderCassetteConfig>,
+}
+
+/// Provider cassette configuration (`[runtime.provider_cassette]` section).
+///
+/// In `record` mode every provider exchange is appended to `path`; in
+/// `replay` mode responses are served from `path` and no network calls are made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderCassetteConfig {
+    /// `record` or `replay`.
+    pub mode: CassetteMode,
+    /// Cassette file path (JSON). `~` is expanded.
+    pub path: String,
+    /// How replayed requests are matched against recorded ones.
+    #[serde(default)]
+    pub matching: CassetteMatching,
+}
+
+/// Whether a cassette captures live exchanges or serves recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// Request matching used when replaying a cassette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CassetteMatching {
+    /// Only serve an interaction whose request hash matches exactly.
+    #[default]
+    Strict,
+    /// Prefer an exact match, otherwise serve the next unused interaction of
+    /// the same kind (tolerates timestamps and other drift in prompts).
+    Lenient,
+}
+
+impl ProviderCassetteConfig {
+    /// Parse a `ZEROCLAW_PROVIDER_CASSETTE` value: `record:<path>`,
+    /// `replay:<path>`, `replay-lenient:<path>`, or a bare path (strict replay).
+    pub fn from_env_value(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        let (mode, matching, path) = match value.split_once(':') {
+            Some(("record", path)) => (CassetteMode::Record, CassetteMatching::Strict, path),
+            Some(("replay", path)) => (CassetteMode::Replay, CassetteMatching::Strict, path),
+            Some(("replay-lenient" | "replay_lenient", path)) => {
+                (CassetteMode::Replay, CassetteMatching::Lenient, path)
+            }
+            _ => (CassetteMode::Replay, CassetteMatching::Strict, value),
+        };
+        let path = path.trim();
+        (!path.is_empty()).then(|| Self {
+            mode,
+            path: path.to_string(),
+            matching,
+        })
+    }
 }
 
 /// Docker runtime configuration (`[runtime.docker]` section).
@@ -1897,6 +2505,7 @@ impl Default for RuntimeConfig {
             kind: default_runtime_kind(),
             docker: DockerRuntimeConfig::default(),
             reasoning_enabled: None,
+            provider_cassette: None,
         }
     }
 }
@@ -1990,7 +2599,7 @@ pub struct SchedulerConfig {
     /// Maximum number of persisted scheduled tasks.
     #[serde(default = "default_scheduler_max_tasks")]
     pub max_tasks: usize,
-    /// Maximum tasks executed per scheduler polling cycle.
+    /// Maximum cron jobs running at once, scheduled and event-triggered alike.
     #[serde(default = "default_scheduler_max_concurrent")]
     pub max_concurrent: usize,
 }
@@ -2031,6 +2640,12 @@ impl Default for SchedulerConfig {
 /// hint = "fast"
 /// provider = "groq"
 /// model = "llama-3.3-70b-versatile"
+///
+/// [[model_routes]]
+/// hint = "think"
+/// provider = "anthropic"
+/// model = "claude-sonnet-4-6"
+/// thinking_budget = 8000
 /// ```
 ///
 /// Usage: pass `hint:reasoning` as the model parameter to route the request.
@@ -2045,6 +2660,12 @@ pub struct ModelRouteConfig {
     /// Optional API key override for this route's provider
     #[serde(default)]
     pub api_key: Option<String>,
+    /// Extended-thinking token budget for this route (Anthropic)
+    #[serde(default)]
+    pub thinking_budget: Option<u32>,
+    /// Reasoning effort for this route: "low", "medium" or "high" (OpenAI o-series)
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
 }
 
 // ── Embedding routing ───────────────────────────────────────────
@@ -2122,6 +2743,12 @@ pub struct HeartbeatConfig {
     pub enabled: bool,
     /// Interval in minutes between heartbeat pings. Default: `30`.
     pub interval_minutes: u32,
+    /// Daily window with no heartbeat task runs, e.g. `"22:00-07:00"`.
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    /// IANA timezone for `quiet_hours` and task `hours=` windows. Default: UTC.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 impl Default for HeartbeatConfig {
@@ -2129,6 +2756,8 @@ impl Default for HeartbeatConfig {
         Self {
             enabled: false,
             interval_minutes: 30,
+            quiet_hours: None,
+            timezone: None,
         }
     }
 }
@@ -2159,6 +2788,81 @@ impl Default for CronConfig {
     }
 }
 
+// ── Backup ──────────────────────────────────────────────────────
+
+/// Backup configuration (`[backup]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackupConfig {
+    /// Where archives are stored: a local directory or `s3://bucket/prefix`.
+    /// Default: `backups/` next to `config.toml`.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Passphrase used to encrypt archives. Unset writes unencrypted archives.
+    /// `ZEROCLAW_BACKUP_PASSPHRASE` overrides it.
+    #[serde(default)]
+    pub passphrase: Option<String>,
+    /// Archives kept after each `backup create`; older ones are deleted.
+    /// `0` keeps everything. Default: `7`.
+    #[serde(default = "default_backup_keep")]
+    pub keep: u32,
+    /// Glob patterns for files to leave out, matched against archive paths
+    /// such as `workspace/sessions/*.jsonl`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Credentials and endpoint for `s3://` targets.
+    #[serde(default)]
+    pub s3: BackupS3Config,
+}
+
+fn default_backup_keep() -> u32 {
+    7
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            target: None,
+            passphrase: None,
+            keep: default_backup_keep(),
+            exclude: Vec::new(),
+            s3: BackupS3Config::default(),
+        }
+    }
+}
+
+/// S3-compatible storage for backups (`[backup.s3]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackupS3Config {
+    /// Endpoint URL for S3-compatible services, e.g. `http://127.0.0.1:9000`
+    /// for MinIO. Requests use path-style URLs when set. Default: AWS S3.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Signing region. Default: `"us-east-1"`.
+    #[serde(default = "default_backup_s3_region")]
+    pub region: String,
+    /// Access key id. Falls back to `AWS_ACCESS_KEY_ID`.
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// Secret access key. Falls back to `AWS_SECRET_ACCESS_KEY`.
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+}
+
+fn default_backup_s3_region() -> String {
+    "us-east-1".into()
+}
+
+impl Default for BackupS3Config {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            region: default_backup_s3_region(),
+            access_key_id: None,
+            secret_access_key: None,
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -2375,13 +3079,25 @@ pub struct DiscordConfig {
 pub struct SlackConfig {
     /// Slack bot OAuth token (xoxb-...).
     pub bot_token: String,
-    /// Slack app-level token for Socket Mode (xapp-...).
+    /// Slack app-level token for Socket Mode (xapp-...). When set, the bot
+    /// receives DMs, mentions and thread replies over a WebSocket.
     pub app_token: Option<String>,
-    /// Optional channel ID to restrict the bot to a single channel.
+    /// Optional channel ID to restrict the bot to a single channel. Without
+    /// `app_token` this channel is polled instead.
     pub channel_id: Option<String>,
     /// Allowed Slack user IDs. Empty = deny all.
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Signing secret for the gateway's Events API endpoint (`POST /slack/events`).
+    /// The endpoint stays disabled until this is set.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    /// Streaming mode for progressive replies via `chat.update`.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
 }
 
 /// Mattermost bot channel configuration.
@@ -2842,22 +3558,31 @@ impl Default for Config {
             embedding_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            backup: BackupConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
+            security: SecurityConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
+            send_message: SendMessageConfig::default(),
             proxy: ProxyConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            personas: HashMap::new(),
+            persona_routes: Vec::new(),
+            secret_refs: Vec::new(),
+            sources: crate::config::layers::ConfigSources::default(),
+            users: HashMap::new(),
+            tenancy: TenancyConfig::default(),
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
         }
@@ -3084,8 +3809,23 @@ async fn resolve_runtime_config_dirs(
     ))
 }
 
-fn decrypt_optional_secret(
-    store: &crate::security::SecretStore,
+fn describe_sources(sources: &crate::config::layers::ConfigSources) -> String {
+    let mut parts: Vec<String> = sources
+        .files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect();
+    if let Some(profile) = &sources.profile {
+        parts.push(format!("profile '{profile}'"));
+    }
+    if !sources.interpolated.is_empty() {
+        parts.push("${ENV} references".into());
+    }
+    parts.join(", ")
+}
+
+fn decrypt_optional_secret(
+    store: &crate::security::SecretStore,
     value: &mut Option<String>,
     field_name: &str,
 ) -> Result<()> {
@@ -3155,6 +3895,64 @@ fn has_ollama_cloud_credential(config_api_key: Option<&str>) -> bool {
 }
 
 impl Config {
+    /// Read, decrypt, apply env overrides to and validate an existing config
+    /// file. Used at startup and when the daemon reloads `config.toml`.
+    pub async fn load_from_path(config_path: &Path, workspace_dir: PathBuf) -> Result<Self> {
+        let mut config = Config::parse_file(config_path).await?;
+        // Set computed paths that are skipped during serialization
+        config.workspace_dir = workspace_dir;
+        config.decrypt_secrets()?;
+        config.apply_env_overrides();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Decrypt the `enc2:` secrets read from disk in place.
+    fn decrypt_secrets(&mut self) -> Result<()> {
+        let zeroclaw_dir = self.config_path.parent().unwrap_or(Path::new("."));
+        let store = crate::security::SecretStore::new(zeroclaw_dir, self.secrets.encrypt);
+        decrypt_optional_secret(&store, &mut self.api_key, "config.api_key")?;
+        decrypt_optional_secret(
+            &store,
+            &mut self.composio.api_key,
+            "config.composio.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut self.browser.computer_use.api_key,
+            "config.browser.computer_use.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut self.web_search.brave_api_key,
+            "config.web_search.brave_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut self.storage.provider.config.db_url,
+            "config.storage.provider.config.db_url",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut self.backup.passphrase,
+            "config.backup.passphrase",
+        )?;
+        decrypt_optional_secret(
+            &store,
+            &mut self.backup.s3.secret_access_key,
+            "config.backup.s3.secret_access_key",
+        )?;
+
+        for agent in self.agents.values_mut() {
+            decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+        Ok(())
+    }
+
     pub async fn load_or_init() -> Result<Self> {
         let (default_zeroclaw_dir, default_workspace_dir) = default_config_and_workspace_dirs()?;
 
@@ -3188,45 +3986,7 @@ impl Config {
                 }
             }
 
-            let contents = fs::read_to_string(&config_path)
-                .await
-                .context("Failed to read config file")?;
-            let mut config: Config =
-                toml::from_str(&contents).context("Failed to parse config file")?;
-            // Set computed paths that are skipped during serialization
-            config.config_path = config_path.clone();
-            config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
-            decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.composio.api_key,
-                "config.composio.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.browser.computer_use.api_key,
-                "config.browser.computer_use.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.brave_api_key,
-                "config.web_search.brave_api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.storage.provider.config.db_url,
-                "config.storage.provider.config.db_url",
-            )?;
-
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
-            }
-            config.apply_env_overrides();
-            config.validate()?;
+            let config = Self::load_from_path(&config_path, workspace_dir).await?;
             tracing::info!(
                 path = %config.config_path.display(),
                 workspace = %config.workspace_dir.display(),
@@ -3261,6 +4021,63 @@ impl Config {
         }
     }
 
+    /// Read `config_path` with its `include` files, the active profile
+    /// (`ZEROCLAW_PROFILE`) and `${ENV}` interpolation applied, resolving
+    /// `secret://` references. Secrets stay encrypted and env overrides are
+    /// not applied.
+    pub async fn parse_file(config_path: &Path) -> Result<Self> {
+        let zeroclaw_dir = config_path.parent().unwrap_or(Path::new("."));
+        let profile = crate::config::layers::active_profile();
+        let (table, sources) =
+            crate::config::layers::compose(config_path, profile.as_deref(), &|name| {
+                std::env::var(name).ok()
+            })?;
+        let mut config = if sources.is_composed() {
+            Self::from_table_resolving_secrets(table, zeroclaw_dir)
+                .await
+                .with_context(|| format!("Composed from {}", describe_sources(&sources)))?
+        } else {
+            // A single plain file: parse the text so errors carry line numbers.
+            let contents = fs::read_to_string(config_path)
+                .await
+                .context("Failed to read config file")?;
+            Self::parse_resolving_secrets(&contents, zeroclaw_dir).await?
+        };
+        config.config_path = config_path.to_path_buf();
+        config.sources = sources;
+        Ok(config)
+    }
+
+    /// Parse `config.toml` contents, resolving `secret://` references
+    /// (relative file references are anchored at `zeroclaw_dir`).
+    pub async fn parse_resolving_secrets(contents: &str, zeroclaw_dir: &Path) -> Result<Self> {
+        use crate::security::secret_refs;
+
+        if !contents.contains(secret_refs::SECRET_REF_PREFIX) {
+            return toml::from_str(contents).context("Failed to parse config file");
+        }
+        let table: toml::Table = toml::from_str(contents).context("Failed to parse config file")?;
+        Self::from_table_resolving_secrets(table, zeroclaw_dir).await
+    }
+
+    async fn from_table_resolving_secrets(table: toml::Table, zeroclaw_dir: &Path) -> Result<Self> {
+        use crate::security::secret_refs;
+
+        let mut doc = toml::Value::Table(table);
+        let secrets: SecretsConfig = doc
+            .get("secrets")
+            .cloned()
+            .map(toml::Value::try_into)
+            .transpose()
+            .context("Failed to parse [secrets]")?
+            .unwrap_or_default();
+        let mut resolver = secret_refs::SecretResolver::new(zeroclaw_dir, secrets.vault);
+        let refs = secret_refs::resolve_toml_refs(&mut doc, &mut resolver).await?;
+        let mut config: Config = doc.try_into().context("Failed to parse config file")?;
+        config.secret_refs = refs;
+        Ok(config)
+    }
+
     /// Validate configuration values that would cause runtime failures.
     ///
     /// Called after TOML deserialization and env-override application to catch
@@ -3284,6 +4101,196 @@ impl Config {
             anyhow::bail!("scheduler.max_tasks must be greater than 0");
         }
 
+        // Heartbeat
+        if let Some(quiet_hours) = &self.heartbeat.quiet_hours {
+            crate::heartbeat::task::HoursWindow::parse(quiet_hours)
+                .map_err(|e| anyhow::anyhow!("heartbeat.quiet_hours: {e}"))?;
+        }
+        if let Some(timezone) = &self.heartbeat.timezone {
+            crate::heartbeat::task::parse_timezone(timezone)
+                .map_err(|e| anyhow::anyhow!("heartbeat.timezone: {e}"))?;
+        }
+
+        // Delegate agents
+        for (name, agent) in &self.agents {
+            if let Some(workspace) = &agent.workspace {
+                let path = std::path::Path::new(workspace);
+                if workspace.trim().is_empty()
+                    || path.is_absolute()
+                    || path
+                        .components()
+                        .any(|c| matches!(c, std::path::Component::ParentDir))
+                {
+                    anyhow::bail!(
+                        "agents.{name}.workspace must be a relative path inside the workspace"
+                    );
+                }
+            }
+            if agent
+                .memory_namespace
+                .as_deref()
+                .is_some_and(|ns| ns.trim().is_empty())
+            {
+                anyhow::bail!("agents.{name}.memory_namespace must not be empty");
+            }
+        }
+
+        // Personas
+        for (name, persona) in &self.personas {
+            if let Some(dir) = &persona.identity_dir {
+                let path = std::path::Path::new(dir);
+                if dir.trim().is_empty()
+                    || path.is_absolute()
+                    || path
+                        .components()
+                        .any(|c| matches!(c, std::path::Component::ParentDir))
+                {
+                    anyhow::bail!(
+                        "personas.{name}.identity_dir must be a relative path inside the workspace"
+                    );
+                }
+            }
+            if let Some(hint) = persona
+                .model
+                .as_deref()
+                .and_then(|m| m.strip_prefix("hint:"))
+            {
+                if !self.model_routes.iter().any(|r| r.hint == hint) {
+                    anyhow::bail!("personas.{name}.model uses unknown model route 'hint:{hint}'");
+                }
+            }
+            if persona
+                .memory_namespace
+                .as_deref()
+                .is_some_and(|ns| ns.trim().is_empty())
+            {
+                anyhow::bail!("personas.{name}.memory_namespace must not be empty");
+            }
+        }
+        for (i, route) in self.persona_routes.iter().enumerate() {
+            if !self.personas.contains_key(&route.persona) {
+                anyhow::bail!(
+                    "persona_routes[{i}].persona '{}' is not defined in [personas]",
+                    route.persona
+                );
+            }
+        }
+
+        let rbac_roles = self.gateway.rbac.effective_roles();
+        let mut role_names = std::collections::HashSet::new();
+        for (i, role) in rbac_roles.iter().enumerate() {
+            if role.name.trim().is_empty() {
+                anyhow::bail!("gateway.rbac.roles[{i}].name must not be empty");
+            }
+            if !role_names.insert(role.name.as_str()) {
+                anyhow::bail!("gateway.rbac.roles[{i}].name '{}' is duplicated", role.name);
+            }
+            for method in role.http_methods.iter().flatten() {
+                if !matches!(
+                    method.as_str(),
+                    "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS"
+                ) {
+                    anyhow::bail!(
+                        "gateway.rbac.roles[{i}].http_methods contains unknown method '{method}'"
+                    );
+                }
+            }
+        }
+        if !role_names.contains(self.gateway.rbac.default_role.as_str()) {
+            anyhow::bail!(
+                "gateway.rbac.default_role '{}' is not a defined role",
+                self.gateway.rbac.default_role
+            );
+        }
+        if self.gateway.rbac.claim.trim().is_empty() {
+            anyhow::bail!("gateway.rbac.claim must not be empty");
+        }
+
+        for (i, issuer) in self.gateway.oidc.issuers.iter().enumerate() {
+            let issuer_url = issuer.issuer.trim();
+            if !(issuer_url.starts_with("https://") || issuer_url.starts_with("http://")) {
+                anyhow::bail!("gateway.oidc.issuers[{i}].issuer must be an http(s) URL");
+            }
+            for algorithm in &issuer.algorithms {
+                let asymmetric = !algorithm.starts_with("HS")
+                    && algorithm.parse::<jsonwebtoken::Algorithm>().is_ok();
+                if !asymmetric {
+                    anyhow::bail!(
+                        "gateway.oidc.issuers[{i}].algorithms contains unsupported algorithm \
+                         '{algorithm}' (use an asymmetric JWS algorithm such as RS256 or ES256)"
+                    );
+                }
+            }
+            if issuer.audiences.is_empty() && !issuer.allow_any_audience {
+                anyhow::bail!(
+                    "gateway.oidc.issuers[{i}].audiences must list the accepted `aud` values \
+                     (or set allow_any_audience = true)"
+                );
+            }
+            if let Some(introspection) = &issuer.introspection {
+                if introspection.client_id.trim().is_empty() {
+                    anyhow::bail!(
+                        "gateway.oidc.issuers[{i}].introspection.client_id must not be empty"
+                    );
+                }
+            }
+        }
+        for path in &self.gateway.oidc.public_paths {
+            if !path.starts_with('/') {
+                anyhow::bail!("gateway.oidc.public_paths entry '{path}' must start with '/'");
+            }
+        }
+        if self.gateway.oidc.user_claim.trim().is_empty() {
+            anyhow::bail!("gateway.oidc.user_claim must not be empty");
+        }
+        if self.gateway.admin.enabled && !role_names.contains(self.gateway.admin.role.as_str()) {
+            anyhow::bail!(
+                "gateway.admin.role '{}' is not a defined gateway.rbac role",
+                self.gateway.admin.role
+            );
+        }
+        if self.gateway.admin.approval_timeout_secs == 0 {
+            anyhow::bail!("gateway.admin.approval_timeout_secs must be greater than 0");
+        }
+
+        let mut identity_owners: HashMap<&str, &str> = HashMap::new();
+        for (name, user) in &self.users {
+            for identity in &user.identities {
+                let valid = identity
+                    .split_once(':')
+                    .is_some_and(|(channel, id)| !channel.is_empty() && !id.is_empty());
+                if !valid {
+                    anyhow::bail!(
+                        "users.{name}.identities entry '{identity}' must look like '<channel>:<sender id>'"
+                    );
+                }
+                if let Some(other) = identity_owners.insert(identity, name) {
+                    anyhow::bail!(
+                        "users.{name}.identities entry '{identity}' is already claimed by users.{other}"
+                    );
+                }
+            }
+            if let Some(workspace) = &user.workspace {
+                let path = std::path::Path::new(workspace);
+                if workspace.trim().is_empty()
+                    || path.is_absolute()
+                    || path
+                        .components()
+                        .any(|c| matches!(c, std::path::Component::ParentDir))
+                {
+                    anyhow::bail!(
+                        "users.{name}.workspace must be a relative path inside the workspace"
+                    );
+                }
+            }
+            if user
+                .max_cost_per_day_usd
+                .is_some_and(|max| !max.is_finite() || max < 0.0)
+            {
+                anyhow::bail!("users.{name}.max_cost_per_day_usd must be a non-negative number");
+            }
+        }
+
         // Model routes
         for (i, route) in self.model_routes.iter().enumerate() {
             if route.hint.trim().is_empty() {
@@ -3497,6 +4504,13 @@ impl Config {
             }
         }
 
+        // Provider cassette: ZEROCLAW_PROVIDER_CASSETTE
+        if let Ok(value) = std::env::var("ZEROCLAW_PROVIDER_CASSETTE") {
+            if let Some(cassette) = ProviderCassetteConfig::from_env_value(&value) {
+                self.runtime.provider_cassette = Some(cassette);
+            }
+        }
+
         // Web search enabled: ZEROCLAW_WEB_SEARCH_ENABLED or WEB_SEARCH_ENABLED
         if let Ok(enabled) = std::env::var("ZEROCLAW_WEB_SEARCH_ENABLED")
             .or_else(|_| std::env::var("WEB_SEARCH_ENABLED"))
@@ -3640,7 +4654,26 @@ impl Config {
         set_runtime_proxy_config(self.proxy.clone());
     }
 
+    /// Write the config back to disk. A config composed from several files
+    /// keeps them untouched and writes what changed at runtime to
+    /// [`layers::override_path`](crate::config::layers::override_path).
     pub async fn save(&self) -> Result<()> {
+        if self.sources.is_composed() {
+            return Box::pin(self.save_runtime_overrides()).await;
+        }
+        let config_to_save = self.encrypted_for_disk()?;
+
+        let toml_str = if self.secret_refs.is_empty() {
+            toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?
+        } else {
+            toml::to_string_pretty(&self.disk_value(&config_to_save)?)
+                .context("Failed to serialize config")?
+        };
+        replace_file(&self.config_path, &toml_str).await
+    }
+
+    /// Copy of the config with secrets encrypted as they are stored on disk.
+    fn encrypted_for_disk(&self) -> Result<Self> {
         // Encrypt secrets before serialization
         let mut config_to_save = self.clone();
         let zeroclaw_dir = self
@@ -3674,84 +4707,161 @@ impl Config {
             "config.storage.provider.config.db_url",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.backup.passphrase,
+            "config.backup.passphrase",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.backup.s3.secret_access_key,
+            "config.backup.s3.secret_access_key",
+        )?;
+
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
 
-        let toml_str =
-            toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?;
+        Ok(config_to_save)
+    }
 
-        let parent_dir = self
+    /// `encrypted` as a TOML value with `secret://` references put back.
+    fn disk_value(&self, encrypted: &Self) -> Result<toml::Value> {
+        let mut doc = toml::Value::try_from(encrypted).context("Failed to serialize config")?;
+        if !self.secret_refs.is_empty() {
+            let current = toml::Value::try_from(self).context("Failed to serialize config")?;
+            crate::security::secret_refs::restore_toml_refs(&mut doc, &current, &self.secret_refs);
+        }
+        Ok(doc)
+    }
+
+    /// Write the values that differ from the composed files to the override
+    /// file, which is merged over them on the next load.
+    async fn save_runtime_overrides(&self) -> Result<()> {
+        use crate::config::layers;
+
+        let zeroclaw_dir = self
             .config_path
             .parent()
             .context("Config path must have a parent directory")?;
+        let profile = self.sources.profile.as_deref();
+        let (table, _) =
+            layers::compose_base(&self.config_path, profile, &|name| std::env::var(name).ok())?;
+        let mut base = Self::from_table_resolving_secrets(table, zeroclaw_dir)
+            .await
+            .with_context(|| format!("Composed from {}", describe_sources(&self.sources)))?;
+        base.config_path = self.config_path.clone();
+        base.decrypt_secrets()?;
+
+        let base = toml::Value::try_from(&base).context("Failed to serialize config")?;
+        let current = toml::Value::try_from(self).context("Failed to serialize config")?;
+        let on_disk = self.disk_value(&self.encrypted_for_disk()?)?;
+        let (Some(base), Some(current), Some(on_disk)) =
+            (base.as_table(), current.as_table(), on_disk.as_table())
+        else {
+            anyhow::bail!("Config did not serialize to a table");
+        };
+        let (overrides, removed) = layers::diff_tables(base, current, on_disk);
+        for path in removed {
+            let origin = self
+                .sources
+                .origins
+                .get(&path)
+                .map_or("the config files", String::as_str);
+            tracing::warn!(
+                "Config value `{path}` was removed at runtime but cannot be removed from \
+                 {origin} automatically; edit that file"
+            );
+        }
 
-        fs::create_dir_all(parent_dir).await.with_context(|| {
+        let override_path = layers::override_path(&self.config_path, profile);
+        if overrides.is_empty() {
+            if override_path.exists() {
+                fs::remove_file(&override_path)
+                    .await
+                    .with_context(|| format!("Failed to remove {}", override_path.display()))?;
+            }
+            return Ok(());
+        }
+        let contents = format!(
+            "# Runtime changes saved by zeroclaw, applied over {} and the files it\n\
+             # includes. Move settings into those files to keep them under your control.\n\n{}",
+            self.config_path.display(),
+            toml::to_string_pretty(&overrides).context("Failed to serialize config")?
+        );
+        replace_file(&override_path, &contents).await
+    }
+}
+
+/// Atomically replace `path` with `contents`, keeping a `.bak` copy of the
+/// previous file until the new one is in place.
+async fn replace_file(path: &Path, contents: &str) -> Result<()> {
+    let parent_dir = path
+        .parent()
+        .context("Config path must have a parent directory")?;
+
+    fs::create_dir_all(parent_dir).await.with_context(|| {
+        format!(
+            "Failed to create config directory: {}",
+            parent_dir.display()
+        )
+    })?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|v| v.to_str())
+        .unwrap_or("config.toml");
+    let temp_path = parent_dir.join(format!(".{file_name}.tmp-{}", uuid::Uuid::new_v4()));
+    let backup_path = parent_dir.join(format!("{file_name}.bak"));
+
+    let mut temp_file = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&temp_path)
+        .await
+        .with_context(|| {
             format!(
-                "Failed to create config directory: {}",
-                parent_dir.display()
+                "Failed to create temporary config file: {}",
+                temp_path.display()
             )
         })?;
+    temp_file
+        .write_all(contents.as_bytes())
+        .await
+        .context("Failed to write temporary config contents")?;
+    temp_file
+        .sync_all()
+        .await
+        .context("Failed to fsync temporary config file")?;
+    drop(temp_file);
 
-        let file_name = self
-            .config_path
-            .file_name()
-            .and_then(|v| v.to_str())
-            .unwrap_or("config.toml");
-        let temp_path = parent_dir.join(format!(".{file_name}.tmp-{}", uuid::Uuid::new_v4()));
-        let backup_path = parent_dir.join(format!("{file_name}.bak"));
-
-        let mut temp_file = OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .open(&temp_path)
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to create temporary config file: {}",
-                    temp_path.display()
-                )
-            })?;
-        temp_file
-            .write_all(toml_str.as_bytes())
-            .await
-            .context("Failed to write temporary config contents")?;
-        temp_file
-            .sync_all()
-            .await
-            .context("Failed to fsync temporary config file")?;
-        drop(temp_file);
+    let had_existing_config = path.exists();
+    if had_existing_config {
+        fs::copy(path, &backup_path).await.with_context(|| {
+            format!(
+                "Failed to create config backup before atomic replace: {}",
+                backup_path.display()
+            )
+        })?;
+    }
 
-        let had_existing_config = self.config_path.exists();
-        if had_existing_config {
-            fs::copy(&self.config_path, &backup_path)
+    if let Err(e) = fs::rename(&temp_path, path).await {
+        let _ = fs::remove_file(&temp_path).await;
+        if had_existing_config && backup_path.exists() {
+            fs::copy(&backup_path, path)
                 .await
-                .with_context(|| {
-                    format!(
-                        "Failed to create config backup before atomic replace: {}",
-                        backup_path.display()
-                    )
-                })?;
-        }
-
-        if let Err(e) = fs::rename(&temp_path, &self.config_path).await {
-            let _ = fs::remove_file(&temp_path).await;
-            if had_existing_config && backup_path.exists() {
-                fs::copy(&backup_path, &self.config_path)
-                    .await
-                    .context("Failed to restore config backup")?;
-            }
-            anyhow::bail!("Failed to atomically replace config file: {e}");
+                .context("Failed to restore config backup")?;
         }
+        anyhow::bail!("Failed to atomically replace config file: {e}");
+    }
 
-        sync_directory(parent_dir).await?;
+    sync_directory(parent_dir).await?;
 
-        if had_existing_config {
-            let _ = fs::remove_file(&backup_path).await;
-        }
-
-        Ok(())
+    if had_existing_config {
+        let _ = fs::remove_file(&backup_path).await;
     }
+
+    Ok(())
 }
 
 async fn sync_directory(path: &Path) -> Result<()> {
@@ -3985,8 +5095,10 @@ default_temperature = 0.7
             heartbeat: HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 15,
+                ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
+            backup: BackupConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
                 telegram: Some(TelegramConfig {
@@ -4018,18 +5130,26 @@ default_temperature = 0.7
             storage: StorageConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
+            security: SecurityConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
+            send_message: SendMessageConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            personas: HashMap::new(),
+            persona_routes: Vec::new(),
+            secret_refs: Vec::new(),
+            sources: crate::config::layers::ConfigSources::default(),
+            users: HashMap::new(),
+            tenancy: TenancyConfig::default(),
             hardware: HardwareConfig::default(),
         };
 
@@ -4114,6 +5234,24 @@ reasoning_enabled = false
         assert_eq!(parsed.runtime.reasoning_enabled, Some(false));
     }
 
+    #[test]
+    async fn runtime_provider_cassette_deserializes() {
+        let raw = r#"
+default_temperature = 0.7
+
+[runtime.provider_cassette]
+mode = "replay"
+path = "tests/fixtures/session.json"
+matching = "lenient"
+"#;
+
+        let parsed: Config = toml::from_str(raw).unwrap();
+        let cassette = parsed.runtime.provider_cassette.unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Replay);
+        assert_eq!(cassette.matching, CassetteMatching::Lenient);
+        assert_eq!(cassette.path, "tests/fixtures/session.json");
+    }
+
     #[test]
     async fn agent_config_defaults() {
         let cfg = AgentConfig::default();
@@ -4182,23 +5320,32 @@ tool_dispatcher = "xml"
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            backup: BackupConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
+            security: SecurityConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
+            send_message: SendMessageConfig::default(),
             proxy: ProxyConfig::default(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            personas: HashMap::new(),
+            persona_routes: Vec::new(),
+            secret_refs: Vec::new(),
+            sources: crate::config::layers::ConfigSources::default(),
+            users: HashMap::new(),
+            tenancy: TenancyConfig::default(),
             hardware: HardwareConfig::default(),
         };
 
@@ -4220,6 +5367,47 @@ tool_dispatcher = "xml"
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn config_secret_refs_resolve_on_load_and_survive_save() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("openrouter.key"), "sk-from-file\n").unwrap();
+        let contents = r#"
+api_key = "secret://file/openrouter.key"
+default_temperature = 0.7
+
+[composio]
+api_key = "secret://file/openrouter.key"
+"#;
+
+        let mut config = Config::parse_resolving_secrets(contents, tmp.path())
+            .await
+            .unwrap();
+        assert_eq!(config.api_key.as_deref(), Some("sk-from-file"));
+        assert_eq!(config.composio.api_key.as_deref(), Some("sk-from-file"));
+        assert_eq!(config.secret_refs.len(), 2);
+
+        config.config_path = tmp.path().join("config.toml");
+        config.composio.api_key = Some("sk-replaced".into());
+        config.save().await.unwrap();
+
+        let saved = tokio::fs::read_to_string(&config.config_path)
+            .await
+            .unwrap();
+        let saved: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(
+            saved.api_key.as_deref(),
+            Some("secret://file/openrouter.key")
+        );
+        let composio_key = saved.composio.api_key.as_deref().unwrap();
+        assert!(crate::security::SecretStore::is_encrypted(composio_key));
+
+        let err =
+            Config::parse_resolving_secrets(r#"api_key = "secret://file/missing.key""#, tmp.path())
+                .await
+                .unwrap_err();
+        assert!(format!("{err:#}").contains("missing.key"));
+    }
+
     #[tokio::test]
     async fn config_save_encrypts_nested_credentials() {
         let dir = std::env::temp_dir().join(format!(
@@ -4249,6 +5437,11 @@ tool_dispatcher = "xml"
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
 
@@ -4336,6 +5529,45 @@ tool_dispatcher = "xml"
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn config_save_of_composed_config_writes_override_file() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let config_path = dir.join("config.toml");
+        let main = "include = [\"models.toml\"]\ndefault_temperature = 0.7\n";
+        fs::write(&config_path, main).await.unwrap();
+        fs::write(dir.join("models.toml"), "default_model = \"model-a\"\n")
+            .await
+            .unwrap();
+
+        let mut config = Config::parse_file(&config_path).await.unwrap();
+        assert!(config.sources.is_composed());
+        config.default_model = Some("model-b".into());
+        config.gateway.port = 4321;
+        config.save().await.unwrap();
+
+        assert_eq!(fs::read_to_string(&config_path).await.unwrap(), main);
+        let overrides = fs::read_to_string(dir.join("config.override.toml"))
+            .await
+            .unwrap();
+        assert!(overrides.contains("default_model = \"model-b\""));
+        assert!(!overrides.contains("[secrets]"));
+
+        let reloaded = Config::parse_file(&config_path).await.unwrap();
+        assert_eq!(reloaded.default_model.as_deref(), Some("model-b"));
+        assert_eq!(reloaded.gateway.port, 4321);
+
+        // Saving without changes against the included files drops the override.
+        let mut reverted = reloaded;
+        reverted.default_model = Some("model-a".into());
+        reverted.gateway.port = Config::default().gateway.port;
+        reverted.save().await.unwrap();
+        assert!(!dir.join("config.override.toml").exists());
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
     // ── Telegram / Discord config ────────────────────────────
 
     #[test]
@@ -4595,6 +5827,9 @@ allowed_users = ["@ops:matrix.org"]
         let json = r#"{"bot_token":"xoxb-tok"}"#;
         let parsed: SlackConfig = serde_json::from_str(json).unwrap();
         assert!(parsed.allowed_users.is_empty());
+        assert!(parsed.signing_secret.is_none());
+        assert_eq!(parsed.stream_mode, StreamMode::Off);
+        assert_eq!(parsed.draft_update_interval_ms, 1000);
     }
 
     #[test]
@@ -4854,6 +6089,9 @@ channel_id = "C123"
             keycloak_url: None,
             keycloak_realm: "oluto".into(),
             keycloak_issuer_url: None,
+            rbac: GatewayRbacConfig::default(),
+            oidc: GatewayOidcConfig::default(),
+            admin: GatewayAdminConfig::default(),
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -4980,7 +6218,10 @@ enable = true
 
     #[test]
     async fn secrets_config_serde_roundtrip() {
-        let s = SecretsConfig { encrypt: false };
+        let s = SecretsConfig {
+            encrypt: false,
+            ..SecretsConfig::default()
+        };
         let toml_str = toml::to_string(&s).unwrap();
         let parsed: SecretsConfig = toml::from_str(&toml_str).unwrap();
         assert!(!parsed.encrypt);
@@ -5292,6 +6533,196 @@ default_temperature = 0.7
         std::env::remove_var("ZEROCLAW_MODEL");
     }
 
+    #[test]
+    async fn validate_rejects_invalid_heartbeat_windows() {
+        let mut config = Config::default();
+        config.heartbeat.quiet_hours = Some("late".into());
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("heartbeat.quiet_hours"));
+
+        config.heartbeat.quiet_hours = Some("22:00-07:00".into());
+        config.heartbeat.timezone = Some("Mars/Base".into());
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("heartbeat.timezone"));
+
+        config.heartbeat.timezone = Some("Europe/Berlin".into());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    async fn validate_requires_oidc_audiences_or_explicit_opt_out() {
+        let mut config = Config::default();
+        config.gateway.oidc.issuers = vec![OidcIssuerConfig {
+            issuer: "https://login.example.com".into(),
+            ..OidcIssuerConfig::default()
+        }];
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("allow_any_audience"));
+
+        config.gateway.oidc.issuers[0].allow_any_audience = true;
+        assert!(config.validate().is_ok());
+        config.gateway.oidc.issuers[0].allow_any_audience = false;
+        config.gateway.oidc.issuers[0].audiences = vec!["zeroclaw".into()];
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    async fn delegate_agent_profile_parses_and_validates_workspace() {
+        let agent: DelegateAgentConfig = toml::from_str(
+            r#"
+            provider = "openrouter"
+            model = "some-model"
+            memory = "read_only"
+            autonomy = "readonly"
+            allowed_commands = ["ls"]
+            workspace = "research"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(agent.memory, DelegateMemoryMode::ReadOnly);
+        assert_eq!(agent.autonomy, Some(AutonomyLevel::ReadOnly));
+        assert!(agent.has_scoped_profile());
+        let mut config = Config::default();
+        config.agents.insert("researcher".into(), agent);
+        assert!(config.validate().is_ok());
+
+        config.agents.get_mut("researcher").unwrap().workspace = Some("../escape".into());
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("agents.researcher.workspace"));
+    }
+
+    #[test]
+    async fn personas_and_routes_parse_and_validate() {
+        let mut config: Config = toml::from_str(
+            r#"
+            default_temperature = 0.7
+
+            [personas.helpdesk]
+            model = "hint:fast"
+            skills = ["faq"]
+            allowed_tools = ["memory_recall", "web_search"]
+
+            [personas.sre]
+            identity_dir = "agents/sre"
+            memory = "shared"
+
+            [[model_routes]]
+            hint = "fast"
+            provider = "groq"
+            model = "llama-3.3-70b"
+
+            [[persona_routes]]
+            persona = "helpdesk"
+            channel = "slack"
+            chat = "C0SUPPORT"
+
+            [[persona_routes]]
+            persona = "sre"
+            channel = "telegram"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.personas["helpdesk"].memory,
+            DelegateMemoryMode::Isolated
+        );
+        assert_eq!(config.personas["sre"].memory, DelegateMemoryMode::Shared);
+        assert_eq!(config.persona_routes[0].chat.as_deref(), Some("C0SUPPORT"));
+        assert!(config.validate().is_ok());
+
+        config.persona_routes[1].persona = "ops".into();
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("persona_routes[1]"));
+
+        config.persona_routes[1].persona = "sre".into();
+        config.personas.get_mut("helpdesk").unwrap().model = Some("hint:slow".into());
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("personas.helpdesk.model"));
+    }
+
+    #[test]
+    async fn gateway_rbac_roles_parse_and_validate() {
+        let mut config: Config = toml::from_str(
+            r#"
+            default_temperature = 0.7
+
+            [gateway.rbac]
+            claim = "groups"
+            default_role = "reader"
+
+            [[gateway.rbac.roles]]
+            name = "operator"
+            claim_values = ["ops", "sre"]
+            allowed_commands = ["git", "ls"]
+            autonomy = "supervised"
+
+            [[gateway.rbac.roles]]
+            name = "reader"
+            allowed_tools = ["http_request", "memory_recall"]
+            http_methods = ["GET"]
+            memory_categories = ["core"]
+            "#,
+        )
+        .unwrap();
+        let roles = config.gateway.rbac.effective_roles();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[0].claim_values, vec!["ops", "sre"]);
+        assert_eq!(roles[0].autonomy, Some(AutonomyLevel::Supervised));
+        assert!(config.validate().is_ok());
+
+        config.gateway.rbac.default_role = "guest".into();
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("gateway.rbac.default_role"));
+
+        config.gateway.rbac.default_role = "reader".into();
+        config.gateway.rbac.roles[1].http_methods = Some(vec!["get".into()]);
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("http_methods"));
+
+        let builtin = GatewayRbacConfig::default().effective_roles();
+        let names: Vec<&str> = builtin.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["admin", "accountant", "viewer"]);
+    }
+
+    #[test]
+    async fn users_and_tenancy_parse_and_validate() {
+        let mut config: Config = toml::from_str(
+            r#"
+            default_temperature = 0.7
+
+            [tenancy]
+            unknown_users = "reject"
+
+            [users.alice]
+            identities = ["telegram:123456789", "email:alice@example.com"]
+            max_actions_per_day = 200
+            max_cost_per_day_usd = 2.5
+
+            [users.bob]
+            identities = ["slack:U012AB3CD"]
+            workspace = "teams/bob"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.tenancy.unknown_users, UnknownUserPolicy::Reject);
+        assert_eq!(config.users["alice"].max_actions_per_day, Some(200));
+        assert_eq!(config.users["bob"].workspace.as_deref(), Some("teams/bob"));
+        assert!(config.validate().is_ok());
+
+        config.users.get_mut("bob").unwrap().identities = vec!["telegram:123456789".into()];
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("already claimed"));
+
+        config.users.get_mut("bob").unwrap().identities = vec!["U012AB3CD".into()];
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("users.bob.identities"));
+
+        config.users.get_mut("bob").unwrap().identities = vec!["slack:U012AB3CD".into()];
+        config.users.get_mut("bob").unwrap().workspace = Some("/srv/bob".into());
+        let error = config.validate().expect_err("expected validation to fail");
+        assert!(error.to_string().contains("users.bob.workspace"));
+    }
+
     #[test]
     async fn validate_ollama_cloud_model_requires_remote_api_url() {
         let _env_guard = env_override_lock().await;
@@ -5769,6 +7200,37 @@ default_model = "legacy-model"
         std::env::remove_var("ZEROCLAW_REASONING_ENABLED");
     }
 
+    #[test]
+    async fn env_override_provider_cassette() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        assert!(config.runtime.provider_cassette.is_none());
+
+        std::env::set_var("ZEROCLAW_PROVIDER_CASSETTE", "record:/tmp/session.json");
+        config.apply_env_overrides();
+        let cassette = config.runtime.provider_cassette.clone().unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Record);
+        assert_eq!(cassette.path, "/tmp/session.json");
+
+        std::env::set_var(
+            "ZEROCLAW_PROVIDER_CASSETTE",
+            "replay-lenient:fixtures/a.json",
+        );
+        config.apply_env_overrides();
+        let cassette = config.runtime.provider_cassette.clone().unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Replay);
+        assert_eq!(cassette.matching, CassetteMatching::Lenient);
+
+        std::env::set_var("ZEROCLAW_PROVIDER_CASSETTE", "fixtures/b.json");
+        config.apply_env_overrides();
+        let cassette = config.runtime.provider_cassette.clone().unwrap();
+        assert_eq!(cassette.mode, CassetteMode::Replay);
+        assert_eq!(cassette.matching, CassetteMatching::Strict);
+        assert_eq!(cassette.path, "fixtures/b.json");
+
+        std::env::remove_var("ZEROCLAW_PROVIDER_CASSETTE");
+    }
+
     #[test]
     async fn env_override_reasoning_invalid_value_ignored() {
         let _env_guard = env_override_lock().await;
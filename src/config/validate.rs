@@ -0,0 +1,982 @@
+//! Semantic validation of a loaded config, behind `zeroclaw config validate`.
+//!
+//! `Config::validate` rejects values that cannot work at all. This pass finds
+//! settings that parse but point at nothing (unknown providers, route hints,
+//! channels), settings that cancel each other out, and allowlist entries that
+//! can never match. Problems are reported with the TOML file and line they
+//! come from.
+
+use super::Config;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Channels that cron `announce` delivery can send to.
+const DELIVERY_CHANNELS: &[&str] = &["telegram", "discord", "slack", "mattermost"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A position in a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// One problem found by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Dotted config key (`model_routes.1.provider`), or the job field for
+    /// stored cron jobs.
+    pub path: String,
+    pub message: String,
+    /// Stored cron job the problem belongs to. Such jobs live in the cron
+    /// database, not in `config.toml`.
+    pub job: Option<String>,
+    pub location: Option<Location>,
+}
+
+impl Diagnostic {
+    fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+            job: None,
+            location: None,
+        }
+    }
+
+    fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(path, message)
+        }
+    }
+
+    fn for_job(mut self, job: &crate::cron::CronJob) -> Self {
+        self.job = Some(match &job.name {
+            Some(name) => format!("{name} ({})", job.id),
+            None => job.id.clone(),
+        });
+        self
+    }
+
+    /// An error in the config itself, which blocks daemon start and reload.
+    pub fn is_blocking(&self) -> bool {
+        self.severity == Severity::Error && self.job.is_none()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(
+                f,
+                "{}:{}:{}: ",
+                location.file.display(),
+                location.line,
+                location.column
+            )?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match &self.job {
+            Some(job) => write!(
+                f,
+                "{severity}: cron job {job}: `{}`: {}",
+                self.path, self.message
+            ),
+            None => write!(f, "{severity}: `{}`: {}", self.path, self.message),
+        }
+    }
+}
+
+/// Run every check against `config` and the cron jobs stored in its
+/// workspace. Diagnostics are sorted errors first and carry file locations
+/// where the key appears in a config file.
+pub fn validate(config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = check_config(config);
+    check_cron_jobs(config, &mut diagnostics);
+    check_heartbeat_tasks(config, &mut diagnostics);
+    locate(config, &mut diagnostics);
+    diagnostics.sort_by_key(|d| d.severity);
+    diagnostics
+}
+
+/// The config-only checks (no cron database access, no locations).
+pub fn check_config(config: &Config) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    check_providers(config, &mut out);
+    check_hints(config, &mut out);
+    check_channel_references(config, &mut out);
+    check_autonomy(config, &mut out);
+    check_channel_allowlists(config, &mut out);
+    out
+}
+
+/// Error text for a provider name `create_provider` rejects.
+pub fn provider_error(name: &str) -> Option<String> {
+    match crate::providers::create_provider(name, None) {
+        Ok(_) => None,
+        Err(err) => Some(
+            err.to_string()
+                .lines()
+                .next()
+                .unwrap_or("invalid provider")
+                .into(),
+        ),
+    }
+}
+
+/// Error text for an embedding provider other than `none`, `openai` or
+/// `custom:<http(s) url>`.
+pub fn embedding_provider_error(name: &str) -> Option<String> {
+    let normalized = name.trim();
+    if normalized.eq_ignore_ascii_case("none") || normalized.eq_ignore_ascii_case("openai") {
+        return None;
+    }
+
+    let Some(url) = normalized.strip_prefix("custom:") else {
+        return Some("supported values: none, openai, custom:<url>".into());
+    };
+
+    let url = url.trim();
+    if url.is_empty() {
+        return Some("custom provider requires a non-empty URL after 'custom:'".into());
+    }
+
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => None,
+        Ok(parsed) => Some(format!(
+            "custom provider URL must use http/https, got '{}'",
+            parsed.scheme()
+        )),
+        Err(err) => Some(format!("invalid custom provider URL: {err}")),
+    }
+}
+
+fn sorted_keys<V>(map: &HashMap<String, V>) -> Vec<&String> {
+    let mut keys: Vec<_> = map.keys().collect();
+    keys.sort();
+    keys
+}
+
+fn check_providers(config: &Config, out: &mut Vec<Diagnostic>) {
+    if let Some(provider) = &config.default_provider {
+        if let Some(reason) = provider_error(provider) {
+            out.push(Diagnostic::error(
+                "default_provider",
+                format!("unknown provider \"{provider}\": {reason}"),
+            ));
+        }
+    }
+    for (i, provider) in config.reliability.fallback_providers.iter().enumerate() {
+        if let Some(reason) = provider_error(provider) {
+            out.push(Diagnostic::warning(
+                format!("reliability.fallback_providers.{i}"),
+                format!("unknown fallback provider \"{provider}\" is skipped: {reason}"),
+            ));
+        }
+    }
+
+    let mut hints: HashMap<&str, usize> = HashMap::new();
+    for (i, route) in config.model_routes.iter().enumerate() {
+        if let Some(reason) = provider_error(&route.provider) {
+            out.push(Diagnostic::error(
+                format!("model_routes.{i}.provider"),
+                format!(
+                    "route \"{}\" uses unknown provider \"{}\" and is skipped at runtime: {reason}",
+                    route.hint, route.provider
+                ),
+            ));
+        }
+        if route.model.trim().is_empty() {
+            out.push(Diagnostic::error(
+                format!("model_routes.{i}.model"),
+                format!("route \"{}\" has an empty model", route.hint),
+            ));
+        }
+        if let Some(previous) = hints.insert(route.hint.as_str(), i) {
+            out.push(Diagnostic::warning(
+                format!("model_routes.{previous}.hint"),
+                format!(
+                    "hint \"{}\" is defined again in model_routes.{i}, which wins",
+                    route.hint
+                ),
+            ));
+        }
+    }
+
+    for (i, route) in config.embedding_routes.iter().enumerate() {
+        if let Some(reason) = embedding_provider_error(&route.provider) {
+            out.push(Diagnostic::error(
+                format!("embedding_routes.{i}.provider"),
+                format!(
+                    "embedding route \"{}\" uses invalid provider \"{}\": {reason}",
+                    route.hint, route.provider
+                ),
+            ));
+        }
+    }
+
+    for name in sorted_keys(&config.agents) {
+        let agent = &config.agents[name];
+        if let Some(reason) = provider_error(&agent.provider) {
+            out.push(Diagnostic::error(
+                format!("agents.{name}.provider"),
+                format!(
+                    "delegate agent uses unknown provider \"{}\": {reason}",
+                    agent.provider
+                ),
+            ));
+        }
+    }
+    for name in sorted_keys(&config.personas) {
+        if let Some(provider) = &config.personas[name].provider {
+            if let Some(reason) = provider_error(provider) {
+                out.push(Diagnostic::error(
+                    format!("personas.{name}.provider"),
+                    format!("persona uses unknown provider \"{provider}\": {reason}"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_hints(config: &Config, out: &mut Vec<Diagnostic>) {
+    let model_hints: BTreeSet<&str> = config
+        .model_routes
+        .iter()
+        .map(|route| route.hint.as_str())
+        .collect();
+    let missing_route = |hint: &str| format!("no [[model_routes]] entry has hint \"{hint}\"");
+
+    if let Some(hint) = config
+        .default_model
+        .as_deref()
+        .and_then(|model| model.strip_prefix("hint:"))
+    {
+        if !model_hints.contains(hint) {
+            out.push(Diagnostic::error("default_model", missing_route(hint)));
+        }
+    }
+    for name in sorted_keys(&config.personas) {
+        if let Some(hint) = config.personas[name]
+            .model
+            .as_deref()
+            .and_then(|model| model.strip_prefix("hint:"))
+        {
+            if !model_hints.contains(hint) {
+                out.push(Diagnostic::error(
+                    format!("personas.{name}.model"),
+                    missing_route(hint),
+                ));
+            }
+        }
+    }
+    for (i, rule) in config.query_classification.rules.iter().enumerate() {
+        if !model_hints.contains(rule.hint.as_str()) {
+            let path = format!("query_classification.rules.{i}.hint");
+            let message = format!("{}; the rule never routes", missing_route(&rule.hint));
+            out.push(if config.query_classification.enabled {
+                Diagnostic::error(path, message)
+            } else {
+                Diagnostic::warning(path, message)
+            });
+        }
+    }
+
+    if let Some(hint) = config
+        .memory
+        .embedding_model
+        .strip_prefix("hint:")
+        .map(str::trim)
+        .filter(|hint| !hint.is_empty())
+    {
+        if !config
+            .embedding_routes
+            .iter()
+            .any(|route| route.hint.trim() == hint)
+        {
+            out.push(Diagnostic::error(
+                "memory.embedding_model",
+                format!("no [[embedding_routes]] entry has hint \"{hint}\""),
+            ));
+        }
+    }
+}
+
+/// Names of the channels with a config section, e.g. `telegram`, `webhook`.
+pub fn configured_channels(config: &Config) -> BTreeSet<String> {
+    let Ok(serde_json::Value::Object(channels)) = serde_json::to_value(&config.channels_config)
+    else {
+        return BTreeSet::new();
+    };
+    let mut names: BTreeSet<String> = channels
+        .into_iter()
+        .filter(|(_, value)| value.is_object())
+        .map(|(name, _)| name)
+        .collect();
+    if config.channels_config.cli {
+        names.insert("cli".into());
+    }
+    names
+}
+
+fn check_channel_references(config: &Config, out: &mut Vec<Diagnostic>) {
+    let channels = configured_channels(config);
+
+    for (i, route) in config.persona_routes.iter().enumerate() {
+        if !config.personas.contains_key(&route.persona) {
+            out.push(Diagnostic::error(
+                format!("persona_routes.{i}.persona"),
+                format!("no [personas.{}] is defined", route.persona),
+            ));
+        }
+        if let Some(channel) = &route.channel {
+            if !channels.contains(channel) {
+                out.push(Diagnostic::warning(
+                    format!("persona_routes.{i}.channel"),
+                    format!("channel \"{channel}\" is not configured; the route never matches"),
+                ));
+            }
+        }
+    }
+
+    for name in sorted_keys(&config.users) {
+        for (i, identity) in config.users[name].identities.iter().enumerate() {
+            let path = format!("users.{name}.identities.{i}");
+            match identity.split_once(':') {
+                Some((channel, id)) if !channel.is_empty() && !id.is_empty() => {
+                    if !channels.contains(channel) {
+                        out.push(Diagnostic::warning(
+                            path,
+                            format!("channel \"{channel}\" is not configured"),
+                        ));
+                    }
+                }
+                _ => out.push(Diagnostic::error(
+                    path,
+                    format!("identity \"{identity}\" must look like <channel>:<sender id>"),
+                )),
+            }
+        }
+    }
+
+    let send_message = &config.send_message;
+    for name in sorted_keys(&send_message.contacts) {
+        let contact = &send_message.contacts[name];
+        if !channels.contains(&contact.channel) {
+            out.push(Diagnostic::warning(
+                format!("send_message.contacts.{name}.channel"),
+                format!("channel \"{}\" is not configured", contact.channel),
+            ));
+        }
+    }
+    for (i, entry) in send_message.allowed_recipients.iter().enumerate() {
+        let path = format!("send_message.allowed_recipients.{i}");
+        match entry.split_once(':') {
+            Some((channel, to)) if !channel.is_empty() && !to.is_empty() => {
+                if channel != "*" && !channels.contains(channel) {
+                    out.push(Diagnostic::warning(
+                        path,
+                        format!("channel \"{channel}\" is not configured"),
+                    ));
+                }
+            }
+            _ => out.push(Diagnostic::error(
+                path,
+                format!("entry \"{entry}\" must look like <channel>:<recipient>"),
+            )),
+        }
+    }
+}
+
+fn check_autonomy(config: &Config, out: &mut Vec<Diagnostic>) {
+    use crate::security::AutonomyLevel;
+
+    let autonomy = &config.autonomy;
+    for (i, tool) in autonomy.auto_approve.iter().enumerate() {
+        if autonomy.always_ask.contains(tool) {
+            out.push(Diagnostic::warning(
+                format!("autonomy.auto_approve.{i}"),
+                format!("\"{tool}\" is also in always_ask, which wins; this entry has no effect"),
+            ));
+        }
+    }
+    if autonomy.level != AutonomyLevel::Supervised && !autonomy.always_ask.is_empty() {
+        out.push(Diagnostic::warning(
+            "autonomy.always_ask",
+            format!(
+                "only applies at level = \"supervised\"; level is {:?}",
+                autonomy.level
+            ),
+        ));
+    }
+    if autonomy.level == AutonomyLevel::Full && config.gateway.admin.approvals {
+        out.push(Diagnostic::warning(
+            "gateway.admin.approvals",
+            "autonomy.level = \"full\" never asks for approval, so the approval queue stays empty",
+        ));
+    }
+
+    let mut seen = BTreeSet::new();
+    for (i, command) in autonomy.allowed_commands.iter().enumerate() {
+        let path = format!("autonomy.allowed_commands.{i}");
+        if command.trim().is_empty() {
+            out.push(Diagnostic::warning(path, "empty entry never matches"));
+        } else if command.contains(char::is_whitespace) {
+            out.push(Diagnostic::warning(
+                path,
+                format!(
+                    "\"{command}\" never matches: commands are matched by executable name only"
+                ),
+            ));
+        } else if command.contains('/') {
+            let name = command.rsplit('/').next().unwrap_or(command);
+            out.push(Diagnostic::warning(
+                path,
+                format!(
+                    "\"{command}\" never matches: commands are matched by executable name; use \"{name}\""
+                ),
+            ));
+        } else if !seen.insert(command.as_str()) {
+            out.push(Diagnostic::warning(
+                path,
+                format!("\"{command}\" is listed twice"),
+            ));
+        }
+    }
+}
+
+/// Check every `allowed_*` string list under `[channels_config.<name>]`.
+fn check_channel_allowlists(config: &Config, out: &mut Vec<Diagnostic>) {
+    let Ok(serde_json::Value::Object(channels)) = serde_json::to_value(&config.channels_config)
+    else {
+        return;
+    };
+    for (channel, section) in &channels {
+        let Some(section) = section.as_object() else {
+            continue;
+        };
+        for (field, value) in section {
+            let Some(entries) = value.as_array().filter(|_| field.starts_with("allowed_")) else {
+                continue;
+            };
+            let entries: Vec<&str> = entries.iter().filter_map(|v| v.as_str()).collect();
+            let has_wildcard = entries.contains(&"*");
+            let mut seen = BTreeSet::new();
+            for (i, entry) in entries.iter().enumerate() {
+                let path = format!("channels_config.{channel}.{field}.{i}");
+                if entry.trim().is_empty() {
+                    out.push(Diagnostic::warning(path, "empty entry never matches"));
+                } else if entry.trim() != *entry {
+                    out.push(Diagnostic::warning(
+                        path,
+                        format!("\"{entry}\" has surrounding whitespace and never matches"),
+                    ));
+                } else if has_wildcard && *entry != "*" {
+                    out.push(Diagnostic::warning(
+                        path,
+                        format!("\"{entry}\" is redundant: \"*\" already allows everyone"),
+                    ));
+                } else if !seen.insert(*entry) {
+                    out.push(Diagnostic::warning(
+                        path,
+                        format!("\"{entry}\" is listed twice"),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn check_cron_jobs(config: &Config, out: &mut Vec<Diagnostic>) {
+    use crate::cron::Schedule;
+
+    // Do not create the cron database just to validate.
+    if !config.workspace_dir.join("cron").join("jobs.db").exists() {
+        return;
+    }
+    let jobs = match crate::cron::list_jobs(config) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            out.push(Diagnostic::warning(
+                "cron",
+                format!("could not read stored cron jobs: {e}"),
+            ));
+            return;
+        }
+    };
+    let channels = configured_channels(config);
+    let hooks_authenticated = config.gateway.require_pairing
+        || config
+            .channels_config
+            .webhook
+            .as_ref()
+            .and_then(|webhook| webhook.secret.as_deref())
+            .is_some_and(|secret| !secret.trim().is_empty());
+
+    for job in &jobs {
+        match &job.schedule {
+            Schedule::Webhook { name } if !hooks_authenticated => {
+                out.push(
+                    Diagnostic::warning(
+                        "schedule.name",
+                        format!(
+                            "hook \"{name}\" is refused by the gateway without pairing or a webhook secret; \
+                             enable gateway.require_pairing or set channels_config.webhook.secret"
+                        ),
+                    )
+                    .for_job(job),
+                );
+            }
+            Schedule::Cron { .. } => {
+                if let Err(e) = crate::cron::validate_schedule(&job.schedule, chrono::Utc::now()) {
+                    out.push(Diagnostic::error("schedule", format!("{e:#}")).for_job(job));
+                }
+            }
+            Schedule::Channel { pattern, channel } => {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    out.push(
+                        Diagnostic::error("schedule.pattern", format!("invalid regex: {e}"))
+                            .for_job(job),
+                    );
+                }
+                if let Some(channel) = channel.as_ref().filter(|c| !channels.contains(*c)) {
+                    out.push(
+                        Diagnostic::warning(
+                            "schedule.channel",
+                            format!("channel \"{channel}\" is not configured; the job never fires"),
+                        )
+                        .for_job(job),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        let delivery = &job.delivery;
+        if !delivery.mode.eq_ignore_ascii_case("announce") {
+            continue;
+        }
+        match delivery.channel.as_deref() {
+            None => out.push(
+                Diagnostic::error("delivery.channel", "required for announce delivery")
+                    .for_job(job),
+            ),
+            Some(channel) => {
+                let lower = channel.to_ascii_lowercase();
+                if !DELIVERY_CHANNELS.contains(&lower.as_str()) {
+                    out.push(
+                        Diagnostic::error(
+                            "delivery.channel",
+                            format!(
+                                "\"{channel}\" cannot receive announcements (supported: {})",
+                                DELIVERY_CHANNELS.join(", ")
+                            ),
+                        )
+                        .for_job(job),
+                    );
+                } else if !channels.contains(&lower) {
+                    out.push(
+                        Diagnostic::error(
+                            "delivery.channel",
+                            format!("channel \"{channel}\" is not configured"),
+                        )
+                        .for_job(job),
+                    );
+                }
+            }
+        }
+        if delivery.to.as_deref().is_none_or(|to| to.trim().is_empty()) {
+            out.push(
+                Diagnostic::error("delivery.to", "required for announce delivery").for_job(job),
+            );
+        }
+    }
+}
+
+/// Tasks in the workspace HEARTBEAT.md whose annotations don't parse. The
+/// heartbeat skips them, so surface them where they can be fixed.
+fn check_heartbeat_tasks(config: &Config, out: &mut Vec<Diagnostic>) {
+    let file = config.workspace_dir.join("HEARTBEAT.md");
+    let Ok(contents) = std::fs::read_to_string(&file) else {
+        return;
+    };
+    for (line, parsed) in crate::heartbeat::task::parse_task_lines(&contents) {
+        if let Err(e) = parsed {
+            let mut diagnostic =
+                Diagnostic::warning("heartbeat.task", format!("{e}; the task is skipped"));
+            diagnostic.location = Some(Location {
+                file: file.clone(),
+                line,
+                column: 1,
+            });
+            out.push(diagnostic);
+        }
+    }
+}
+
+/// Attach the file, line and column where each diagnostic's key is set. The
+/// last file in merge order that sets the key wins, matching how layers merge.
+/// Keys set in a `[profiles.<name>]` table are found there.
+pub fn locate(config: &Config, diagnostics: &mut [Diagnostic]) {
+    let files: Vec<PathBuf> = if config.sources.files.is_empty() {
+        vec![config.config_path.clone()]
+    } else {
+        config.sources.files.clone()
+    };
+    let documents: Vec<(PathBuf, String)> = files
+        .into_iter()
+        .rev()
+        .filter_map(|file| {
+            let contents = std::fs::read_to_string(&file).ok()?;
+            Some((file, contents))
+        })
+        .collect();
+
+    for diagnostic in diagnostics
+        .iter_mut()
+        .filter(|d| d.job.is_none() && d.location.is_none())
+    {
+        let keys: Vec<&str> = diagnostic.path.split('.').collect();
+        let mut candidates = Vec::new();
+        if let Some(profile) = &config.sources.profile {
+            let mut prefixed = vec!["profiles", profile.as_str()];
+            prefixed.extend(&keys);
+            candidates.push(prefixed);
+        }
+        candidates.push(keys);
+
+        // Prefer an exact key; fall back to the nearest enclosing table.
+        let mut best: Option<(usize, Location)> = None;
+        for (file, contents) in &documents {
+            for keys in &candidates {
+                if let Some((depth, offset)) = span_of(contents, keys) {
+                    if best
+                        .as_ref()
+                        .is_none_or(|(best_depth, _)| depth > *best_depth)
+                    {
+                        best = Some((depth, location_at(file, contents, offset)));
+                    }
+                }
+            }
+            if best
+                .as_ref()
+                .is_some_and(|(depth, _)| *depth == keys_len(&candidates))
+            {
+                break;
+            }
+        }
+        diagnostic.location = best.map(|(_, location)| location);
+    }
+}
+
+fn keys_len(candidates: &[Vec<&str>]) -> usize {
+    candidates.last().map_or(0, Vec::len)
+}
+
+/// Byte offset of the deepest part of `keys` found in `contents`, with the
+/// number of keys matched. Only the unprefixed keys count toward the depth.
+fn span_of(contents: &str, keys: &[&str]) -> Option<(usize, usize)> {
+    use toml::de::{DeTable, DeValue};
+
+    let root = DeTable::parse(contents).ok()?;
+    let prefix = usize::from(keys.first() == Some(&"profiles")) * 2;
+    let mut table = root.get_ref();
+    let mut value: Option<&toml::Spanned<DeValue<'_>>> = None;
+    let mut found = None;
+    for (depth, key) in keys.iter().enumerate() {
+        let next = match value.map(toml::Spanned::get_ref) {
+            None => table
+                .iter()
+                .find(|(k, _)| k.get_ref().as_ref() == *key)
+                .map(|(k, v)| (k.span().start, v)),
+            Some(DeValue::Table(t)) => {
+                table = t;
+                t.iter()
+                    .find(|(k, _)| k.get_ref().as_ref() == *key)
+                    .map(|(k, v)| (k.span().start, v))
+            }
+            Some(DeValue::Array(items)) => key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index))
+                .map(|item| (item.span().start, item)),
+            Some(_) => None,
+        };
+        let Some((offset, next)) = next else {
+            break;
+        };
+        value = Some(next);
+        if depth + 1 > prefix {
+            found = Some((depth + 1 - prefix, offset));
+        }
+    }
+    found
+}
+
+fn location_at(file: &Path, contents: &str, offset: usize) -> Location {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    Location {
+        file: file.to_path_buf(),
+        line,
+        column,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_config(tmp: &TempDir, contents: &str) -> Config {
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        let table: toml::Table = toml::from_str(contents).unwrap();
+        let mut config: Config = toml::Value::Table(table).try_into().unwrap();
+        config.config_path = path;
+        config.workspace_dir = tmp.path().join("workspace");
+        config
+    }
+
+    const BASE: &str = "default_temperature = 0.7\n";
+
+    #[test]
+    fn default_config_has_no_blocking_problems() {
+        let config = Config::default();
+        let diagnostics = check_config(&config);
+        assert!(
+            diagnostics.iter().all(|d| !d.is_blocking()),
+            "{diagnostics:?}"
+        );
+    }
+
+    #[test]
+    fn unknown_route_and_agent_providers_are_located() {
+        let tmp = TempDir::new().unwrap();
+        let config = write_config(
+            &tmp,
+            &format!(
+                "{BASE}\n[[model_routes]]\nhint = \"fast\"\nprovider = \"openrouter\"\nmodel = \"m\"\n\n\
+                 [[model_routes]]\nhint = \"think\"\nprovider = \"nope-ai\"\nmodel = \"m\"\n\n\
+                 [agents.research]\nprovider = \"bogus\"\nmodel = \"m\"\n"
+            ),
+        );
+        let diagnostics = validate(&config);
+
+        let route = diagnostics
+            .iter()
+            .find(|d| d.path == "model_routes.1.provider")
+            .expect("route diagnostic");
+        assert_eq!(route.severity, Severity::Error);
+        let location = route.location.as_ref().unwrap();
+        assert_eq!(location.file, config.config_path);
+        assert_eq!(location.line, 10);
+        assert!(route
+            .to_string()
+            .contains("config.toml:10:1: error: `model_routes.1.provider`"));
+
+        let agent = diagnostics
+            .iter()
+            .find(|d| d.path == "agents.research.provider")
+            .expect("agent diagnostic");
+        assert_eq!(agent.location.as_ref().unwrap().line, 14);
+    }
+
+    #[test]
+    fn hints_and_channel_references_must_resolve() {
+        let tmp = TempDir::new().unwrap();
+        let config = write_config(
+            &tmp,
+            &format!(
+                "{BASE}default_model = \"hint:missing\"\n\
+                 [[persona_routes]]\npersona = \"ghost\"\nchannel = \"telegram\"\n\
+                 [users.alice]\nidentities = [\"slack:U1\", \"no-colon\"]\n\
+                 [send_message]\nallowed_recipients = [\"*:ops\", \"ops\"]\n\
+                 [send_message.contacts.oncall]\nchannel = \"discord\"\nto = \"123\"\n"
+            ),
+        );
+        let diagnostics = check_config(&config);
+        let find = |path: &str| diagnostics.iter().find(|d| d.path == path);
+
+        assert_eq!(find("default_model").unwrap().severity, Severity::Error);
+        assert_eq!(
+            find("persona_routes.0.persona").unwrap().severity,
+            Severity::Error
+        );
+        assert_eq!(
+            find("persona_routes.0.channel").unwrap().severity,
+            Severity::Warning
+        );
+        assert!(find("users.alice.identities.0")
+            .unwrap()
+            .message
+            .contains("\"slack\" is not configured"));
+        assert_eq!(
+            find("users.alice.identities.1").unwrap().severity,
+            Severity::Error
+        );
+        assert!(find("send_message.contacts.oncall.channel").is_some());
+        assert!(find("send_message.allowed_recipients.0").is_none());
+        assert_eq!(
+            find("send_message.allowed_recipients.1").unwrap().severity,
+            Severity::Error
+        );
+    }
+
+    #[test]
+    fn conflicting_autonomy_and_unreachable_allowlist_entries_warn() {
+        let mut config = Config::default();
+        config.autonomy.auto_approve = vec!["shell".into()];
+        config.autonomy.always_ask = vec!["shell".into()];
+        config.autonomy.allowed_commands =
+            vec!["git".into(), "/usr/bin/git".into(), "git status".into()];
+        config.channels_config.telegram = Some(crate::config::TelegramConfig {
+            bot_token: "t".into(),
+            allowed_users: vec!["*".into(), "alice".into(), " bob".into()],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+        });
+
+        let diagnostics = check_config(&config);
+        let messages: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{} {}", d.path, d.message))
+            .collect();
+        let has = |needle: &str| messages.iter().any(|m| m.contains(needle));
+        assert!(has(
+            "autonomy.auto_approve.0 \"shell\" is also in always_ask"
+        ));
+        assert!(has(
+            "autonomy.allowed_commands.1 \"/usr/bin/git\" never matches"
+        ));
+        assert!(has("use \"git\""));
+        assert!(has(
+            "autonomy.allowed_commands.2 \"git status\" never matches"
+        ));
+        assert!(has(
+            "channels_config.telegram.allowed_users.1 \"alice\" is redundant"
+        ));
+        assert!(has(
+            "channels_config.telegram.allowed_users.2 \" bob\" has surrounding"
+        ));
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn stored_cron_jobs_are_checked_for_delivery_channels() {
+        let tmp = TempDir::new().unwrap();
+        let config = write_config(&tmp, BASE);
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let job = crate::cron::add_job(&config, "*/5 * * * *", "echo hi").unwrap();
+        crate::cron::update_job(
+            &config,
+            &job.id,
+            crate::cron::CronJobPatch {
+                delivery: Some(crate::cron::DeliveryConfig {
+                    mode: "announce".into(),
+                    channel: Some("telegram".into()),
+                    to: None,
+                    best_effort: true,
+                }),
+                ..crate::cron::CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        let diagnostics = validate(&config);
+        let job_diagnostics: Vec<_> = diagnostics.iter().filter(|d| d.job.is_some()).collect();
+        assert_eq!(job_diagnostics.len(), 2, "{diagnostics:?}");
+        assert!(job_diagnostics
+            .iter()
+            .any(|d| d.path == "delivery.channel" && d.message.contains("not configured")));
+        assert!(job_diagnostics.iter().any(|d| d.path == "delivery.to"));
+        assert!(job_diagnostics.iter().all(|d| !d.is_blocking()));
+        assert!(job_diagnostics[0].to_string().contains(&job.id));
+    }
+
+    #[test]
+    fn unauthenticated_hook_jobs_are_reported() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = write_config(&tmp, BASE);
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        crate::cron::add_shell_job(
+            &config,
+            None,
+            crate::cron::Schedule::Webhook {
+                name: "deploy".into(),
+            },
+            "echo deployed",
+        )
+        .unwrap();
+
+        config.gateway.require_pairing = false;
+        let diagnostics = validate(&config);
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.path == "schedule.name" && d.message.contains("\"deploy\"")),
+            "{diagnostics:?}"
+        );
+
+        config.gateway.require_pairing = true;
+        assert!(validate(&config).iter().all(|d| d.path != "schedule.name"));
+    }
+
+    #[test]
+    fn invalid_heartbeat_tasks_are_reported_with_their_line() {
+        let tmp = TempDir::new().unwrap();
+        let config = write_config(&tmp, BASE);
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        std::fs::write(
+            config.workspace_dir.join("HEARTBEAT.md"),
+            "# Tasks\n- Check email\n- Night report | hours=22-25\n",
+        )
+        .unwrap();
+
+        let diagnostics = validate(&config);
+        let heartbeat: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.path == "heartbeat.task")
+            .collect();
+        assert_eq!(heartbeat.len(), 1, "{diagnostics:?}");
+        assert!(!heartbeat[0].is_blocking());
+        let location = heartbeat[0].location.as_ref().unwrap();
+        assert!(location.file.ends_with("HEARTBEAT.md"));
+        assert_eq!(location.line, 3);
+    }
+
+    #[test]
+    fn profile_keys_are_located_in_the_profile_table() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = write_config(
+            &tmp,
+            &format!(
+                "{BASE}default_provider = \"openrouter\"\n\n[profiles.prod]\ndefault_provider = \"nope-ai\"\n"
+            ),
+        );
+        config.default_provider = Some("nope-ai".into());
+        config.sources.profile = Some("prod".into());
+        let diagnostics = validate(&config);
+        let provider = diagnostics
+            .iter()
+            .find(|d| d.path == "default_provider")
+            .unwrap();
+        assert_eq!(provider.location.as_ref().unwrap().line, 5);
+    }
+}
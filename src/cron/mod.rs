@@ -1,12 +1,14 @@
 use crate::config::Config;
 use crate::security::SecurityPolicy;
 use anyhow::{bail, Result};
+use std::fmt::Write;
 
 mod schedule;
 mod store;
 mod types;
 
 pub mod scheduler;
+pub mod triggers;
 
 #[allow(unused_imports)]
 pub use schedule::{
@@ -14,15 +16,21 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_job_with, add_shell_job, dependents_of, due_jobs, get_job,
+    jobs_revision, list_jobs, list_runs, record_last_run, record_run, remove_job,
+    reschedule_after_run, set_next_run, update_job, upsert_backup_job, validate_dependencies,
+};
+pub use triggers::{TriggerEvent, TriggerKind, WatchTracker};
+#[allow(unused_imports)]
+pub use types::{
+    CatchUpPolicy, CronJob, CronJobPatch, CronRun, DeliveryConfig, DependencyCondition,
+    JobDependency, JobType, OverlapPolicy, RunStatus, Schedule, SessionTarget,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
     match command {
-        crate::CronCommands::List => {
+        crate::CronCommands::List { tree } => {
             let jobs = list_jobs(config)?;
             if jobs.is_empty() {
                 println!("No scheduled tasks yet.");
@@ -31,19 +39,26 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 return Ok(());
             }
 
+            if tree {
+                println!("🕒 Scheduled jobs ({}):", jobs.len());
+                print!("{}", render_job_tree(&jobs));
+                return Ok(());
+            }
+
             println!("🕒 Scheduled jobs ({}):", jobs.len());
             for job in jobs {
                 let last_run = job
                     .last_run
                     .map_or_else(|| "never".into(), |d| d.to_rfc3339());
-                let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
+                let last_status = job.last_status.as_deref().unwrap_or("n/a");
+                let next = if job.schedule.is_event_trigger() {
+                    describe_schedule(&job.schedule)
+                } else {
+                    job.next_run.to_rfc3339()
+                };
                 println!(
                     "- {} | {:?} | next={} | last={} ({})",
-                    job.id,
-                    job.schedule,
-                    job.next_run.to_rfc3339(),
-                    last_run,
-                    last_status,
+                    job.id, job.schedule, next, last_run, last_status,
                 );
                 if !job.command.is_empty() {
                     println!("    cmd: {}", job.command);
@@ -51,6 +66,12 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 if let Some(prompt) = &job.prompt {
                     println!("    prompt: {prompt}");
                 }
+                if !job.after.is_empty() {
+                    println!("    after: {}", format_dependencies(&job.after));
+                }
+                if let Some(policy) = format_run_policy(&job) {
+                    println!("    policy: {policy}");
+                }
             }
             Ok(())
         }
@@ -70,6 +91,23 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("  Cmd : {}", job.command);
             Ok(())
         }
+        crate::CronCommands::AddAfter { after, command } => {
+            let job = add_job_with(
+                config,
+                JobType::Shell,
+                None,
+                Schedule::After,
+                CronJobPatch {
+                    command: Some(command),
+                    after: Some(parse_dependencies(&after)?),
+                    ..CronJobPatch::default()
+                },
+            )?;
+            println!("✅ Added chained cron job {}", job.id);
+            println!("  After: {}", format_dependencies(&job.after));
+            println!("  Cmd  : {}", job.command);
+            Ok(())
+        }
         crate::CronCommands::AddAt { at, command } => {
             let at = chrono::DateTime::parse_from_rfc3339(&at)
                 .map_err(|e| anyhow::anyhow!("Invalid RFC3339 timestamp for --at: {e}"))?
@@ -90,6 +128,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("  Cmd      : {}", job.command);
             Ok(())
         }
+        crate::CronCommands::AddTrigger { trigger, command } => {
+            let schedule = Schedule::parse_trigger(&trigger).map_err(anyhow::Error::msg)?;
+            let job = add_shell_job(config, None, schedule, &command)?;
+            println!("✅ Added event-triggered cron job {}", job.id);
+            println!("  On  : {}", describe_schedule(&job.schedule));
+            println!("  Cmd : {}", job.command);
+            Ok(())
+        }
         crate::CronCommands::Once { delay, command } => {
             let job = add_once(config, &delay, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
@@ -103,9 +149,21 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             tz,
             command,
             name,
+            after,
+            overlap,
+            catch_up,
+            jitter_secs,
         } => {
-            if expression.is_none() && tz.is_none() && command.is_none() && name.is_none() {
-                bail!("At least one of --expression, --tz, --command, or --name must be provided");
+            if expression.is_none()
+                && tz.is_none()
+                && command.is_none()
+                && name.is_none()
+                && after.is_empty()
+                && overlap.is_none()
+                && catch_up.is_none()
+                && jitter_secs.is_none()
+            {
+                bail!("At least one of --expression, --tz, --command, --name, --after, --overlap, --catch-up, or --jitter must be provided");
             }
 
             // Merge expression/tz with the existing schedule so that
@@ -139,6 +197,22 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 schedule,
                 command,
                 name,
+                after: if after.is_empty() {
+                    None
+                } else {
+                    Some(parse_dependencies(&after)?)
+                },
+                overlap: overlap
+                    .as_deref()
+                    .map(OverlapPolicy::try_from)
+                    .transpose()
+                    .map_err(anyhow::Error::msg)?,
+                catch_up: catch_up
+                    .as_deref()
+                    .map(CatchUpPolicy::try_from)
+                    .transpose()
+                    .map_err(anyhow::Error::msg)?,
+                jitter_secs,
                 ..CronJobPatch::default()
             };
 
@@ -149,6 +223,36 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("  Cmd : {}", job.command);
             Ok(())
         }
+        crate::CronCommands::Runs { id, limit } => {
+            let job = get_job(config, &id)?;
+            let runs = list_runs(config, &job.id, limit)?;
+            if runs.is_empty() {
+                println!("No runs recorded for cron job {} yet.", job.id);
+                return Ok(());
+            }
+
+            println!("🕒 Recent runs of {} ({}):", job.id, runs.len());
+            for run in runs {
+                let duration = run
+                    .duration_ms
+                    .map_or_else(String::new, |ms| format!(" | {ms}ms"));
+                println!(
+                    "- {} | {}{}",
+                    run.started_at.to_rfc3339(),
+                    run.status,
+                    duration
+                );
+                if let Some(output) = run.output.as_deref().map(str::trim) {
+                    if !output.is_empty() {
+                        println!(
+                            "    {}",
+                            crate::util::truncate_with_ellipsis(output, 200).replace('\n', " ")
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
         crate::CronCommands::Remove { id } => remove_job(config, &id),
         crate::CronCommands::Pause { id } => {
             pause_job(config, &id)?;
@@ -200,7 +304,125 @@ pub fn resume_job(config: &Config, id: &str) -> Result<CronJob> {
     )
 }
 
-fn parse_delay(input: &str) -> Result<chrono::Duration> {
+fn describe_schedule(schedule: &Schedule) -> String {
+    match schedule {
+        Schedule::Watch { path, glob } => match glob {
+            Some(glob) => format!("on change in {path} ({glob})"),
+            None => format!("on change in {path}"),
+        },
+        Schedule::Webhook { name } => format!("on POST /hooks/{name}"),
+        Schedule::Channel { pattern, channel } => match channel {
+            Some(channel) => format!("on {channel} message /{pattern}/"),
+            None => format!("on channel message /{pattern}/"),
+        },
+        Schedule::After => "after upstream jobs".to_string(),
+        Schedule::Cron { .. } | Schedule::At { .. } | Schedule::Every { .. } => {
+            format!("{schedule:?}")
+        }
+    }
+}
+
+/// Non-default overlap, catch-up and jitter settings, if any.
+fn format_run_policy(job: &CronJob) -> Option<String> {
+    let mut parts = Vec::new();
+    if job.overlap != OverlapPolicy::default() {
+        parts.push(format!("overlap={}", job.overlap.as_str()));
+    }
+    if job.catch_up != CatchUpPolicy::default() {
+        parts.push(format!("catch_up={}", job.catch_up));
+    }
+    if job.jitter_secs > 0 {
+        parts.push(format!("jitter={}s", job.jitter_secs));
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+fn parse_dependencies(raw: &[String]) -> Result<Vec<JobDependency>> {
+    raw.iter()
+        .map(|item| JobDependency::parse(item).map_err(anyhow::Error::msg))
+        .collect()
+}
+
+fn format_dependencies(deps: &[JobDependency]) -> String {
+    deps.iter()
+        .map(|dep| format!("{} ({})", dep.job_id, dep.condition.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Render jobs as a forest: scheduled jobs at the root, chained jobs nested
+/// under each upstream they depend on.
+fn render_job_tree(jobs: &[CronJob]) -> String {
+    fn label(job: &CronJob) -> String {
+        match &job.name {
+            Some(name) => format!("{} [{name}]", job.id),
+            None => job.id.clone(),
+        }
+    }
+
+    fn render_children(
+        jobs: &[CronJob],
+        parent: &CronJob,
+        prefix: &str,
+        path: &mut Vec<String>,
+        out: &mut String,
+    ) {
+        let children: Vec<(&CronJob, &JobDependency)> = jobs
+            .iter()
+            .filter_map(|job| {
+                job.after
+                    .iter()
+                    .find(|dep| dep.job_id == parent.id)
+                    .map(|dep| (job, dep))
+            })
+            .collect();
+
+        for (idx, (child, dep)) in children.iter().enumerate() {
+            let last = idx + 1 == children.len();
+            let branch = if last { "└─" } else { "├─" };
+            let _ = writeln!(
+                out,
+                "{prefix}{branch} ({}) {}",
+                dep.condition.as_str(),
+                label(child)
+            );
+            // Cycles are rejected at add/update time; guard anyway.
+            if path.contains(&child.id) {
+                continue;
+            }
+            path.push(child.id.clone());
+            let next_prefix = format!("{prefix}{}", if last { "   " } else { "│  " });
+            render_children(jobs, child, &next_prefix, path, out);
+            path.pop();
+        }
+    }
+
+    let mut out = String::new();
+    for root in jobs.iter().filter(|job| {
+        job.after
+            .iter()
+            .all(|dep| !jobs.iter().any(|other| other.id == dep.job_id))
+    }) {
+        let next = if root.schedule.is_event_trigger() {
+            describe_schedule(&root.schedule)
+        } else {
+            root.next_run.to_rfc3339()
+        };
+        let _ = writeln!(out, "- {} | next={next}", label(root));
+        if !root.after.is_empty() {
+            let _ = writeln!(
+                out,
+                "    (upstream missing: {})",
+                format_dependencies(&root.after)
+            );
+        }
+        let mut path = vec![root.id.clone()];
+        render_children(jobs, root, "  ", &mut path, &mut out);
+    }
+    out
+}
+
+pub(crate) fn parse_delay(input: &str) -> Result<chrono::Duration> {
     let input = input.trim();
     if input.is_empty() {
         anyhow::bail!("delay must not be empty");
@@ -264,6 +486,10 @@ mod tests {
                 tz: tz.map(Into::into),
                 command: command.map(Into::into),
                 name: name.map(Into::into),
+                after: vec![],
+                overlap: None,
+                catch_up: None,
+                jitter_secs: None,
             },
             config,
         )
@@ -413,4 +639,161 @@ mod tests {
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
         assert!(security.is_command_allowed("echo safe"));
     }
+
+    #[test]
+    fn add_after_chains_job_and_tree_nests_it() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let backup = make_job(&config, "0 1 * * *", None, "echo backup");
+
+        handle_command(
+            crate::CronCommands::AddAfter {
+                command: "echo summary".into(),
+                after: vec![format!("{}:always", backup.id)],
+            },
+            &config,
+        )
+        .unwrap();
+
+        let jobs = list_jobs(&config).unwrap();
+        let summary = jobs.iter().find(|job| job.id != backup.id).unwrap();
+        assert_eq!(summary.after[0].job_id, backup.id);
+        assert_eq!(summary.schedule, Schedule::After);
+
+        let tree = render_job_tree(&jobs);
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(&format!("- {}", backup.id)));
+        assert_eq!(lines[1], format!("  └─ (always) {}", summary.id));
+    }
+
+    #[test]
+    fn add_after_with_unknown_upstream_fails_without_creating_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let result = handle_command(
+            crate::CronCommands::AddAfter {
+                command: "echo summary".into(),
+                after: vec!["missing".into()],
+            },
+            &config,
+        );
+        assert!(result.is_err());
+        assert!(list_jobs(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn update_after_rejects_cycle() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let a = make_job(&config, "*/5 * * * *", None, "echo a");
+        let b = make_job(&config, "*/5 * * * *", None, "echo b");
+        let update_after = |id: &str, upstream: &str| {
+            handle_command(
+                crate::CronCommands::Update {
+                    id: id.into(),
+                    expression: None,
+                    tz: None,
+                    command: None,
+                    name: None,
+                    after: vec![upstream.into()],
+                    overlap: None,
+                    catch_up: None,
+                    jitter_secs: None,
+                },
+                &config,
+            )
+        };
+
+        update_after(&b.id, &a.id).unwrap();
+        let err = update_after(&a.id, &b.id).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn update_sets_run_policies_via_handler() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = make_job(&config, "*/5 * * * *", None, "echo policy");
+
+        handle_command(
+            crate::CronCommands::Update {
+                id: job.id.clone(),
+                expression: None,
+                tz: None,
+                command: None,
+                name: None,
+                after: vec![],
+                overlap: Some("cancel-previous".into()),
+                catch_up: Some("none".into()),
+                jitter_secs: Some(15),
+            },
+            &config,
+        )
+        .unwrap();
+
+        let updated = get_job(&config, &job.id).unwrap();
+        assert_eq!(updated.overlap, OverlapPolicy::CancelPrevious);
+        assert_eq!(updated.catch_up, CatchUpPolicy::None);
+        assert_eq!(
+            format_run_policy(&updated).as_deref(),
+            Some("overlap=cancel_previous catch_up=none jitter=15s")
+        );
+
+        let err = handle_command(
+            crate::CronCommands::Update {
+                id: job.id,
+                expression: None,
+                tz: None,
+                command: None,
+                name: None,
+                after: vec![],
+                overlap: Some("wait".into()),
+                catch_up: None,
+                jitter_secs: None,
+            },
+            &config,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("overlap policy"));
+    }
+
+    #[test]
+    fn add_trigger_creates_event_job_outside_due_queue() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        handle_command(
+            crate::CronCommands::AddTrigger {
+                trigger: "hook:nightly".into(),
+                command: "echo hook".into(),
+            },
+            &config,
+        )
+        .unwrap();
+
+        let jobs = list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(
+            jobs[0].schedule,
+            Schedule::Webhook {
+                name: "nightly".into()
+            }
+        );
+        assert!(
+            due_jobs(&config, chrono::Utc::now() + chrono::Duration::days(3650))
+                .unwrap()
+                .is_empty()
+        );
+
+        let bad = handle_command(
+            crate::CronCommands::AddTrigger {
+                trigger: "hook:bad/name".into(),
+                command: "echo hook".into(),
+            },
+            &config,
+        );
+        assert!(bad.is_err());
+    }
 }
@@ -2,8 +2,15 @@ use crate::cron::Schedule;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use cron::Schedule as CronExprSchedule;
+use std::path::{Component, Path};
 use std::str::FromStr;
 
+/// Event-triggered jobs never come due on their own; the far-future
+/// `next_run` keeps them out of [`crate::cron::due_jobs`].
+pub(crate) fn event_trigger_next_run() -> DateTime<Utc> {
+    DateTime::from_timestamp(253_402_300_799, 0).unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
 pub fn next_run_for_schedule(schedule: &Schedule, from: DateTime<Utc>) -> Result<DateTime<Utc>> {
     match schedule {
         Schedule::Cron { expr, tz } => {
@@ -35,6 +42,10 @@ pub fn next_run_for_schedule(schedule: &Schedule, from: DateTime<Utc>) -> Result
             from.checked_add_signed(delta)
                 .ok_or_else(|| anyhow::anyhow!("every_ms overflowed DateTime"))
         }
+        Schedule::Watch { .. }
+        | Schedule::Webhook { .. }
+        | Schedule::Channel { .. }
+        | Schedule::After => Ok(event_trigger_next_run()),
     }
 }
 
@@ -57,6 +68,45 @@ pub fn validate_schedule(schedule: &Schedule, now: DateTime<Utc>) -> Result<()>
             }
             Ok(())
         }
+        Schedule::Watch { path, glob } => {
+            let relative = Path::new(path.trim());
+            if path.trim().is_empty()
+                || relative.is_absolute()
+                || relative
+                    .components()
+                    .any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)))
+            {
+                anyhow::bail!(
+                    "Invalid schedule: watch path must be relative to the workspace: {path}"
+                );
+            }
+            if let Some(glob) = glob {
+                glob::Pattern::new(glob)
+                    .with_context(|| format!("Invalid schedule: bad watch glob: {glob}"))?;
+            }
+            Ok(())
+        }
+        Schedule::Webhook { name } => {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "Invalid schedule: hook name must be non-empty [A-Za-z0-9_-]: {name}"
+                );
+            }
+            Ok(())
+        }
+        Schedule::Channel { pattern, .. } => {
+            if pattern.trim().is_empty() {
+                anyhow::bail!("Invalid schedule: channel pattern must not be empty");
+            }
+            regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid schedule: bad channel pattern: {pattern}"))?;
+            Ok(())
+        }
+        Schedule::After => Ok(()),
     }
 }
 
@@ -111,4 +161,31 @@ mod tests {
         let next = next_run_for_schedule(&schedule, from).unwrap();
         assert_eq!(next, Utc.with_ymd_and_hms(2026, 2, 16, 17, 0, 0).unwrap());
     }
+
+    #[test]
+    fn validate_schedule_checks_event_triggers() {
+        let now = Utc::now();
+        let watch = |path: &str| Schedule::Watch {
+            path: path.into(),
+            glob: Some("*.csv".into()),
+        };
+        assert!(validate_schedule(&watch("inbox"), now).is_ok());
+        assert!(validate_schedule(&watch("/etc"), now).is_err());
+        assert!(validate_schedule(&watch("../outside"), now).is_err());
+
+        let hook = |name: &str| Schedule::Webhook { name: name.into() };
+        assert!(validate_schedule(&hook("nightly-report"), now).is_ok());
+        assert!(validate_schedule(&hook("a/b"), now).is_err());
+
+        let channel = Schedule::Channel {
+            pattern: "(unclosed".into(),
+            channel: None,
+        };
+        assert!(validate_schedule(&channel, now).is_err());
+
+        assert_eq!(
+            next_run_for_schedule(&hook("x"), now).unwrap(),
+            event_trigger_next_run()
+        );
+    }
 }
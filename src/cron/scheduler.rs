@@ -2,22 +2,98 @@ use crate::channels::{
     Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
 use crate::config::Config;
+use crate::cron::schedule::event_trigger_next_run;
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    dependents_of, due_jobs, get_job, list_jobs, next_run_for_schedule, record_last_run,
+    record_run, remove_job, reschedule_after_run, set_next_run, update_job, CronJob, CronJobPatch,
+    DeliveryConfig, JobType, OverlapPolicy, RunStatus, Schedule, SessionTarget, TriggerEvent,
+    WatchTracker,
 };
+use crate::providers;
+use crate::security::rbac::{self, RbacPolicy};
 use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use futures_util::{stream, StreamExt};
+use parking_lot::Mutex;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::process::Stdio;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
 use tokio::time::{self, Duration};
+use tokio_util::sync::CancellationToken;
 
 const MIN_POLL_SECONDS: u64 = 5;
 const SHELL_JOB_TIMEOUT_SECS: u64 = 120;
 const SCHEDULER_COMPONENT: &str = "scheduler";
+const MAX_JOB_INPUT_CHARS: usize = 8 * 1024;
+/// A scheduled run more than this many poll intervals late counts as missed
+/// and goes through the job's catch-up policy.
+const CATCH_UP_GRACE_POLLS: u64 = 2;
+const MAX_CATCH_UP_SCAN: u32 = 1000;
+
+/// Outcome of an upstream job, handed to the jobs that run after it.
+#[derive(Debug, Clone)]
+struct UpstreamRun {
+    job_id: String,
+    success: bool,
+    output: String,
+}
+
+impl UpstreamRun {
+    fn status(&self) -> &'static str {
+        if self.success {
+            "ok"
+        } else {
+            "error"
+        }
+    }
+
+    fn input(&self) -> JobInput {
+        let output = truncate_with_ellipsis(&self.output, MAX_JOB_INPUT_CHARS);
+        JobInput {
+            header: format!(
+                "upstream job {} finished with status={}",
+                self.job_id,
+                self.status()
+            ),
+            env: vec![
+                ("ZEROCLAW_UPSTREAM_JOB_ID", self.job_id.clone()),
+                ("ZEROCLAW_UPSTREAM_STATUS", self.status().to_string()),
+                ("ZEROCLAW_UPSTREAM_OUTPUT", output.clone()),
+            ],
+            body: output,
+        }
+    }
+}
+
+/// Extra input for a single run: appended to agent prompts and exported to
+/// shell jobs as environment variables.
+#[derive(Debug, Clone)]
+struct JobInput {
+    header: String,
+    body: String,
+    env: Vec<(&'static str, String)>,
+}
+
+impl From<&TriggerEvent> for JobInput {
+    fn from(event: &TriggerEvent) -> Self {
+        let payload = truncate_with_ellipsis(&event.payload, MAX_JOB_INPUT_CHARS);
+        Self {
+            header: format!("triggered by {} {}", event.kind.as_str(), event.source),
+            env: vec![
+                ("ZEROCLAW_TRIGGER_KIND", event.kind.as_str().to_string()),
+                ("ZEROCLAW_TRIGGER_SOURCE", event.source.clone()),
+                ("ZEROCLAW_TRIGGER_PAYLOAD", payload.clone()),
+            ],
+            body: payload,
+        }
+    }
+}
 
 pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
@@ -27,8 +103,11 @@ pub async fn run(config: Config) -> Result<()> {
         &config.autonomy,
         &config.workspace_dir,
     ));
+    let runner = JobRunner::global(&config);
+    runner.resize(config.scheduler.max_concurrent);
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
+    let mut watches = WatchTracker::new();
 
     loop {
         interval.tick().await;
@@ -44,28 +123,98 @@ pub async fn run(config: Config) -> Result<()> {
             }
         };
 
-        process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        // Runs continue in the background; the runner enforces overlap and
+        // concurrency policies across ticks.
+        process_due_jobs(&config, &security, &runner, jobs, SCHEDULER_COMPONENT).await;
+
+        match list_jobs(&config) {
+            Ok(jobs) => {
+                for (job, event) in watches.poll(&config.workspace_dir, &jobs) {
+                    spawn_triggered_job(config.clone(), job, event);
+                }
+            }
+            Err(e) => tracing::warn!("Scheduler watch scan failed: {e}"),
+        }
     }
 }
 
+/// Run an event-triggered job in the background, recording it like a
+/// scheduled run (delivery, run history and chained jobs included).
+pub fn spawn_triggered_job(config: Config, job: CronJob, event: TriggerEvent) {
+    tokio::spawn(async move {
+        tracing::debug!(
+            "Cron job '{}' triggered by {} '{}'",
+            job.id,
+            event.kind.as_str(),
+            event.source
+        );
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = JobRunner::global(&config);
+        let input = JobInput::from(&event);
+        runner
+            .start(&config, &security, job, 1, Some(input), SCHEDULER_COMPONENT)
+            .await;
+    });
+}
+
 pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
-    execute_job_with_retry(config, &security, job).await
+    execute_job_with_retry(config, &security, job, None).await
+}
+
+/// Run a job once outside its schedule and add it to the run history.
+/// Returns `(success, output, duration_ms)`.
+pub async fn run_job_now(config: &Config, job: &CronJob) -> (bool, String, i64) {
+    let started_at = Utc::now();
+    let (success, output) = execute_job_now(config, job).await;
+    let finished_at = Utc::now();
+    let duration_ms = (finished_at - started_at).num_milliseconds();
+    let status = if success { "ok" } else { "error" };
+
+    let _ = record_run(
+        config,
+        &job.id,
+        started_at,
+        finished_at,
+        status,
+        Some(&output),
+        duration_ms,
+    );
+    let _ = record_last_run(config, &job.id, finished_at, success, &output);
+    (success, output, duration_ms)
 }
 
 async fn execute_job_with_retry(
     config: &Config,
     security: &SecurityPolicy,
     job: &CronJob,
+    input: Option<&JobInput>,
 ) -> (bool, String) {
     let mut last_output = String::new();
     let retries = config.reliability.scheduler_retries;
     let mut backoff_ms = config.reliability.provider_backoff_ms.max(200);
+    let job = with_input_prompt(job, input);
+    // Jobs created under a gateway role keep that role's limits when they run.
+    let role_scope = job.role.as_deref().map(|name| {
+        let rbac = RbacPolicy::for_config(config);
+        let role = rbac.role_named(name);
+        rbac.scope_for(role, Some(format!("cron:{}", job.id)))
+    });
 
     for attempt in 0..=retries {
-        let (success, output) = match job.job_type {
-            JobType::Shell => run_job_command(config, security, job).await,
-            JobType::Agent => run_agent_job(config, security, job).await,
+        let run = Box::pin(async {
+            match job.job_type {
+                JobType::Shell => run_job_command(config, security, &job, input).await,
+                JobType::Agent => run_agent_job(config, security, &job).await,
+                JobType::Backup => crate::backup::run_scheduled(config).await,
+            }
+        });
+        let (success, output) = match &role_scope {
+            Some(scope) => rbac::scope(scope.clone(), run).await,
+            None => run.await,
         };
         last_output = output;
 
@@ -88,32 +237,393 @@ async fn execute_job_with_retry(
     (false, last_output)
 }
 
+/// Start due jobs, applying each job's catch-up policy when the scheduler is
+/// running behind. Returns handles for the runs that were started.
 async fn process_due_jobs(
     config: &Config,
     security: &Arc<SecurityPolicy>,
+    runner: &Arc<JobRunner>,
     jobs: Vec<CronJob>,
     component: &str,
-) {
+) -> Vec<JoinHandle<()>> {
     // Refresh scheduler health on every successful poll cycle, including idle cycles.
     crate::health::mark_component_ok(component);
 
-    let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
-                let config = config.clone();
-                let security = Arc::clone(security);
-                let component = component.to_owned();
-                async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
+    let now = Utc::now();
+    let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
+    let grace = chrono::Duration::seconds(
+        i64::try_from(poll_secs.saturating_mul(CATCH_UP_GRACE_POLLS)).unwrap_or(i64::MAX),
+    );
+
+    let mut handles = Vec::new();
+    for job in jobs {
+        let runs = if matches!(job.schedule, Schedule::At { .. }) || now - job.next_run <= grace {
+            1
+        } else {
+            let missed = overdue_occurrences(&job, now);
+            let runs = job.catch_up.runs_for(missed);
+            if runs == 0 {
+                record_decision(
+                    config,
+                    &job.id,
+                    now,
+                    RunStatus::Missed,
+                    &format!(
+                        "{missed} scheduled run(s) missed while the scheduler was down (catch_up={})",
+                        job.catch_up
+                    ),
+                );
+                advance_schedule(config, &job, now);
+                continue;
+            }
+            runs
+        };
+
+        if let Some(handle) = runner
+            .start(config, security, job, runs, None, component)
+            .await
+        {
+            handles.push(handle);
+        }
+    }
+    handles
+}
+
+/// Occurrences of `job` due at or before `now`, starting at its `next_run`.
+fn overdue_occurrences(job: &CronJob, now: DateTime<Utc>) -> u32 {
+    let mut count = 0;
+    let mut at = job.next_run;
+    while at <= now && count < MAX_CATCH_UP_SCAN {
+        count += 1;
+        match next_run_for_schedule(&job.schedule, at) {
+            Ok(next) if next > at => at = next,
+            _ => break,
+        }
+    }
+    count.max(1)
+}
+
+/// Move a scheduled job past its current occurrence so later ticks do not
+/// pick it up again.
+fn advance_schedule(config: &Config, job: &CronJob, now: DateTime<Utc>) {
+    let next = match job.schedule {
+        Schedule::At { .. } => Ok(event_trigger_next_run()),
+        _ => next_run_for_schedule(&job.schedule, now),
+    };
+    let result = next.and_then(|next| set_next_run(config, &job.id, next));
+    if let Err(e) = result {
+        tracing::warn!("Failed to advance cron job '{}': {e}", job.id);
+    }
+}
+
+/// Record a scheduler decision in the job's run history.
+fn record_decision(
+    config: &Config,
+    job_id: &str,
+    started_at: DateTime<Utc>,
+    status: RunStatus,
+    detail: &str,
+) {
+    let finished_at = Utc::now();
+    let duration_ms = (finished_at - started_at).num_milliseconds();
+    if let Err(e) = record_run(
+        config,
+        job_id,
+        started_at,
+        finished_at,
+        status.as_str(),
+        Some(detail),
+        duration_ms,
+    ) {
+        tracing::debug!("Failed to record cron decision for '{job_id}': {e}");
+    }
+}
+
+/// Execute a claimed job `runs` times, after its jitter delay for scheduled
+/// runs. Returns `false` when the run was cancelled.
+async fn run_claimed(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+    runs: u32,
+    input: Option<&JobInput>,
+    component: &str,
+    cancel: &CancellationToken,
+) -> bool {
+    let started_at = Utc::now();
+    let work = async {
+        if input.is_none() && job.jitter_secs > 0 {
+            let delay = rand::random_range(0..=job.jitter_secs);
+            time::sleep(Duration::from_secs(delay)).await;
+        }
+        for _ in 0..runs {
+            let (job_id, success) =
+                execute_and_persist_job(config, security, job, input, component).await;
+            if !success {
+                tracing::warn!("Scheduler job '{job_id}' failed");
+            }
+        }
+    };
+
+    tokio::select! {
+        () = work => true,
+        () = cancel.cancelled() => {
+            record_decision(
+                config,
+                &job.id,
+                started_at,
+                RunStatus::Cancelled,
+                "superseded by a newer run (overlap=cancel_previous)",
+            );
+            false
+        }
+    }
+}
+
+/// A run requested while the job's previous run was still in progress
+/// (overlap=queue).
+struct QueuedRun {
+    /// Input of the newest triggered request, so the rerun sees the latest
+    /// event rather than the one that started the in-progress run.
+    input: Option<JobInput>,
+}
+
+/// A job run currently held by the [`JobRunner`].
+struct ActiveRun {
+    run_id: u64,
+    cancel: CancellationToken,
+    /// Another run was requested while this one was in progress.
+    queued: Option<QueuedRun>,
+}
+
+/// Tracks in-flight cron runs so overlap policies and the
+/// `scheduler.max_concurrent` limit apply to scheduled and triggered runs alike.
+struct JobRunner {
+    slots: Arc<Semaphore>,
+    max_concurrent: Mutex<usize>,
+    active: Mutex<HashMap<String, ActiveRun>>,
+    deferred: Mutex<HashSet<String>>,
+    next_run_id: AtomicU64,
+}
+
+impl JobRunner {
+    fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent: Mutex::new(max_concurrent),
+            active: Mutex::new(HashMap::new()),
+            deferred: Mutex::new(HashSet::new()),
+            next_run_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Process-wide runner shared by the scheduler loop, gateway hooks and
+    /// channel triggers. Sized from the first config that asks for it; the
+    /// scheduler loop resizes it to its own config on every (re)start.
+    fn global(config: &Config) -> Arc<Self> {
+        static RUNNER: OnceLock<Arc<JobRunner>> = OnceLock::new();
+        Arc::clone(RUNNER.get_or_init(|| Arc::new(Self::new(config.scheduler.max_concurrent))))
+    }
+
+    /// Apply a new `scheduler.max_concurrent` without disturbing runs in
+    /// flight. Shrinking takes effect as running jobs release their slots.
+    fn resize(&self, max_concurrent: usize) {
+        let target = max_concurrent.max(1);
+        let mut current = self.max_concurrent.lock();
+        if target > *current {
+            self.slots.add_permits(target - *current);
+        } else if target < *current {
+            let excess = *current - target;
+            let owed = excess - self.slots.forget_permits(excess);
+            if owed > 0 {
+                let slots = Arc::clone(&self.slots);
+                let owed = u32::try_from(owed).unwrap_or(u32::MAX);
+                tokio::spawn(async move {
+                    if let Ok(permits) = slots.acquire_many_owned(owed).await {
+                        permits.forget();
+                    }
+                });
+            }
+        }
+        *current = target;
+    }
+
+    /// Start `runs` back-to-back executions of `job` in the background unless
+    /// its overlap policy or the concurrency limit says otherwise. `input` is
+    /// `None` for scheduled runs, which also advances the job's `next_run`.
+    /// Triggered runs wait for a free slot instead of being dropped, since
+    /// their event would otherwise be lost.
+    async fn start(
+        self: &Arc<Self>,
+        config: &Config,
+        security: &Arc<SecurityPolicy>,
+        job: CronJob,
+        runs: u32,
+        input: Option<JobInput>,
+        component: &str,
+    ) -> Option<JoinHandle<()>> {
+        let now = Utc::now();
+        let scheduled = input.is_none();
+        let mut input = input;
+
+        let permit = loop {
+            let cancelled_previous = {
+                let mut active = self.active.lock();
+                match (active.get_mut(&job.id), job.overlap) {
+                    (None, _) => false,
+                    (Some(_), OverlapPolicy::Skip) => {
+                        drop(active);
+                        self.decline(
+                            config,
+                            &job,
+                            scheduled,
+                            now,
+                            RunStatus::SkippedOverlap,
+                            "previous run still in progress (overlap=skip)",
+                        );
+                        return None;
+                    }
+                    (Some(run), OverlapPolicy::Queue) => {
+                        let (status, detail) = match run.queued.as_mut() {
+                            None => {
+                                run.queued = Some(QueuedRun {
+                                    input: input.take(),
+                                });
+                                (
+                                    RunStatus::Queued,
+                                    "will run when the in-progress run finishes (overlap=queue)",
+                                )
+                            }
+                            Some(queued) if input.is_some() => {
+                                queued.input = input.take();
+                                (
+                                    RunStatus::Queued,
+                                    "replaced the input of the queued run with this newer event (overlap=queue)",
+                                )
+                            }
+                            Some(_) => (
+                                RunStatus::SkippedOverlap,
+                                "a run is already queued behind the in-progress run",
+                            ),
+                        };
+                        drop(active);
+                        self.decline(config, &job, scheduled, now, status, detail);
+                        return None;
+                    }
+                    (Some(_), OverlapPolicy::CancelPrevious) => {
+                        if let Some(previous) = active.remove(&job.id) {
+                            previous.cancel.cancel();
+                        }
+                        true
+                    }
                 }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+            };
+
+            match Arc::clone(&self.slots).try_acquire_owned() {
+                Ok(permit) => break permit,
+                // The cancelled run releases its slot as soon as it unwinds.
+                Err(_) if cancelled_previous => {
+                    break Arc::clone(&self.slots).acquire_owned().await.ok()?;
+                }
+                Err(_) => {
+                    if self.deferred.lock().insert(job.id.clone()) {
+                        let max_concurrent = *self.max_concurrent.lock();
+                        record_decision(
+                            config,
+                            &job.id,
+                            now,
+                            RunStatus::Deferred,
+                            &format!(
+                                "{max_concurrent} cron jobs already running (scheduler.max_concurrent)"
+                            ),
+                        );
+                    }
+                    // Scheduled jobs stay due and are picked up on a later tick.
+                    if scheduled {
+                        return None;
+                    }
+                    // Wait for a slot to free up, then re-check the overlap
+                    // policy since the job may have started in the meantime.
+                    drop(self.slots.acquire().await.ok()?);
+                }
+            }
+        };
+        self.deferred.lock().remove(&job.id);
+
+        let run_id = self.next_run_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = CancellationToken::new();
+        self.active.lock().insert(
+            job.id.clone(),
+            ActiveRun {
+                run_id,
+                cancel: cancel.clone(),
+                queued: None,
+            },
+        );
+        if scheduled {
+            advance_schedule(config, &job, now);
+        }
 
-    while let Some((job_id, success)) = in_flight.next().await {
-        if !success {
-            tracing::warn!("Scheduler job '{job_id}' failed");
+        let runner = Arc::clone(self);
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let component = component.to_owned();
+        Some(tokio::spawn(async move {
+            let _permit = permit;
+            let mut runs = runs;
+            loop {
+                let finished = run_claimed(
+                    &config,
+                    &security,
+                    &job,
+                    runs,
+                    input.as_ref(),
+                    &component,
+                    &cancel,
+                )
+                .await;
+                if !finished {
+                    break;
+                }
+                let Some(queued) = runner.finish_or_requeue(&job.id, run_id) else {
+                    break;
+                };
+                runs = 1;
+                input = queued.input;
+            }
+        }))
+    }
+
+    fn decline(
+        &self,
+        config: &Config,
+        job: &CronJob,
+        scheduled: bool,
+        now: DateTime<Utc>,
+        status: RunStatus,
+        detail: &str,
+    ) {
+        record_decision(config, &job.id, now, status, detail);
+        if scheduled {
+            advance_schedule(config, job, now);
+        }
+    }
+
+    /// Release the job after a run. Returns the queued run that should start
+    /// immediately, if any.
+    fn finish_or_requeue(&self, job_id: &str, run_id: u64) -> Option<QueuedRun> {
+        let mut active = self.active.lock();
+        match active.get_mut(job_id) {
+            Some(run) if run.run_id == run_id => {
+                let queued = run.queued.take();
+                if queued.is_none() {
+                    active.remove(job_id);
+                }
+                queued
+            }
+            // Superseded by a newer run (overlap=cancel_previous).
+            _ => None,
         }
     }
 }
@@ -122,19 +632,111 @@ async fn execute_and_persist_job(
     config: &Config,
     security: &SecurityPolicy,
     job: &CronJob,
+    input: Option<&JobInput>,
     component: &str,
 ) -> (String, bool) {
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
     let started_at = Utc::now();
-    let (success, output) = execute_job_with_retry(config, security, job).await;
+    let (success, output) = execute_job_with_retry(config, security, job, input).await;
     let finished_at = Utc::now();
     let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
 
+    run_dependent_jobs(
+        config,
+        security,
+        UpstreamRun {
+            job_id: job.id.clone(),
+            success,
+            output,
+        },
+    )
+    .await;
+
     (job.id.clone(), success)
 }
 
+/// Run every job chained after `root` whose dependencies are now satisfied,
+/// then cascade to their own dependents.
+async fn run_dependent_jobs(config: &Config, security: &SecurityPolicy, root: UpstreamRun) {
+    let mut executed = HashSet::from([root.job_id.clone()]);
+    let mut queue = VecDeque::from([root]);
+
+    while let Some(upstream) = queue.pop_front() {
+        let dependents = match dependents_of(config, &upstream.job_id) {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load cron jobs chained after '{}': {e}",
+                    upstream.job_id
+                );
+                continue;
+            }
+        };
+
+        for job in dependents {
+            if !job.enabled
+                || executed.contains(&job.id)
+                || !dependencies_satisfied(config, &job, &upstream)
+            {
+                continue;
+            }
+            executed.insert(job.id.clone());
+
+            let started_at = Utc::now();
+            let (success, output) =
+                execute_job_with_retry(config, security, &job, Some(&upstream.input())).await;
+            let finished_at = Utc::now();
+            let success =
+                persist_job_result(config, &job, success, &output, started_at, finished_at).await;
+            if !success {
+                tracing::warn!("Chained cron job '{}' failed", job.id);
+            }
+
+            queue.push_back(UpstreamRun {
+                job_id: job.id,
+                success,
+                output,
+            });
+        }
+    }
+}
+
+/// All of `job`'s dependencies hold: the triggering run matches its condition
+/// and every other upstream finished (with a matching status) since `job` last ran.
+fn dependencies_satisfied(config: &Config, job: &CronJob, trigger: &UpstreamRun) -> bool {
+    job.after.iter().all(|dep| {
+        if dep.job_id == trigger.job_id {
+            return dep.condition.matches(trigger.success);
+        }
+        let Ok(upstream) = get_job(config, &dep.job_id) else {
+            return false;
+        };
+        let fresh = match (upstream.last_run, job.last_run) {
+            (Some(upstream_run), Some(own_run)) => upstream_run > own_run,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        fresh
+            && upstream
+                .last_status
+                .as_deref()
+                .is_some_and(|status| dep.condition.matches(status == "ok"))
+    })
+}
+
+/// Append run input (upstream result or trigger payload) to an agent job's prompt.
+fn with_input_prompt<'a>(job: &'a CronJob, input: Option<&JobInput>) -> Cow<'a, CronJob> {
+    let Some(input) = input.filter(|_| matches!(job.job_type, JobType::Agent)) else {
+        return Cow::Borrowed(job);
+    };
+    let mut job = job.clone();
+    let prompt = job.prompt.take().unwrap_or_default();
+    job.prompt = Some(format!("{prompt}\n\n[{}]\n{}", input.header, input.body));
+    Cow::Owned(job)
+}
+
 async fn run_agent_job(
     config: &Config,
     security: &SecurityPolicy,
@@ -174,24 +776,74 @@ async fn run_agent_job(
                 model_override,
                 config.default_temperature,
                 vec![],
+                None,
             )
             .await
         }
     };
 
     match run_result {
-        Ok(response) => (
-            true,
-            if response.trim().is_empty() {
-                "agent job executed".to_string()
-            } else {
-                response
+        Ok(response) => match job.output_schema.as_ref() {
+            Some(schema) => match coerce_agent_output(config, job, schema, &response).await {
+                Ok(structured) => (true, structured),
+                Err(e) => (
+                    false,
+                    format!("agent job output did not match output_schema: {e}"),
+                ),
             },
-        ),
+            None => (
+                true,
+                if response.trim().is_empty() {
+                    "agent job executed".to_string()
+                } else {
+                    response
+                },
+            ),
+        },
         Err(e) => (false, format!("agent job failed: {e}")),
     }
 }
 
+/// Convert an agent job's final answer into JSON matching `output_schema`.
+async fn coerce_agent_output(
+    config: &Config,
+    job: &CronJob,
+    raw_schema: &serde_json::Value,
+    response: &str,
+) -> Result<String> {
+    let schema = providers::structured::parse_response_schema(raw_schema, "cron_output")?;
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = job
+        .model
+        .as_deref()
+        .or(config.default_model.as_deref())
+        .unwrap_or("anthropic/claude-sonnet-4");
+    let provider = providers::create_routed_provider_with_options(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &config.model_routes,
+        model,
+        &providers::ProviderRuntimeOptions {
+            auth_profile_override: None,
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+            cassette: config.runtime.provider_cassette.clone(),
+        },
+    )?;
+    let value = providers::structured::coerce_to_schema(
+        provider.as_ref(),
+        response,
+        &schema,
+        model,
+        config.default_temperature,
+    )
+    .await?;
+    Ok(serde_json::to_string(&value)?)
+}
+
 async fn persist_job_result(
     config: &Config,
     job: &CronJob,
@@ -216,7 +868,12 @@ async fn persist_job_result(
         &job.id,
         started_at,
         finished_at,
-        if success { "ok" } else { "error" },
+        if success {
+            RunStatus::Ok
+        } else {
+            RunStatus::Error
+        }
+        .as_str(),
         Some(output),
         duration_ms,
     );
@@ -269,7 +926,11 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
                 _ => false,
             }
         }
-        Schedule::At { .. } => false,
+        Schedule::At { .. }
+        | Schedule::Watch { .. }
+        | Schedule::Webhook { .. }
+        | Schedule::Channel { .. }
+        | Schedule::After => false,
     };
 
     if too_frequent {
@@ -295,6 +956,17 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
 
+    deliver_announcement(config, channel, target, output).await
+}
+
+/// Send `output` to `target` on a configured channel. Shared by cron
+/// delivery and heartbeat notifications.
+pub(crate) async fn deliver_announcement(
+    config: &Config,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Result<()> {
     match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
@@ -421,12 +1093,14 @@ async fn run_job_command(
     config: &Config,
     security: &SecurityPolicy,
     job: &CronJob,
+    input: Option<&JobInput>,
 ) -> (bool, String) {
     run_job_command_with_timeout(
         config,
         security,
         job,
         Duration::from_secs(SHELL_JOB_TIMEOUT_SECS),
+        input,
     )
     .await
 }
@@ -436,6 +1110,7 @@ async fn run_job_command_with_timeout(
     security: &SecurityPolicy,
     job: &CronJob,
     timeout: Duration,
+    input: Option<&JobInput>,
 ) -> (bool, String) {
     if !security.can_act() {
         return (
@@ -475,7 +1150,12 @@ async fn run_job_command_with_timeout(
         );
     }
 
-    let child = match Command::new("sh")
+    let mut command = Command::new("sh");
+    if let Some(input) = input {
+        command.envs(input.env.iter().map(|(key, value)| (*key, value)));
+    }
+
+    let child = match command
         .arg("-lc")
         .arg(&job.command)
         .current_dir(&config.workspace_dir)
@@ -552,6 +1232,12 @@ mod tests {
             last_run: None,
             last_status: None,
             last_output: None,
+            output_schema: None,
+            after: Vec::new(),
+            overlap: OverlapPolicy::Skip,
+            catch_up: crate::cron::CatchUpPolicy::Once,
+            jitter_secs: 0,
+            role: None,
         }
     }
 
@@ -566,7 +1252,7 @@ mod tests {
         let job = test_job("echo scheduler-ok");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(success);
         assert!(output.contains("scheduler-ok"));
         assert!(output.contains("status=exit status: 0"));
@@ -579,7 +1265,7 @@ mod tests {
         let job = test_job("ls definitely_missing_file_for_scheduler_test");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("definitely_missing_file_for_scheduler_test"));
         assert!(output.contains("status=exit status:"));
@@ -594,7 +1280,8 @@ mod tests {
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
         let (success, output) =
-            run_job_command_with_timeout(&config, &security, &job, Duration::from_millis(50)).await;
+            run_job_command_with_timeout(&config, &security, &job, Duration::from_millis(50), None)
+                .await;
         assert!(!success);
         assert!(output.contains("job timed out after"));
     }
@@ -607,7 +1294,7 @@ mod tests {
         let job = test_job("curl https://evil.example");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("blocked by security policy"));
         assert!(output.contains("command not allowed"));
@@ -621,7 +1308,7 @@ mod tests {
         let job = test_job("cat /etc/passwd");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("blocked by security policy"));
         assert!(output.contains("forbidden path argument"));
@@ -636,7 +1323,7 @@ mod tests {
         let job = test_job("echo should-not-run");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("blocked by security policy"));
         assert!(output.contains("read-only"));
@@ -650,7 +1337,7 @@ mod tests {
         let job = test_job("echo should-not-run");
         let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
 
-        let (success, output) = run_job_command(&config, &security, &job).await;
+        let (success, output) = run_job_command(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("blocked by security policy"));
         assert!(output.contains("rate limit exceeded"));
@@ -673,7 +1360,7 @@ mod tests {
         .unwrap();
         let job = test_job("sh ./retry-once.sh");
 
-        let (success, output) = execute_job_with_retry(&config, &security, &job).await;
+        let (success, output) = execute_job_with_retry(&config, &security, &job, None).await;
         assert!(success);
         assert!(output.contains("recovered"));
     }
@@ -688,7 +1375,7 @@ mod tests {
 
         let job = test_job("ls always_missing_for_retry_test");
 
-        let (success, output) = execute_job_with_retry(&config, &security, &job).await;
+        let (success, output) = execute_job_with_retry(&config, &security, &job, None).await;
         assert!(!success);
         assert!(output.contains("always_missing_for_retry_test"));
     }
@@ -750,7 +1437,9 @@ mod tests {
         let component = unique_component("scheduler-idle");
 
         crate::health::mark_component_error(&component, "pre-existing error");
-        process_due_jobs(&config, &security, Vec::new(), &component).await;
+        let runner = Arc::new(JobRunner::new(config.scheduler.max_concurrent));
+        let handles = process_due_jobs(&config, &security, &runner, Vec::new(), &component).await;
+        assert!(handles.is_empty());
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
@@ -771,13 +1460,293 @@ mod tests {
         let component = unique_component("scheduler-fail");
 
         crate::health::mark_component_ok(&component);
-        process_due_jobs(&config, &security, vec![job], &component).await;
+        let runner = Arc::new(JobRunner::new(config.scheduler.max_concurrent));
+        for handle in process_due_jobs(&config, &security, &runner, vec![job], &component).await {
+            handle.await.unwrap();
+        }
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
         assert_eq!(entry["status"], "ok");
     }
 
+    fn claim(runner: &JobRunner, job_id: &str) -> CancellationToken {
+        let cancel = CancellationToken::new();
+        runner.active.lock().insert(
+            job_id.to_string(),
+            ActiveRun {
+                run_id: 0,
+                cancel: cancel.clone(),
+                queued: None,
+            },
+        );
+        cancel
+    }
+
+    fn run_statuses(config: &Config, job_id: &str) -> Vec<String> {
+        cron::list_runs(config, job_id, 20)
+            .unwrap()
+            .into_iter()
+            .map(|run| run.status)
+            .collect()
+    }
+
+    fn with_patch(config: &Config, job_id: &str, patch: CronJobPatch) -> CronJob {
+        update_job(config, job_id, patch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn overlapping_run_is_skipped_or_queued_by_policy() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(4));
+        let job = cron::add_job(&config, "*/5 * * * *", "echo overlap").unwrap();
+        claim(&runner, &job.id);
+
+        let started = runner
+            .start(&config, &security, job.clone(), 1, None, "test")
+            .await;
+        assert!(started.is_none());
+        assert_eq!(run_statuses(&config, &job.id), vec!["skipped_overlap"]);
+
+        let job = with_patch(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap: Some(OverlapPolicy::Queue),
+                ..CronJobPatch::default()
+            },
+        );
+        for _ in 0..2 {
+            let started = runner
+                .start(&config, &security, job.clone(), 1, None, "test")
+                .await;
+            assert!(started.is_none());
+        }
+        assert_eq!(
+            run_statuses(&config, &job.id),
+            vec!["skipped_overlap", "queued", "skipped_overlap"]
+        );
+
+        assert!(runner.finish_or_requeue(&job.id, 0).is_some());
+        assert!(runner.finish_or_requeue(&job.id, 0).is_none());
+        assert!(runner.active.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queued_triggered_run_keeps_newest_input() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(4));
+        let job = cron::add_job(&config, "*/5 * * * *", "echo queued").unwrap();
+        let job = with_patch(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap: Some(OverlapPolicy::Queue),
+                ..CronJobPatch::default()
+            },
+        );
+        claim(&runner, &job.id);
+
+        for payload in ["first", "second"] {
+            let event = TriggerEvent {
+                kind: crate::cron::TriggerKind::Webhook,
+                source: "deploy".into(),
+                payload: payload.into(),
+            };
+            let started = runner
+                .start(
+                    &config,
+                    &security,
+                    job.clone(),
+                    1,
+                    Some(JobInput::from(&event)),
+                    "test",
+                )
+                .await;
+            assert!(started.is_none());
+        }
+
+        let queued = runner.finish_or_requeue(&job.id, 0).unwrap();
+        assert_eq!(queued.input.unwrap().body, "second");
+        assert_eq!(run_statuses(&config, &job.id), vec!["queued", "queued"]);
+    }
+
+    #[tokio::test]
+    async fn triggered_run_waits_for_free_slot() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(1));
+        let held = Arc::clone(&runner.slots).try_acquire_owned().unwrap();
+        let job = cron::add_job(&config, "*/5 * * * *", "echo waited").unwrap();
+        let event = TriggerEvent {
+            kind: crate::cron::TriggerKind::Webhook,
+            source: "deploy".into(),
+            payload: "{}".into(),
+        };
+
+        let start = {
+            let runner = Arc::clone(&runner);
+            let config = config.clone();
+            let job = job.clone();
+            tokio::spawn(async move {
+                runner
+                    .start(
+                        &config,
+                        &security,
+                        job,
+                        1,
+                        Some(JobInput::from(&event)),
+                        "test",
+                    )
+                    .await
+            })
+        };
+        time::sleep(Duration::from_millis(50)).await;
+        assert!(!start.is_finished());
+        drop(held);
+
+        let handle = start.await.unwrap().expect("triggered run should start");
+        handle.await.unwrap();
+        assert_eq!(run_statuses(&config, &job.id), vec!["ok", "deferred"]);
+    }
+
+    #[tokio::test]
+    async fn resize_applies_new_concurrency_limit() {
+        let runner = JobRunner::new(2);
+        runner.resize(4);
+        assert_eq!(runner.slots.available_permits(), 4);
+
+        let held = Arc::clone(&runner.slots).try_acquire_many_owned(3).unwrap();
+        runner.resize(1);
+        assert_eq!(runner.slots.available_permits(), 0);
+        drop(held);
+        time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runner.slots.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn cancel_previous_supersedes_in_progress_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(4));
+        let job = cron::add_job(&config, "*/5 * * * *", "echo latest").unwrap();
+        let job = with_patch(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap: Some(OverlapPolicy::CancelPrevious),
+                ..CronJobPatch::default()
+            },
+        );
+        let previous = claim(&runner, &job.id);
+
+        let handle = runner
+            .start(&config, &security, job.clone(), 1, None, "test")
+            .await
+            .expect("new run should start");
+        assert!(previous.is_cancelled());
+        handle.await.unwrap();
+
+        assert_eq!(run_statuses(&config, &job.id), vec!["ok"]);
+        assert!(runner.active.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrency_limit_defers_job_and_keeps_it_due() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(1));
+        let _held = Arc::clone(&runner.slots).try_acquire_owned().unwrap();
+        let job = cron::add_job(&config, "*/5 * * * *", "echo busy").unwrap();
+
+        for _ in 0..2 {
+            let started = runner
+                .start(&config, &security, job.clone(), 1, None, "test")
+                .await;
+            assert!(started.is_none());
+        }
+
+        assert_eq!(run_statuses(&config, &job.id), vec!["deferred"]);
+        assert_eq!(
+            cron::get_job(&config, &job.id).unwrap().next_run,
+            job.next_run
+        );
+    }
+
+    #[tokio::test]
+    async fn catch_up_policy_controls_missed_runs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runner = Arc::new(JobRunner::new(4));
+        let job = cron::add_shell_job(
+            &config,
+            None,
+            Schedule::Every { every_ms: 60_000 },
+            "echo catchup",
+        )
+        .unwrap();
+        let overdue = |catch_up| {
+            with_patch(
+                &config,
+                &job.id,
+                CronJobPatch {
+                    catch_up: Some(catch_up),
+                    ..CronJobPatch::default()
+                },
+            );
+            cron::set_next_run(&config, &job.id, Utc::now() - ChronoDuration::minutes(10)).unwrap();
+            cron::get_job(&config, &job.id).unwrap()
+        };
+
+        let job_none = overdue(crate::cron::CatchUpPolicy::None);
+        let handles = process_due_jobs(&config, &security, &runner, vec![job_none], "test").await;
+        assert!(handles.is_empty());
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "missed");
+        assert!(runs[0]
+            .output
+            .as_deref()
+            .unwrap()
+            .contains("scheduled run(s) missed"));
+        assert!(cron::get_job(&config, &job.id).unwrap().next_run > Utc::now());
+
+        let job_all = overdue(crate::cron::CatchUpPolicy::All(3));
+        let handles = process_due_jobs(&config, &security, &runner, vec![job_all], "test").await;
+        assert_eq!(handles.len(), 1);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let statuses = run_statuses(&config, &job.id);
+        assert_eq!(statuses.iter().filter(|s| *s == "ok").count(), 3);
+    }
+
     #[tokio::test]
     async fn persist_job_result_records_run_and_reschedules_shell_job() {
         let tmp = TempDir::new().unwrap();
@@ -795,6 +1764,121 @@ mod tests {
         assert_eq!(updated.last_status.as_deref(), Some("ok"));
     }
 
+    fn chain(config: &Config, job_id: &str, upstream: &str) {
+        update_job(
+            config,
+            job_id,
+            CronJobPatch {
+                after: Some(vec![crate::cron::JobDependency::parse(upstream).unwrap()]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn chained_job_runs_after_upstream_with_its_output() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let upstream = cron::add_job(&config, "*/5 * * * *", "echo backup-done").unwrap();
+        let downstream = cron::add_job(
+            &config,
+            "*/5 * * * *",
+            "echo \"$ZEROCLAW_UPSTREAM_STATUS $ZEROCLAW_UPSTREAM_OUTPUT\"",
+        )
+        .unwrap();
+        let on_failure = cron::add_job(&config, "*/5 * * * *", "echo cleanup").unwrap();
+        chain(&config, &downstream.id, &upstream.id);
+        chain(&config, &on_failure.id, &format!("{}:failure", upstream.id));
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (_, success) = execute_and_persist_job(
+            &config,
+            &security,
+            &upstream,
+            None,
+            &unique_component("chain"),
+        )
+        .await;
+        assert!(success);
+
+        let runs = cron::list_runs(&config, &downstream.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        let output = runs[0].output.as_deref().unwrap_or_default();
+        assert!(output.contains("ok status=exit status: 0"));
+        assert!(output.contains("backup-done"));
+
+        assert!(cron::list_runs(&config, &on_failure.id, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn dependencies_satisfied_waits_for_every_upstream() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+        let a = cron::add_job(&config, "*/5 * * * *", "echo a").unwrap();
+        let b = cron::add_job(&config, "*/5 * * * *", "echo b").unwrap();
+        let joined = cron::add_job(&config, "*/5 * * * *", "echo joined").unwrap();
+        let joined = update_job(
+            &config,
+            &joined.id,
+            CronJobPatch {
+                after: Some(vec![
+                    crate::cron::JobDependency::parse(&a.id).unwrap(),
+                    crate::cron::JobDependency::parse(&b.id).unwrap(),
+                ]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        let trigger = UpstreamRun {
+            job_id: a.id.clone(),
+            success: true,
+            output: String::new(),
+        };
+
+        assert!(!dependencies_satisfied(&config, &joined, &trigger));
+
+        cron::reschedule_after_run(&config, &b, true, "done").unwrap();
+        assert!(dependencies_satisfied(&config, &joined, &trigger));
+
+        let failed = UpstreamRun {
+            success: false,
+            ..trigger
+        };
+        assert!(!dependencies_satisfied(&config, &joined, &failed));
+    }
+
+    #[test]
+    fn with_input_prompt_appends_output_to_agent_jobs_only() {
+        let upstream = UpstreamRun {
+            job_id: "backup".into(),
+            success: false,
+            output: "disk full".into(),
+        }
+        .input();
+        let shell = test_job("echo hi");
+        assert!(matches!(
+            with_input_prompt(&shell, Some(&upstream)),
+            Cow::Borrowed(_)
+        ));
+
+        let mut agent = test_job("");
+        agent.job_type = JobType::Agent;
+        agent.prompt = Some("Summarize".into());
+        let prompt = with_input_prompt(&agent, Some(&upstream))
+            .prompt
+            .clone()
+            .unwrap();
+        assert!(prompt.starts_with("Summarize"));
+        assert!(prompt.contains("upstream job backup finished with status=error"));
+        assert!(prompt.ends_with("disk full"));
+    }
+
     #[tokio::test]
     async fn persist_job_result_success_deletes_one_shot() {
         let tmp = TempDir::new().unwrap();
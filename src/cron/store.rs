@@ -1,16 +1,45 @@
 use crate::config::Config;
 use crate::cron::{
-    next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    next_run_for_schedule, schedule_cron_expression, validate_schedule, CatchUpPolicy, CronJob,
+    CronJobPatch, CronRun, DeliveryConfig, JobDependency, JobType, OverlapPolicy, Schedule,
+    SessionTarget,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
 use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 use uuid::Uuid;
 
 const MAX_CRON_OUTPUT_BYTES: usize = 16 * 1024;
 const TRUNCATED_OUTPUT_MARKER: &str = "\n...[truncated]";
+const MAX_JITTER_SECS: u64 = 3600;
+
+/// Bumped whenever this process adds, changes or removes a job.
+static JOBS_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// Cheap fingerprint of the job table for caches: this process's change
+/// counter plus the database file's modification time, which also covers
+/// changes made by other processes such as the CLI.
+pub fn jobs_revision(config: &Config) -> (u64, Option<SystemTime>) {
+    let modified = std::fs::metadata(db_path(config))
+        .and_then(|meta| meta.modified())
+        .ok();
+    (JOBS_REVISION.load(Ordering::Acquire), modified)
+}
+
+fn jobs_changed() {
+    JOBS_REVISION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Gateway role of the caller creating or editing a job; the job runs
+/// under it so scheduling cannot widen what the caller may do.
+fn active_role() -> Option<String> {
+    crate::security::rbac::current().map(|scope| scope.permissions.name.clone())
+}
 
 impl rusqlite::types::FromSql for JobType {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
@@ -44,8 +73,8 @@ pub fn add_shell_job(
         conn.execute(
             "INSERT INTO cron_jobs (
                 id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                enabled, delivery, delete_after_run, created_at, next_run
-             ) VALUES (?1, ?2, ?3, ?4, 'shell', NULL, ?5, 'isolated', NULL, 1, ?6, 0, ?7, ?8)",
+                enabled, delivery, delete_after_run, created_at, next_run, role
+             ) VALUES (?1, ?2, ?3, ?4, 'shell', NULL, ?5, 'isolated', NULL, 1, ?6, 0, ?7, ?8, ?9)",
             params![
                 id,
                 expression,
@@ -55,11 +84,61 @@ pub fn add_shell_job(
                 serde_json::to_string(&DeliveryConfig::default())?,
                 now.to_rfc3339(),
                 next_run.to_rfc3339(),
+                active_role(),
             ],
         )
         .context("Failed to insert cron shell job")?;
         Ok(())
     })?;
+    jobs_changed();
+
+    get_job(config, &id)
+}
+
+/// Add the built-in backup job, or move the existing one to `schedule`.
+pub fn upsert_backup_job(config: &Config, schedule: Schedule) -> Result<CronJob> {
+    let now = Utc::now();
+    validate_schedule(&schedule, now)?;
+    if let Some(job) = list_jobs(config)?
+        .into_iter()
+        .find(|job| job.job_type == JobType::Backup)
+    {
+        return update_job(
+            config,
+            &job.id,
+            CronJobPatch {
+                schedule: Some(schedule),
+                enabled: Some(true),
+                ..CronJobPatch::default()
+            },
+        );
+    }
+
+    let next_run = next_run_for_schedule(&schedule, now)?;
+    let id = Uuid::new_v4().to_string();
+    let expression = schedule_cron_expression(&schedule).unwrap_or_default();
+    let schedule_json = serde_json::to_string(&schedule)?;
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run
+             ) VALUES (?1, ?2, 'zeroclaw backup create', ?3, 'backup', NULL, 'backup', 'isolated',
+                NULL, 1, ?4, 0, ?5, ?6)",
+            params![
+                id,
+                expression,
+                schedule_json,
+                serde_json::to_string(&DeliveryConfig::default())?,
+                now.to_rfc3339(),
+                next_run.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert cron backup job")?;
+        Ok(())
+    })?;
+    jobs_changed();
 
     get_job(config, &id)
 }
@@ -87,8 +166,8 @@ pub fn add_agent_job(
         conn.execute(
             "INSERT INTO cron_jobs (
                 id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                enabled, delivery, delete_after_run, created_at, next_run
-             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11)",
+                enabled, delivery, delete_after_run, created_at, next_run, role
+             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11, ?12)",
             params![
                 id,
                 expression,
@@ -101,11 +180,13 @@ pub fn add_agent_job(
                 if delete_after_run { 1 } else { 0 },
                 now.to_rfc3339(),
                 next_run.to_rfc3339(),
+                active_role(),
             ],
         )
         .context("Failed to insert cron agent job")?;
         Ok(())
     })?;
+    jobs_changed();
 
     get_job(config, &id)
 }
@@ -114,7 +195,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    output_schema, depends_on, overlap_policy, catch_up, jitter_secs, role
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -132,7 +214,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    output_schema, depends_on, overlap_policy, catch_up, jitter_secs, role
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -154,6 +237,7 @@ pub fn remove_job(config: &Config, id: &str) -> Result<()> {
     if changed == 0 {
         anyhow::bail!("Cron job '{id}' not found");
     }
+    jobs_changed();
 
     println!("✅ Removed cron job {id}");
     Ok(())
@@ -165,9 +249,11 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    output_schema, depends_on, overlap_policy, catch_up, jitter_secs, role
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
+               AND (depends_on IS NULL OR depends_on = '')
              ORDER BY next_run ASC
              LIMIT ?2",
         )?;
@@ -182,9 +268,144 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     })
 }
 
+/// Create a job of `job_type` with `patch` applied before anything is
+/// written, so the row is inserted in one statement with every option set.
+pub fn add_job_with(
+    config: &Config,
+    job_type: JobType,
+    name: Option<String>,
+    schedule: Schedule,
+    patch: CronJobPatch,
+) -> Result<CronJob> {
+    let now = Utc::now();
+    validate_schedule(&schedule, now)?;
+    let mut job = CronJob {
+        id: Uuid::new_v4().to_string(),
+        expression: schedule_cron_expression(&schedule).unwrap_or_default(),
+        next_run: next_run_for_schedule(&schedule, now)?,
+        schedule,
+        command: String::new(),
+        prompt: None,
+        name,
+        job_type,
+        session_target: SessionTarget::Isolated,
+        model: None,
+        enabled: true,
+        delivery: DeliveryConfig::default(),
+        delete_after_run: false,
+        created_at: now,
+        last_run: None,
+        last_status: None,
+        last_output: None,
+        output_schema: None,
+        after: Vec::new(),
+        overlap: OverlapPolicy::default(),
+        catch_up: CatchUpPolicy::default(),
+        jitter_secs: 0,
+        role: active_role(),
+    };
+    // Pass the schedule through the patch so dependencies that conflict with
+    // it are rejected instead of silently replacing it.
+    let patch = CronJobPatch {
+        schedule: Some(job.schedule.clone()),
+        ..patch
+    };
+    apply_patch(config, &mut job, patch)?;
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run, output_schema,
+                depends_on, overlap_policy, catch_up, jitter_secs, role
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                ?17, ?18, ?19, ?20)",
+            params![
+                job.id,
+                job.expression,
+                job.command,
+                serde_json::to_string(&job.schedule)?,
+                <JobType as Into<&str>>::into(job.job_type).to_string(),
+                job.prompt,
+                job.name,
+                job.session_target.as_str(),
+                job.model,
+                if job.enabled { 1 } else { 0 },
+                serde_json::to_string(&job.delivery)?,
+                if job.delete_after_run { 1 } else { 0 },
+                job.created_at.to_rfc3339(),
+                job.next_run.to_rfc3339(),
+                job.output_schema
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?,
+                encode_dependencies(&job.after)?,
+                job.overlap.as_str(),
+                job.catch_up.to_string(),
+                i64::try_from(job.jitter_secs).context("jitter_secs overflows i64")?,
+                job.role,
+            ],
+        )
+        .context("Failed to insert cron job")?;
+        Ok(())
+    })?;
+    jobs_changed();
+
+    get_job(config, &job.id)
+}
+
 pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<CronJob> {
     let mut job = get_job(config, job_id)?;
+    apply_patch(config, &mut job, patch)?;
+    if let Some(role) = active_role() {
+        job.role = Some(role);
+    }
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs
+             SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
+                 session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
+                 next_run = ?12, output_schema = ?13, depends_on = ?14, overlap_policy = ?15,
+                 catch_up = ?16, jitter_secs = ?17, role = ?18
+             WHERE id = ?19",
+            params![
+                job.expression,
+                job.command,
+                serde_json::to_string(&job.schedule)?,
+                <JobType as Into<&str>>::into(job.job_type).to_string(),
+                job.prompt,
+                job.name,
+                job.session_target.as_str(),
+                job.model,
+                if job.enabled { 1 } else { 0 },
+                serde_json::to_string(&job.delivery)?,
+                if job.delete_after_run { 1 } else { 0 },
+                job.next_run.to_rfc3339(),
+                job.output_schema
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?,
+                encode_dependencies(&job.after)?,
+                job.overlap.as_str(),
+                job.catch_up.to_string(),
+                i64::try_from(job.jitter_secs).context("jitter_secs overflows i64")?,
+                job.role,
+                job.id,
+            ],
+        )
+        .context("Failed to update cron job")?;
+        Ok(())
+    })?;
+    jobs_changed();
+
+    get_job(config, job_id)
+}
+
+fn apply_patch(config: &Config, job: &mut CronJob, patch: CronJobPatch) -> Result<()> {
     let mut schedule_changed = false;
+    let schedule_given = patch.schedule.is_some();
+    let dependencies_touched = schedule_given || patch.after.is_some();
 
     if let Some(schedule) = patch.schedule {
         validate_schedule(&schedule, Utc::now())?;
@@ -216,39 +437,149 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
+    if let Some(output_schema) = patch.output_schema {
+        crate::providers::structured::parse_response_schema(&output_schema, "cron_output")?;
+        job.output_schema = Some(output_schema);
+    }
+    if let Some(after) = patch.after {
+        validate_dependencies(config, Some(&job.id), &after)?;
+        job.after = after;
+        // Setting dependencies chains the job; its own schedule would never fire.
+        if !job.after.is_empty() && !schedule_given && job.schedule != Schedule::After {
+            job.schedule = Schedule::After;
+            job.expression = String::new();
+            schedule_changed = true;
+        }
+    }
+    if let Some(overlap) = patch.overlap {
+        job.overlap = overlap;
+    }
+    if let Some(catch_up) = patch.catch_up {
+        job.catch_up = catch_up;
+    }
+    if let Some(jitter_secs) = patch.jitter_secs {
+        if jitter_secs > MAX_JITTER_SECS {
+            anyhow::bail!("Invalid jitter: must be at most {MAX_JITTER_SECS} seconds");
+        }
+        job.jitter_secs = jitter_secs;
+    }
 
     if schedule_changed {
         job.next_run = next_run_for_schedule(&job.schedule, Utc::now())?;
     }
+    if job.output_schema.is_some() && job.job_type != JobType::Agent {
+        anyhow::bail!("output_schema is only supported for agent jobs");
+    }
+    if dependencies_touched {
+        match (job.schedule == Schedule::After, job.after.is_empty()) {
+            (true, true) => {
+                anyhow::bail!("Schedule 'after' requires at least one upstream job")
+            }
+            (false, false) => anyhow::bail!(
+                "Jobs with upstream dependencies run when those jobs finish; use schedule kind 'after' instead of their own schedule"
+            ),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Jobs that list `job_id` among their upstream dependencies.
+pub fn dependents_of(config: &Config, job_id: &str) -> Result<Vec<CronJob>> {
+    Ok(list_jobs(config)?
+        .into_iter()
+        .filter(|job| job.after.iter().any(|dep| dep.job_id == job_id))
+        .collect())
+}
+
+/// Check that `deps` reference existing jobs and would not introduce a
+/// dependency cycle. `job_id` is `None` for a job that does not exist yet.
+pub fn validate_dependencies(
+    config: &Config,
+    job_id: Option<&str>,
+    deps: &[JobDependency],
+) -> Result<()> {
+    if deps.is_empty() {
+        return Ok(());
+    }
+
+    let jobs = list_jobs(config)?;
+    let mut graph: HashMap<&str, Vec<&str>> = jobs
+        .iter()
+        .map(|job| {
+            (
+                job.id.as_str(),
+                job.after.iter().map(|dep| dep.job_id.as_str()).collect(),
+            )
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    for dep in deps {
+        if Some(dep.job_id.as_str()) == job_id {
+            anyhow::bail!("Cron job '{}' cannot depend on itself", dep.job_id);
+        }
+        if !graph.contains_key(dep.job_id.as_str()) {
+            anyhow::bail!("Cron dependency '{}' not found", dep.job_id);
+        }
+        if !seen.insert(dep.job_id.as_str()) {
+            anyhow::bail!("Cron dependency '{}' listed more than once", dep.job_id);
+        }
+    }
+
+    // A brand-new job has no dependents, so it cannot close a cycle.
+    let Some(job_id) = job_id else {
+        return Ok(());
+    };
+    graph.insert(job_id, deps.iter().map(|dep| dep.job_id.as_str()).collect());
+    if let Some(cycle) = find_dependency_cycle(&graph, job_id) {
+        anyhow::bail!("Cron dependency cycle detected: {}", cycle.join(" -> "));
+    }
+    Ok(())
+}
+
+/// Depth-first search for a path from `start` back to itself.
+fn find_dependency_cycle(graph: &HashMap<&str, Vec<&str>>, start: &str) -> Option<Vec<String>> {
+    fn visit<'a>(
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        node: &'a str,
+        start: &str,
+        path: &mut Vec<&'a str>,
+        visited: &mut HashSet<&'a str>,
+    ) -> bool {
+        for &next in graph.get(node).map(Vec::as_slice).unwrap_or_default() {
+            if next == start {
+                path.push(next);
+                return true;
+            }
+            if visited.insert(next) {
+                path.push(next);
+                if visit(graph, next, start, path, visited) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
 
+    let mut path = vec![start];
+    let mut visited = HashSet::new();
+    visit(graph, start, start, &mut path, &mut visited)
+        .then(|| path.into_iter().map(str::to_string).collect())
+}
+
+/// Move a job's next occurrence without touching its last-run fields, e.g.
+/// when the scheduler claims or skips a due run.
+pub fn set_next_run(config: &Config, job_id: &str, next_run: DateTime<Utc>) -> Result<()> {
     with_connection(config, |conn| {
         conn.execute(
-            "UPDATE cron_jobs
-             SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
-                 session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
-            params![
-                job.expression,
-                job.command,
-                serde_json::to_string(&job.schedule)?,
-                <JobType as Into<&str>>::into(job.job_type).to_string(),
-                job.prompt,
-                job.name,
-                job.session_target.as_str(),
-                job.model,
-                if job.enabled { 1 } else { 0 },
-                serde_json::to_string(&job.delivery)?,
-                if job.delete_after_run { 1 } else { 0 },
-                job.next_run.to_rfc3339(),
-                job.id,
-            ],
+            "UPDATE cron_jobs SET next_run = ?1 WHERE id = ?2",
+            params![next_run.to_rfc3339(), job_id],
         )
-        .context("Failed to update cron job")?;
+        .context("Failed to update cron job next run")?;
         Ok(())
-    })?;
-
-    get_job(config, job_id)
+    })
 }
 
 pub fn record_last_run(
@@ -425,6 +756,28 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let next_run_raw: String = row.get(13)?;
     let last_run_raw: Option<String> = row.get(14)?;
     let created_at_raw: String = row.get(12)?;
+    let output_schema_raw: Option<String> = row.get(17)?;
+    let output_schema = match output_schema_raw.as_deref().map(str::trim) {
+        Some(raw) if !raw.is_empty() => Some(
+            serde_json::from_str(raw)
+                .with_context(|| format!("Failed to parse cron output schema JSON: {raw}"))
+                .map_err(sql_conversion_error)?,
+        ),
+        _ => None,
+    };
+    let depends_on_raw: Option<String> = row.get(18)?;
+    let after = decode_dependencies(depends_on_raw.as_deref()).map_err(sql_conversion_error)?;
+    let overlap = match row.get::<_, Option<String>>(19)? {
+        Some(raw) if !raw.trim().is_empty() => OverlapPolicy::try_from(raw.trim())
+            .map_err(|e| sql_conversion_error(anyhow::anyhow!(e)))?,
+        _ => OverlapPolicy::default(),
+    };
+    let catch_up = match row.get::<_, Option<String>>(20)? {
+        Some(raw) if !raw.trim().is_empty() => CatchUpPolicy::try_from(raw.trim())
+            .map_err(|e| sql_conversion_error(anyhow::anyhow!(e)))?,
+        _ => CatchUpPolicy::default(),
+    };
+    let jitter_secs = u64::try_from(row.get::<_, i64>(21)?).unwrap_or(0);
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -447,6 +800,12 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         },
         last_status: row.get(15)?,
         last_output: row.get(16)?,
+        output_schema,
+        after,
+        overlap,
+        catch_up,
+        jitter_secs,
+        role: row.get(22)?,
     })
 }
 
@@ -469,6 +828,21 @@ fn decode_schedule(schedule_raw: Option<&str>, expression: &str) -> Result<Sched
     })
 }
 
+fn encode_dependencies(deps: &[JobDependency]) -> Result<Option<String>> {
+    if deps.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(deps)?))
+}
+
+fn decode_dependencies(raw: Option<&str>) -> Result<Vec<JobDependency>> {
+    match raw.map(str::trim) {
+        Some(trimmed) if !trimmed.is_empty() => serde_json::from_str(trimmed)
+            .with_context(|| format!("Failed to parse cron dependencies JSON: {trimmed}")),
+        _ => Ok(Vec::new()),
+    }
+}
+
 fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
     if let Some(raw) = delivery_raw {
         let trimmed = raw.trim();
@@ -510,8 +884,12 @@ fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Resul
     }
 }
 
+fn db_path(config: &Config) -> PathBuf {
+    config.workspace_dir.join("cron").join("jobs.db")
+}
+
 fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
-    let db_path = config.workspace_dir.join("cron").join("jobs.db");
+    let db_path = db_path(config);
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create cron directory: {}", parent.display()))?;
@@ -539,7 +917,13 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run         TEXT NOT NULL,
             last_run         TEXT,
             last_status      TEXT,
-            last_output      TEXT
+            last_output      TEXT,
+            output_schema    TEXT,
+            depends_on       TEXT,
+            overlap_policy   TEXT,
+            catch_up         TEXT,
+            jitter_secs      INTEGER NOT NULL DEFAULT 0,
+            role             TEXT
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
 
@@ -568,6 +952,12 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "output_schema", "TEXT")?;
+    add_column_if_missing(&conn, "depends_on", "TEXT")?;
+    add_column_if_missing(&conn, "overlap_policy", "TEXT")?;
+    add_column_if_missing(&conn, "catch_up", "TEXT")?;
+    add_column_if_missing(&conn, "jitter_secs", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "role", "TEXT")?;
 
     f(&conn)
 }
@@ -600,6 +990,39 @@ mod tests {
         assert!(matches!(job.schedule, Schedule::Cron { .. }));
     }
 
+    #[tokio::test]
+    async fn jobs_remember_the_role_they_were_created_under() {
+        use crate::security::rbac::{self, RbacPolicy};
+
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let unscoped = add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+        assert_eq!(unscoped.role, None);
+
+        let policy = RbacPolicy::default();
+        let viewer = policy.scope_for(policy.role_named("viewer"), None);
+        let (created, updated) = rbac::scope(viewer, async {
+            let created = add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+            let updated = update_job(
+                &config,
+                &unscoped.id,
+                CronJobPatch {
+                    enabled: Some(false),
+                    ..CronJobPatch::default()
+                },
+            )
+            .unwrap();
+            (created, updated)
+        })
+        .await;
+        assert_eq!(created.role.as_deref(), Some("viewer"));
+        assert_eq!(updated.role.as_deref(), Some("viewer"));
+        assert_eq!(
+            get_job(&config, &created.id).unwrap().role.as_deref(),
+            Some("viewer")
+        );
+    }
+
     #[test]
     fn add_list_remove_roundtrip() {
         let tmp = TempDir::new().unwrap();
@@ -833,4 +1256,221 @@ mod tests {
         assert!(last_output.ends_with(TRUNCATED_OUTPUT_MARKER));
         assert!(last_output.len() <= MAX_CRON_OUTPUT_BYTES);
     }
+
+    #[test]
+    fn update_job_sets_and_validates_output_schema() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_agent_job(
+            &config,
+            None,
+            Schedule::Cron {
+                expr: "*/5 * * * *".into(),
+                tz: None,
+            },
+            "summarize",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(job.output_schema.is_none());
+
+        let schema = serde_json::json!({"type": "object"});
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                output_schema: Some(schema.clone()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.output_schema, Some(schema));
+
+        let err = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                output_schema: Some(serde_json::json!("nope")),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("output schema"));
+    }
+
+    #[test]
+    fn output_schema_is_rejected_for_shell_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo schema").unwrap();
+
+        let err = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                output_schema: Some(serde_json::json!({"type": "object"})),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("only supported for agent jobs"));
+    }
+
+    #[test]
+    fn add_job_with_inserts_options_in_one_row() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let upstream = add_job(&config, "*/5 * * * *", "echo upstream").unwrap();
+        let schema = serde_json::json!({"type": "object"});
+
+        let job = add_job_with(
+            &config,
+            JobType::Agent,
+            Some("report".into()),
+            Schedule::After,
+            CronJobPatch {
+                prompt: Some("summarize".into()),
+                output_schema: Some(schema.clone()),
+                after: Some(vec![JobDependency::parse(&upstream.id).unwrap()]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(job.output_schema, Some(schema));
+        assert_eq!(job.after.len(), 1);
+
+        let err = add_job_with(
+            &config,
+            JobType::Agent,
+            None,
+            Schedule::After,
+            CronJobPatch {
+                prompt: Some("summarize".into()),
+                after: Some(vec![JobDependency::parse("missing").unwrap()]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+        assert_eq!(list_jobs(&config).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn update_job_persists_run_policies() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo policy").unwrap();
+        assert_eq!(job.overlap, OverlapPolicy::Skip);
+        assert_eq!(job.catch_up, CatchUpPolicy::Once);
+        assert_eq!(job.jitter_secs, 0);
+
+        update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap: Some(OverlapPolicy::Queue),
+                catch_up: Some(CatchUpPolicy::All(3)),
+                jitter_secs: Some(30),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.overlap, OverlapPolicy::Queue);
+        assert_eq!(stored.catch_up, CatchUpPolicy::All(3));
+        assert_eq!(stored.jitter_secs, 30);
+
+        assert!(update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                jitter_secs: Some(MAX_JITTER_SECS + 1),
+                ..CronJobPatch::default()
+            },
+        )
+        .is_err());
+    }
+
+    fn depend(config: &Config, job_id: &str, deps: &[&str]) -> Result<CronJob> {
+        update_job(
+            config,
+            job_id,
+            CronJobPatch {
+                after: Some(
+                    deps.iter()
+                        .map(|d| JobDependency::parse(d).unwrap())
+                        .collect(),
+                ),
+                ..CronJobPatch::default()
+            },
+        )
+    }
+
+    #[test]
+    fn update_job_persists_dependencies_and_excludes_dependents_from_due() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let backup = add_job(&config, "*/5 * * * *", "echo backup").unwrap();
+        let summary = add_job(&config, "*/5 * * * *", "echo summary").unwrap();
+
+        let updated = depend(&config, &summary.id, &[&format!("{}:always", backup.id)]).unwrap();
+        assert_eq!(updated.after.len(), 1);
+        assert_eq!(updated.after[0].job_id, backup.id);
+        assert_eq!(
+            updated.after[0].condition,
+            crate::cron::DependencyCondition::Always
+        );
+
+        let dependents = dependents_of(&config, &backup.id).unwrap();
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].id, summary.id);
+
+        let due = due_jobs(&config, Utc::now() + ChronoDuration::days(1)).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, backup.id);
+
+        assert_eq!(updated.schedule, Schedule::After);
+        let err = depend(&config, &summary.id, &[]).unwrap_err();
+        assert!(err.to_string().contains("at least one upstream job"));
+
+        let cleared = update_job(
+            &config,
+            &summary.id,
+            CronJobPatch {
+                schedule: Some(Schedule::Cron {
+                    expr: "*/5 * * * *".into(),
+                    tz: None,
+                }),
+                after: Some(Vec::new()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(cleared.after.is_empty());
+    }
+
+    #[test]
+    fn update_job_rejects_dependency_cycles_and_unknown_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let a = add_job(&config, "*/5 * * * *", "echo a").unwrap();
+        let b = add_job(&config, "*/5 * * * *", "echo b").unwrap();
+        let c = add_job(&config, "*/5 * * * *", "echo c").unwrap();
+
+        depend(&config, &b.id, &[&a.id]).unwrap();
+        depend(&config, &c.id, &[&b.id]).unwrap();
+
+        let err = depend(&config, &a.id, &[&c.id]).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+        assert!(get_job(&config, &a.id).unwrap().after.is_empty());
+
+        let err = depend(&config, &a.id, &[&a.id]).unwrap_err();
+        assert!(err.to_string().contains("itself"));
+
+        let err = depend(&config, &a.id, &["missing-job"]).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
 }
@@ -0,0 +1,369 @@
+//! Event triggers for cron jobs: workspace file watches, named gateway hooks
+//! and channel message patterns. Triggered runs share the regular job
+//! storage, delivery and run history.
+
+use crate::config::Config;
+use crate::cron::{jobs_revision, list_jobs, CronJob, Schedule};
+use anyhow::Result;
+use parking_lot::Mutex;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::SystemTime;
+
+/// Directory depth and file count limits for a single watch scan.
+const MAX_WATCH_DEPTH: usize = 8;
+const MAX_WATCH_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerKind {
+    Watch,
+    Webhook,
+    Channel,
+}
+
+impl TriggerKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Watch => "watch",
+            Self::Webhook => "webhook",
+            Self::Channel => "channel",
+        }
+    }
+}
+
+/// An event that fired a job, with the payload handed to the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerEvent {
+    pub kind: TriggerKind,
+    /// Watch path, hook name or channel name.
+    pub source: String,
+    /// Changed paths, request body or message text.
+    pub payload: String,
+}
+
+/// Enabled jobs bound to the gateway hook `name`.
+pub fn jobs_for_webhook(config: &Config, name: &str) -> Result<Vec<CronJob>> {
+    Ok(list_jobs(config)?
+        .into_iter()
+        .filter(|job| {
+            job.enabled && matches!(&job.schedule, Schedule::Webhook { name: hook } if hook == name)
+        })
+        .collect())
+}
+
+/// An enabled channel-triggered job with its pattern compiled.
+struct ChannelTrigger {
+    job: CronJob,
+    channel: Option<String>,
+    pattern: Regex,
+}
+
+impl ChannelTrigger {
+    fn matches(&self, channel: &str, content: &str) -> bool {
+        self.channel
+            .as_deref()
+            .is_none_or(|only| only.eq_ignore_ascii_case(channel))
+            && self.pattern.is_match(content)
+    }
+}
+
+type TriggerRevision = (u64, Option<SystemTime>);
+type TriggerCache = HashMap<PathBuf, (TriggerRevision, Arc<Vec<ChannelTrigger>>)>;
+
+/// Compiled channel triggers per workspace, so inbound messages do not hit
+/// the job database or recompile patterns. An entry is rebuilt when
+/// [`jobs_revision`] reports a change.
+static CHANNEL_TRIGGERS: LazyLock<Mutex<TriggerCache>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn channel_triggers(config: &Config) -> Result<Arc<Vec<ChannelTrigger>>> {
+    let revision = jobs_revision(config);
+    let mut cache = CHANNEL_TRIGGERS.lock();
+    if let Some((cached, triggers)) = cache.get(&config.workspace_dir) {
+        if *cached == revision {
+            return Ok(Arc::clone(triggers));
+        }
+    }
+
+    let triggers: Arc<Vec<ChannelTrigger>> = Arc::new(
+        list_jobs(config)?
+            .into_iter()
+            .filter(|job| job.enabled)
+            .filter_map(|job| {
+                let Schedule::Channel { pattern, channel } = &job.schedule else {
+                    return None;
+                };
+                let pattern = Regex::new(pattern).ok()?;
+                let channel = channel.clone();
+                Some(ChannelTrigger {
+                    job,
+                    channel,
+                    pattern,
+                })
+            })
+            .collect(),
+    );
+    cache.insert(
+        config.workspace_dir.clone(),
+        (revision, Arc::clone(&triggers)),
+    );
+    Ok(triggers)
+}
+
+/// Enabled jobs whose channel pattern matches a message on `channel`.
+pub fn jobs_for_channel_message(
+    config: &Config,
+    channel: &str,
+    content: &str,
+) -> Result<Vec<CronJob>> {
+    Ok(channel_triggers(config)?
+        .iter()
+        .filter(|trigger| trigger.matches(channel, content))
+        .map(|trigger| trigger.job.clone())
+        .collect())
+}
+
+/// Fire every channel-triggered job matching an inbound message.
+pub fn dispatch_channel_message(config: &Config, channel: &str, content: &str) {
+    let jobs = match jobs_for_channel_message(config, channel, content) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Failed to load channel-triggered cron jobs: {e}");
+            return;
+        }
+    };
+    for job in jobs {
+        crate::cron::scheduler::spawn_triggered_job(
+            config.clone(),
+            job,
+            TriggerEvent {
+                kind: TriggerKind::Channel,
+                source: channel.to_string(),
+                payload: content.to_string(),
+            },
+        );
+    }
+}
+
+type FileStamp = (Option<SystemTime>, u64);
+
+/// Polling file watcher. The first scan of a job records a baseline; later
+/// scans report files that were created or modified since the previous one.
+#[derive(Debug, Default)]
+pub struct WatchTracker {
+    snapshots: HashMap<String, HashMap<PathBuf, FileStamp>>,
+}
+
+impl WatchTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn poll(&mut self, workspace_dir: &Path, jobs: &[CronJob]) -> Vec<(CronJob, TriggerEvent)> {
+        let mut fired = Vec::new();
+        let mut live = HashSet::new();
+
+        for job in jobs.iter().filter(|job| job.enabled) {
+            let Schedule::Watch { path, glob } = &job.schedule else {
+                continue;
+            };
+            live.insert(job.id.clone());
+
+            let pattern = glob.as_deref().and_then(|g| glob::Pattern::new(g).ok());
+            let root = workspace_dir.join(path);
+            let current = scan_watch_root(&root, pattern.as_ref());
+            let Some(previous) = self.snapshots.insert(job.id.clone(), current) else {
+                continue;
+            };
+
+            let mut changed: Vec<String> = self.snapshots[&job.id]
+                .iter()
+                .filter(|(file, stamp)| previous.get(*file) != Some(stamp))
+                .map(|(file, _)| {
+                    file.strip_prefix(workspace_dir)
+                        .unwrap_or(file)
+                        .display()
+                        .to_string()
+                })
+                .collect();
+            if changed.is_empty() {
+                continue;
+            }
+            changed.sort();
+            fired.push((
+                job.clone(),
+                TriggerEvent {
+                    kind: TriggerKind::Watch,
+                    source: path.clone(),
+                    payload: changed.join("\n"),
+                },
+            ));
+        }
+
+        self.snapshots.retain(|id, _| live.contains(id));
+        fired
+    }
+}
+
+fn scan_watch_root(root: &Path, pattern: Option<&glob::Pattern>) -> HashMap<PathBuf, FileStamp> {
+    let mut files = HashMap::new();
+    let mut stack = vec![(root.to_path_buf(), 0usize)];
+
+    while let Some((path, depth)) = stack.pop() {
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_file() {
+            let matches = pattern.is_none_or(|p| {
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                p.matches_path(relative)
+                    || path
+                        .file_name()
+                        .is_some_and(|name| p.matches(&name.to_string_lossy()))
+            });
+            if matches {
+                files.insert(path, (meta.modified().ok(), meta.len()));
+                if files.len() >= MAX_WATCH_ENTRIES {
+                    break;
+                }
+            }
+        } else if meta.is_dir() && depth <= MAX_WATCH_DEPTH {
+            let Ok(entries) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            stack.extend(entries.flatten().map(|e| (e.path(), depth + 1)));
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::{add_shell_job, get_job};
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn watch_tracker_reports_new_and_modified_matching_files() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let inbox = config.workspace_dir.join("inbox");
+        std::fs::create_dir_all(&inbox).unwrap();
+        std::fs::write(inbox.join("old.csv"), "a").unwrap();
+
+        let job = add_shell_job(
+            &config,
+            None,
+            Schedule::Watch {
+                path: "inbox".into(),
+                glob: Some("*.csv".into()),
+            },
+            "echo new",
+        )
+        .unwrap();
+        let jobs = vec![get_job(&config, &job.id).unwrap()];
+        let mut tracker = WatchTracker::new();
+
+        assert!(tracker.poll(&config.workspace_dir, &jobs).is_empty());
+
+        std::fs::write(inbox.join("new.csv"), "b").unwrap();
+        std::fs::write(inbox.join("notes.txt"), "ignored").unwrap();
+        let fired = tracker.poll(&config.workspace_dir, &jobs);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1.kind, TriggerKind::Watch);
+        assert_eq!(
+            fired[0].1.payload,
+            Path::new("inbox").join("new.csv").display().to_string()
+        );
+
+        assert!(tracker.poll(&config.workspace_dir, &jobs).is_empty());
+    }
+
+    #[test]
+    fn jobs_are_selected_by_hook_name_and_channel_pattern() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let hook = add_shell_job(
+            &config,
+            None,
+            Schedule::Webhook {
+                name: "deploy".into(),
+            },
+            "echo deploy",
+        )
+        .unwrap();
+        let keyword = add_shell_job(
+            &config,
+            None,
+            Schedule::Channel {
+                pattern: "(?i)\\breport\\b".into(),
+                channel: Some("slack".into()),
+            },
+            "echo report",
+        )
+        .unwrap();
+
+        let hooks = jobs_for_webhook(&config, "deploy").unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].id, hook.id);
+        assert!(jobs_for_webhook(&config, "other").unwrap().is_empty());
+
+        let matched = jobs_for_channel_message(&config, "slack", "Send the REPORT").unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].id, keyword.id);
+        assert!(jobs_for_channel_message(&config, "telegram", "report")
+            .unwrap()
+            .is_empty());
+        assert!(jobs_for_channel_message(&config, "slack", "reporting")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn channel_trigger_cache_follows_job_changes() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_shell_job(
+            &config,
+            None,
+            Schedule::Channel {
+                pattern: "deploy".into(),
+                channel: None,
+            },
+            "echo deploy",
+        )
+        .unwrap();
+        assert_eq!(
+            jobs_for_channel_message(&config, "slack", "deploy now")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        crate::cron::update_job(
+            &config,
+            &job.id,
+            crate::cron::CronJobPatch {
+                enabled: Some(false),
+                ..crate::cron::CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(jobs_for_channel_message(&config, "slack", "deploy now")
+            .unwrap()
+            .is_empty());
+    }
+}
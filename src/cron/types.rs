@@ -7,6 +7,8 @@ pub enum JobType {
     #[default]
     Shell,
     Agent,
+    /// Built-in `zeroclaw backup create`, added with `zeroclaw backup schedule`.
+    Backup,
 }
 
 impl From<JobType> for &'static str {
@@ -14,6 +16,7 @@ impl From<JobType> for &'static str {
         match value {
             JobType::Shell => "shell",
             JobType::Agent => "agent",
+            JobType::Backup => "backup",
         }
     }
 }
@@ -25,8 +28,9 @@ impl TryFrom<&str> for JobType {
         match value.to_lowercase().as_str() {
             "shell" => Ok(JobType::Shell),
             "agent" => Ok(JobType::Agent),
+            "backup" => Ok(JobType::Backup),
             _ => Err(format!(
-                "Invalid job type '{}'. Expected one of: 'shell', 'agent'",
+                "Invalid job type '{}'. Expected one of: 'shell', 'agent', 'backup'",
                 value
             )),
         }
@@ -72,6 +76,65 @@ pub enum Schedule {
     Every {
         every_ms: u64,
     },
+    /// Fires when files under `path` (relative to the workspace) are created
+    /// or modified, optionally filtered by a glob such as `*.csv`.
+    Watch {
+        path: String,
+        #[serde(default)]
+        glob: Option<String>,
+    },
+    /// Fires on an authenticated `POST /hooks/<name>` gateway request.
+    Webhook {
+        name: String,
+    },
+    /// Fires when an inbound channel message matches the `pattern` regex.
+    Channel {
+        pattern: String,
+        #[serde(default)]
+        channel: Option<String>,
+    },
+    /// Fires when the job's upstream dependencies (`after`) finish.
+    After,
+}
+
+impl Schedule {
+    /// Whether this schedule fires on external events rather than on time.
+    pub fn is_event_trigger(&self) -> bool {
+        matches!(
+            self,
+            Self::Watch { .. } | Self::Webhook { .. } | Self::Channel { .. } | Self::After
+        )
+    }
+
+    /// Parse the CLI trigger form: `watch:<path>[:<glob>]`, `hook:<name>`
+    /// or `channel:<regex>`.
+    pub fn parse_trigger(raw: &str) -> Result<Self, String> {
+        let (kind, rest) = raw
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid trigger '{raw}': expected <kind>:<value>"))?;
+        match kind.to_ascii_lowercase().as_str() {
+            "watch" => {
+                let (path, glob) = match rest.split_once(':') {
+                    Some((path, glob)) => (path, Some(glob.to_string())),
+                    None => (rest, None),
+                };
+                Ok(Self::Watch {
+                    path: path.to_string(),
+                    glob,
+                })
+            }
+            "hook" | "webhook" => Ok(Self::Webhook {
+                name: rest.to_string(),
+            }),
+            "channel" => Ok(Self::Channel {
+                pattern: rest.to_string(),
+                channel: None,
+            }),
+            _ => Err(format!(
+                "Invalid trigger kind '{kind}'. Expected one of: 'watch', 'hook', 'channel'"
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -101,6 +164,217 @@ fn default_true() -> bool {
     true
 }
 
+/// When a downstream job fires relative to its upstream job's result.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyCondition {
+    #[default]
+    Success,
+    Failure,
+    Always,
+}
+
+impl DependencyCondition {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Always => "always",
+        }
+    }
+
+    /// Whether an upstream run with the given outcome satisfies this condition.
+    pub fn matches(self, success: bool) -> bool {
+        match self {
+            Self::Success => success,
+            Self::Failure => !success,
+            Self::Always => true,
+        }
+    }
+}
+
+impl TryFrom<&str> for DependencyCondition {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "success" | "ok" => Ok(Self::Success),
+            "failure" | "error" => Ok(Self::Failure),
+            "always" => Ok(Self::Always),
+            _ => Err(format!(
+                "Invalid dependency condition '{}'. Expected one of: 'success', 'failure', 'always'",
+                value
+            )),
+        }
+    }
+}
+
+/// An upstream job that must finish before this job runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JobDependency {
+    pub job_id: String,
+    #[serde(default)]
+    pub condition: DependencyCondition,
+}
+
+impl JobDependency {
+    /// Parse the CLI form `<job_id>[:success|failure|always]`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let (job_id, condition) = match raw.rsplit_once(':') {
+            Some((id, cond)) => (id, DependencyCondition::try_from(cond)?),
+            None => (raw, DependencyCondition::default()),
+        };
+        let job_id = job_id.trim();
+        if job_id.is_empty() {
+            return Err(format!("Invalid dependency '{raw}': missing job id"));
+        }
+        Ok(Self {
+            job_id: job_id.to_string(),
+            condition,
+        })
+    }
+}
+
+/// What the scheduler does when a job comes due while its previous run is
+/// still in progress.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    #[default]
+    Skip,
+    /// Run once more as soon as the current run finishes.
+    Queue,
+    CancelPrevious,
+}
+
+impl OverlapPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Queue => "queue",
+            Self::CancelPrevious => "cancel_previous",
+        }
+    }
+}
+
+impl TryFrom<&str> for OverlapPolicy {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().replace('-', "_").as_str() {
+            "skip" => Ok(Self::Skip),
+            "queue" => Ok(Self::Queue),
+            "cancel_previous" => Ok(Self::CancelPrevious),
+            _ => Err(format!(
+                "Invalid overlap policy '{}'. Expected one of: 'skip', 'queue', 'cancel_previous'",
+                value
+            )),
+        }
+    }
+}
+
+/// How many missed occurrences a job makes up for when the scheduler comes
+/// back after downtime. Stored and parsed as `none`, `once` or `all:<max>`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(try_from = "String", into = "String")]
+pub enum CatchUpPolicy {
+    /// Skip missed runs and wait for the next occurrence.
+    None,
+    #[default]
+    Once,
+    /// Replay every missed occurrence, up to the given count.
+    All(u32),
+}
+
+impl CatchUpPolicy {
+    /// Runs to execute for `missed` overdue occurrences.
+    pub fn runs_for(self, missed: u32) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Once => 1,
+            Self::All(max) => missed.min(max).max(1),
+        }
+    }
+}
+
+impl std::fmt::Display for CatchUpPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::Once => f.write_str("once"),
+            Self::All(max) => write!(f, "all:{max}"),
+        }
+    }
+}
+
+impl TryFrom<&str> for CatchUpPolicy {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let lowered = value.trim().to_lowercase();
+        match lowered.split_once(':') {
+            None if lowered == "none" => Ok(Self::None),
+            None if lowered == "once" => Ok(Self::Once),
+            Some(("all", max)) => match max.trim().parse::<u32>() {
+                Ok(max) if max > 0 => Ok(Self::All(max)),
+                _ => Err(format!(
+                    "Invalid catch-up limit in '{value}': expected a positive integer"
+                )),
+            },
+            _ => Err(format!(
+                "Invalid catch-up policy '{}'. Expected one of: 'none', 'once', 'all:<max>'",
+                value
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for CatchUpPolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl From<CatchUpPolicy> for String {
+    fn from(value: CatchUpPolicy) -> Self {
+        value.to_string()
+    }
+}
+
+/// `CronRun::status` values. Besides `ok`/`error`, runs record the
+/// scheduler's decision when a job did not run as scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Ok,
+    Error,
+    /// Came due while the previous run was still in progress.
+    SkippedOverlap,
+    /// Will run again when the in-progress run finishes.
+    Queued,
+    /// Stopped because a newer run superseded it.
+    Cancelled,
+    /// Occurrences missed while the scheduler was down.
+    Missed,
+    /// Held back by the scheduler's max concurrent job limit.
+    Deferred,
+}
+
+impl RunStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::SkippedOverlap => "skipped_overlap",
+            Self::Queued => "queued",
+            Self::Cancelled => "cancelled",
+            Self::Missed => "missed",
+            Self::Deferred => "deferred",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CronJob {
     pub id: String,
@@ -120,6 +394,23 @@ pub struct CronJob {
     pub last_run: Option<DateTime<Utc>>,
     pub last_status: Option<String>,
     pub last_output: Option<String>,
+    /// JSON schema the agent job's final output must conform to.
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
+    /// Upstream jobs this job runs after. A job with dependencies is triggered
+    /// by its upstream runs instead of its own schedule.
+    #[serde(default)]
+    pub after: Vec<JobDependency>,
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+    /// Random delay of up to this many seconds before each scheduled run.
+    #[serde(default)]
+    pub jitter_secs: u64,
+    /// Gateway role the job was created under; runs are limited to it.
+    #[serde(default)]
+    pub role: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -144,11 +435,18 @@ pub struct CronJobPatch {
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
+    pub output_schema: Option<serde_json::Value>,
+    pub after: Option<Vec<JobDependency>>,
+    pub overlap: Option<OverlapPolicy>,
+    pub catch_up: Option<CatchUpPolicy>,
+    pub jitter_secs: Option<u64>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JobType;
+    use super::{
+        CatchUpPolicy, DependencyCondition, JobDependency, JobType, OverlapPolicy, Schedule,
+    };
 
     #[test]
     fn job_type_try_from_accepts_known_values_case_insensitive() {
@@ -156,6 +454,7 @@ mod tests {
         assert_eq!(JobType::try_from("SHELL").unwrap(), JobType::Shell);
         assert_eq!(JobType::try_from("agent").unwrap(), JobType::Agent);
         assert_eq!(JobType::try_from("AgEnT").unwrap(), JobType::Agent);
+        assert_eq!(JobType::try_from("backup").unwrap(), JobType::Backup);
     }
 
     #[test]
@@ -163,4 +462,83 @@ mod tests {
         assert!(JobType::try_from("").is_err());
         assert!(JobType::try_from("unknown").is_err());
     }
+
+    #[test]
+    fn job_dependency_parse_defaults_to_success() {
+        let dep = JobDependency::parse("backup").unwrap();
+        assert_eq!(dep.job_id, "backup");
+        assert_eq!(dep.condition, DependencyCondition::Success);
+
+        let dep = JobDependency::parse("backup:ALWAYS").unwrap();
+        assert_eq!(dep.condition, DependencyCondition::Always);
+
+        assert!(JobDependency::parse("backup:sometimes").is_err());
+        assert!(JobDependency::parse(":failure").is_err());
+    }
+
+    #[test]
+    fn dependency_condition_matches_outcome() {
+        assert!(DependencyCondition::Success.matches(true));
+        assert!(!DependencyCondition::Success.matches(false));
+        assert!(DependencyCondition::Failure.matches(false));
+        assert!(DependencyCondition::Always.matches(false));
+    }
+
+    #[test]
+    fn run_policies_parse_cli_forms() {
+        assert_eq!(
+            OverlapPolicy::try_from("cancel-previous").unwrap(),
+            OverlapPolicy::CancelPrevious
+        );
+        assert!(OverlapPolicy::try_from("wait").is_err());
+
+        assert_eq!(
+            CatchUpPolicy::try_from("NONE").unwrap(),
+            CatchUpPolicy::None
+        );
+        assert_eq!(
+            CatchUpPolicy::try_from("all:5").unwrap(),
+            CatchUpPolicy::All(5)
+        );
+        assert!(CatchUpPolicy::try_from("all:0").is_err());
+        assert!(CatchUpPolicy::try_from("some").is_err());
+        assert_eq!(CatchUpPolicy::All(5).to_string(), "all:5");
+
+        let json = serde_json::to_string(&CatchUpPolicy::All(3)).unwrap();
+        assert_eq!(json, "\"all:3\"");
+        assert_eq!(
+            serde_json::from_str::<CatchUpPolicy>(&json).unwrap(),
+            CatchUpPolicy::All(3)
+        );
+    }
+
+    #[test]
+    fn catch_up_runs_are_bounded_by_policy() {
+        assert_eq!(CatchUpPolicy::None.runs_for(4), 0);
+        assert_eq!(CatchUpPolicy::Once.runs_for(4), 1);
+        assert_eq!(CatchUpPolicy::All(3).runs_for(4), 3);
+        assert_eq!(CatchUpPolicy::All(10).runs_for(4), 4);
+    }
+
+    #[test]
+    fn schedule_parse_trigger_accepts_known_kinds() {
+        assert_eq!(
+            Schedule::parse_trigger("watch:inbox:*.csv").unwrap(),
+            Schedule::Watch {
+                path: "inbox".into(),
+                glob: Some("*.csv".into()),
+            }
+        );
+        assert_eq!(
+            Schedule::parse_trigger("hook:deploy").unwrap(),
+            Schedule::Webhook {
+                name: "deploy".into()
+            }
+        );
+        assert!(Schedule::parse_trigger("channel:(?i)^report")
+            .unwrap()
+            .is_event_trigger());
+        assert!(Schedule::parse_trigger("timer:5").is_err());
+        assert!(Schedule::parse_trigger("inbox").is_err());
+    }
 }
@@ -1,108 +1,221 @@
+mod reload;
+
 use crate::config::Config;
+use crate::heartbeat::state::HeartbeatState;
+use crate::heartbeat::task::HeartbeatTask;
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+/// How often the daemon checks `config.toml` for edits.
+const CONFIG_POLL_SECONDS: u64 = 2;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
-    let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
-    let max_backoff = config
-        .reliability
-        .channel_max_backoff_secs
-        .max(initial_backoff);
-
+    reload::check(&config)?;
     crate::health::mark_component_ok("daemon");
 
-    if config.heartbeat.enabled {
-        let _ =
-            crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
-                .await;
+    let mut config = config;
+    let state_writer = spawn_state_writer(config.clone());
+    let mut components: HashMap<&'static str, JoinHandle<()>> = HashMap::new();
+    for name in reload::COMPONENTS {
+        if let Some(handle) = spawn_component(name, &config, &host, port).await {
+            components.insert(name, handle);
+        }
     }
 
-    let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
+    println!("🧠 ZeroClaw daemon started");
+    println!("   Gateway:  http://{host}:{port}");
+    println!("   Components: gateway, channels, heartbeat, scheduler");
+    println!(
+        "   Config:   {} (reloaded on change or SIGHUP)",
+        config.config_path.display()
+    );
+    println!("   Ctrl+C to stop");
 
-    {
-        let gateway_cfg = config.clone();
-        let gateway_host = host.clone();
-        handles.push(spawn_component_supervisor(
-            "gateway",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = gateway_cfg.clone();
-                let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
-            },
-        ));
+    let mut stamp = reload::config_stamp(&config).await;
+    let mut poll = tokio::time::interval(Duration::from_secs(CONFIG_POLL_SECONDS));
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut hangup = hangup_signal();
+
+    loop {
+        let requested = tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                break;
+            }
+            () = recv_hangup(&mut hangup) => true,
+            _ = poll.tick() => false,
+        };
+
+        let current = reload::config_stamp(&config).await;
+        if !requested && current == stamp {
+            continue;
+        }
+        stamp = current;
+
+        let new_config = match reload::load_candidate(&config).await {
+            Ok(new_config) => new_config,
+            Err(e) => {
+                crate::health::mark_component_error("config", format!("reload rejected: {e:#}"));
+                tracing::error!("Config reload rejected; keeping the running config: {e:#}");
+                println!("⚠️  Config reload rejected; keeping the running config: {e:#}");
+                continue;
+            }
+        };
+        crate::health::mark_component_ok("config");
+
+        let changes = reload::diff(&config, &new_config);
+        if changes.is_empty() {
+            if requested {
+                tracing::info!("Config reload requested; no changes");
+            }
+            continue;
+        }
+        config = new_config;
+        stamp = reload::config_stamp(&config).await;
+        for &name in &changes.components {
+            if let Some(handle) = components.remove(name) {
+                stop_component(handle).await;
+            }
+            if let Some(handle) = spawn_component(name, &config, &host, port).await {
+                components.insert(name, handle);
+            }
+        }
+        tracing::info!("Config reloaded ({})", changes.summary());
+        println!("🔄 Config reloaded ({})", changes.summary());
     }
 
-    {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
+    crate::health::mark_component_error("daemon", "shutdown requested");
+
+    state_writer.abort();
+    let _ = state_writer.await;
+    for (_, handle) in components {
+        stop_component(handle).await;
+    }
+
+    Ok(())
+}
+
+/// Start one supervised component with `config`, or return `None` when the
+/// config leaves it disabled.
+async fn spawn_component(
+    name: &'static str,
+    config: &Config,
+    host: &str,
+    port: u16,
+) -> Option<JoinHandle<()>> {
+    let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
+    let max_backoff = config
+        .reliability
+        .channel_max_backoff_secs
+        .max(initial_backoff);
+    let cfg = config.clone();
+
+    match name {
+        "gateway" => {
+            let host = host.to_string();
+            Some(spawn_component_supervisor(
+                name,
                 initial_backoff,
                 max_backoff,
                 move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    let cfg = cfg.clone();
+                    let host = host.clone();
+                    async move { crate::gateway::run_gateway(&host, port, cfg).await }
                 },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
-            tracing::info!("No real-time channels configured; channel supervisor disabled");
+            ))
         }
-    }
-
-    if config.heartbeat.enabled {
-        let heartbeat_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "heartbeat",
+        "channels" if has_supervised_channels(config) => Some(spawn_component_supervisor(
+            name,
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = heartbeat_cfg.clone();
-                Box::pin(run_heartbeat_worker(cfg))
+                let cfg = cfg.clone();
+                async move { crate::channels::start_channels(cfg).await }
             },
-        ));
-    }
-
-    if config.cron.enabled {
-        let scheduler_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "scheduler",
+        )),
+        "channels" => {
+            crate::health::mark_component_ok(name);
+            tracing::info!("No real-time channels configured; channel supervisor disabled");
+            None
+        }
+        "heartbeat" if config.heartbeat.enabled => {
+            let _ = crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(
+                &config.workspace_dir,
+            )
+            .await;
+            Some(spawn_component_supervisor(
+                name,
+                initial_backoff,
+                max_backoff,
+                move || {
+                    let cfg = cfg.clone();
+                    Box::pin(run_heartbeat_worker(cfg))
+                },
+            ))
+        }
+        "scheduler" if config.cron.enabled => Some(spawn_component_supervisor(
+            name,
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = scheduler_cfg.clone();
+                let cfg = cfg.clone();
                 async move { crate::cron::scheduler::run(cfg).await }
             },
-        ));
-    } else {
-        crate::health::mark_component_ok("scheduler");
-        tracing::info!("Cron disabled; scheduler supervisor not started");
+        )),
+        "scheduler" => {
+            crate::health::mark_component_ok(name);
+            tracing::info!("Cron disabled; scheduler supervisor not started");
+            None
+        }
+        _ => None,
     }
+}
 
-    println!("🧠 ZeroClaw daemon started");
-    println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
-    println!("   Ctrl+C to stop");
+async fn stop_component(handle: JoinHandle<()>) {
+    handle.abort();
+    let _ = handle.await;
+}
 
-    tokio::signal::ctrl_c().await?;
-    crate::health::mark_component_error("daemon", "shutdown requested");
+#[cfg(unix)]
+type HangupSignal = tokio::signal::unix::Signal;
+#[cfg(not(unix))]
+type HangupSignal = ();
 
-    for handle in &handles {
-        handle.abort();
+fn hangup_signal() -> Option<HangupSignal> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::hangup()) {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                tracing::warn!("SIGHUP reload unavailable: {e}");
+                None
+            }
+        }
     }
-    for handle in handles {
-        let _ = handle.await;
+    #[cfg(not(unix))]
+    {
+        None
     }
+}
 
-    Ok(())
+/// Resolve on SIGHUP; never resolves where the signal is unavailable.
+async fn recv_hangup(signal: &mut Option<HangupSignal>) {
+    #[cfg(unix)]
+    if let Some(signal) = signal {
+        if signal.recv().await.is_some() {
+            return;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = signal;
+    std::future::pending::<()>().await;
 }
 
 pub fn state_file_path(config: &Config) -> PathBuf {
@@ -193,18 +306,69 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
             continue;
         }
 
-        for task in tasks {
-            let prompt = format!("[Heartbeat Task] {task}");
-            let temp = config.default_temperature;
-            if let Err(e) =
-                crate::agent::run(config.clone(), Some(prompt), None, None, temp, vec![]).await
-            {
+        let mut state = HeartbeatState::load(&config.workspace_dir).unwrap_or_else(|e| {
+            tracing::warn!("Heartbeat state unreadable, starting fresh: {e}");
+            HeartbeatState::default()
+        });
+        let tick = Utc::now();
+        for task in engine.due_tasks(&tasks, &state, tick) {
+            run_heartbeat_task(&config, task, &mut state, tick).await;
+        }
+        state.retain_tasks(tasks.iter().map(|task| task.id.as_str()));
+        if let Err(e) = state.save(&config.workspace_dir) {
+            tracing::warn!("Failed to save heartbeat state: {e}");
+        }
+    }
+}
+
+/// Run one heartbeat task, record the result and notify its target. The run
+/// is recorded at `tick`, the instant its due check used, so a task that
+/// starts late in a busy tick is still due on the next one.
+async fn run_heartbeat_task(
+    config: &Config,
+    task: &HeartbeatTask,
+    state: &mut HeartbeatState,
+    tick: DateTime<Utc>,
+) {
+    let mut prompt = format!("[Heartbeat Task] {}", task.text);
+    if task.on_change {
+        prompt.push_str(
+            "\n\nReply with a short, stable summary of the current state. \
+             It is compared with the previous result to decide whether to notify.",
+        );
+    }
+    let temp = config.default_temperature;
+    let output =
+        match crate::agent::run(config.clone(), Some(prompt), None, None, temp, vec![], None).await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                state.record_failure(&task.id, tick);
                 crate::health::mark_component_error("heartbeat", e.to_string());
                 tracing::warn!("Heartbeat task failed: {e}");
-            } else {
-                crate::health::mark_component_ok("heartbeat");
+                return;
             }
-        }
+        };
+    crate::health::mark_component_ok("heartbeat");
+
+    let changed = state.record_success(&task.id, tick, &output);
+    let Some(target) = &task.notify else {
+        return;
+    };
+    if task.on_change && !changed {
+        tracing::debug!("Heartbeat task '{}' unchanged; not notifying", task.id);
+        return;
+    }
+    match crate::cron::scheduler::deliver_announcement(
+        config,
+        &target.channel,
+        &target.recipient,
+        &output,
+    )
+    .await
+    {
+        Ok(()) => state.record_notified(&task.id, Utc::now()),
+        Err(e) => tracing::warn!("Heartbeat notification for task '{}' failed: {e}", task.id),
     }
 }
 
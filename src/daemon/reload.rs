@@ -0,0 +1,269 @@
+//! Hot config reload for `zeroclaw daemon`: detect changes to `config.toml`,
+//! work out which components they touch, and report what will be restarted.
+
+use crate::config::{validate, Config};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Components the daemon supervises, in start order.
+pub const COMPONENTS: [&str; 4] = ["gateway", "channels", "heartbeat", "scheduler"];
+
+/// Top-level sections read by only some components. Any other section
+/// (provider, autonomy, memory, tools, ...) is read by every component that
+/// runs the agent, so changing it restarts all of them.
+const SECTION_COMPONENTS: &[(&str, &[&str])] = &[
+    ("gateway", &["gateway", "channels"]),
+    ("channels_config", &["gateway", "channels"]),
+    ("tunnel", &["gateway"]),
+    ("heartbeat", &["heartbeat"]),
+    ("cron", &["gateway", "scheduler"]),
+    ("scheduler", &["scheduler"]),
+    ("backup", &["scheduler"]),
+];
+
+/// What changed between two configs and which components must restart.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Changed top-level sections, e.g. `autonomy`, `channels_config`.
+    pub sections: Vec<String>,
+    /// Components to restart, in start order.
+    pub components: Vec<&'static str>,
+    /// Changes that only apply after the daemon itself restarts.
+    pub needs_daemon_restart: Vec<String>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// One-line summary for logs and the console.
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no changes".into();
+        }
+        let restarted = if self.components.is_empty() {
+            "none".to_string()
+        } else {
+            self.components.join(", ")
+        };
+        let changed = self.sections.join(", ");
+        if self.needs_daemon_restart.is_empty() {
+            format!("changed: {changed}; restarting: {restarted}")
+        } else {
+            format!(
+                "changed: {changed}; restarting: {restarted}; needs a daemon restart: {}",
+                self.needs_daemon_restart.join(", ")
+            )
+        }
+    }
+}
+
+/// Compare two configs section by section.
+pub fn diff(old: &Config, new: &Config) -> ConfigDiff {
+    let (Ok(serde_json::Value::Object(old_map)), Ok(serde_json::Value::Object(new_map))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return ConfigDiff {
+            sections: vec!["*".into()],
+            components: COMPONENTS.to_vec(),
+            needs_daemon_restart: Vec::new(),
+        };
+    };
+
+    let keys: BTreeSet<&String> = old_map.keys().chain(new_map.keys()).collect();
+    let sections: Vec<String> = keys
+        .into_iter()
+        .filter(|key| old_map.get(*key) != new_map.get(*key))
+        .cloned()
+        .collect();
+
+    let mut affected: BTreeSet<&'static str> = BTreeSet::new();
+    for section in &sections {
+        match SECTION_COMPONENTS
+            .iter()
+            .find(|(name, _)| *name == section.as_str())
+        {
+            Some((_, components)) => affected.extend(components.iter().copied()),
+            None => affected.extend(COMPONENTS),
+        }
+    }
+
+    let mut needs_daemon_restart = Vec::new();
+    if old.gateway.host != new.gateway.host {
+        needs_daemon_restart.push("gateway.host".to_string());
+    }
+    if old.gateway.port != new.gateway.port {
+        needs_daemon_restart.push("gateway.port".to_string());
+    }
+
+    ConfigDiff {
+        sections,
+        components: COMPONENTS
+            .into_iter()
+            .filter(|component| affected.contains(component))
+            .collect(),
+        needs_daemon_restart,
+    }
+}
+
+/// Modification time and size of a config file, used to spot edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+async fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some(FileStamp {
+        modified: metadata.modified().ok()?,
+        len: metadata.len(),
+    })
+}
+
+/// Stamps of `config.toml` and every file it was composed from.
+pub async fn config_stamp(config: &Config) -> Vec<Option<FileStamp>> {
+    let mut files = vec![config.config_path.clone()];
+    files.extend(
+        config
+            .sources
+            .files
+            .iter()
+            .filter(|file| **file != config.config_path)
+            .cloned(),
+    );
+    let mut stamps = Vec::with_capacity(files.len());
+    for file in &files {
+        stamps.push(file_stamp(file).await);
+    }
+    stamps
+}
+
+/// Load and validate the config at `current.config_path`, keeping the
+/// current workspace directory.
+pub async fn load_candidate(current: &Config) -> anyhow::Result<Config> {
+    let config =
+        Config::load_from_path(&current.config_path, current.workspace_dir.clone()).await?;
+    check(&config)?;
+    Ok(config)
+}
+
+/// Run `config validate` checks. Warnings and problems in stored cron jobs
+/// are logged; errors in the config itself are returned.
+pub fn check(config: &Config) -> anyhow::Result<()> {
+    let mut blocking = Vec::new();
+    for diagnostic in validate::validate(config) {
+        if diagnostic.is_blocking() {
+            blocking.push(diagnostic.to_string());
+        } else {
+            tracing::warn!("{diagnostic}");
+            println!("⚠️  {diagnostic}");
+        }
+    }
+    if blocking.is_empty() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "config has {} error(s) (see `zeroclaw config validate`):\n  {}",
+        blocking.len(),
+        blocking.join("\n  ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutonomyConfig;
+    use crate::security::AutonomyLevel;
+
+    #[test]
+    fn identical_configs_have_no_diff() {
+        let config = Config::default();
+        let diff = diff(&config, &config.clone());
+        assert!(diff.is_empty());
+        assert!(diff.components.is_empty());
+        assert_eq!(diff.summary(), "no changes");
+    }
+
+    #[test]
+    fn scoped_sections_restart_only_their_components() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.heartbeat.interval_minutes += 10;
+        let changes = diff(&old, &new);
+        assert_eq!(changes.sections, vec!["heartbeat"]);
+        assert_eq!(changes.components, vec!["heartbeat"]);
+
+        let mut new = old.clone();
+        new.channels_config.telegram = Some(crate::config::TelegramConfig {
+            bot_token: "token".into(),
+            allowed_users: vec!["alice".into()],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+        });
+        let changes = diff(&old, &new);
+        assert_eq!(changes.sections, vec!["channels_config"]);
+        assert_eq!(changes.components, vec!["gateway", "channels"]);
+    }
+
+    #[test]
+    fn shared_sections_restart_every_component() {
+        let old = Config::default();
+        let new = Config {
+            autonomy: AutonomyConfig {
+                level: AutonomyLevel::ReadOnly,
+                ..AutonomyConfig::default()
+            },
+            default_temperature: 0.2,
+            ..old.clone()
+        };
+        let changes = diff(&old, &new);
+        assert_eq!(changes.sections, vec!["autonomy", "default_temperature"]);
+        assert_eq!(changes.components, COMPONENTS.to_vec());
+        assert!(changes.summary().contains("restarting: gateway, channels"));
+    }
+
+    #[test]
+    fn listen_address_changes_are_reported_as_needing_a_daemon_restart() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.gateway.port = 4000;
+        let changes = diff(&old, &new);
+        assert_eq!(changes.needs_daemon_restart, vec!["gateway.port"]);
+        assert!(changes
+            .summary()
+            .contains("needs a daemon restart: gateway.port"));
+    }
+
+    #[tokio::test]
+    async fn load_candidate_rejects_invalid_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let current = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::write(&current.config_path, "default_temperature = 0.3\n").unwrap();
+        let loaded = load_candidate(&current).await.unwrap();
+        assert!((loaded.default_temperature - 0.3).abs() < f64::EPSILON);
+        assert_eq!(loaded.workspace_dir, current.workspace_dir);
+
+        std::fs::write(&current.config_path, "default_temperature = \"hot\"\n").unwrap();
+        assert!(load_candidate(&current).await.is_err());
+
+        std::fs::write(
+            &current.config_path,
+            "default_temperature = 0.3\ndefault_provider = \"nope-ai\"\n",
+        )
+        .unwrap();
+        let err = load_candidate(&current).await.unwrap_err().to_string();
+        assert!(
+            err.contains("config.toml:2:1: error: `default_provider`"),
+            "{err}"
+        );
+    }
+}
@@ -434,44 +434,34 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
             ));
         }
     }
+
+    // Cross-references, conflicts and unreachable allowlist entries
+    let diagnostics = crate::config::validate::check_config(config);
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == crate::config::validate::Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if diagnostics.is_empty() {
+        items.push(DiagItem::ok(cat, "config cross-references resolve"));
+    } else {
+        let summary = format!(
+            "{errors} error(s), {warnings} warning(s) in config references — run `zeroclaw config validate`"
+        );
+        items.push(if errors > 0 {
+            DiagItem::error(cat, summary)
+        } else {
+            DiagItem::warn(cat, summary)
+        });
+    }
 }
 
 fn provider_validation_error(name: &str) -> Option<String> {
-    match crate::providers::create_provider(name, None) {
-        Ok(_) => None,
-        Err(err) => Some(
-            err.to_string()
-                .lines()
-                .next()
-                .unwrap_or("invalid provider")
-                .into(),
-        ),
-    }
+    crate::config::validate::provider_error(name)
 }
 
 fn embedding_provider_validation_error(name: &str) -> Option<String> {
-    let normalized = name.trim();
-    if normalized.eq_ignore_ascii_case("none") || normalized.eq_ignore_ascii_case("openai") {
-        return None;
-    }
-
-    let Some(url) = normalized.strip_prefix("custom:") else {
-        return Some("supported values: none, openai, custom:<url>".into());
-    };
-
-    let url = url.trim();
-    if url.is_empty() {
-        return Some("custom provider requires a non-empty URL after 'custom:'".into());
-    }
-
-    match reqwest::Url::parse(url) {
-        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => None,
-        Ok(parsed) => Some(format!(
-            "custom provider URL must use http/https, got '{}'",
-            parsed.scheme()
-        )),
-        Err(err) => Some(format!("invalid custom provider URL: {err}")),
-    }
+    crate::config::validate::embedding_provider_error(name)
 }
 
 // ── Workspace integrity ──────────────────────────────────────────
@@ -961,6 +951,8 @@ mod tests {
             provider: "groq".into(),
             model: String::new(),
             api_key: None,
+            thinking_budget: None,
+            reasoning_effort: None,
         }];
         let mut items = Vec::new();
         check_config_semantics(&config, &mut items);
@@ -1076,6 +1068,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         config.agents.insert(
@@ -1090,6 +1087,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
 
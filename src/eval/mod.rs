@@ -0,0 +1,646 @@
+//! Agent task evaluation (`zeroclaw eval`).
+//!
+//! A suite is a TOML file of scripted tasks. Each task runs against a fresh
+//! agent in a throwaway workspace; afterwards its assertions are checked and a
+//! report records tool-call traces, provider-reported tokens and latency. Reports
+//! can be saved as a baseline and compared on later runs. Pair with a replay
+//! cassette (`ZEROCLAW_PROVIDER_CASSETTE`) for offline CI.
+
+pub mod report;
+pub mod suite;
+
+pub use report::{compare, AssertionResult, EvalReport, TaskReport, ToolTrace};
+pub use suite::{Assertion, EvalSuite, EvalTask};
+
+use crate::agent::agent::Agent;
+use crate::config::Config;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use crate::providers::metered::MeteredProvider;
+use crate::providers::{self, Provider, ProviderRuntimeOptions};
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Handle `zeroclaw eval <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::EvalCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::EvalCommands::Run {
+            suite,
+            tasks,
+            output,
+            baseline,
+            save_baseline,
+            keep_workspaces,
+            workspace_root,
+        } => {
+            let (suite_def, base_dir) = EvalSuite::load(&suite)?;
+            let options = RunOptions {
+                only: tasks,
+                keep_workspaces,
+                workspace_root,
+            };
+            let report = run_suite(config, &suite_def, &base_dir, &options).await?;
+            report.print_summary();
+
+            if let Some(path) = &output {
+                report.save(path)?;
+                println!("Report written to {}", path.display());
+            }
+
+            let mut regressions = 0;
+            if let Some(path) = &baseline {
+                let previous = EvalReport::load(path)?;
+                let comparison = compare(&previous, &report);
+                println!("Against baseline {}:", path.display());
+                comparison.print();
+                regressions = comparison.regressions.len();
+            }
+
+            if let Some(path) = &save_baseline {
+                report.save(path)?;
+                println!("Baseline saved to {}", path.display());
+            }
+
+            if report.failed > 0 || regressions > 0 {
+                bail!(
+                    "Eval failed: {} task(s) failed, {regressions} regression(s) against baseline",
+                    report.failed
+                );
+            }
+            Ok(())
+        }
+        crate::EvalCommands::Compare { baseline, report } => {
+            let comparison = compare(&EvalReport::load(&baseline)?, &EvalReport::load(&report)?);
+            comparison.print();
+            if !comparison.regressions.is_empty() {
+                bail!(
+                    "{} regression(s) against baseline",
+                    comparison.regressions.len()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Options for [`run_suite`].
+#[derive(Debug, Default, Clone)]
+pub struct RunOptions {
+    /// Only run tasks with these names (all tasks when empty).
+    pub only: Vec<String>,
+    /// Leave task workspaces on disk for inspection.
+    pub keep_workspaces: bool,
+    /// Parent of the per-task workspaces; `<tmp>/zeroclaw-eval/<suite>` when
+    /// unset.
+    pub workspace_root: Option<PathBuf>,
+}
+
+/// Run every selected task of `suite` and collect a report.
+///
+/// One provider is shared across tasks (and the rubric judge) so a recording
+/// cassette captures the whole suite in a single file.
+pub async fn run_suite(
+    config: &Config,
+    suite: &EvalSuite,
+    base_dir: &Path,
+    options: &RunOptions,
+) -> Result<EvalReport> {
+    let mut config = config.clone();
+    if let Some(provider) = &suite.provider {
+        config.default_provider = Some(provider.clone());
+    }
+    if let Some(model) = &suite.model {
+        config.default_model = Some(model.clone());
+    }
+    if let Some(temperature) = suite.temperature {
+        config.default_temperature = temperature;
+    }
+
+    let selected: Vec<&EvalTask> = suite
+        .tasks
+        .iter()
+        .filter(|task| options.only.is_empty() || options.only.contains(&task.name))
+        .collect();
+    if selected.is_empty() {
+        bail!("No eval tasks match {:?}", options.only);
+    }
+
+    let provider_name = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".into());
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let provider: Arc<dyn Provider> = Arc::from(providers::create_routed_provider_with_options(
+        &provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &config.model_routes,
+        &model,
+        &ProviderRuntimeOptions {
+            auth_profile_override: None,
+            zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+            cassette: config.runtime.provider_cassette.clone(),
+        },
+    )?);
+
+    let template = suite.template.as_ref().map(|dir| base_dir.join(dir));
+    let judge_model = suite.judge_model.clone().unwrap_or_else(|| model.clone());
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    // Workspace paths end up in the system prompt and tool results, so they
+    // must be the same on every run for a strict cassette to replay.
+    let workspace_root = options.workspace_root.clone().unwrap_or_else(|| {
+        std::env::temp_dir()
+            .join("zeroclaw-eval")
+            .join(sanitize(suite.name()))
+    });
+    let mut tasks = Vec::with_capacity(selected.len());
+    for task in selected {
+        let workspace = workspace_root.join(sanitize(&task.name));
+        let run = TaskRun {
+            config: &config,
+            provider: provider.clone(),
+            task,
+            workspace: &workspace,
+            template: template.as_deref(),
+            timeout: Duration::from_secs(task.timeout_secs.unwrap_or(suite.timeout_secs)),
+            judge_model: &judge_model,
+        };
+        let result = run.execute().await;
+        if options.keep_workspaces {
+            println!("  workspace for {}: {}", task.name, workspace.display());
+        } else {
+            let _ = std::fs::remove_dir_all(&workspace);
+        }
+        tasks.push(result?);
+    }
+
+    let passed = tasks.iter().filter(|task| task.passed).count();
+    Ok(EvalReport {
+        suite: suite.name().to_string(),
+        provider: provider_name,
+        model,
+        started_at,
+        passed,
+        failed: tasks.len() - passed,
+        tasks,
+    })
+}
+
+struct TaskRun<'a> {
+    config: &'a Config,
+    provider: Arc<dyn Provider>,
+    task: &'a EvalTask,
+    workspace: &'a Path,
+    template: Option<&'a Path>,
+    timeout: Duration,
+    judge_model: &'a str,
+}
+
+impl TaskRun<'_> {
+    /// Prepare the workspace, run the agent and check assertions. Only
+    /// workspace setup errors are returned as `Err`; agent failures become a
+    /// failed task.
+    async fn execute(&self) -> Result<TaskReport> {
+        self.prepare_workspace()?;
+
+        let mut config = self.config.clone();
+        config.workspace_dir = self.workspace.to_path_buf();
+        // Keep memory inside the throwaway workspace.
+        config.memory.backend = "sqlite".into();
+        config.storage = crate::config::StorageConfig::default();
+
+        let observer = Arc::new(TraceObserver::default());
+        let metered = MeteredProvider::new(self.provider.clone());
+        let usage = metered.totals_handle();
+
+        let started = Instant::now();
+        let mut outputs = Vec::new();
+        let outcome = match Agent::from_config_with(&config, Box::new(metered), observer.clone()) {
+            Ok(mut agent) => {
+                let turns = async {
+                    for prompt in self.task.prompts() {
+                        outputs.push(agent.turn(prompt).await?);
+                    }
+                    anyhow::Ok(())
+                };
+                match tokio::time::timeout(self.timeout, turns).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::anyhow!(
+                        "Timed out after {}s",
+                        self.timeout.as_secs()
+                    )),
+                }
+            }
+            Err(error) => Err(error),
+        };
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        let trace = observer.take();
+        let usage = *usage.lock();
+        let mut report = TaskReport {
+            name: self.task.name.clone(),
+            passed: false,
+            error: outcome.err().map(|e| format!("{e:#}")),
+            outputs,
+            assertions: Vec::new(),
+            trace,
+            llm_calls: usage.calls,
+            input_tokens: usage.tokens.input_tokens,
+            output_tokens: usage.tokens.output_tokens,
+            unmetered_calls: usage.unmetered_calls,
+            latency_ms,
+        };
+        if report.error.is_none() {
+            for assertion in &self.task.assertions {
+                let result = self.check(assertion, &report).await;
+                report.assertions.push(result);
+            }
+            report.passed = report.assertions.iter().all(|result| result.passed);
+        }
+        Ok(report)
+    }
+
+    fn prepare_workspace(&self) -> Result<()> {
+        if self.workspace.exists() {
+            std::fs::remove_dir_all(self.workspace)
+                .with_context(|| format!("Failed to clear {}", self.workspace.display()))?;
+        }
+        std::fs::create_dir_all(self.workspace)
+            .with_context(|| format!("Failed to create {}", self.workspace.display()))?;
+        if let Some(template) = self.template {
+            copy_dir(template, self.workspace)
+                .with_context(|| format!("Failed to copy eval template {}", template.display()))?;
+        }
+        for (relative, content) in &self.task.files {
+            let path = self.workspace.join(relative);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    async fn check(&self, assertion: &Assertion, report: &TaskReport) -> AssertionResult {
+        let (passed, detail) = match assertion {
+            Assertion::FileExists { path } => {
+                let exists = self.workspace.join(path).exists();
+                (exists, if exists { "" } else { "missing" }.to_string())
+            }
+            Assertion::FileAbsent { path } => {
+                let exists = self.workspace.join(path).exists();
+                (!exists, if exists { "present" } else { "" }.to_string())
+            }
+            Assertion::FileMatches { path, pattern } => {
+                match std::fs::read_to_string(self.workspace.join(path)) {
+                    Ok(content) => {
+                        let matched = regex_matches(pattern, &content);
+                        (matched, if matched { "" } else { "no match" }.to_string())
+                    }
+                    Err(e) => (false, format!("cannot read: {e}")),
+                }
+            }
+            Assertion::ToolCalled { tool, min, max } => {
+                let count = report.trace.iter().filter(|t| &t.tool == tool).count();
+                let within = count >= min.unwrap_or(1) && max.map_or(true, |max| count <= max);
+                (within, format!("{count} call(s)"))
+            }
+            Assertion::ToolNotCalled { tool } => {
+                let count = report.trace.iter().filter(|t| &t.tool == tool).count();
+                (count == 0, format!("{count} call(s)"))
+            }
+            Assertion::OutputMatches { pattern } => {
+                let output = report.outputs.last().map(String::as_str).unwrap_or("");
+                let matched = regex_matches(pattern, output);
+                (matched, if matched { "" } else { "no match" }.to_string())
+            }
+            Assertion::Rubric { rubric } => match self.judge(rubric, report).await {
+                Ok(verdict) => verdict,
+                Err(e) => (false, format!("judge failed: {e}")),
+            },
+        };
+        AssertionResult {
+            assertion: assertion.describe(),
+            passed,
+            detail,
+        }
+    }
+
+    /// Ask the judge model to grade the run. The reply must start with PASS
+    /// or FAIL; the rest is kept as the detail.
+    async fn judge(&self, rubric: &str, report: &TaskReport) -> Result<(bool, String)> {
+        let tools: Vec<&str> = report.trace.iter().map(|t| t.tool.as_str()).collect();
+        let message = format!(
+            "Rubric:\n{rubric}\n\nUser prompts:\n{}\n\nTools called (in order): {}\n\nFinal reply:\n{}",
+            self.task.prompts().join("\n---\n"),
+            if tools.is_empty() {
+                "(none)".to_string()
+            } else {
+                tools.join(", ")
+            },
+            report.outputs.last().map(String::as_str).unwrap_or("")
+        );
+        let verdict = self
+            .provider
+            .chat_with_system(Some(JUDGE_SYSTEM_PROMPT), &message, self.judge_model, 0.0)
+            .await?;
+        Ok(parse_verdict(&verdict))
+    }
+}
+
+const JUDGE_SYSTEM_PROMPT: &str = "You grade an AI agent's work against a rubric. \
+Answer with PASS or FAIL on the first line, then one sentence explaining why.";
+
+fn parse_verdict(reply: &str) -> (bool, String) {
+    let trimmed = reply.trim();
+    let passed = trimmed
+        .split_whitespace()
+        .next()
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphabetic()))
+        .is_some_and(|word| word.eq_ignore_ascii_case("pass"));
+    let detail = trimmed.lines().skip(1).collect::<Vec<_>>().join(" ");
+    let detail = if detail.trim().is_empty() {
+        trimmed.to_string()
+    } else {
+        detail.trim().to_string()
+    };
+    (passed, detail)
+}
+
+fn regex_matches(pattern: &str, text: &str) -> bool {
+    // Patterns are validated when the suite loads.
+    Regex::new(pattern).is_ok_and(|re| re.is_match(text))
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Recursively copy `from` into `to`, skipping symlinks.
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = to.join(entry.file_name());
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Observer that keeps the tool-call trace of one task.
+#[derive(Default)]
+struct TraceObserver {
+    calls: Mutex<Vec<ToolTrace>>,
+}
+
+impl TraceObserver {
+    fn take(&self) -> Vec<ToolTrace> {
+        std::mem::take(&mut *self.calls.lock())
+    }
+}
+
+impl Observer for TraceObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        if let ObserverEvent::ToolCall {
+            tool,
+            duration,
+            success,
+        } = event
+        {
+            self.calls.lock().push(ToolTrace {
+                tool: tool.clone(),
+                success: *success,
+                duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            });
+        }
+    }
+
+    fn record_metric(&self, _metric: &ObserverMetric) {}
+
+    fn name(&self) -> &str {
+        "eval"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CassetteMatching, CassetteMode, ProviderCassetteConfig};
+    use crate::providers::cassette::{
+        Cassette, ExchangeKind, Interaction, RecordedCapabilities, RecordedRequest,
+        RecordedResponse,
+    };
+    use crate::providers::traits::TokenUsage;
+    use crate::providers::ToolCall;
+
+    fn interaction(kind: ExchangeKind, response: RecordedResponse) -> Interaction {
+        Interaction {
+            hash: String::new(),
+            request: RecordedRequest {
+                kind,
+                model: "m".into(),
+                temperature: 0.0,
+                messages: vec![],
+                tools: vec![],
+                response_schema: None,
+            },
+            response,
+        }
+    }
+
+    /// A cassette in which the agent writes hello.txt, then replies; the
+    /// judge then grades the run.
+    fn write_cassette(path: &Path) {
+        let cassette = Cassette {
+            capabilities: RecordedCapabilities {
+                native_tool_calling: true,
+                ..RecordedCapabilities::default()
+            },
+            interactions: vec![
+                interaction(
+                    ExchangeKind::Chat,
+                    RecordedResponse {
+                        tool_calls: vec![ToolCall {
+                            id: "call_1".into(),
+                            name: "file_write".into(),
+                            arguments: r#"{"path":"hello.txt","content":"hi there"}"#.into(),
+                        }],
+                        usage: Some(TokenUsage {
+                            input_tokens: 100,
+                            output_tokens: 20,
+                        }),
+                        ..RecordedResponse::default()
+                    },
+                ),
+                interaction(
+                    ExchangeKind::Chat,
+                    RecordedResponse {
+                        text: Some("Created hello.txt".into()),
+                        usage: Some(TokenUsage {
+                            input_tokens: 150,
+                            output_tokens: 5,
+                        }),
+                        ..RecordedResponse::default()
+                    },
+                ),
+                interaction(
+                    ExchangeKind::ChatWithSystem,
+                    RecordedResponse {
+                        text: Some("PASS\nThe file was created.".into()),
+                        ..RecordedResponse::default()
+                    },
+                ),
+            ],
+            ..Cassette::default()
+        };
+        cassette.save(path).unwrap();
+    }
+
+    fn suite() -> EvalSuite {
+        toml::from_str(
+            r#"
+name = "smoke"
+
+[[task]]
+name = "write-file"
+prompt = "Create hello.txt saying hi"
+[task.files]
+"seed.txt" = "seed"
+
+[[task.assert]]
+type = "file_matches"
+path = "hello.txt"
+pattern = "^hi"
+
+[[task.assert]]
+type = "file_exists"
+path = "seed.txt"
+
+[[task.assert]]
+type = "tool_called"
+tool = "file_write"
+max = 1
+
+[[task.assert]]
+type = "tool_not_called"
+tool = "shell"
+
+[[task.assert]]
+type = "output_matches"
+pattern = "(?i)created"
+
+[[task.assert]]
+type = "rubric"
+rubric = "The agent created the file"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_suite_replays_cassette_and_checks_assertions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cassette = tmp.path().join("smoke.cassette.json");
+        write_cassette(&cassette);
+
+        let mut config = Config::default();
+        config.config_path = tmp.path().join("config.toml");
+        config.runtime.provider_cassette = Some(ProviderCassetteConfig {
+            mode: CassetteMode::Replay,
+            path: cassette.display().to_string(),
+            matching: CassetteMatching::Lenient,
+        });
+
+        let options = RunOptions {
+            keep_workspaces: true,
+            workspace_root: Some(tmp.path().join("workspaces")),
+            ..RunOptions::default()
+        };
+        let report = run_suite(&config, &suite(), tmp.path(), &options)
+            .await
+            .unwrap();
+
+        let task = &report.tasks[0];
+        assert!(task.error.is_none(), "{:?}", task.error);
+        assert!(
+            task.passed,
+            "failed assertions: {:?}",
+            task.assertions
+                .iter()
+                .filter(|a| !a.passed)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(report.passed, 1);
+        assert_eq!(task.trace.len(), 1);
+        assert_eq!(task.trace[0].tool, "file_write");
+        assert_eq!(task.llm_calls, 2);
+        assert_eq!((task.input_tokens, task.output_tokens), (250, 25));
+        assert_eq!(task.unmetered_calls, 0);
+        assert_eq!(task.outputs, vec!["Created hello.txt".to_string()]);
+        assert!(tmp.path().join("workspaces/write-file/hello.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn agent_error_fails_task_without_checking_assertions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cassette = tmp.path().join("empty.json");
+        Cassette::default().save(&cassette).unwrap();
+
+        let mut config = Config::default();
+        config.config_path = tmp.path().join("config.toml");
+        config.runtime.provider_cassette = Some(ProviderCassetteConfig {
+            mode: CassetteMode::Replay,
+            path: cassette.display().to_string(),
+            matching: CassetteMatching::Strict,
+        });
+
+        let options = RunOptions {
+            workspace_root: Some(tmp.path().join("workspaces")),
+            ..RunOptions::default()
+        };
+        let report = run_suite(&config, &suite(), tmp.path(), &options)
+            .await
+            .unwrap();
+        assert_eq!(report.failed, 1);
+        assert!(report.tasks[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Cassette miss"));
+        assert!(report.tasks[0].assertions.is_empty());
+    }
+
+    #[test]
+    fn parse_verdict_reads_first_word() {
+        assert_eq!(
+            parse_verdict("PASS\nLooks right."),
+            (true, "Looks right.".to_string())
+        );
+        assert!(parse_verdict("**Pass** — fine").0);
+        assert!(!parse_verdict("FAIL\nNo file.").0);
+        assert!(!parse_verdict("").0);
+    }
+}
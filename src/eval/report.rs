@@ -0,0 +1,293 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Relative change in tokens or latency worth flagging.
+const DRIFT_THRESHOLD: f64 = 0.25;
+
+/// Result of one `zeroclaw eval run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub suite: String,
+    pub provider: String,
+    pub model: String,
+    pub started_at: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub tasks: Vec<TaskReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskReport {
+    pub name: String,
+    pub passed: bool,
+    /// Agent error or timeout; assertions are not checked when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Final reply of each turn.
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    #[serde(default)]
+    pub assertions: Vec<AssertionResult>,
+    /// Tool calls in execution order.
+    #[serde(default)]
+    pub trace: Vec<ToolTrace>,
+    pub llm_calls: usize,
+    /// Prompt tokens as reported by the provider.
+    #[serde(default)]
+    pub input_tokens: u64,
+    /// Completion tokens as reported by the provider.
+    #[serde(default)]
+    pub output_tokens: u64,
+    /// LLM calls whose provider reported no usage; token counts are partial
+    /// when this is non-zero.
+    #[serde(default)]
+    pub unmetered_calls: usize,
+    pub latency_ms: u64,
+}
+
+impl TaskReport {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub assertion: String,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolTrace {
+    pub tool: String,
+    pub success: bool,
+    pub duration_ms: u64,
+}
+
+impl EvalReport {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read eval report {}", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse eval report {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write eval report {}", path.display()))
+    }
+
+    pub fn print_summary(&self) {
+        println!(
+            "Suite: {} ({} tasks, {} / {})",
+            self.suite,
+            self.tasks.len(),
+            self.provider,
+            self.model
+        );
+        for task in &self.tasks {
+            let mark = if task.passed { "✅" } else { "❌" };
+            let unmetered = if task.unmetered_calls > 0 {
+                format!(" ({} calls unmetered)", task.unmetered_calls)
+            } else {
+                String::new()
+            };
+            println!(
+                "  {mark} {}  {} tool calls · {} LLM calls · {} in / {} out tokens{unmetered} · {:.1}s",
+                task.name,
+                task.trace.len(),
+                task.llm_calls,
+                task.input_tokens,
+                task.output_tokens,
+                task.latency_ms as f64 / 1000.0
+            );
+            if let Some(error) = &task.error {
+                println!("      error: {error}");
+            }
+            for result in task.assertions.iter().filter(|r| !r.passed) {
+                if result.detail.is_empty() {
+                    println!("      failed: {}", result.assertion);
+                } else {
+                    println!("      failed: {} — {}", result.assertion, result.detail);
+                }
+            }
+        }
+        println!("Summary: {}/{} passed", self.passed, self.tasks.len());
+    }
+}
+
+/// Differences between a baseline report and a new one.
+#[derive(Debug, Default)]
+pub struct Comparison {
+    /// Tasks that passed in the baseline and fail now.
+    pub regressions: Vec<String>,
+    /// Tasks that failed in the baseline and pass now.
+    pub improvements: Vec<String>,
+    /// Token, latency and tool-count drift, and tasks missing on either side.
+    pub notes: Vec<String>,
+}
+
+impl Comparison {
+    pub fn print(&self) {
+        for line in &self.regressions {
+            println!("  ⬇ regression: {line}");
+        }
+        for line in &self.improvements {
+            println!("  ⬆ improved:   {line}");
+        }
+        for line in &self.notes {
+            println!("  • {line}");
+        }
+        if self.regressions.is_empty() && self.improvements.is_empty() && self.notes.is_empty() {
+            println!("  No changes against baseline.");
+        }
+    }
+}
+
+pub fn compare(baseline: &EvalReport, current: &EvalReport) -> Comparison {
+    let mut comparison = Comparison::default();
+
+    for task in &current.tasks {
+        let Some(before) = baseline.tasks.iter().find(|b| b.name == task.name) else {
+            comparison
+                .notes
+                .push(format!("{}: new task (not in baseline)", task.name));
+            continue;
+        };
+        match (before.passed, task.passed) {
+            (true, false) => comparison.regressions.push(task.name.clone()),
+            (false, true) => comparison.improvements.push(task.name.clone()),
+            _ => {}
+        }
+        // Partial counts are not comparable.
+        if before.unmetered_calls == 0 && task.unmetered_calls == 0 {
+            if let Some(note) = drift("tokens", before.total_tokens(), task.total_tokens()) {
+                comparison.notes.push(format!("{}: {note}", task.name));
+            }
+        }
+        if let Some(note) = drift("latency ms", before.latency_ms, task.latency_ms) {
+            comparison.notes.push(format!("{}: {note}", task.name));
+        }
+        if before.trace.len() != task.trace.len() {
+            comparison.notes.push(format!(
+                "{}: tool calls {} → {}",
+                task.name,
+                before.trace.len(),
+                task.trace.len()
+            ));
+        }
+    }
+
+    for before in &baseline.tasks {
+        if !current.tasks.iter().any(|t| t.name == before.name) {
+            comparison
+                .notes
+                .push(format!("{}: in baseline but not run", before.name));
+        }
+    }
+
+    comparison
+}
+
+fn drift(label: &str, before: u64, after: u64) -> Option<String> {
+    if before == 0 {
+        return None;
+    }
+    let change = (after as f64 - before as f64) / before as f64;
+    (change.abs() >= DRIFT_THRESHOLD)
+        .then(|| format!("{label} {before} → {after} ({:+.0}%)", change * 100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(name: &str, passed: bool, tokens: u64, tools: usize) -> TaskReport {
+        TaskReport {
+            name: name.into(),
+            passed,
+            error: None,
+            outputs: vec![],
+            assertions: vec![],
+            trace: (0..tools)
+                .map(|_| ToolTrace {
+                    tool: "shell".into(),
+                    success: true,
+                    duration_ms: 1,
+                })
+                .collect(),
+            llm_calls: 1,
+            input_tokens: tokens,
+            output_tokens: 0,
+            unmetered_calls: 0,
+            latency_ms: 1000,
+        }
+    }
+
+    fn report(tasks: Vec<TaskReport>) -> EvalReport {
+        EvalReport {
+            suite: "core".into(),
+            provider: "p".into(),
+            model: "m".into(),
+            started_at: String::new(),
+            passed: tasks.iter().filter(|t| t.passed).count(),
+            failed: tasks.iter().filter(|t| !t.passed).count(),
+            tasks,
+        }
+    }
+
+    #[test]
+    fn compare_flags_regressions_improvements_and_drift() {
+        let baseline = report(vec![
+            task("a", true, 1000, 1),
+            task("b", false, 1000, 1),
+            task("gone", true, 10, 0),
+        ]);
+        let current = report(vec![
+            task("a", false, 2000, 3),
+            task("b", true, 1100, 1),
+            task("new", true, 10, 0),
+        ]);
+
+        let comparison = compare(&baseline, &current);
+        assert_eq!(comparison.regressions, vec!["a".to_string()]);
+        assert_eq!(comparison.improvements, vec!["b".to_string()]);
+        assert!(comparison
+            .notes
+            .iter()
+            .any(|n| n.contains("a: tokens 1000 → 2000 (+100%)")));
+        assert!(comparison.notes.iter().any(|n| n == "a: tool calls 1 → 3"));
+        assert!(!comparison.notes.iter().any(|n| n.starts_with("b:")));
+        assert!(comparison.notes.iter().any(|n| n.starts_with("new:")));
+        assert!(comparison.notes.iter().any(|n| n.starts_with("gone:")));
+    }
+
+    #[test]
+    fn compare_skips_token_drift_with_unmetered_calls() {
+        let baseline = report(vec![task("a", true, 1000, 1)]);
+        let mut current = report(vec![task("a", true, 2000, 1)]);
+        current.tasks[0].unmetered_calls = 1;
+
+        let comparison = compare(&baseline, &current);
+        assert!(!comparison.notes.iter().any(|n| n.contains("tokens")));
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("reports/baseline.json");
+        let original = report(vec![task("a", true, 42, 2)]);
+        original.save(&path).unwrap();
+
+        let loaded = EvalReport::load(&path).unwrap();
+        assert_eq!(loaded.tasks.len(), 1);
+        assert_eq!(loaded.tasks[0].total_tokens(), 42);
+        assert_eq!(loaded.tasks[0].trace.len(), 2);
+    }
+}
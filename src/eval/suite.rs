@@ -0,0 +1,298 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+/// An evaluation suite loaded from TOML.
+///
+/// ```toml
+/// name = "core"
+/// model = "anthropic/claude-sonnet-4"
+///
+/// [[task]]
+/// name = "write-file"
+/// prompt = "Create hello.txt containing the word hi"
+///
+/// [[task.assert]]
+/// type = "file_matches"
+/// path = "hello.txt"
+/// pattern = "hi"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvalSuite {
+    /// Suite name (defaults to the file stem).
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Provider override for every task.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model override for every task.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Temperature override for every task.
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Model used for `rubric` assertions (defaults to the task model).
+    #[serde(default)]
+    pub judge_model: Option<String>,
+    /// Directory copied into every task workspace (skills, prompt files,
+    /// fixtures). Relative to the suite file.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Default per-task timeout.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default, rename = "task")]
+    pub tasks: Vec<EvalTask>,
+}
+
+/// One scripted task.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvalTask {
+    pub name: String,
+    /// Single user message.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Multi-turn alternative to `prompt`; sent in order to the same agent.
+    #[serde(default)]
+    pub turns: Vec<String>,
+    /// Files written into the workspace before the task runs.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, rename = "assert")]
+    pub assertions: Vec<Assertion>,
+}
+
+impl EvalTask {
+    pub fn prompts(&self) -> Vec<&str> {
+        match &self.prompt {
+            Some(prompt) => vec![prompt.as_str()],
+            None => self.turns.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// A check applied after a task finishes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Assertion {
+    /// A workspace file exists.
+    FileExists { path: String },
+    /// A workspace file does not exist.
+    FileAbsent { path: String },
+    /// A workspace file's content matches a regex.
+    FileMatches { path: String, pattern: String },
+    /// A tool was called at least `min` (default 1) and at most `max` times.
+    ToolCalled {
+        tool: String,
+        #[serde(default)]
+        min: Option<usize>,
+        #[serde(default)]
+        max: Option<usize>,
+    },
+    /// A tool was never called.
+    ToolNotCalled { tool: String },
+    /// The agent's final reply matches a regex.
+    OutputMatches { pattern: String },
+    /// An LLM judge grades the run against a rubric.
+    Rubric { rubric: String },
+}
+
+impl Assertion {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::FileExists { path } => format!("file_exists {path}"),
+            Self::FileAbsent { path } => format!("file_absent {path}"),
+            Self::FileMatches { path, pattern } => format!("file_matches {path} /{pattern}/"),
+            Self::ToolCalled { tool, min, max } => match (min, max) {
+                (_, Some(max)) => {
+                    format!("tool_called {tool} ({}..={max})", min.unwrap_or(1))
+                }
+                (Some(min), None) => format!("tool_called {tool} (>= {min})"),
+                (None, None) => format!("tool_called {tool}"),
+            },
+            Self::ToolNotCalled { tool } => format!("tool_not_called {tool}"),
+            Self::OutputMatches { pattern } => format!("output_matches /{pattern}/"),
+            Self::Rubric { rubric } => {
+                let short: String = rubric.chars().take(60).collect();
+                format!("rubric \"{short}\"")
+            }
+        }
+    }
+
+    fn pattern(&self) -> Option<&str> {
+        match self {
+            Self::FileMatches { pattern, .. } | Self::OutputMatches { pattern } => Some(pattern),
+            _ => None,
+        }
+    }
+
+    fn path(&self) -> Option<&str> {
+        match self {
+            Self::FileExists { path }
+            | Self::FileAbsent { path }
+            | Self::FileMatches { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl EvalSuite {
+    /// Load and validate a suite. Returns the suite and the directory that
+    /// relative paths in it resolve against.
+    pub fn load(path: &Path) -> Result<(Self, PathBuf)> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read eval suite {}", path.display()))?;
+        let mut suite: Self = toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse eval suite {}", path.display()))?;
+        if suite.name.is_none() {
+            suite.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+        }
+        suite.validate()?;
+        let base_dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        Ok((suite, base_dir))
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("eval")
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.tasks.is_empty() {
+            bail!("Eval suite '{}' has no [[task]] entries", self.name());
+        }
+        let mut seen = HashSet::new();
+        for task in &self.tasks {
+            let name = task.name.trim();
+            if name.is_empty() {
+                bail!("Eval task name must not be empty");
+            }
+            if !seen.insert(name) {
+                bail!("Duplicate eval task name '{name}'");
+            }
+            match (&task.prompt, task.turns.is_empty()) {
+                (Some(_), false) => bail!("Task '{name}': use either prompt or turns, not both"),
+                (None, true) => bail!("Task '{name}': prompt or turns is required"),
+                _ => {}
+            }
+            for file in task.files.keys() {
+                ensure_relative(name, file)?;
+            }
+            for assertion in &task.assertions {
+                if let Some(path) = assertion.path() {
+                    ensure_relative(name, path)?;
+                }
+                if let Some(pattern) = assertion.pattern() {
+                    Regex::new(pattern)
+                        .with_context(|| format!("Task '{name}': invalid regex '{pattern}'"))?;
+                }
+                if let Assertion::ToolCalled {
+                    tool,
+                    min,
+                    max: Some(max),
+                } = assertion
+                {
+                    if min.unwrap_or(1) > *max {
+                        bail!("Task '{name}': tool_called {tool} has min greater than max");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn ensure_relative(task: &str, path: &str) -> Result<()> {
+    let candidate = Path::new(path);
+    if path.trim().is_empty()
+        || candidate.is_absolute()
+        || candidate
+            .components()
+            .any(|c| matches!(c, Component::ParentDir))
+    {
+        bail!("Task '{task}': path '{path}' must be relative to the workspace without '..'");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tasks_and_assertions() {
+        let suite: EvalSuite = toml::from_str(
+            r#"
+name = "core"
+judge_model = "judge"
+
+[[task]]
+name = "write"
+prompt = "Create hello.txt"
+[task.files]
+"notes/input.md" = "seed"
+
+[[task.assert]]
+type = "file_matches"
+path = "hello.txt"
+pattern = "hi"
+
+[[task.assert]]
+type = "tool_called"
+tool = "file_write"
+max = 2
+
+[[task]]
+name = "chat"
+turns = ["hello", "and again"]
+
+[[task.assert]]
+type = "rubric"
+rubric = "Replies politely"
+"#,
+        )
+        .unwrap();
+        suite.validate().unwrap();
+        assert_eq!(suite.tasks.len(), 2);
+        assert_eq!(suite.timeout_secs, 300);
+        assert_eq!(suite.tasks[0].files["notes/input.md"], "seed");
+        assert!(matches!(
+            suite.tasks[0].assertions[1],
+            Assertion::ToolCalled { max: Some(2), .. }
+        ));
+        assert_eq!(suite.tasks[1].prompts(), vec!["hello", "and again"]);
+    }
+
+    #[test]
+    fn validation_rejects_bad_tasks() {
+        let parse = |raw: &str| toml::from_str::<EvalSuite>(raw).unwrap().validate();
+
+        assert!(parse("").is_err());
+        assert!(parse("[[task]]\nname = \"a\"\n").is_err());
+        assert!(parse("[[task]]\nname = \"a\"\nprompt = \"x\"\nturns = [\"y\"]\n").is_err());
+        assert!(parse(
+            "[[task]]\nname = \"a\"\nprompt = \"x\"\n[[task]]\nname = \"a\"\nprompt = \"y\"\n"
+        )
+        .is_err());
+        assert!(parse(
+            "[[task]]\nname = \"a\"\nprompt = \"x\"\n[[task.assert]]\ntype = \"file_exists\"\npath = \"../etc/passwd\"\n"
+        )
+        .is_err());
+        assert!(parse(
+            "[[task]]\nname = \"a\"\nprompt = \"x\"\n[[task.assert]]\ntype = \"output_matches\"\npattern = \"(\"\n"
+        )
+        .is_err());
+    }
+}
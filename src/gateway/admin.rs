@@ -0,0 +1,451 @@
+//! Embedded admin dashboard (`GET /admin`) and the JSON admin API it uses
+//! (`/admin/api/*`), enabled by `[gateway.admin]`.
+//!
+//! The page is a single static document with no external assets; it holds no
+//! data until the operator supplies a bearer token. Every API route requires
+//! a paired bearer token, or an OIDC token whose `[gateway.rbac]` role is
+//! `gateway.admin.role`.
+
+use super::{oidc, AppState};
+use crate::approval::{self, ApprovalResponse};
+use crate::sessions::SessionStore;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
+    routing::{delete, get, post},
+    Router,
+};
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Route of the dashboard page; served without a token under OIDC.
+pub const PAGE_PATH: &str = "/admin";
+
+const DASHBOARD_HTML: &str = include_str!("admin.html");
+
+const DEFAULT_LIST_LIMIT: usize = 50;
+const MAX_LIST_LIMIT: usize = 500;
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+type ApiResult = Result<Json<serde_json::Value>, ApiError>;
+
+fn api_error(status: StatusCode, message: impl std::fmt::Display) -> ApiError {
+    (
+        status,
+        Json(serde_json::json!({ "error": message.to_string() })),
+    )
+}
+
+/// Dashboard page plus the admin API, guarded by [`require_admin`].
+pub fn router(state: &AppState) -> Router<AppState> {
+    let api = Router::new()
+        .route("/health", get(api_health))
+        .route("/channels", get(api_channels))
+        .route("/sessions", get(api_sessions))
+        .route("/sessions/{id}", get(api_session))
+        .route("/cron/jobs", get(api_cron_jobs))
+        .route("/cron/jobs/{id}/runs", get(api_cron_runs))
+        .route("/cron/jobs/{id}/pause", post(api_cron_pause))
+        .route("/cron/jobs/{id}/resume", post(api_cron_resume))
+        .route("/cron/jobs/{id}/run", post(api_cron_run))
+        .route("/memory", get(api_memory))
+        .route("/memory/{key}", delete(api_memory_forget))
+        .route("/approvals", get(api_approvals))
+        .route("/approvals/{id}", post(api_approval_decide))
+        .route("/events", get(api_events))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_admin,
+        ));
+
+    Router::new()
+        .route(PAGE_PATH, get(dashboard))
+        .nest("/admin/api", api)
+}
+
+/// GET /admin — the embedded dashboard.
+async fn dashboard() -> impl IntoResponse {
+    (
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            (header::X_FRAME_OPTIONS, "DENY"),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; script-src 'unsafe-inline'; style-src 'unsafe-inline'; \
+                 connect-src 'self'; frame-ancestors 'none'",
+            ),
+        ],
+        Html(DASHBOARD_HTML),
+    )
+}
+
+async fn require_admin(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    match authorize(&state, request.headers()) {
+        Ok(()) => next.run(request).await,
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+fn authorize(state: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    if let Some(principal) = oidc::current_principal() {
+        let role = state.rbac.resolve(&principal.claims);
+        let required = state.config.lock().gateway.admin.role.clone();
+        if role.name == required {
+            return Ok(());
+        }
+        tracing::warn!(
+            "Admin API: rejected '{}' with role '{}'",
+            principal.subject,
+            role.name
+        );
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            format!("Admin API requires the '{required}' role"),
+        ));
+    }
+    if state.oidc_service.is_some() {
+        return Err(api_error(
+            StatusCode::UNAUTHORIZED,
+            "Authorization required",
+        ));
+    }
+    // Without OIDC the paired bearer token is the only credential; an
+    // unpaired gateway would otherwise expose the API to anyone who can reach it.
+    if !state.pairing.require_pairing() {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            "Admin API requires gateway.require_pairing = true or [gateway.oidc] issuers",
+        ));
+    }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("")
+        .trim();
+    if token.is_empty() || !state.pairing.is_authenticated(token) {
+        tracing::warn!("Admin API: rejected — not paired / invalid bearer token");
+        return Err(api_error(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct ListQuery {
+    limit: Option<usize>,
+    category: Option<String>,
+    query: Option<String>,
+}
+
+impl ListQuery {
+    fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .clamp(1, MAX_LIST_LIMIT)
+    }
+}
+
+/// GET /admin/api/health — component health, as `zeroclaw status` reports it.
+async fn api_health(State(state): State<AppState>) -> ApiResult {
+    Ok(Json(serde_json::json!({
+        "paired": state.pairing.is_paired(),
+        "runtime": crate::health::snapshot(),
+    })))
+}
+
+/// GET /admin/api/channels — per-channel listener health.
+async fn api_channels() -> ApiResult {
+    let snapshot = crate::health::snapshot();
+    let channels: Vec<serde_json::Value> = snapshot
+        .components
+        .iter()
+        .filter_map(|(component, health)| {
+            let name = component.strip_prefix("channel:")?;
+            Some(serde_json::json!({
+                "name": name,
+                "status": health.status,
+                "updated_at": health.updated_at,
+                "last_ok": health.last_ok,
+                "last_error": health.last_error,
+                "restart_count": health.restart_count,
+            }))
+        })
+        .collect();
+    Ok(Json(serde_json::json!({
+        "supervisor": snapshot.components.get("channels"),
+        "channels": channels,
+    })))
+}
+
+/// GET /admin/api/sessions — recent conversations, newest first.
+async fn api_sessions(State(state): State<AppState>, Query(query): Query<ListQuery>) -> ApiResult {
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let sessions = SessionStore::new(&workspace_dir)
+        .list()
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let sessions: Vec<serde_json::Value> = sessions
+        .into_iter()
+        .take(query.limit())
+        .map(|session| {
+            serde_json::json!({
+                "id": session.id,
+                "messages": session.messages,
+                "updated": session.updated.map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect();
+    Ok(Json(serde_json::json!({ "sessions": sessions })))
+}
+
+/// GET /admin/api/sessions/{id} — the last `limit` messages of a conversation.
+async fn api_session(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ListQuery>,
+) -> ApiResult {
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let store = SessionStore::new(&workspace_dir);
+    if !store.exists(&id) {
+        return Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("Session '{id}' not found"),
+        ));
+    }
+    let messages = store
+        .load(&id)
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let skip = messages.len().saturating_sub(query.limit());
+    let total = messages.len();
+    let messages: Vec<_> = messages.into_iter().skip(skip).collect();
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "total": total,
+        "messages": messages,
+    })))
+}
+
+/// GET /admin/api/cron/jobs
+async fn api_cron_jobs(State(state): State<AppState>) -> ApiResult {
+    let config = state.config.lock().clone();
+    let jobs = crate::cron::list_jobs(&config)
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(Json(serde_json::json!({
+        "enabled": config.cron.enabled,
+        "jobs": jobs,
+    })))
+}
+
+/// GET /admin/api/cron/jobs/{id}/runs — run history, newest first.
+async fn api_cron_runs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ListQuery>,
+) -> ApiResult {
+    let config = state.config.lock().clone();
+    crate::cron::get_job(&config, &id).map_err(|e| api_error(StatusCode::NOT_FOUND, e))?;
+    let runs = crate::cron::list_runs(&config, &id, query.limit())
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(Json(serde_json::json!({ "job_id": id, "runs": runs })))
+}
+
+/// POST /admin/api/cron/jobs/{id}/pause
+async fn api_cron_pause(State(state): State<AppState>, Path(id): Path<String>) -> ApiResult {
+    let config = state.config.lock().clone();
+    let job =
+        crate::cron::pause_job(&config, &id).map_err(|e| api_error(StatusCode::NOT_FOUND, e))?;
+    Ok(Json(serde_json::json!({ "job": job })))
+}
+
+/// POST /admin/api/cron/jobs/{id}/resume
+async fn api_cron_resume(State(state): State<AppState>, Path(id): Path<String>) -> ApiResult {
+    let config = state.config.lock().clone();
+    let job =
+        crate::cron::resume_job(&config, &id).map_err(|e| api_error(StatusCode::NOT_FOUND, e))?;
+    Ok(Json(serde_json::json!({ "job": job })))
+}
+
+/// POST /admin/api/cron/jobs/{id}/run — start a run now; the result lands in
+/// the run history.
+async fn api_cron_run(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let config = state.config.lock().clone();
+    if !config.cron.enabled {
+        return Err(api_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "cron is disabled by config (cron.enabled=false)",
+        ));
+    }
+    let job =
+        crate::cron::get_job(&config, &id).map_err(|e| api_error(StatusCode::NOT_FOUND, e))?;
+    tokio::spawn(async move {
+        let (success, _, duration_ms) = crate::cron::scheduler::run_job_now(&config, &job).await;
+        tracing::info!(
+            "Admin run of cron job '{}' finished (success={success}, {duration_ms}ms)",
+            job.id
+        );
+    });
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({ "status": "started", "job_id": id })),
+    ))
+}
+
+/// GET /admin/api/memory — browse by `category`, or search with `query`.
+async fn api_memory(State(state): State<AppState>, Query(query): Query<ListQuery>) -> ApiResult {
+    let category = query
+        .category
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(crate::memory::cli::parse_category);
+    let search = query
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+
+    let entries = match search {
+        Some(search) => state
+            .mem
+            .recall(search, query.limit(), None)
+            .await
+            .map(|entries| {
+                entries
+                    .into_iter()
+                    .filter(|entry| category.as_ref().is_none_or(|c| &entry.category == c))
+                    .collect::<Vec<_>>()
+            }),
+        None => state.mem.list(category.as_ref(), None).await,
+    }
+    .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let total = entries.len();
+    let entries: Vec<serde_json::Value> = entries
+        .into_iter()
+        .take(query.limit())
+        .map(|entry| {
+            serde_json::json!({
+                "key": entry.key,
+                "content": entry.content,
+                "category": entry.category.to_string(),
+                "timestamp": entry.timestamp,
+                "session_id": entry.session_id,
+                "score": entry.score,
+            })
+        })
+        .collect();
+    Ok(Json(serde_json::json!({
+        "backend": state.mem.name(),
+        "total": total,
+        "entries": entries,
+    })))
+}
+
+/// DELETE /admin/api/memory/{key}
+async fn api_memory_forget(State(state): State<AppState>, Path(key): Path<String>) -> ApiResult {
+    let deleted = state
+        .mem
+        .forget(&key)
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !deleted {
+        return Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("No memory with key '{key}'"),
+        ));
+    }
+    tracing::info!("Admin API: forgot memory '{key}'");
+    Ok(Json(serde_json::json!({ "deleted": key })))
+}
+
+/// GET /admin/api/approvals — tool calls waiting for a decision.
+async fn api_approvals(State(state): State<AppState>) -> ApiResult {
+    let enabled = state.config.lock().gateway.admin.approvals;
+    Ok(Json(serde_json::json!({
+        "enabled": enabled,
+        "pending": approval::pending_approvals(),
+    })))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DecisionBody {
+    decision: ApprovalResponse,
+}
+
+/// POST /admin/api/approvals/{id} — `{"decision": "yes" | "no" | "always"}`.
+async fn api_approval_decide(Path(id): Path<String>, Json(body): Json<DecisionBody>) -> ApiResult {
+    if !approval::resolve_pending(&id, body.decision) {
+        return Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("Approval '{id}' is not pending (already decided or timed out)"),
+        ));
+    }
+    tracing::info!("Admin API: approval '{id}' decided: {:?}", body.decision);
+    Ok(Json(
+        serde_json::json!({ "id": id, "decision": body.decision }),
+    ))
+}
+
+/// GET /admin/api/events — server-sent observer events: the recent buffer,
+/// then live events as they are recorded.
+async fn api_events() -> impl IntoResponse {
+    let backlog = crate::observability::tail::recent();
+    let receiver = crate::observability::tail::subscribe();
+    let backlog = futures_util::stream::iter(backlog);
+    let live = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let stream =
+        futures_util::StreamExt::map(futures_util::StreamExt::chain(backlog, live), |event| {
+            Ok::<_, Infallible>(Event::default().data(event.to_string()))
+        });
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_limit_is_clamped() {
+        let query = ListQuery::default();
+        assert_eq!(query.limit(), DEFAULT_LIST_LIMIT);
+        let query = ListQuery {
+            limit: Some(0),
+            ..ListQuery::default()
+        };
+        assert_eq!(query.limit(), 1);
+        let query = ListQuery {
+            limit: Some(10_000),
+            ..ListQuery::default()
+        };
+        assert_eq!(query.limit(), MAX_LIST_LIMIT);
+    }
+
+    #[test]
+    fn dashboard_is_self_contained() {
+        assert!(DASHBOARD_HTML.contains("/admin/api/"));
+        assert!(!DASHBOARD_HTML.contains("<script src"));
+        assert!(!DASHBOARD_HTML.contains("<link"));
+    }
+}
@@ -7,9 +7,12 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+pub mod admin;
 pub mod oidc;
 
-use crate::channels::{Channel, LinqChannel, NextcloudTalkChannel, SendMessage, WhatsAppChannel};
+use crate::channels::{
+    Channel, LinqChannel, NextcloudTalkChannel, SendMessage, SlackChannel, WhatsAppChannel,
+};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::providers::{self, ChatMessage, Provider, ProviderCapabilityError};
@@ -23,7 +26,7 @@ use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::Bytes,
-    extract::{ConnectInfo, Query, State},
+    extract::{ConnectInfo, Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
     routing::{get, post},
@@ -48,7 +51,7 @@ pub struct RequestContext {
     pub business_id: Option<String>,
     /// The business IANA timezone (e.g. "America/Toronto") from the webhook body.
     pub timezone: Option<String>,
-    /// User role resolved from JWT `realm_access.roles` ("admin", "accountant", "viewer").
+    /// Name of the `[gateway.rbac]` role resolved from the JWT.
     pub user_role: Option<String>,
 }
 
@@ -94,6 +97,10 @@ fn nextcloud_talk_memory_key(msg: &crate::channels::traits::ChannelMessage) -> S
     format!("nextcloud_talk_{}_{}", msg.sender, msg.id)
 }
 
+fn slack_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("slack_{}_{}", msg.sender, msg.id)
+}
+
 fn hash_webhook_secret(value: &str) -> String {
     use sha2::{Digest, Sha256};
 
@@ -337,6 +344,10 @@ pub struct AppState {
     pub nextcloud_talk: Option<Arc<NextcloudTalkChannel>>,
     /// Nextcloud Talk webhook secret for signature verification
     pub nextcloud_talk_webhook_secret: Option<Arc<str>>,
+    /// Slack channel for Events API deliveries; set only with a signing secret
+    pub slack: Option<Arc<SlackChannel>>,
+    /// Slack signing secret for request verification (`X-Slack-Signature`)
+    pub slack_signing_secret: Option<Arc<str>>,
     /// Observability backend for metrics scraping
     pub observer: Arc<dyn crate::observability::Observer>,
     /// Tools registry for agentic tool-call loop (shell, file_read, memory, etc.)
@@ -351,6 +362,8 @@ pub struct AppState {
     pub message_timeout_secs: u64,
     /// OIDC service for JWT validation (None = pass-through mode)
     pub oidc_service: Option<oidc::OidcService>,
+    /// Role → permission map enforced for JWT-authenticated requests
+    pub rbac: Arc<crate::security::rbac::RbacPolicy>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -382,6 +395,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
             secrets_encrypt: config.secrets.encrypt,
             reasoning_enabled: config.runtime.reasoning_enabled,
+            cassette: config.runtime.provider_cassette.clone(),
         },
     )?);
     let model = config
@@ -442,6 +456,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file by exact replacement, line range or unified diff. Use when: changing part of a file without rewriting it. Don't use when: creating a new file.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -472,6 +490,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         "schedule",
         "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
     ));
+    if config.send_message.enabled {
+        tool_descs.push((
+            "send_message",
+            "Send a message to a named contact or recipient on any configured channel. Use when: notifying people proactively (e.g. post to #ops on Slack, DM on Telegram). Don't use when: replying in the current conversation.",
+        ));
+    }
     tool_descs.push((
         "pushover",
         "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
@@ -479,7 +503,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if !config.agents.is_empty() {
         tool_descs.push((
             "delegate",
-            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single prompt and returns its response.",
+            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). Pass agents or tasks to fan out concurrently; background=true returns an id to check with delegate_status/delegate_result or stop with delegate_cancel.",
         ));
     }
 
@@ -671,6 +695,39 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
             .map(Arc::from);
 
+    // Slack signing secret for Events API request verification
+    // Priority: environment variable > config file
+    let slack_signing_secret: Option<Arc<str>> = std::env::var("ZEROCLAW_SLACK_SIGNING_SECRET")
+        .ok()
+        .and_then(|secret| {
+            let secret = secret.trim();
+            (!secret.is_empty()).then(|| secret.to_owned())
+        })
+        .or_else(|| {
+            config.channels_config.slack.as_ref().and_then(|sl| {
+                sl.signing_secret
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|secret| !secret.is_empty())
+                    .map(ToOwned::to_owned)
+            })
+        })
+        .map(Arc::from);
+
+    // Slack Events API channel (requires a signing secret; unsigned events are never accepted)
+    let slack_channel: Option<Arc<SlackChannel>> = config
+        .channels_config
+        .slack
+        .as_ref()
+        .filter(|_| slack_signing_secret.is_some())
+        .map(|sl| {
+            Arc::new(SlackChannel::new(
+                sl.bot_token.clone(),
+                sl.channel_id.clone(),
+                sl.allowed_users.clone(),
+            ))
+        });
+
     // ── Pairing guard ──────────────────────────────────────
     let pairing = Arc::new(PairingGuard::new(
         config.gateway.require_pairing,
@@ -725,11 +782,17 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if linq_channel.is_some() {
         println!("  POST /linq      — Linq message webhook (iMessage/RCS/SMS)");
     }
+    if slack_channel.is_some() {
+        println!("  POST /slack/events — Slack Events API webhook");
+    }
     if nextcloud_talk_channel.is_some() {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
+    if config.gateway.admin.enabled {
+        println!("  GET  /admin     — admin dashboard (JSON API under /admin/api)");
+    }
     if let Some(code) = pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
@@ -746,18 +809,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     crate::health::mark_component_ok("gateway");
 
-    // ── OIDC (Keycloak JWT validation) ─────────────────────────
-    let oidc_service = config.gateway.keycloak_url.as_ref().map(|url| {
-        oidc::OidcService::new(
-            url,
-            &config.gateway.keycloak_realm,
-            config.gateway.keycloak_issuer_url.as_deref(),
-            reqwest::Client::new(),
-        )
-    });
-    if oidc_service.is_some() {
-        println!("  \u{1f512} OIDC:     Keycloak JWT validation enabled");
+    // ── OIDC (JWT / introspection on every route) ──────────────
+    let oidc_service = oidc::OidcService::from_config(&config.gateway, reqwest::Client::new());
+    if let Some(oidc_svc) = &oidc_service {
+        println!(
+            "  \u{1f512} OIDC:     {} (public: {})",
+            oidc_svc.issuer_urls().join(", "),
+            config.gateway.oidc.public_paths.join(", ")
+        );
     }
+    let rbac = Arc::new(crate::security::rbac::RbacPolicy::for_config(&config));
 
     // Build shared state
     let observer: Arc<dyn crate::observability::Observer> =
@@ -781,6 +842,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         linq_signing_secret,
         nextcloud_talk: nextcloud_talk_channel,
         nextcloud_talk_webhook_secret,
+        slack: slack_channel,
+        slack_signing_secret,
         observer,
         tools_registry,
         system_prompt,
@@ -788,12 +851,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         multimodal,
         message_timeout_secs,
         oidc_service,
+        rbac,
     };
 
     // Build router with middleware
     // /webhook gets a higher body limit (20MB) for multipart file uploads;
     // all other routes keep the default 64KB limit.
-    let app = Router::new()
+    let mut routes = Router::new()
         .route("/health", get(handle_health))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
@@ -806,6 +870,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/slack/events", post(handle_slack_events))
+        .route("/hooks/{name}", post(handle_hook));
+    if config.gateway.admin.enabled {
+        routes = routes.merge(admin::router(&state));
+    }
+    let app = routes
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_oidc_auth,
+        ))
         .with_state(state)
         .layer(axum::extract::DefaultBodyLimit::max(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -827,6 +901,55 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
 
+/// Authenticate every non-public route when `[gateway.oidc]` is configured
+/// and run the handler with the caller as the current principal.
+async fn require_oidc_auth(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let Some(oidc_svc) = state.oidc_service.as_ref() else {
+        return next.run(request).await;
+    };
+    // The dashboard page is static; its API calls carry the token.
+    let path = request.uri().path();
+    if oidc_svc.is_public_path(path) || path == admin::PAGE_PATH {
+        return next.run(request).await;
+    }
+
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+    let Some(token) = token else {
+        tracing::warn!("OIDC enabled but no Bearer token provided");
+        let err = serde_json::json!({"error": "Authorization required"});
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            Json(err),
+        )
+            .into_response();
+    };
+
+    match oidc_svc.authenticate(token).await {
+        Ok(principal) => oidc::with_principal(principal, next.run(request)).await,
+        Err(e) => {
+            tracing::warn!("Token validation failed: {e:#}");
+            let err = serde_json::json!({"error": "Unauthorized \u{2014} invalid token"});
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer error=\"invalid_token\"")],
+                Json(err),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET /health — always public (no secrets leaked)
 async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     let body = serde_json::json!({
@@ -995,7 +1118,8 @@ async fn run_gateway_chat_with_multimodal(
 /// with all security layers active (SecurityPolicy, env_clear, credential
 /// scrubbing, response sanitization), and returns the cleaned response.
 ///
-/// Security: `approval: None` means no interactive approval is possible;
+/// Security: unless `[gateway.admin].approvals` queues approval-gated calls
+/// for the admin dashboard, no interactive approval is possible;
 /// tools execute if they pass SecurityPolicy validation (command allowlist,
 /// path checks, rate limits). The request is already authenticated via
 /// pairing + webhook secret before reaching this function.
@@ -1022,31 +1146,11 @@ async fn run_gateway_agentic(
     let mut history = Vec::with_capacity(3);
     history.push(ChatMessage::system((*state.system_prompt).clone()));
 
-    // Inject user role context if available (from JWT via REQUEST_CTX)
-    let _ = REQUEST_CTX.try_with(|ctx| {
-        if let Some(ref role) = ctx.user_role {
-            let role_msg = match role.as_str() {
-                "viewer" => format!(
-                    "[System: The current user has role '{}'. They can only view data (GET requests). \
-                     Do NOT attempt any write operations (POST/PATCH/PUT/DELETE). \
-                     If they ask to create, update, or delete anything, politely explain that their \
-                     viewer role does not allow modifications.]",
-                    role
-                ),
-                "accountant" => format!(
-                    "[System: The current user has role '{}'. They can view data and perform \
-                     standard bookkeeping (create/edit transactions, invoices, contacts, etc.) \
-                     but cannot perform admin operations (QuickBooks import, business setup).]",
-                    role
-                ),
-                _ => format!(
-                    "[System: The current user has role '{}'. All operations are available.]",
-                    role
-                ),
-            };
-            history.push(ChatMessage::system(role_msg));
-        }
-    });
+    // Describe the resolved role from its configured permissions, so the
+    // model is told what the tool loop will actually allow.
+    if let Some(role) = crate::security::rbac::current() {
+        history.push(ChatMessage::system(role.permissions.prompt_note()));
+    }
 
     history.extend(user_messages);
 
@@ -1058,24 +1162,27 @@ async fn run_gateway_agentic(
     };
 
     // ── Run tool-call loop with timeout ──
+    let approval = crate::approval::ApprovalManager::for_dashboard(&state.config.lock());
+    let tool_loop = run_tool_call_loop(
+        state.provider.as_ref(),
+        &mut history,
+        state.tools_registry.as_ref(),
+        state.observer.as_ref(),
+        provider_label,
+        &state.model,
+        state.temperature,
+        true,       // silent — no CLI output
+        approval.as_ref(), // queued for the admin dashboard when enabled
+        "gateway",  // channel_name for logging/metrics
+        &state.multimodal,
+        state.max_tool_iterations,
+        None,       // cancellation_token: not needed for sync HTTP
+        None,       // on_delta: no streaming for webhook responses
+    );
+    // Webhook requests are stateless: each one is its own tool session.
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout_budget_secs),
-        run_tool_call_loop(
-            state.provider.as_ref(),
-            &mut history,
-            state.tools_registry.as_ref(),
-            state.observer.as_ref(),
-            provider_label,
-            &state.model,
-            state.temperature,
-            true,       // silent — no CLI output
-            None,       // approval: None — auto-deny tools needing approval
-            "gateway",  // channel_name for logging/metrics
-            &state.multimodal,
-            state.max_tool_iterations,
-            None,       // cancellation_token: not needed for sync HTTP
-            None,       // on_delta: no streaming for webhook responses
-        ),
+        crate::sessions::scope(format!("gateway-{}", Uuid::new_v4()), tool_loop),
     )
     .await;
 
@@ -1254,26 +1361,15 @@ pub struct WebhookBody {
     pub timezone: Option<String>,
 }
 
-/// POST /webhook — main webhook endpoint (accepts JSON or multipart/form-data)
-async fn handle_webhook(
-    State(state): State<AppState>,
-    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
-    body: Bytes,
-) -> impl IntoResponse {
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
-    if !state.rate_limiter.allow_webhook(&rate_key) {
-        tracing::warn!("/webhook rate limit exceeded");
-        let err = serde_json::json!({
-            "error": "Too many webhook requests. Please retry later.",
-            "retry_after": RATE_LIMIT_WINDOW_SECS,
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
-    }
-
+/// Pairing bearer token plus optional `X-Webhook-Secret`, shared by
+/// `/webhook` and `/hooks/<name>`.
+fn authorize_webhook_request(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
     // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
+    // An OIDC-authenticated caller already presented a verified bearer token.
+    if state.pairing.require_pairing() && oidc::current_principal().is_none() {
         let auth = headers
             .get(header::AUTHORIZATION)
             .and_then(|v| v.to_str().ok())
@@ -1284,7 +1380,7 @@ async fn handle_webhook(
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
             });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+            return Err((StatusCode::UNAUTHORIZED, Json(err)));
         }
     }
 
@@ -1301,11 +1397,111 @@ async fn handle_webhook(
             _ => {
                 tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
                 let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
-                return (StatusCode::UNAUTHORIZED, Json(err));
+                return Err((StatusCode::UNAUTHORIZED, Json(err)));
             }
         }
     }
 
+    Ok(())
+}
+
+/// POST /hooks/<name> — fire the cron jobs bound to a named hook.
+///
+/// The raw request body is handed to each job as its trigger payload.
+async fn handle_hook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/hooks/{name} rate limit exceeded");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    // Hooks start cron jobs, so they are never served without some form of
+    // authentication, even when /webhook itself is left open.
+    if !state.pairing.require_pairing()
+        && state.webhook_secret_hash.is_none()
+        && oidc::current_principal().is_none()
+    {
+        tracing::warn!("/hooks/{name} rejected — no pairing or webhook secret configured");
+        let err = serde_json::json!({
+            "error": "Hooks require gateway pairing or a webhook secret (channels_config.webhook.secret)"
+        });
+        return (StatusCode::FORBIDDEN, Json(err));
+    }
+
+    if let Err(rejection) = authorize_webhook_request(&state, &headers) {
+        return rejection;
+    }
+
+    let config = state.config.lock().clone();
+    if !config.cron.enabled {
+        let err = serde_json::json!({"error": "cron is disabled by config (cron.enabled=false)"});
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(err));
+    }
+
+    let jobs = match crate::cron::triggers::jobs_for_webhook(&config, &name) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Failed to load cron jobs for hook '{name}': {e}");
+            let err = serde_json::json!({"error": "Failed to load hook jobs"});
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(err));
+        }
+    };
+    if jobs.is_empty() {
+        let err = serde_json::json!({"error": format!("No enabled job is bound to hook '{name}'")});
+        return (StatusCode::NOT_FOUND, Json(err));
+    }
+
+    let payload = String::from_utf8_lossy(&body).into_owned();
+    let job_ids: Vec<String> = jobs.iter().map(|job| job.id.clone()).collect();
+    for job in jobs {
+        crate::cron::scheduler::spawn_triggered_job(
+            config.clone(),
+            job,
+            crate::cron::TriggerEvent {
+                kind: crate::cron::TriggerKind::Webhook,
+                source: name.clone(),
+                payload: payload.clone(),
+            },
+        );
+    }
+
+    let body = serde_json::json!({"status": "accepted", "hook": name, "jobs": job_ids});
+    (StatusCode::ACCEPTED, Json(body))
+}
+
+/// POST /webhook — main webhook endpoint (accepts JSON or multipart/form-data)
+async fn handle_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/webhook rate limit exceeded");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    if let Err(rejection) = authorize_webhook_request(&state, &headers) {
+        return rejection;
+    }
+
     // ── Parse body (JSON or multipart) ──
     let content_type = headers
         .get(header::CONTENT_TYPE)
@@ -1391,9 +1587,9 @@ async fn handle_webhook(
         }
     }
 
-    // ── JWT extraction + OIDC validation ──
+    // ── JWT extraction + OIDC principal ──
     // Extract the Bearer token from the Authorization header, filtering out
-    // pairing tokens (those are ZeroClaw-issued, not Keycloak JWTs).
+    // pairing tokens (those are ZeroClaw-issued, not IdP tokens).
     let bearer_jwt = headers
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
@@ -1401,38 +1597,27 @@ async fn handle_webhook(
         .filter(|t| !t.is_empty() && (!state.pairing.require_pairing() || !state.pairing.is_authenticated(t)))
         .map(String::from);
 
-    // When OIDC is configured, validate the JWT and extract claims.
-    // business_id from JWT claims takes precedence over request body (prevents spoofing).
-    let (validated_business_id, user_role) = if let Some(ref oidc_svc) = &state.oidc_service {
-        // OIDC configured — JWT is REQUIRED
-        let token = match &bearer_jwt {
-            Some(t) => t,
-            None => {
-                tracing::warn!("OIDC enabled but no Bearer token provided");
-                let err = serde_json::json!({"error": "Authorization required"});
-                return (StatusCode::UNAUTHORIZED, Json(err));
-            }
+    // When OIDC is configured, the auth middleware has already validated the
+    // token. business_id from the tenant claim takes precedence over the
+    // request body (prevents spoofing).
+    let (validated_business_id, role_scope) = if state.oidc_service.is_some() {
+        let Some(principal) = oidc::current_principal() else {
+            tracing::warn!("OIDC enabled but request reached /webhook unauthenticated");
+            let err = serde_json::json!({"error": "Authorization required"});
+            return (StatusCode::UNAUTHORIZED, Json(err));
         };
-        match oidc_svc.validate_token(token).await {
-            Ok(claims) => {
-                let role = oidc::resolve_role(&claims);
-                match resolve_business_id(claims.business_id, business_id.clone(), true) {
-                    Ok(bid) => (bid, Some(role)),
-                    Err(reason) => {
-                        tracing::warn!(
-                            "Tenant isolation: JWT missing business_id claim but request body \
-                             provided one — rejecting to prevent cross-tenant access"
-                        );
-                        let err = serde_json::json!({
-                            "error": format!("Unauthorized \u{2014} {reason}")
-                        });
-                        return (StatusCode::UNAUTHORIZED, Json(err));
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::warn!("JWT validation failed: {e:#}");
-                let err = serde_json::json!({"error": "Unauthorized \u{2014} invalid JWT token"});
+        let role = state.rbac.resolve(&principal.claims);
+        let scope = state.rbac.scope_for(role, Some(principal.subject.clone()));
+        match resolve_business_id(principal.tenant.clone(), business_id.clone(), true) {
+            Ok(bid) => (bid, Some(scope)),
+            Err(reason) => {
+                tracing::warn!(
+                    "Tenant isolation: token missing tenant claim but request body \
+                     provided business_id — rejecting to prevent cross-tenant access"
+                );
+                let err = serde_json::json!({
+                    "error": format!("Unauthorized \u{2014} {reason}")
+                });
                 return (StatusCode::UNAUTHORIZED, Json(err));
             }
         }
@@ -1445,7 +1630,9 @@ async fn handle_webhook(
         jwt_token: bearer_jwt,
         business_id: validated_business_id,
         timezone: timezone.clone(),
-        user_role,
+        user_role: role_scope
+            .as_ref()
+            .map(|scope| scope.permissions.name.clone()),
     };
 
     if state.auto_save {
@@ -1481,9 +1668,15 @@ async fn handle_webhook(
 
     // Run the agentic loop inside a task-local scope so ShellTool can
     // read the request context without process-global env var mutation.
-    let result = REQUEST_CTX
-        .scope(request_ctx, run_gateway_agentic(&state, &provider_label, &message))
-        .await;
+    // The role scope makes SecurityPolicy and the tool loop enforce RBAC.
+    let agentic = REQUEST_CTX.scope(
+        request_ctx,
+        run_gateway_agentic(&state, &provider_label, &message),
+    );
+    let result = match role_scope {
+        Some(scope) => crate::security::rbac::scope(scope, agentic).await,
+        None => agentic.await,
+    };
 
     match result {
         Ok(response) => {
@@ -1940,6 +2133,132 @@ async fn handle_nextcloud_talk_webhook(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// POST /slack/events — Slack Events API webhook
+///
+/// Answers the `url_verification` handshake, acknowledges retries without
+/// reprocessing them, and replies to messages in the background so Slack
+/// gets its acknowledgement within the 3-second deadline.
+async fn handle_slack_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let (Some(slack), Some(signing_secret)) = (state.slack.clone(), &state.slack_signing_secret)
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Slack Events API not configured"})),
+        );
+    };
+
+    let body_str = String::from_utf8_lossy(&body);
+
+    // ── Security: every Slack request must carry a valid signature ──
+    let timestamp = headers
+        .get("X-Slack-Request-Timestamp")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let signature = headers
+        .get("X-Slack-Signature")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !crate::channels::slack::verify_slack_signature(
+        signing_secret,
+        timestamp,
+        &body_str,
+        signature,
+    ) {
+        tracing::warn!(
+            "Slack webhook signature verification failed (signature: {})",
+            if signature.is_empty() {
+                "missing"
+            } else {
+                "invalid"
+            }
+        );
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid signature"})),
+        );
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    if payload.get("type").and_then(|t| t.as_str()) == Some("url_verification") {
+        let challenge = payload
+            .get("challenge")
+            .and_then(|c| c.as_str())
+            .unwrap_or_default();
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({"challenge": challenge})),
+        );
+    }
+
+    // Slack redelivers when the first attempt was slow; the original is already being handled.
+    if headers.contains_key("X-Slack-Retry-Num") {
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    }
+
+    let Some(event) = payload.get("event") else {
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    };
+    let bot_user_id = slack.bot_user_id().await;
+    let Some(msg) = slack.parse_event(event, &bot_user_id) else {
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    };
+
+    tokio::spawn(async move {
+        tracing::info!(
+            "Slack message from {}: {}",
+            msg.sender,
+            truncate_with_ellipsis(&msg.content, 50)
+        );
+
+        // The daemon's channel pipeline owns Slack conversations when it runs
+        // in this process; answer directly only for a standalone gateway.
+        let Err(msg) = crate::channels::dispatch_inbound(msg).await else {
+            return;
+        };
+
+        if state.auto_save {
+            let key = slack_memory_key(&msg);
+            let _ = state
+                .mem
+                .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                .await;
+        }
+
+        let provider_label = state
+            .config
+            .lock()
+            .default_provider
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let reply =
+            match run_gateway_chat_with_multimodal(&state, &provider_label, &msg.content).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!("LLM error for Slack message: {e:#}");
+                    "Sorry, I couldn't process your message right now.".to_string()
+                }
+            };
+        if let Err(e) = slack
+            .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await
+        {
+            tracing::error!("Failed to send Slack reply: {e}");
+        }
+    });
+
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1965,6 +2284,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn security_timeout_accommodates_tool_loop() {
         // HTTP-level timeout must exceed the maximum tool-call budget
         // to avoid premature termination of agentic requests.
@@ -2019,6 +2339,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2026,6 +2348,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2070,6 +2393,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer,
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2077,6 +2402,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2087,6 +2413,233 @@ mod tests {
         assert!(text.contains("zeroclaw_heartbeat_ticks_total 1"));
     }
 
+    #[tokio::test]
+    async fn oidc_layer_guards_private_routes_and_skips_public_paths() {
+        async fn introspect(body: String) -> axum::Json<serde_json::Value> {
+            if body.contains("token=good-token") {
+                axum::Json(serde_json::json!({
+                    "active": true,
+                    "sub": "svc-reporting",
+                    "iss": "https://idp.example.com",
+                    "aud": "zeroclaw"
+                }))
+            } else {
+                axum::Json(serde_json::json!({ "active": false }))
+            }
+        }
+
+        let idp = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let idp_addr = idp.local_addr().unwrap();
+        tokio::spawn(async move {
+            let app = Router::new().route("/introspect", post(introspect));
+            axum::serve(idp, app).await.unwrap();
+        });
+
+        let mut gateway_config = crate::config::GatewayConfig::default();
+        gateway_config.oidc.issuers = vec![crate::config::OidcIssuerConfig {
+            issuer: "https://idp.example.com".into(),
+            audiences: vec!["zeroclaw".into()],
+            introspection: Some(crate::config::OidcIntrospectionConfig {
+                endpoint: Some(format!("http://{idp_addr}/introspect")),
+                client_id: "gateway".into(),
+                client_secret: "s3cret".into(),
+            }),
+            ..Default::default()
+        }];
+        let oidc_service = oidc::OidcService::from_config(&gateway_config, reqwest::Client::new());
+        assert!(oidc_service.is_some());
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            system_prompt: Arc::new(String::new()),
+            max_tool_iterations: 10,
+            multimodal: crate::config::MultimodalConfig::default(),
+            message_timeout_secs: 300,
+            oidc_service,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
+        };
+
+        let app = Router::new()
+            .route("/health", get(handle_health))
+            .route("/metrics", get(handle_metrics))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_oidc_auth,
+            ))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let client = reqwest::Client::new();
+        let health = client
+            .get(format!("http://{addr}/health"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(health.status(), reqwest::StatusCode::OK);
+
+        let anonymous = client
+            .get(format!("http://{addr}/metrics"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+        assert!(anonymous.headers().contains_key("www-authenticate"));
+
+        let rejected = client
+            .get(format!("http://{addr}/metrics"))
+            .bearer_auth("bad-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(rejected.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let accepted = client
+            .get(format!("http://{addr}/metrics"))
+            .bearer_auth("good-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn admin_api_requires_a_paired_token_and_serves_dashboard_data() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("admin_test_key", "prefers tea", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.gateway.admin.enabled = true;
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(mem),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &["zc_admin_test".to_string()])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            system_prompt: Arc::new(String::new()),
+            max_tool_iterations: 10,
+            multimodal: crate::config::MultimodalConfig::default(),
+            message_timeout_secs: 300,
+            oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
+        };
+
+        let app = Router::new().merge(admin::router(&state)).with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        let client = reqwest::Client::new();
+        let url = |path: &str| format!("http://{addr}{path}");
+
+        let page = client.get(url("/admin")).send().await.unwrap();
+        assert_eq!(page.status(), reqwest::StatusCode::OK);
+        assert!(page.text().await.unwrap().contains("ZeroClaw admin"));
+
+        let anonymous = client.get(url("/admin/api/health")).send().await.unwrap();
+        assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let health: serde_json::Value = client
+            .get(url("/admin/api/health"))
+            .bearer_auth("zc_admin_test")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(health["paired"], true);
+        assert!(health["runtime"]["components"].is_object());
+
+        let memory: serde_json::Value = client
+            .get(url("/admin/api/memory?query=tea"))
+            .bearer_auth("zc_admin_test")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(memory["entries"][0]["key"], "admin_test_key");
+        assert_eq!(memory["entries"][0]["category"], "core");
+
+        let forget = client
+            .delete(url("/admin/api/memory/admin_test_key"))
+            .bearer_auth("zc_admin_test")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(forget.status(), reqwest::StatusCode::OK);
+        let again = client
+            .delete(url("/admin/api/memory/admin_test_key"))
+            .bearer_auth("zc_admin_test")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(again.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let unknown_job = client
+            .get(url("/admin/api/cron/jobs/missing/runs"))
+            .bearer_auth("zc_admin_test")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unknown_job.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let decide = client
+            .post(url("/admin/api/approvals/not-pending"))
+            .bearer_auth("zc_admin_test")
+            .json(&serde_json::json!({"decision": "yes"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(decide.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+
     #[test]
     fn gateway_rate_limiter_blocks_after_limit() {
         let limiter = GatewayRateLimiter::new(2, 2, 100);
@@ -2438,6 +2991,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2445,6 +3000,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -2474,6 +3030,98 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn hook_endpoint_reports_unknown_and_accepts_bound_hooks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let job = crate::cron::add_shell_job(
+            &config,
+            None,
+            crate::cron::Schedule::Webhook {
+                name: "deploy".into(),
+            },
+            "echo deployed",
+        )
+        .unwrap();
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            system_prompt: Arc::new(String::new()),
+            max_tool_iterations: 10,
+            multimodal: crate::config::MultimodalConfig::default(),
+            message_timeout_secs: 300,
+            oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
+        };
+
+        let unauthenticated = handle_hook(
+            State(state.clone()),
+            test_connect_info(),
+            Path("deploy".into()),
+            HeaderMap::new(),
+            Bytes::new(),
+        )
+        .await
+        .into_response();
+        assert_eq!(unauthenticated.status(), StatusCode::FORBIDDEN);
+
+        let mut state = state;
+        state.webhook_secret_hash = Some(Arc::from(hash_webhook_secret("hook-secret")));
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Webhook-Secret", HeaderValue::from_static("hook-secret"));
+
+        let missing = handle_hook(
+            State(state.clone()),
+            test_connect_info(),
+            Path("unknown".into()),
+            headers.clone(),
+            Bytes::new(),
+        )
+        .await
+        .into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let accepted = handle_hook(
+            State(state),
+            test_connect_info(),
+            Path("deploy".into()),
+            headers,
+            Bytes::from_static(b"{\"ref\":\"main\"}"),
+        )
+        .await
+        .into_response();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        let payload = accepted.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["hook"], "deploy");
+        assert_eq!(parsed["jobs"][0], job.id);
+    }
+
     #[tokio::test]
     async fn webhook_autosave_stores_distinct_keys_per_request() {
         let provider_impl = Arc::new(MockProvider::default());
@@ -2500,6 +3148,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2507,6 +3157,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let headers = HeaderMap::new();
@@ -2574,6 +3225,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2581,6 +3234,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let response = handle_webhook(
@@ -2622,6 +3276,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2629,6 +3285,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -2675,6 +3332,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2682,6 +3341,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -2733,6 +3393,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2740,6 +3402,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -2789,6 +3452,8 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: Some(channel),
             nextcloud_talk_webhook_secret: Some(Arc::from(secret)),
+            slack: None,
+            slack_signing_secret: None,
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             system_prompt: Arc::new(String::new()),
@@ -2796,6 +3461,7 @@ mod tests {
             multimodal: crate::config::MultimodalConfig::default(),
             message_timeout_secs: 300,
             oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -2815,6 +3481,86 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn slack_events_verify_signature_and_answer_url_verification() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+        let secret = generate_test_secret();
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: Some(Arc::new(SlackChannel::new(
+                "xoxb-fake".into(),
+                None,
+                vec!["*".into()],
+            ))),
+            slack_signing_secret: Some(Arc::from(secret.as_str())),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            system_prompt: Arc::new(String::new()),
+            max_tool_iterations: 10,
+            multimodal: crate::config::MultimodalConfig::default(),
+            message_timeout_secs: 300,
+            oidc_service: None,
+            rbac: Arc::new(crate::security::rbac::RbacPolicy::default()),
+        };
+
+        let body = r#"{"type":"url_verification","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"}"#;
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = {
+            use hmac::{Hmac, Mac};
+            use sha2::Sha256;
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(format!("v0:{timestamp}:{body}").as_bytes());
+            format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Slack-Request-Timestamp",
+            HeaderValue::from_str(&timestamp).unwrap(),
+        );
+        headers.insert("X-Slack-Signature", HeaderValue::from_static("v0=deadbeef"));
+        let response =
+            handle_slack_events(State(state.clone()), headers.clone(), Bytes::from(body))
+                .await
+                .into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        headers.insert(
+            "X-Slack-Signature",
+            HeaderValue::from_str(&signature).unwrap(),
+        );
+        let response = handle_slack_events(State(state), headers, Bytes::from(body))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(
+            parsed["challenge"],
+            "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"
+        );
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
+    }
+
     // ══════════════════════════════════════════════════════════
     // WhatsApp Signature Verification Tests (CWE-345 Prevention)
     // ══════════════════════════════════════════════════════════
@@ -3060,59 +3806,29 @@ mod tests {
     // ── Role context injection tests ──────────────────────────────────────
 
     /// Helper: build a chat history the same way `run_gateway_agentic` does,
-    /// within a REQUEST_CTX scope that has the given `user_role`.
+    /// with the given RBAC role active.
     async fn build_history_with_role(
         role: Option<&str>,
     ) -> Vec<crate::providers::traits::ChatMessage> {
         use crate::providers::traits::ChatMessage;
+        use crate::security::rbac;
 
-        let system_prompt = "You are a test assistant.".to_string();
-        let user_msg = "hello";
-        let role_owned = role.map(String::from);
-
-        let ctx = RequestContext {
-            jwt_token: None,
-            business_id: None,
-            timezone: None,
-            user_role: role_owned,
+        let build = async {
+            let mut history = vec![ChatMessage::system("You are a test assistant.")];
+            if let Some(role) = rbac::current() {
+                history.push(ChatMessage::system(role.permissions.prompt_note()));
+            }
+            history.push(ChatMessage::user("hello"));
+            history
         };
-
-        REQUEST_CTX
-            .scope(ctx, async {
-                let user_messages = vec![ChatMessage::user(user_msg)];
-                let mut history = Vec::with_capacity(3);
-                history.push(ChatMessage::system(system_prompt));
-
-                // Replicate the role injection logic from run_gateway_agentic
-                let _ = REQUEST_CTX.try_with(|ctx| {
-                    if let Some(ref role) = ctx.user_role {
-                        let role_msg = match role.as_str() {
-                            "viewer" => format!(
-                                "[System: The current user has role '{}'. They can only view data (GET requests). \
-                                 Do NOT attempt any write operations (POST/PATCH/PUT/DELETE). \
-                                 If they ask to create, update, or delete anything, politely explain that their \
-                                 viewer role does not allow modifications.]",
-                                role
-                            ),
-                            "accountant" => format!(
-                                "[System: The current user has role '{}'. They can view data and perform \
-                                 standard bookkeeping (create/edit transactions, invoices, contacts, etc.) \
-                                 but cannot perform admin operations (QuickBooks import, business setup).]",
-                                role
-                            ),
-                            _ => format!(
-                                "[System: The current user has role '{}'. All operations are available.]",
-                                role
-                            ),
-                        };
-                        history.push(ChatMessage::system(role_msg));
-                    }
-                });
-
-                history.extend(user_messages);
-                history
-            })
-            .await
+        match role {
+            Some(name) => {
+                let policy = rbac::RbacPolicy::default();
+                let scope = policy.scope_for(policy.role_named(name), None);
+                rbac::scope(scope, build).await
+            }
+            None => build.await,
+        }
     }
 
     #[tokio::test]
@@ -3123,18 +3839,17 @@ mod tests {
         assert_eq!(history[0].role, "system");
         assert_eq!(history[1].role, "system");
         assert!(history[1].content.contains("viewer"));
-        assert!(history[1].content.contains("Do NOT attempt any write operations"));
+        assert!(history[1].content.contains("does not allow it"));
         assert_eq!(history[2].role, "user");
     }
 
     #[tokio::test]
-    async fn role_injection_accountant_adds_bookkeeping_message() {
+    async fn role_injection_accountant_lists_its_limits() {
         let history = build_history_with_role(Some("accountant")).await;
         assert_eq!(history.len(), 3);
         assert_eq!(history[1].role, "system");
         assert!(history[1].content.contains("accountant"));
-        assert!(history[1].content.contains("standard bookkeeping"));
-        assert!(history[1].content.contains("cannot perform admin operations"));
+        assert!(!history[1].content.contains("read-only"));
     }
 
     #[tokio::test]
@@ -1,160 +1,446 @@
-//! JWKS-based JWT validation for Keycloak OIDC tokens.
+//! Standards-based OIDC/JWT authentication for the gateway.
 //!
-//! Adapted from LedgerForge `src/services/oidc.rs` for the ZeroClaw gateway.
-//! Validates RS256 tokens, extracts claims (sub, email, business_id, roles),
-//! and caches JWKS keys with rate-limited refresh.
+//! Originally a Keycloak-only validator adapted from LedgerForge
+//! `src/services/oidc.rs`; now any OpenID Connect provider (Auth0, Okta,
+//! Google, Dex, Keycloak) can be trusted through `[gateway.oidc]`:
+//!
+//! - JWTs are matched to a configured issuer by their `iss` claim and verified
+//!   against that issuer's JWKS, found through
+//!   `.well-known/openid-configuration` discovery and cached with rate-limited
+//!   refresh. Audiences are always checked unless the issuer sets
+//!   `allow_any_audience`.
+//! - Opaque tokens are checked with RFC 7662 introspection; active results
+//!   are cached until the token expires.
+//! - The verified claims become a [`Principal`] whose user id and tenant come
+//!   from configurable claim paths. Roles are resolved from the raw claims by
+//!   [`crate::security::rbac::RbacPolicy`].
+//!
+//! The gateway runs every non-public route inside [`with_principal`], so
+//! handlers read the caller through [`current_principal`].
 
+use crate::config::{GatewayConfig, OidcIssuerConfig};
+use crate::security::rbac::claim_values;
 use anyhow::{bail, Context, Result};
+use base64::Engine;
 use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use parking_lot::Mutex;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 /// Minimum interval between JWKS refreshes (5 minutes).
 const JWKS_REFRESH_INTERVAL_SECS: u64 = 300;
 
-/// OIDC token validation service.
-/// Fetches JWKS from Keycloak and validates RS256 tokens.
-///
-/// The JWKS cache is shared across clones via `Arc`, so Axum's
-/// per-request state cloning does not discard cached keys.
+/// Longest an active introspection result is reused, so a token revoked at
+/// the IdP stops working within this delay even if its `exp` is far off.
+const INTROSPECTION_CACHE_MAX_SECS: u64 = 60;
+
+/// Upper bound on cached introspection results per issuer.
+const INTROSPECTION_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/// Algorithms accepted when an issuer does not list its own. Symmetric (`HS*`)
+/// algorithms are never accepted: the gateway holds no shared secret.
+const DEFAULT_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+tokio::task_local! {
+    static PRINCIPAL: Arc<Principal>;
+}
+
+/// The authenticated caller of a gateway request.
 #[derive(Debug, Clone)]
-pub struct OidcService {
-    jwks_url: String,
-    issuer: String,
-    cached_jwks: Arc<RwLock<Option<JwkSet>>>,
-    last_refresh: Arc<RwLock<Instant>>,
-    http: reqwest::Client,
+pub struct Principal {
+    /// User id from `[gateway.oidc].user_claim`.
+    pub subject: String,
+    /// Tenant (`business_id`) from `[gateway.oidc].tenant_claim`.
+    pub tenant: Option<String>,
+    /// Issuer that vouched for the token.
+    pub issuer: String,
+    /// Full verified claim set (or introspection response).
+    pub claims: serde_json::Value,
 }
 
-/// Claims from a Keycloak-issued RS256 JWT token.
-#[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
-pub struct KeycloakClaims {
-    /// Keycloak user UUID (subject)
-    pub sub: String,
-    pub preferred_username: Option<String>,
-    pub email: Option<String>,
-    pub realm_access: Option<RealmAccess>,
-    /// Custom claim injected via Keycloak protocol mapper
-    pub business_id: Option<String>,
-    pub exp: i64,
-    pub iat: i64,
-    pub iss: String,
+/// Run `f` with `principal` as the authenticated caller.
+pub async fn with_principal<F: Future>(principal: Principal, f: F) -> F::Output {
+    PRINCIPAL.scope(Arc::new(principal), f).await
+}
+
+/// The authenticated caller of the current request, if any.
+pub fn current_principal() -> Option<Arc<Principal>> {
+    PRINCIPAL.try_with(Arc::clone).ok()
 }
 
+/// Subset of the OpenID Provider metadata the gateway needs.
 #[derive(Debug, Clone, Deserialize)]
-pub struct RealmAccess {
-    pub roles: Vec<String>,
+struct ProviderMetadata {
+    issuer: String,
+    jwks_uri: Option<String>,
+    introspection_endpoint: Option<String>,
+}
+
+/// Verification state for one trusted issuer.
+#[derive(Debug)]
+struct IssuerVerifier {
+    config: OidcIssuerConfig,
+    algorithms: Vec<Algorithm>,
+    metadata: RwLock<Option<ProviderMetadata>>,
+    cached_jwks: RwLock<Option<JwkSet>>,
+    last_refresh: RwLock<Option<Instant>>,
+    /// Active introspection responses by token hash, with their expiry.
+    introspected: Mutex<HashMap<String, (serde_json::Value, Instant)>>,
+}
+
+/// OIDC token validation service.
+///
+/// State is shared across clones via `Arc`, so Axum's per-request state
+/// cloning does not discard cached discovery documents or keys.
+#[derive(Debug, Clone)]
+pub struct OidcService {
+    issuers: Arc<Vec<IssuerVerifier>>,
+    user_claim: String,
+    tenant_claim: String,
+    public_paths: Arc<Vec<String>>,
+    leeway_secs: u64,
+    http: reqwest::Client,
 }
 
 impl OidcService {
-    /// Create a new OidcService.
-    ///
-    /// `keycloak_url` — base URL for JWKS fetching (e.g. `https://auth.dev.oluto.app`)
-    /// `realm`        — Keycloak realm name (e.g. `oluto`)
-    /// `issuer_url`   — optional public URL for token issuer validation.
-    ///                   If None, `keycloak_url` is used for both.
-    /// `http`         — shared reqwest client (reused from gateway)
-    pub fn new(
-        keycloak_url: &str,
-        realm: &str,
-        issuer_url: Option<&str>,
-        http: reqwest::Client,
-    ) -> Self {
-        let base = keycloak_url.trim_end_matches('/');
-        let issuer_base = issuer_url
-            .map(|u| u.trim_end_matches('/').to_string())
-            .unwrap_or_else(|| base.to_string());
-        let issuer = format!("{issuer_base}/realms/{realm}");
-        let jwks_url = format!("{base}/realms/{realm}/protocol/openid-connect/certs");
-
-        tracing::info!("OIDC service enabled — issuer: {issuer}");
-
-        Self {
-            jwks_url,
-            issuer,
-            cached_jwks: Arc::new(RwLock::new(None)),
-            last_refresh: Arc::new(RwLock::new(
-                Instant::now() - std::time::Duration::from_secs(JWKS_REFRESH_INTERVAL_SECS),
-            )),
+    /// Build the service from `[gateway.oidc]` (plus the legacy `keycloak_*`
+    /// keys). Returns `None` when no issuer is configured.
+    pub fn from_config(config: &GatewayConfig, http: reqwest::Client) -> Option<Self> {
+        let issuers: Vec<IssuerVerifier> = config
+            .oidc_issuers()
+            .into_iter()
+            .map(|issuer| {
+                let algorithms = if issuer.algorithms.is_empty() {
+                    DEFAULT_ALGORITHMS.to_vec()
+                } else {
+                    issuer
+                        .algorithms
+                        .iter()
+                        .filter_map(|alg| alg.parse().ok())
+                        .collect()
+                };
+                tracing::info!("OIDC issuer trusted: {}", issuer.issuer);
+                if issuer.allow_any_audience {
+                    tracing::warn!(
+                        "OIDC issuer {} accepts tokens for any audience",
+                        issuer.issuer
+                    );
+                }
+                IssuerVerifier {
+                    config: issuer,
+                    algorithms,
+                    metadata: RwLock::new(None),
+                    cached_jwks: RwLock::new(None),
+                    last_refresh: RwLock::new(None),
+                    introspected: Mutex::new(HashMap::new()),
+                }
+            })
+            .collect();
+        if issuers.is_empty() {
+            return None;
+        }
+        Some(Self {
+            issuers: Arc::new(issuers),
+            user_claim: config.oidc.user_claim.clone(),
+            tenant_claim: config.oidc.tenant_claim.clone(),
+            public_paths: Arc::new(config.oidc.public_paths.clone()),
+            leeway_secs: config.oidc.leeway_secs,
             http,
+        })
+    }
+
+    /// Issuer URLs, for startup logging.
+    pub fn issuer_urls(&self) -> Vec<&str> {
+        self.issuers
+            .iter()
+            .map(|issuer| issuer.config.issuer.as_str())
+            .collect()
+    }
+
+    /// Whether `path` is served without a token.
+    pub fn is_public_path(&self, path: &str) -> bool {
+        self.public_paths
+            .iter()
+            .any(|public| match public.strip_suffix("/*") {
+                Some(prefix) => path == prefix || path.starts_with(&format!("{prefix}/")),
+                None => path == public,
+            })
+    }
+
+    /// Validate a bearer token (JWT or opaque) and map it to a [`Principal`].
+    pub async fn authenticate(&self, token: &str) -> Result<Principal> {
+        if let Some(iss) = unverified_issuer(token) {
+            let issuer = self
+                .issuers
+                .iter()
+                .find(|issuer| issuer.config.issuer == iss)
+                .with_context(|| format!("JWT issuer '{iss}' is not trusted"))?;
+            let claims = issuer
+                .verify_jwt(token, &self.http, self.leeway_secs)
+                .await?;
+            return self.principal(&issuer.config.issuer, claims);
+        }
+
+        let mut last_error = None;
+        for issuer in self
+            .issuers
+            .iter()
+            .filter(|issuer| issuer.config.introspection.is_some())
+        {
+            match issuer.introspect(token, &self.http, self.leeway_secs).await {
+                Ok(claims) => return self.principal(&issuer.config.issuer, claims),
+                Err(e) => last_error = Some(e),
+            }
         }
+        Err(last_error.unwrap_or_else(|| {
+            anyhow::anyhow!("token is not a JWT and no issuer has introspection configured")
+        }))
     }
 
-    /// Validate a Keycloak RS256 JWT token.
-    /// Returns the decoded claims on success.
-    pub async fn validate_token(&self, token: &str) -> Result<KeycloakClaims> {
+    fn principal(&self, issuer: &str, claims: serde_json::Value) -> Result<Principal> {
+        let subject = claim_values(&claims, &self.user_claim)
+            .into_iter()
+            .next()
+            .with_context(|| format!("token has no '{}' claim", self.user_claim))?;
+        let tenant = claim_values(&claims, &self.tenant_claim).into_iter().next();
+        Ok(Principal {
+            subject,
+            tenant,
+            issuer: issuer.to_string(),
+            claims,
+        })
+    }
+}
+
+impl IssuerVerifier {
+    /// Verify signature, issuer, audience and expiry of a JWT.
+    async fn verify_jwt(
+        &self,
+        token: &str,
+        http: &reqwest::Client,
+        leeway_secs: u64,
+    ) -> Result<serde_json::Value> {
         let header = decode_header(token).context("invalid JWT header")?;
+        if !self.algorithms.contains(&header.alg) {
+            bail!(
+                "JWT algorithm {:?} is not accepted for issuer {}",
+                header.alg,
+                self.config.issuer
+            );
+        }
 
-        if header.alg != Algorithm::RS256 {
-            bail!("unsupported JWT algorithm {:?}, expected RS256", header.alg);
+        let decoding_key = self.get_decoding_key(header.kid.as_deref(), http).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.leeway = leeway_secs;
+        if self.config.allow_any_audience {
+            // Some providers (e.g. Keycloak) do not set `aud` to a single
+            // predictable value; the issuer check is then the trust anchor.
+            validation.validate_aud = false;
+        } else {
+            // An empty list accepts no audience at all.
+            validation.set_audience(&self.config.audiences);
         }
 
-        let kid = header
-            .kid
-            .as_deref()
-            .context("JWT header missing kid")?;
+        let token_data = decode::<serde_json::Value>(token, &decoding_key, &validation)
+            .context("JWT validation failed")?;
+        Ok(token_data.claims)
+    }
 
-        let decoding_key = self.get_decoding_key(kid).await?;
+    /// RFC 7662 introspection of an opaque token.
+    async fn introspect(
+        &self,
+        token: &str,
+        http: &reqwest::Client,
+        leeway_secs: u64,
+    ) -> Result<serde_json::Value> {
+        let introspection = self
+            .config
+            .introspection
+            .as_ref()
+            .context("introspection is not configured")?;
+        let cache_key = hex::encode(Sha256::digest(token.as_bytes()));
+        if let Some((claims, _)) = self
+            .introspected
+            .lock()
+            .get(&cache_key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+        {
+            return Ok(claims.clone());
+        }
+        let endpoint = match &introspection.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => self
+                .metadata(http)
+                .await?
+                .introspection_endpoint
+                .with_context(|| {
+                    format!(
+                        "issuer {} does not advertise an introspection_endpoint",
+                        self.config.issuer
+                    )
+                })?,
+        };
 
-        let mut validation = Validation::new(Algorithm::RS256);
-        validation.set_issuer(&[&self.issuer]);
-        // Keycloak tokens don't always have an `aud` claim matching a single value,
-        // so we skip audience validation. The issuer check is sufficient
-        // since we only trust our own Keycloak realm.
-        validation.validate_aud = false;
+        let response: serde_json::Value = http
+            .post(&endpoint)
+            .basic_auth(&introspection.client_id, Some(&introspection.client_secret))
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .context("token introspection request failed")?
+            .error_for_status()
+            .context("token introspection was rejected")?
+            .json()
+            .await
+            .context("token introspection returned invalid JSON")?;
 
-        let token_data = decode::<KeycloakClaims>(token, &decoding_key, &validation)
-            .context("JWT validation failed")?;
+        if response.get("active").and_then(serde_json::Value::as_bool) != Some(true) {
+            bail!("token is not active");
+        }
+        if let Some(iss) = response.get("iss").and_then(serde_json::Value::as_str) {
+            if iss != self.config.issuer {
+                bail!("introspected token was issued by '{iss}'");
+            }
+        }
+        let now = chrono::Utc::now().timestamp();
+        let exp = response.get("exp").and_then(serde_json::Value::as_i64);
+        if let Some(exp) = exp {
+            if exp.saturating_add(i64::try_from(leeway_secs).unwrap_or(i64::MAX)) < now {
+                bail!("introspected token has expired");
+            }
+        }
+        if !self.config.allow_any_audience {
+            let audiences = claim_values(&response, "aud");
+            if !audiences
+                .iter()
+                .any(|aud| self.config.audiences.contains(aud))
+            {
+                bail!("introspected token audience is not accepted");
+            }
+        }
 
-        Ok(token_data.claims)
+        let ttl = exp.map_or(INTROSPECTION_CACHE_MAX_SECS, |exp| {
+            u64::try_from(exp.saturating_sub(now))
+                .unwrap_or(0)
+                .min(INTROSPECTION_CACHE_MAX_SECS)
+        });
+        let now = Instant::now();
+        let mut cache = self.introspected.lock();
+        cache.retain(|_, (_, expires_at)| *expires_at > now);
+        if cache.len() < INTROSPECTION_CACHE_MAX_ENTRIES {
+            cache.insert(
+                cache_key,
+                (response.clone(), now + Duration::from_secs(ttl)),
+            );
+        }
+        Ok(response)
+    }
+
+    /// Discovery document, fetched once and cached.
+    async fn metadata(&self, http: &reqwest::Client) -> Result<ProviderMetadata> {
+        if let Some(metadata) = self.metadata.read().await.as_ref() {
+            return Ok(metadata.clone());
+        }
+        let url = self.config.discovery_url.clone().unwrap_or_else(|| {
+            format!(
+                "{}/.well-known/openid-configuration",
+                self.config.issuer.trim_end_matches('/')
+            )
+        });
+        tracing::debug!("Fetching OIDC discovery document from {url}");
+        let metadata: ProviderMetadata = http
+            .get(&url)
+            .send()
+            .await
+            .context("OIDC discovery fetch failed")?
+            .error_for_status()
+            .context("OIDC discovery fetch failed")?
+            .json()
+            .await
+            .context("OIDC discovery parse failed")?;
+        if metadata.issuer != self.config.issuer {
+            bail!(
+                "discovery document issuer '{}' does not match configured issuer '{}'",
+                metadata.issuer,
+                self.config.issuer
+            );
+        }
+        *self.metadata.write().await = Some(metadata.clone());
+        Ok(metadata)
     }
 
     /// Get the DecodingKey for the given kid, refreshing JWKS if needed.
-    async fn get_decoding_key(&self, kid: &str) -> Result<DecodingKey> {
+    /// Tokens without a `kid` are accepted when the JWKS holds a single key.
+    async fn get_decoding_key(
+        &self,
+        kid: Option<&str>,
+        http: &reqwest::Client,
+    ) -> Result<DecodingKey> {
         // Try cached JWKS first
         {
             let jwks = self.cached_jwks.read().await;
-            if let Some(ref jwks) = *jwks {
-                if let Some(jwk) = jwks.find(kid) {
-                    return DecodingKey::from_jwk(jwk)
-                        .context("failed to create DecodingKey from cached JWK");
-                }
+            if let Some(jwk) = jwks.as_ref().and_then(|jwks| find_jwk(jwks, kid)) {
+                return DecodingKey::from_jwk(jwk)
+                    .context("failed to create DecodingKey from cached JWK");
             }
         }
 
         // Key not found in cache — refresh JWKS (with rate limiting)
-        self.refresh_jwks().await?;
+        self.refresh_jwks(http).await?;
 
         // Try again after refresh
         let jwks = self.cached_jwks.read().await;
         let jwks = jwks.as_ref().context("JWKS cache empty after refresh")?;
-        let jwk = jwks
-            .find(kid)
-            .with_context(|| format!("key id '{kid}' not found in JWKS after refresh"))?;
+        let jwk = find_jwk(jwks, kid).with_context(|| {
+            format!(
+                "key id '{}' not found in JWKS after refresh",
+                kid.unwrap_or("<none>")
+            )
+        })?;
 
         DecodingKey::from_jwk(jwk).context("failed to create DecodingKey from JWK")
     }
 
-    /// Fetch JWKS from Keycloak, rate-limited to avoid hammering the endpoint.
-    async fn refresh_jwks(&self) -> Result<()> {
+    /// Fetch the issuer's JWKS, rate-limited to avoid hammering the endpoint.
+    async fn refresh_jwks(&self, http: &reqwest::Client) -> Result<()> {
         {
             let last = self.last_refresh.read().await;
-            if last.elapsed().as_secs() < JWKS_REFRESH_INTERVAL_SECS {
-                let has_keys = self.cached_jwks.read().await.is_some();
-                if has_keys {
-                    return Ok(());
-                }
+            let recent = last.is_some_and(|at| at.elapsed().as_secs() < JWKS_REFRESH_INTERVAL_SECS);
+            if recent && self.cached_jwks.read().await.is_some() {
+                return Ok(());
             }
         }
 
-        tracing::debug!("Refreshing JWKS from {}", self.jwks_url);
+        let jwks_url = match &self.config.jwks_uri {
+            Some(uri) => uri.clone(),
+            None => self.metadata(http).await?.jwks_uri.with_context(|| {
+                format!(
+                    "issuer {} does not advertise a jwks_uri",
+                    self.config.issuer
+                )
+            })?,
+        };
+        tracing::debug!("Refreshing JWKS from {jwks_url}");
 
-        let jwks: JwkSet = self
-            .http
-            .get(&self.jwks_url)
+        let jwks: JwkSet = http
+            .get(&jwks_url)
             .send()
             .await
             .context("JWKS fetch failed")?
@@ -162,138 +448,313 @@ impl OidcService {
             .await
             .context("JWKS parse failed")?;
 
-        tracing::info!("Loaded {} keys from JWKS endpoint", jwks.keys.len());
+        tracing::info!(
+            "Loaded {} keys from JWKS endpoint for {}",
+            jwks.keys.len(),
+            self.config.issuer
+        );
 
         *self.cached_jwks.write().await = Some(jwks);
-        *self.last_refresh.write().await = Instant::now();
+        *self.last_refresh.write().await = Some(Instant::now());
 
         Ok(())
     }
 }
 
-/// Map Keycloak `realm_access.roles` to the highest Oluto role.
-/// Priority: "admin" > "accountant" > "viewer".
-/// Returns "viewer" if no recognized role is found.
-pub fn resolve_role(claims: &KeycloakClaims) -> String {
-    let roles = match claims.realm_access {
-        Some(ref ra) => &ra.roles,
-        None => return "viewer".to_string(),
-    };
-
-    if roles.iter().any(|r| r == "admin") {
-        "admin".to_string()
-    } else if roles.iter().any(|r| r == "accountant") {
-        "accountant".to_string()
-    } else {
-        "viewer".to_string()
+fn find_jwk<'a>(jwks: &'a JwkSet, kid: Option<&str>) -> Option<&'a jsonwebtoken::jwk::Jwk> {
+    match kid {
+        Some(kid) => jwks.find(kid),
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
     }
 }
 
+/// The `iss` claim of a JWT-shaped token, read without verification so the
+/// right issuer's keys can be selected. `None` for opaque tokens.
+fn unverified_issuer(token: &str) -> Option<String> {
+    let mut parts = token.split('.');
+    let (_, payload, _) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    claims.get("iss")?.as_str().map(String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{GatewayOidcConfig, OidcIntrospectionConfig};
+    use axum::{routing::get, routing::post, Json, Router};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+
+    struct TestIdp {
+        issuer: String,
+        key: EncodingKey,
+        introspections: Arc<AtomicUsize>,
+    }
 
-    #[test]
-    fn test_resolve_role_admin() {
-        let claims = KeycloakClaims {
-            sub: "u1".into(),
-            preferred_username: None,
-            email: None,
-            realm_access: Some(RealmAccess {
-                roles: vec!["viewer".into(), "admin".into(), "accountant".into()],
-            }),
-            business_id: None,
-            exp: 0,
-            iat: 0,
-            iss: String::new(),
-        };
-        assert_eq!(resolve_role(&claims), "admin");
+    impl TestIdp {
+        fn sign(&self, claims: &serde_json::Value) -> String {
+            let mut header = Header::new(Algorithm::EdDSA);
+            header.kid = Some("test-key".into());
+            encode(&header, claims, &self.key).unwrap()
+        }
     }
 
-    #[test]
-    fn test_resolve_role_accountant() {
-        let claims = KeycloakClaims {
-            sub: "u2".into(),
-            preferred_username: None,
-            email: None,
-            realm_access: Some(RealmAccess {
-                roles: vec!["viewer".into(), "accountant".into()],
-            }),
-            business_id: None,
-            exp: 0,
-            iat: 0,
-            iss: String::new(),
-        };
-        assert_eq!(resolve_role(&claims), "accountant");
+    /// Serves discovery, JWKS and introspection for a generated Ed25519 key.
+    async fn spawn_idp() -> TestIdp {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let x = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(pair.public_key().as_ref());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let discovery = json!({
+            "issuer": issuer,
+            "jwks_uri": format!("{issuer}/jwks"),
+            "introspection_endpoint": format!("{issuer}/introspect"),
+        });
+        let jwks = json!({ "keys": [{
+            "kty": "OKP", "crv": "Ed25519", "x": x,
+            "kid": "test-key", "alg": "EdDSA", "use": "sig"
+        }]});
+        let introspected_issuer = issuer.clone();
+        let introspections = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&introspections);
+        let app = Router::new()
+            .route(
+                "/.well-known/openid-configuration",
+                get(move || async move { Json(discovery) }),
+            )
+            .route("/jwks", get(move || async move { Json(jwks) }))
+            .route(
+                "/introspect",
+                post(move |body: String| {
+                    let issuer = introspected_issuer.clone();
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        if body.contains("token=opaque-good") {
+                            Json(json!({
+                                "active": true, "sub": "svc-1", "iss": issuer,
+                                "aud": ["zeroclaw"], "org": { "id": "acme" },
+                                "exp": chrono::Utc::now().timestamp() + 600
+                            }))
+                        } else {
+                            Json(json!({ "active": false }))
+                        }
+                    }
+                }),
+            );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        TestIdp {
+            issuer,
+            key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+            introspections,
+        }
     }
 
-    #[test]
-    fn test_resolve_role_viewer_default() {
-        let claims = KeycloakClaims {
-            sub: "u3".into(),
-            preferred_username: None,
-            email: None,
-            realm_access: Some(RealmAccess {
-                roles: vec!["uma_authorization".into()],
-            }),
-            business_id: None,
-            exp: 0,
-            iat: 0,
-            iss: String::new(),
-        };
-        assert_eq!(resolve_role(&claims), "viewer");
+    fn gateway_config(issuers: Vec<OidcIssuerConfig>) -> GatewayConfig {
+        GatewayConfig {
+            oidc: GatewayOidcConfig {
+                issuers,
+                tenant_claim: "org.id".into(),
+                ..GatewayOidcConfig::default()
+            },
+            ..GatewayConfig::default()
+        }
     }
 
-    #[test]
-    fn test_resolve_role_no_realm_access() {
-        let claims = KeycloakClaims {
-            sub: "u4".into(),
-            preferred_username: None,
-            email: None,
-            realm_access: None,
-            business_id: None,
-            exp: 0,
-            iat: 0,
-            iss: String::new(),
-        };
-        assert_eq!(resolve_role(&claims), "viewer");
+    fn claims(issuer: &str, aud: &str) -> serde_json::Value {
+        let now = chrono::Utc::now().timestamp();
+        json!({
+            "sub": "user-1", "iss": issuer, "aud": aud,
+            "iat": now, "exp": now + 600,
+            "org": { "id": "acme" }, "realm_access": { "roles": ["admin"] }
+        })
     }
 
-    #[test]
-    fn test_deserialize_full_claims() {
-        let json = serde_json::json!({
-            "sub": "kc-user-001",
-            "preferred_username": "janedoe",
-            "email": "jane@example.com",
-            "realm_access": { "roles": ["admin"] },
-            "business_id": "aaaa-bbbb-cccc",
-            "exp": 1999999999_i64,
-            "iat": 1999999000_i64,
-            "iss": "http://localhost:8080/realms/oluto"
-        });
+    #[tokio::test]
+    async fn jwt_verified_through_discovery_with_audience_and_claim_mapping() {
+        let idp = spawn_idp().await;
+        let service = OidcService::from_config(
+            &gateway_config(vec![OidcIssuerConfig {
+                issuer: idp.issuer.clone(),
+                audiences: vec!["zeroclaw".into(), "zeroclaw-admin".into()],
+                ..OidcIssuerConfig::default()
+            }]),
+            reqwest::Client::new(),
+        )
+        .unwrap();
+
+        let principal = service
+            .authenticate(&idp.sign(&claims(&idp.issuer, "zeroclaw-admin")))
+            .await
+            .unwrap();
+        assert_eq!(principal.subject, "user-1");
+        assert_eq!(principal.tenant.as_deref(), Some("acme"));
+        assert_eq!(principal.issuer, idp.issuer);
+        assert_eq!(principal.claims["realm_access"]["roles"][0], "admin");
+
+        assert!(service
+            .authenticate(&idp.sign(&claims(&idp.issuer, "someone-else")))
+            .await
+            .is_err());
+        let mut expired = claims(&idp.issuer, "zeroclaw");
+        expired["exp"] = json!(chrono::Utc::now().timestamp() - 3600);
+        assert!(service.authenticate(&idp.sign(&expired)).await.is_err());
+        let untrusted = service
+            .authenticate(&idp.sign(&claims("https://evil.example", "zeroclaw")))
+            .await
+            .unwrap_err();
+        assert!(untrusted.to_string().contains("not trusted"));
+    }
+
+    #[tokio::test]
+    async fn second_issuer_and_opaque_tokens_via_introspection() {
+        let idp = spawn_idp().await;
+        let other = spawn_idp().await;
+        let service = OidcService::from_config(
+            &gateway_config(vec![
+                OidcIssuerConfig {
+                    issuer: other.issuer.clone(),
+                    allow_any_audience: true,
+                    ..OidcIssuerConfig::default()
+                },
+                OidcIssuerConfig {
+                    issuer: idp.issuer.clone(),
+                    audiences: vec!["zeroclaw".into()],
+                    introspection: Some(OidcIntrospectionConfig {
+                        endpoint: None,
+                        client_id: "gateway".into(),
+                        client_secret: "s3cret".into(),
+                    }),
+                    ..OidcIssuerConfig::default()
+                },
+            ]),
+            reqwest::Client::new(),
+        )
+        .unwrap();
+
+        let from_other = service
+            .authenticate(&other.sign(&claims(&other.issuer, "anything")))
+            .await
+            .unwrap();
+        assert_eq!(from_other.issuer, other.issuer);
+        // Signed by the wrong issuer's key.
+        assert!(service
+            .authenticate(&idp.sign(&claims(&other.issuer, "anything")))
+            .await
+            .is_err());
+
+        let opaque = service.authenticate("opaque-good").await.unwrap();
+        assert_eq!(opaque.subject, "svc-1");
+        assert_eq!(opaque.tenant.as_deref(), Some("acme"));
+        assert!(service.authenticate("opaque-revoked").await.is_err());
+
+        // Active results are reused for a while; failures are not.
+        service.authenticate("opaque-good").await.unwrap();
+        assert!(service.authenticate("opaque-revoked").await.is_err());
+        assert_eq!(idp.introspections.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn long_lived_opaque_tokens_are_introspected_again_after_the_cap() {
+        let idp = spawn_idp().await;
+        let service = OidcService::from_config(
+            &gateway_config(vec![OidcIssuerConfig {
+                issuer: idp.issuer.clone(),
+                audiences: vec!["zeroclaw".into()],
+                introspection: Some(OidcIntrospectionConfig {
+                    endpoint: None,
+                    client_id: "gateway".into(),
+                    client_secret: "s3cret".into(),
+                }),
+                ..OidcIssuerConfig::default()
+            }]),
+            reqwest::Client::new(),
+        )
+        .unwrap();
+
+        // The test IdP issues tokens valid for 10 minutes.
+        service.authenticate("opaque-good").await.unwrap();
+        let cap = Instant::now() + Duration::from_secs(INTROSPECTION_CACHE_MAX_SECS);
+        {
+            let mut cache = service.issuers[0].introspected.lock();
+            let (_, expires_at) = cache.values_mut().next().unwrap();
+            assert!(*expires_at <= cap);
+            // Let the capped entry lapse.
+            *expires_at = Instant::now();
+        }
+        service.authenticate("opaque-good").await.unwrap();
+        assert_eq!(idp.introspections.load(Ordering::SeqCst), 2);
+    }
 
-        let claims: KeycloakClaims = serde_json::from_value(json).unwrap();
-        assert_eq!(claims.sub, "kc-user-001");
-        assert_eq!(claims.preferred_username.as_deref(), Some("janedoe"));
-        assert_eq!(claims.email.as_deref(), Some("jane@example.com"));
-        assert_eq!(claims.business_id.as_deref(), Some("aaaa-bbbb-cccc"));
-        assert_eq!(claims.realm_access.as_ref().unwrap().roles, vec!["admin"]);
+    #[tokio::test]
+    async fn issuer_without_audiences_accepts_no_token() {
+        let idp = spawn_idp().await;
+        let service = OidcService::from_config(
+            &gateway_config(vec![OidcIssuerConfig {
+                issuer: idp.issuer.clone(),
+                ..OidcIssuerConfig::default()
+            }]),
+            reqwest::Client::new(),
+        )
+        .unwrap();
+        assert!(service
+            .authenticate(&idp.sign(&claims(&idp.issuer, "zeroclaw")))
+            .await
+            .is_err());
     }
 
     #[test]
-    fn test_deserialize_minimal_claims() {
-        let json = serde_json::json!({
-            "sub": "kc-user-002",
-            "exp": 1999999999_i64,
-            "iat": 1999999000_i64,
-            "iss": "http://localhost:8080/realms/oluto"
-        });
+    fn keycloak_settings_map_to_an_issuer_and_public_paths_match() {
+        let config = GatewayConfig {
+            keycloak_url: Some("http://keycloak:8080/".into()),
+            keycloak_issuer_url: Some("https://auth.example.com".into()),
+            ..GatewayConfig::default()
+        };
+        let issuers = config.oidc_issuers();
+        assert_eq!(issuers.len(), 1);
+        assert_eq!(issuers[0].issuer, "https://auth.example.com/realms/oluto");
+        assert_eq!(
+            issuers[0].jwks_uri.as_deref(),
+            Some("http://keycloak:8080/realms/oluto/protocol/openid-connect/certs")
+        );
+        assert!(
+            OidcService::from_config(&GatewayConfig::default(), reqwest::Client::new()).is_none()
+        );
+
+        let mut config = config;
+        config.oidc.public_paths.push("/hooks/*".into());
+        let service = OidcService::from_config(&config, reqwest::Client::new()).unwrap();
+        assert!(service.is_public_path("/health"));
+        assert!(service.is_public_path("/hooks/deploy"));
+        assert!(!service.is_public_path("/hooksx"));
+        assert!(!service.is_public_path("/metrics"));
+        assert!(!service.is_public_path("/webhook"));
+    }
 
-        let claims: KeycloakClaims = serde_json::from_value(json).unwrap();
-        assert_eq!(claims.sub, "kc-user-002");
-        assert!(claims.preferred_username.is_none());
-        assert!(claims.email.is_none());
-        assert!(claims.realm_access.is_none());
-        assert!(claims.business_id.is_none());
+    #[test]
+    fn unverified_issuer_only_reads_jwt_shaped_tokens() {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(br#"{"iss":"https://issuer.example","sub":"u"}"#);
+        assert_eq!(
+            unverified_issuer(&format!("e30.{payload}.sig")).as_deref(),
+            Some("https://issuer.example")
+        );
+        assert!(unverified_issuer("opaque-token").is_none());
+        assert!(unverified_issuer("a.b.c.d").is_none());
+        assert!(unverified_issuer("a.!!!.c").is_none());
     }
 }
@@ -1,11 +1,19 @@
 use crate::config::HeartbeatConfig;
+use crate::heartbeat::state::HeartbeatState;
+use crate::heartbeat::task::{parse_task_lines, parse_timezone, HeartbeatTask, HoursWindow};
 use crate::observability::{Observer, ObserverEvent};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::time::{self, Duration};
 use tracing::{info, warn};
 
+/// Slack for `every=` checks, so a tick read a little early on the wall
+/// clock does not push a task to the following tick.
+const DUE_SLACK_SECS: i64 = 5;
+
 /// Heartbeat engine — reads HEARTBEAT.md and executes tasks periodically
 pub struct HeartbeatEngine {
     config: HeartbeatConfig,
@@ -65,7 +73,7 @@ impl HeartbeatEngine {
     }
 
     /// Read HEARTBEAT.md and return all parsed tasks.
-    pub async fn collect_tasks(&self) -> Result<Vec<String>> {
+    pub async fn collect_tasks(&self) -> Result<Vec<HeartbeatTask>> {
         let heartbeat_path = self.workspace_dir.join("HEARTBEAT.md");
         if !heartbeat_path.exists() {
             return Ok(Vec::new());
@@ -74,13 +82,63 @@ impl HeartbeatEngine {
         Ok(Self::parse_tasks(&content))
     }
 
-    /// Parse tasks from HEARTBEAT.md (lines starting with `- `)
-    fn parse_tasks(content: &str) -> Vec<String> {
-        content
-            .lines()
-            .filter_map(|line| {
-                let trimmed = line.trim();
-                trimmed.strip_prefix("- ").map(ToString::to_string)
+    /// Parse tasks from HEARTBEAT.md (lines starting with `- `). Lines with
+    /// invalid annotations are skipped with a warning.
+    fn parse_tasks(content: &str) -> Vec<HeartbeatTask> {
+        parse_task_lines(content)
+            .into_iter()
+            .filter_map(|(line, parsed)| match parsed {
+                Ok(task) => Some(task),
+                Err(e) => {
+                    warn!("💓 Skipping HEARTBEAT.md task on line {line}: {e}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Default timezone for quiet hours and task windows.
+    fn timezone(&self) -> Tz {
+        self.config
+            .timezone
+            .as_deref()
+            .and_then(|tz| parse_timezone(tz).ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Whether `now` falls inside `[heartbeat].quiet_hours`.
+    pub fn in_quiet_hours(&self, now: DateTime<Utc>) -> bool {
+        self.config
+            .quiet_hours
+            .as_deref()
+            .and_then(|raw| HoursWindow::parse(raw).ok())
+            .is_some_and(|window| window.contains_at(now, self.timezone()))
+    }
+
+    /// Tasks that should run at `now`: outside quiet hours, inside their own
+    /// active hours, and at least `every` after their last run. Runs should be
+    /// recorded at the same `now`, not when each task starts.
+    pub fn due_tasks<'a>(
+        &self,
+        tasks: &'a [HeartbeatTask],
+        state: &HeartbeatState,
+        now: DateTime<Utc>,
+    ) -> Vec<&'a HeartbeatTask> {
+        if self.in_quiet_hours(now) {
+            return Vec::new();
+        }
+        let timezone = self.timezone();
+        tasks
+            .iter()
+            .filter(|task| task.in_active_hours(now, timezone))
+            .filter(|task| {
+                let last_run = state.task(&task.id).and_then(|s| s.last_run_at);
+                match (task.every, last_run) {
+                    (Some(every), Some(last_run)) => {
+                        now - last_run + chrono::Duration::seconds(DUE_SLACK_SECS) >= every
+                    }
+                    _ => true,
+                }
             })
             .collect()
     }
@@ -93,9 +151,16 @@ impl HeartbeatEngine {
                            # Add tasks below (one per line, starting with `- `)\n\
                            # The agent will check this file on each heartbeat tick.\n\
                            #\n\
+                           # Optional annotations follow the task, separated by ` | `:\n\
+                           #   every=2h            run at most this often\n\
+                           #   hours=09:00-18:00   only run inside this daily window\n\
+                           #   tz=Europe/Berlin    timezone for hours=\n\
+                           #   to=telegram:<chat>  send the result to a channel recipient\n\
+                           #   on_change           only send when the result changed\n\
+                           #\n\
                            # Examples:\n\
-                           # - Check my email for important messages\n\
-                           # - Review my calendar for upcoming events\n\
+                           # - Check my email for important messages | every=1h | to=telegram:123456 | on_change\n\
+                           # - Review my calendar for upcoming events | hours=08:00-09:00\n\
                            # - Check the weather forecast\n";
             tokio::fs::write(&path, default).await?;
         }
@@ -112,9 +177,9 @@ mod tests {
         let content = "# Tasks\n\n- Check email\n- Review calendar\nNot a task\n- Third task";
         let tasks = HeartbeatEngine::parse_tasks(content);
         assert_eq!(tasks.len(), 3);
-        assert_eq!(tasks[0], "Check email");
-        assert_eq!(tasks[1], "Review calendar");
-        assert_eq!(tasks[2], "Third task");
+        assert_eq!(tasks[0].text, "Check email");
+        assert_eq!(tasks[1].text, "Review calendar");
+        assert_eq!(tasks[2].text, "Third task");
     }
 
     #[test]
@@ -133,8 +198,8 @@ mod tests {
         let content = "  - Indented task\n\t- Tab indented";
         let tasks = HeartbeatEngine::parse_tasks(content);
         assert_eq!(tasks.len(), 2);
-        assert_eq!(tasks[0], "Indented task");
-        assert_eq!(tasks[1], "Tab indented");
+        assert_eq!(tasks[0].text, "Indented task");
+        assert_eq!(tasks[1].text, "Tab indented");
     }
 
     #[test]
@@ -145,8 +210,8 @@ mod tests {
         // "- Real task" => "Real task"
         // "- Another" => "Another"
         assert_eq!(tasks.len(), 2);
-        assert_eq!(tasks[0], "Real task");
-        assert_eq!(tasks[1], "Another");
+        assert_eq!(tasks[0].text, "Real task");
+        assert_eq!(tasks[1].text, "Another");
     }
 
     #[test]
@@ -164,7 +229,7 @@ mod tests {
         let content = "- hello  ";
         let tasks = HeartbeatEngine::parse_tasks(content);
         assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0], "hello");
+        assert_eq!(tasks[0].text, "hello");
     }
 
     #[test]
@@ -172,8 +237,8 @@ mod tests {
         let content = "- Check email 📧\n- Review calendar 📅\n- 日本語タスク";
         let tasks = HeartbeatEngine::parse_tasks(content);
         assert_eq!(tasks.len(), 3);
-        assert!(tasks[0].contains("📧"));
-        assert!(tasks[2].contains("日本語"));
+        assert!(tasks[0].text.contains("📧"));
+        assert!(tasks[2].text.contains("日本語"));
     }
 
     #[test]
@@ -181,15 +246,15 @@ mod tests {
         let content = "# Periodic Tasks\n\n## Quick\n- Task A\n\n## Long\n- Task B\n\n* Not a dash bullet\n1. Not numbered";
         let tasks = HeartbeatEngine::parse_tasks(content);
         assert_eq!(tasks.len(), 2);
-        assert_eq!(tasks[0], "Task A");
-        assert_eq!(tasks[1], "Task B");
+        assert_eq!(tasks[0].text, "Task A");
+        assert_eq!(tasks[1].text, "Task B");
     }
 
     #[test]
     fn parse_tasks_single_task() {
         let tasks = HeartbeatEngine::parse_tasks("- Only one");
         assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0], "Only one");
+        assert_eq!(tasks[0].text, "Only one");
     }
 
     #[test]
@@ -201,7 +266,86 @@ mod tests {
         });
         let tasks = HeartbeatEngine::parse_tasks(&content);
         assert_eq!(tasks.len(), 100);
-        assert_eq!(tasks[99], "Task 99");
+        assert_eq!(tasks[99].text, "Task 99");
+    }
+
+    #[test]
+    fn parse_tasks_reads_annotations_and_skips_invalid_lines() {
+        let content = "- Check email | every=1h | on_change\n- Broken | every=soon\n- Plain";
+        let tasks = HeartbeatEngine::parse_tasks(content);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].text, "Check email");
+        assert_eq!(tasks[0].every, Some(chrono::Duration::hours(1)));
+        assert!(tasks[0].on_change);
+        assert_eq!(tasks[1].text, "Plain");
+    }
+
+    fn engine_with(quiet_hours: Option<&str>) -> HeartbeatEngine {
+        HeartbeatEngine::new(
+            HeartbeatConfig {
+                enabled: true,
+                quiet_hours: quiet_hours.map(Into::into),
+                ..HeartbeatConfig::default()
+            },
+            std::env::temp_dir(),
+            Arc::new(crate::observability::NoopObserver),
+        )
+    }
+
+    fn utc(hour: u32, minute: u32) -> DateTime<Utc> {
+        chrono::NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn due_tasks_respects_cadence_and_active_hours() {
+        let engine = engine_with(None);
+        let tasks = HeartbeatEngine::parse_tasks(
+            "- Every tick\n- Hourly | every=1h\n- Office | hours=09:00-17:00",
+        );
+        let mut state = HeartbeatState::default();
+
+        let due = engine.due_tasks(&tasks, &state, utc(8, 0));
+        let names: Vec<&str> = due.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(names, vec!["Every tick", "Hourly"]);
+
+        state.record_success(&tasks[1].id, utc(8, 0), "done");
+        let due = engine.due_tasks(&tasks, &state, utc(8, 30));
+        let names: Vec<&str> = due.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(names, vec!["Every tick"]);
+
+        let due = engine.due_tasks(&tasks, &state, utc(9, 0));
+        assert_eq!(due.len(), 3);
+    }
+
+    #[test]
+    fn tasks_every_interval_run_on_every_tick() {
+        let engine = engine_with(None);
+        let tasks = HeartbeatEngine::parse_tasks("- First | every=30m\n- Second | every=30m");
+        let mut state = HeartbeatState::default();
+
+        let early = chrono::Duration::seconds(2);
+        for tick in [utc(8, 0), utc(8, 30) - early, utc(9, 0)] {
+            let due = engine.due_tasks(&tasks, &state, tick);
+            assert_eq!(due.len(), 2, "at {tick}");
+            for task in due {
+                state.record_success(&task.id, tick, "done");
+            }
+        }
+    }
+
+    #[test]
+    fn due_tasks_is_empty_during_quiet_hours() {
+        let engine = engine_with(Some("22:00-07:00"));
+        let tasks = HeartbeatEngine::parse_tasks("- Check email");
+        let state = HeartbeatState::default();
+
+        assert!(engine.in_quiet_hours(utc(23, 15)));
+        assert!(engine.due_tasks(&tasks, &state, utc(23, 15)).is_empty());
+        assert_eq!(engine.due_tasks(&tasks, &state, utc(12, 0)).len(), 1);
     }
 
     #[tokio::test]
@@ -248,6 +392,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 30,
+                ..HeartbeatConfig::default()
             },
             dir.clone(),
             observer,
@@ -273,6 +418,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 30,
+                ..HeartbeatConfig::default()
             },
             dir.clone(),
             observer,
@@ -290,6 +436,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: false,
                 interval_minutes: 30,
+                ..HeartbeatConfig::default()
             },
             std::env::temp_dir(),
             observer,
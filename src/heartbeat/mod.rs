@@ -1,4 +1,6 @@
 pub mod engine;
+pub mod state;
+pub mod task;
 
 #[cfg(test)]
 mod tests {
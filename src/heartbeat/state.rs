@@ -0,0 +1,127 @@
+//! Persisted per-task heartbeat state (`state/heartbeat_state.json`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "heartbeat_state.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskState {
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_status: Option<String>,
+    /// SHA-256 of the last successful result.
+    pub last_result_hash: Option<String>,
+    pub last_notified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatState {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, TaskState>,
+}
+
+impl HeartbeatState {
+    /// Load the state file, or an empty state when there is none yet.
+    pub fn load(workspace_dir: &Path) -> Result<Self> {
+        let path = state_path(workspace_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, workspace_dir: &Path) -> Result<()> {
+        let path = state_path(workspace_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn task(&self, id: &str) -> Option<&TaskState> {
+        self.tasks.get(id)
+    }
+
+    /// Record a successful run. Returns `true` when the result differs from
+    /// the previous successful one (or there was none).
+    pub fn record_success(&mut self, id: &str, at: DateTime<Utc>, output: &str) -> bool {
+        let hash = result_hash(output);
+        let entry = self.tasks.entry(id.to_string()).or_default();
+        let changed = entry.last_result_hash.as_deref() != Some(hash.as_str());
+        entry.last_run_at = Some(at);
+        entry.last_status = Some("ok".into());
+        entry.last_result_hash = Some(hash);
+        changed
+    }
+
+    pub fn record_failure(&mut self, id: &str, at: DateTime<Utc>) {
+        let entry = self.tasks.entry(id.to_string()).or_default();
+        entry.last_run_at = Some(at);
+        entry.last_status = Some("error".into());
+    }
+
+    pub fn record_notified(&mut self, id: &str, at: DateTime<Utc>) {
+        self.tasks
+            .entry(id.to_string())
+            .or_default()
+            .last_notified_at = Some(at);
+    }
+
+    /// Drop state for tasks that are no longer in HEARTBEAT.md.
+    pub fn retain_tasks<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        let keep: std::collections::HashSet<&str> = ids.into_iter().collect();
+        self.tasks.retain(|id, _| keep.contains(id.as_str()));
+    }
+}
+
+fn result_hash(output: &str) -> String {
+    hex::encode(Sha256::digest(output.trim().as_bytes()))
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_success_detects_changed_results_and_persists() {
+        let tmp = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let mut state = HeartbeatState::load(tmp.path()).unwrap();
+        assert!(state.tasks.is_empty());
+
+        assert!(state.record_success("inbox", now, "2 new mails"));
+        assert!(!state.record_success("inbox", now, "  2 new mails\n"));
+        assert!(state.record_success("inbox", now, "3 new mails"));
+        state.record_failure("inbox", now);
+        state.save(tmp.path()).unwrap();
+
+        let loaded = HeartbeatState::load(tmp.path()).unwrap();
+        let task = loaded.task("inbox").unwrap();
+        assert_eq!(task.last_status.as_deref(), Some("error"));
+        assert_eq!(
+            task.last_result_hash.as_deref(),
+            Some(result_hash("3 new mails").as_str())
+        );
+    }
+
+    #[test]
+    fn retain_tasks_drops_removed_entries() {
+        let mut state = HeartbeatState::default();
+        state.record_failure("a", Utc::now());
+        state.record_failure("b", Utc::now());
+        state.retain_tasks(["b"]);
+        assert!(state.task("a").is_none());
+        assert!(state.task("b").is_some());
+    }
+}
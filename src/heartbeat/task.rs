@@ -0,0 +1,263 @@
+//! HEARTBEAT.md task lines and their annotations.
+//!
+//! A task is a `- ` bullet. Trailing ` | `-separated segments annotate it:
+//!
+//! ```text
+//! - Check the build dashboard | every=2h | hours=09:00-18:00 | tz=Europe/Berlin | to=telegram:123456 | on_change
+//! ```
+//!
+//! Segments that are not a known annotation stay part of the task text, so
+//! plain bullets keep working unchanged.
+
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+/// A daily time window, e.g. `09:00-18:00`. Windows whose end is before
+/// their start wrap past midnight (`22:00-07:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoursWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl HoursWindow {
+    /// Parse `HH:MM-HH:MM` or whole hours such as `9-17`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let (start, end) = raw
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid hours window '{raw}': expected HH:MM-HH:MM"))?;
+        Ok(Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Whether `now`, viewed in `tz`, falls inside the window.
+    pub fn contains_at(&self, now: DateTime<Utc>, tz: Tz) -> bool {
+        self.contains(now.with_timezone(&tz).time())
+    }
+}
+
+fn parse_time(raw: &str) -> Result<NaiveTime, String> {
+    let raw = raw.trim();
+    let parsed = if raw.contains(':') {
+        NaiveTime::parse_from_str(raw, "%H:%M").ok()
+    } else {
+        match raw.parse::<u32>() {
+            Ok(hour) if hour > 23 => {
+                return Err(format!(
+                    "Invalid time '{raw}': hour must be between 0 and 23"
+                ));
+            }
+            Ok(hour) => NaiveTime::from_hms_opt(hour, 0, 0),
+            Err(_) => None,
+        }
+    };
+    parsed.ok_or_else(|| format!("Invalid time '{raw}': expected HH:MM"))
+}
+
+/// Parse an IANA timezone name.
+pub fn parse_timezone(raw: &str) -> Result<Tz, String> {
+    Tz::from_str(raw.trim()).map_err(|_| format!("Invalid IANA timezone: {raw}"))
+}
+
+/// Where a task's result is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyTarget {
+    pub channel: String,
+    pub recipient: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatTask {
+    /// Key for the task's persisted state: the `id=` annotation, or a hash of
+    /// the task text.
+    pub id: String,
+    pub text: String,
+    /// Minimum time between runs. Without it the task runs on every tick.
+    pub every: Option<chrono::Duration>,
+    /// Only run inside this daily window.
+    pub hours: Option<HoursWindow>,
+    /// Timezone for `hours`; falls back to `[heartbeat].timezone`.
+    pub timezone: Option<Tz>,
+    pub notify: Option<NotifyTarget>,
+    /// Only notify when the result differs from the previous run.
+    pub on_change: bool,
+}
+
+impl HeartbeatTask {
+    /// Parse the content of a `- ` bullet (without the bullet).
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut segments: Vec<&str> = line.split('|').collect();
+        let mut task = Self {
+            id: String::new(),
+            text: String::new(),
+            every: None,
+            hours: None,
+            timezone: None,
+            notify: None,
+            on_change: false,
+        };
+        let mut id = None;
+
+        // Consume annotations from the end until a segment is plain text.
+        while segments.len() > 1 {
+            let segment = segments[segments.len() - 1].trim();
+            let (key, value) = match segment.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (segment, None),
+            };
+            match (key.to_ascii_lowercase().as_str(), value) {
+                ("every", Some(value)) => {
+                    let every = crate::cron::parse_delay(value)
+                        .map_err(|e| format!("Invalid every='{value}': {e}"))?;
+                    if every <= chrono::Duration::zero() {
+                        return Err(format!("Invalid every='{value}': must be positive"));
+                    }
+                    task.every = Some(every);
+                }
+                ("hours", Some(value)) => task.hours = Some(HoursWindow::parse(value)?),
+                ("tz", Some(value)) => task.timezone = Some(parse_timezone(value)?),
+                ("to", Some(value)) => {
+                    let (channel, recipient) = value
+                        .split_once(':')
+                        .filter(|(c, r)| !c.trim().is_empty() && !r.trim().is_empty())
+                        .ok_or_else(|| {
+                            format!("Invalid to='{value}': expected <channel>:<recipient>")
+                        })?;
+                    task.notify = Some(NotifyTarget {
+                        channel: channel.trim().to_ascii_lowercase(),
+                        recipient: recipient.trim().to_string(),
+                    });
+                }
+                ("id", Some(value)) if !value.is_empty() => id = Some(value.to_string()),
+                ("on_change" | "on-change", None) => task.on_change = true,
+                _ => break,
+            }
+            segments.pop();
+        }
+
+        task.text = segments.join("|").trim().to_string();
+        if task.text.is_empty() {
+            return Err("Heartbeat task has no text".into());
+        }
+        task.id = id.unwrap_or_else(|| text_id(&task.text));
+        Ok(task)
+    }
+
+    /// Whether the task's own window allows it to run at `now`.
+    pub fn in_active_hours(&self, now: DateTime<Utc>, default_tz: Tz) -> bool {
+        self.hours
+            .is_none_or(|window| window.contains_at(now, self.timezone.unwrap_or(default_tz)))
+    }
+}
+
+/// Parse every `- ` bullet in HEARTBEAT.md content, keeping the 1-based line
+/// number so invalid tasks can be reported where they are.
+pub fn parse_task_lines(content: &str) -> Vec<(usize, Result<HeartbeatTask, String>)> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let body = line.trim().strip_prefix("- ")?;
+            Some((index + 1, HeartbeatTask::parse(body)))
+        })
+        .collect()
+}
+
+fn text_id(text: &str) -> String {
+    let digest = Sha256::digest(text.as_bytes());
+    hex::encode(&digest[..6])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_task_keeps_text_and_defaults() {
+        let task = HeartbeatTask::parse("Check email").unwrap();
+        assert_eq!(task.text, "Check email");
+        assert!(task.every.is_none());
+        assert!(task.notify.is_none());
+        assert!(!task.on_change);
+        assert_eq!(task.id.len(), 12);
+        assert_eq!(task.id, HeartbeatTask::parse("Check email").unwrap().id);
+    }
+
+    #[test]
+    fn parse_annotations() {
+        let task = HeartbeatTask::parse(
+            "Check the build | every=2h | hours=09:00-18:00 | tz=Europe/Berlin | to=Telegram:12345 | on_change",
+        )
+        .unwrap();
+        assert_eq!(task.text, "Check the build");
+        assert_eq!(task.every, Some(chrono::Duration::hours(2)));
+        assert_eq!(task.hours, Some(HoursWindow::parse("9-18").unwrap()));
+        assert_eq!(task.timezone, Some(chrono_tz::Europe::Berlin));
+        assert_eq!(
+            task.notify,
+            Some(NotifyTarget {
+                channel: "telegram".into(),
+                recipient: "12345".into(),
+            })
+        );
+        assert!(task.on_change);
+    }
+
+    #[test]
+    fn parse_keeps_pipes_that_are_not_annotations() {
+        let task = HeartbeatTask::parse("Compare A | B | id=compare").unwrap();
+        assert_eq!(task.text, "Compare A | B");
+        assert_eq!(task.id, "compare");
+    }
+
+    #[test]
+    fn parse_rejects_invalid_annotation_values() {
+        assert!(HeartbeatTask::parse("Task | every=soon").is_err());
+        assert!(HeartbeatTask::parse("Task | hours=morning").is_err());
+        assert!(HeartbeatTask::parse("Task | tz=Mars/Base").is_err());
+        assert!(HeartbeatTask::parse("Task | to=telegram").is_err());
+        assert!(HeartbeatTask::parse(" | on_change").is_err());
+        assert!(HeartbeatTask::parse("Task | hours=9-25").is_err());
+        assert!(HeartbeatTask::parse("Task | hours=24-6").is_err());
+    }
+
+    #[test]
+    fn parse_task_lines_reports_line_numbers() {
+        let lines = parse_task_lines("# Tasks\n- Check email\n\n- Report | hours=25-6\n");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].0, 2);
+        assert!(lines[0].1.is_ok());
+        assert_eq!(lines[1].0, 4);
+        assert!(lines[1]
+            .1
+            .as_ref()
+            .unwrap_err()
+            .contains("between 0 and 23"));
+    }
+
+    #[test]
+    fn hours_window_wraps_past_midnight() {
+        let window = HoursWindow::parse("22:00-07:00").unwrap();
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert!(window.contains(at(23, 30)));
+        assert!(window.contains(at(6, 59)));
+        assert!(!window.contains(at(7, 0)));
+        assert!(!window.contains(at(12, 0)));
+
+        let day = HoursWindow::parse("09:00-17:00").unwrap();
+        assert!(day.contains(at(9, 0)));
+        assert!(!day.contains(at(17, 0)));
+    }
+}
@@ -45,11 +45,13 @@ use serde::{Deserialize, Serialize};
 pub mod agent;
 pub(crate) mod approval;
 pub(crate) mod auth;
+pub(crate) mod backup;
 pub mod channels;
 pub mod config;
 pub(crate) mod cron;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
+pub(crate) mod eval;
 pub mod gateway;
 pub(crate) mod hardware;
 pub(crate) mod health;
@@ -67,6 +69,7 @@ pub mod rag;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
+pub(crate) mod sessions;
 pub(crate) mod skills;
 pub mod tools;
 pub(crate) mod tunnel;
@@ -176,7 +179,11 @@ pub(crate) enum MigrateCommands {
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) enum CronCommands {
     /// List all scheduled tasks
-    List,
+    List {
+        /// Show chained jobs as a dependency tree
+        #[arg(long)]
+        tree: bool,
+    },
     /// Add a new scheduled task
     #[command(long_about = "\
 Add a new recurring scheduled task.
@@ -197,6 +204,24 @@ Examples:
         /// Command to run
         command: String,
     },
+    /// Add a task chained after other jobs
+    #[command(long_about = "\
+Add a task that runs when its upstream jobs finish instead of on a schedule.
+
+Each --after takes <id>[:success|failure|always]; the condition defaults \
+to success. With several upstreams, every one must have finished since \
+the task last ran.
+
+Examples:
+  zeroclaw cron add-after --after <backup-id> 'echo report'
+  zeroclaw cron add-after --after <backup-id>:failure 'echo backup failed'")]
+    AddAfter {
+        /// Upstream job: <id>[:success|failure|always]
+        #[arg(long = "after", value_name = "JOB", required = true)]
+        after: Vec<String>,
+        /// Command to run
+        command: String,
+    },
     /// Add a one-shot scheduled task at an RFC3339 timestamp
     #[command(long_about = "\
 Add a one-shot task that fires at a specific UTC timestamp.
@@ -244,6 +269,44 @@ Examples:
         /// Command to run
         command: String,
     },
+    /// Add an event-triggered task (file watch, gateway hook or channel pattern)
+    #[command(long_about = "\
+Add a task that runs when an event fires instead of on a schedule.
+
+Triggers:
+  watch:<path>[:<glob>]  files created or modified under a workspace path
+  hook:<name>            authenticated POST /hooks/<name> on the gateway
+  channel:<regex>        inbound channel message matching the pattern
+
+The event payload (changed paths, request body or message text) is \
+exported to the command as ZEROCLAW_TRIGGER_PAYLOAD.
+
+Examples:
+  zeroclaw cron add-trigger 'watch:inbox:*.csv' 'wc -l \"$ZEROCLAW_TRIGGER_PAYLOAD\"'
+  zeroclaw cron add-trigger hook:deploy 'git pull'")]
+    AddTrigger {
+        /// Trigger: watch:<path>[:<glob>], hook:<name> or channel:<regex>
+        trigger: String,
+        /// Command to run
+        command: String,
+    },
+    /// Show recent runs of a task
+    #[command(long_about = "\
+Show the most recent runs of a scheduled task.
+
+Besides ok/error, the status explains runs the scheduler did not start: \
+skipped_overlap, queued, cancelled, missed or deferred.
+
+Examples:
+  zeroclaw cron runs <task-id>
+  zeroclaw cron runs <task-id> --limit 50")]
+    Runs {
+        /// Task ID
+        id: String,
+        /// Maximum number of runs to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
     /// Remove a scheduled task
     Remove {
         /// Task ID
@@ -258,7 +321,8 @@ Only the fields you specify are changed; others remain unchanged.
 Examples:
   zeroclaw cron update <task-id> --expression '0 8 * * *'
   zeroclaw cron update <task-id> --tz Europe/London --name 'Morning check'
-  zeroclaw cron update <task-id> --command 'Updated message'")]
+  zeroclaw cron update <task-id> --command 'Updated message'
+  zeroclaw cron update <task-id> --overlap queue --catch-up all:3 --jitter 30")]
     Update {
         /// Task ID
         id: String,
@@ -274,6 +338,18 @@ Examples:
         /// New job name
         #[arg(long)]
         name: Option<String>,
+        /// Replace upstream dependencies: <id>[:success|failure|always]
+        #[arg(long = "after", value_name = "JOB")]
+        after: Vec<String>,
+        /// When the previous run is still going: skip, queue or cancel_previous
+        #[arg(long)]
+        overlap: Option<String>,
+        /// Runs to make up after downtime: none, once or all:<max>
+        #[arg(long = "catch-up", value_name = "POLICY")]
+        catch_up: Option<String>,
+        /// Random start delay of up to this many seconds
+        #[arg(long = "jitter", value_name = "SECS")]
+        jitter_secs: Option<u64>,
     },
     /// Pause a scheduled task
     Pause {
@@ -326,6 +402,116 @@ pub enum MemoryCommands {
     },
 }
 
+/// Agent evaluation subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum EvalCommands {
+    /// Run an evaluation suite and report pass/fail, traces, tokens and latency
+    Run {
+        /// Suite file (TOML)
+        suite: std::path::PathBuf,
+        /// Only run tasks with these names (repeatable)
+        #[arg(long = "task", value_name = "NAME")]
+        tasks: Vec<String>,
+        /// Write the JSON report to this file
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+        /// Compare against a saved baseline report and fail on regressions
+        #[arg(long)]
+        baseline: Option<std::path::PathBuf>,
+        /// Save this run as the new baseline
+        #[arg(long)]
+        save_baseline: Option<std::path::PathBuf>,
+        /// Keep task workspaces instead of deleting them
+        #[arg(long)]
+        keep_workspaces: bool,
+        /// Directory for task workspaces (default: <tmp>/zeroclaw-eval/<suite>)
+        #[arg(long)]
+        workspace_root: Option<std::path::PathBuf>,
+    },
+    /// Compare a report against a baseline without running anything
+    Compare {
+        /// Baseline report (JSON)
+        baseline: std::path::PathBuf,
+        /// Current report (JSON)
+        report: std::path::PathBuf,
+    },
+}
+
+/// Conversation session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum SessionsCommands {
+    /// List recorded sessions
+    List,
+    /// Export a session as Markdown, OpenAI-format JSONL or HTML
+    Export {
+        /// Session id (see `zeroclaw sessions list`)
+        id: String,
+        /// Output format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "jsonl", "html"])]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Seed a new session from an OpenAI or Anthropic JSONL transcript
+    Import {
+        /// Transcript file (JSONL or JSON)
+        file: std::path::PathBuf,
+        /// Id for the new session (default: import-<timestamp>)
+        #[arg(long)]
+        id: Option<String>,
+    },
+}
+
+/// Secret store subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum SecretsCommands {
+    /// Re-encrypt every stored secret under a freshly generated key
+    RotateKey,
+}
+
+/// Backup subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum BackupCommands {
+    /// Archive the config directory and workspace
+    Create {
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+        /// Do not encrypt, even when a passphrase is configured
+        #[arg(long)]
+        plain: bool,
+    },
+    /// List stored backups, oldest first
+    List {
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Restore a backup over the config directory and workspace
+    Restore {
+        /// Backup name from `zeroclaw backup list`, or `latest`
+        name: String,
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+        /// Unpack under this directory instead of over the live state
+        #[arg(long)]
+        into: Option<std::path::PathBuf>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Run `backup create` on a cron schedule (replaces an existing schedule)
+    Schedule {
+        /// Cron expression, e.g. "0 3 * * *"
+        expression: String,
+        /// IANA timezone for the expression
+        #[arg(long)]
+        tz: Option<String>,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) enum IntegrationCommands {
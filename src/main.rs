@@ -55,6 +55,7 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
 mod agent;
 mod approval;
 mod auth;
+mod backup;
 mod channels;
 mod rag {
     pub use zeroclaw::rag::*;
@@ -63,6 +64,7 @@ mod config;
 mod cron;
 mod daemon;
 mod doctor;
+mod eval;
 mod gateway;
 mod hardware;
 mod health;
@@ -79,6 +81,7 @@ mod providers;
 mod runtime;
 mod security;
 mod service;
+mod sessions;
 mod skills;
 mod tools;
 mod tunnel;
@@ -99,6 +102,10 @@ struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Config profile to overlay (`[profiles.<name>]` or `config.<name>.toml`); overrides ZEROCLAW_PROFILE
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -196,6 +203,10 @@ Examples:
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Resume (or start) a recorded session by id
+        #[arg(long)]
+        session: Option<String>,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -331,12 +342,89 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Run agent evaluation suites
+    #[command(long_about = "\
+Run agent evaluation suites.
+
+Each task runs in a throwaway workspace against the configured provider \
+(or a replay cassette), then its assertions are checked: files exist, \
+tools called, output matches, or an LLM-judged rubric. Reports include \
+tool-call traces, provider-reported tokens and latency, and can be compared \
+against a saved baseline.
+
+Examples:
+  zeroclaw eval run evals/core.toml
+  zeroclaw eval run evals/core.toml --baseline evals/core.baseline.json
+  ZEROCLAW_PROVIDER_CASSETTE=replay-lenient:evals/core.cassette.json zeroclaw eval run evals/core.toml
+  zeroclaw eval compare evals/core.baseline.json report.json")]
+    Eval {
+        #[command(subcommand)]
+        eval_command: EvalCommands,
+    },
+
+    /// Export and import conversation sessions
+    #[command(long_about = "\
+Export and import conversation sessions.
+
+CLI and channel conversations are recorded under <workspace>/sessions/ \
+with credentials redacted. Export them as Markdown, OpenAI-format JSONL \
+(including tool calls and results) or HTML, or import an OpenAI/Anthropic \
+JSONL transcript to seed a new session.
+
+Examples:
+  zeroclaw sessions list
+  zeroclaw sessions export cli-20260101-120000 --format html -o chat.html
+  zeroclaw sessions import transcript.jsonl --id support-case
+  zeroclaw agent --session support-case")]
+    Sessions {
+        #[command(subcommand)]
+        sessions_command: SessionsCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
         migrate_command: MigrateCommands,
     },
 
+    /// Manage the encrypted secret store
+    #[command(long_about = "\
+Manage the encrypted secret store.
+
+Values in config.toml and auth profiles are encrypted with the key in \
+.secret_key next to config.toml. 'rotate-key' decrypts every stored value, \
+generates a new key and re-encrypts everything under it. Values given as \
+secret:// references are not stored and are unaffected.
+
+Examples:
+  zeroclaw secrets rotate-key")]
+    Secrets {
+        #[command(subcommand)]
+        secrets_command: SecretsCommands,
+    },
+
+    /// Back up and restore the config directory and workspace
+    #[command(long_about = "\
+Back up and restore the config directory and workspace.
+
+Archives hold config.toml, .secret_key, auth profiles, memory, the cron \
+store, sessions, skills and identity files. Live SQLite databases are \
+copied with SQLite's online backup API. Archives are encrypted when \
+backup.passphrase or ZEROCLAW_BACKUP_PASSPHRASE is set. Targets are a \
+local directory or s3://bucket/prefix (AWS S3, MinIO and other \
+S3-compatible services).
+
+Examples:
+  zeroclaw backup create
+  zeroclaw backup create --target s3://my-bucket/zeroclaw
+  zeroclaw backup list
+  zeroclaw backup restore latest --into /tmp/zeroclaw-restore
+  zeroclaw backup schedule '0 3 * * *'")]
+    Backup {
+        #[command(subcommand)]
+        backup_command: BackupCommands,
+    },
+
     /// Manage provider subscription authentication profiles
     Auth {
         #[command(subcommand)]
@@ -435,6 +523,16 @@ Examples:
 enum ConfigCommands {
     /// Dump the full configuration JSON Schema to stdout
     Schema,
+    /// Show the files and profile the configuration is composed from
+    Show {
+        /// Print every effective value (secrets redacted) with the file, profile,
+        /// environment variable or default it came from
+        #[arg(long)]
+        resolved: bool,
+    },
+    /// Check provider, route, channel and cron references, conflicting
+    /// autonomy settings and allowlist entries that never match
+    Validate,
 }
 
 #[derive(Subcommand, Debug)]
@@ -534,10 +632,120 @@ enum MigrateCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum EvalCommands {
+    /// Run an evaluation suite and report pass/fail, traces, tokens and latency
+    Run {
+        /// Suite file (TOML)
+        suite: std::path::PathBuf,
+        /// Only run tasks with these names (repeatable)
+        #[arg(long = "task", value_name = "NAME")]
+        tasks: Vec<String>,
+        /// Write the JSON report to this file
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+        /// Compare against a saved baseline report and fail on regressions
+        #[arg(long)]
+        baseline: Option<std::path::PathBuf>,
+        /// Save this run as the new baseline
+        #[arg(long)]
+        save_baseline: Option<std::path::PathBuf>,
+        /// Keep task workspaces instead of deleting them
+        #[arg(long)]
+        keep_workspaces: bool,
+        /// Directory for task workspaces (default: <tmp>/zeroclaw-eval/<suite>)
+        #[arg(long)]
+        workspace_root: Option<std::path::PathBuf>,
+    },
+    /// Compare a report against a baseline without running anything
+    Compare {
+        /// Baseline report (JSON)
+        baseline: std::path::PathBuf,
+        /// Current report (JSON)
+        report: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionsCommands {
+    /// List recorded sessions
+    List,
+    /// Export a session as Markdown, OpenAI-format JSONL or HTML
+    Export {
+        /// Session id (see `zeroclaw sessions list`)
+        id: String,
+        /// Output format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "jsonl", "html"])]
+        format: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Seed a new session from an OpenAI or Anthropic JSONL transcript
+    Import {
+        /// Transcript file (JSONL or JSON)
+        file: std::path::PathBuf,
+        /// Id for the new session (default: import-<timestamp>)
+        #[arg(long)]
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretsCommands {
+    /// Re-encrypt every stored secret under a freshly generated key
+    RotateKey,
+}
+
+#[derive(Subcommand, Debug)]
+enum BackupCommands {
+    /// Archive the config directory and workspace
+    Create {
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+        /// Do not encrypt, even when a passphrase is configured
+        #[arg(long)]
+        plain: bool,
+    },
+    /// List stored backups, oldest first
+    List {
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Restore a backup over the config directory and workspace
+    Restore {
+        /// Backup name from `zeroclaw backup list`, or `latest`
+        name: String,
+        /// Local directory or s3://bucket/prefix (default: backup.target)
+        #[arg(long)]
+        target: Option<String>,
+        /// Unpack under this directory instead of over the live state
+        #[arg(long)]
+        into: Option<std::path::PathBuf>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Run `backup create` on a cron schedule (replaces an existing schedule)
+    Schedule {
+        /// Cron expression, e.g. "0 3 * * *"
+        expression: String,
+        /// IANA timezone for the expression
+        #[arg(long)]
+        tz: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum CronCommands {
     /// List all scheduled tasks
-    List,
+    List {
+        /// Show chained jobs as a dependency tree
+        #[arg(long)]
+        tree: bool,
+    },
     /// Add a new scheduled task
     Add {
         /// Cron expression
@@ -548,6 +756,14 @@ enum CronCommands {
         /// Command to run
         command: String,
     },
+    /// Add a task chained after other jobs
+    AddAfter {
+        /// Upstream job: <id>[:success|failure|always]
+        #[arg(long = "after", value_name = "JOB", required = true)]
+        after: Vec<String>,
+        /// Command to run
+        command: String,
+    },
     /// Add a one-shot scheduled task at an RFC3339 timestamp
     AddAt {
         /// One-shot timestamp in RFC3339 format
@@ -569,6 +785,21 @@ enum CronCommands {
         /// Command to run
         command: String,
     },
+    /// Add an event-triggered task (file watch, gateway hook or channel pattern)
+    AddTrigger {
+        /// Trigger: watch:<path>[:<glob>], hook:<name> or channel:<regex>
+        trigger: String,
+        /// Command to run
+        command: String,
+    },
+    /// Show recent runs of a task
+    Runs {
+        /// Task ID
+        id: String,
+        /// Maximum number of runs to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
     /// Remove a scheduled task
     Remove {
         /// Task ID
@@ -590,6 +821,18 @@ enum CronCommands {
         /// New job name
         #[arg(long)]
         name: Option<String>,
+        /// Replace upstream dependencies: <id>[:success|failure|always]
+        #[arg(long = "after", value_name = "JOB")]
+        after: Vec<String>,
+        /// When the previous run is still going: skip, queue or cancel_previous
+        #[arg(long)]
+        overlap: Option<String>,
+        /// Runs to make up after downtime: none, once or all:<max>
+        #[arg(long = "catch-up", value_name = "POLICY")]
+        catch_up: Option<String>,
+        /// Random start delay of up to this many seconds
+        #[arg(long = "jitter", value_name = "SECS")]
+        jitter_secs: Option<u64>,
     },
     /// Pause a scheduled task
     Pause {
@@ -732,6 +975,13 @@ async fn main() -> Result<()> {
         std::env::set_var("ZEROCLAW_CONFIG_DIR", config_dir);
     }
 
+    if let Some(profile) = &cli.profile {
+        if profile.trim().is_empty() {
+            bail!("--profile cannot be empty");
+        }
+        std::env::set_var(config::layers::PROFILE_ENV, profile);
+    }
+
     // Completions must remain stdout-only and should not load config or initialize logging.
     // This avoids warnings/log lines corrupting sourced completion scripts.
     if let Commands::Completions { shell } = &cli.command {
@@ -816,9 +1066,18 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
-        } => agent::run(config, message, provider, model, temperature, peripheral)
-            .await
-            .map(|_| ()),
+            session,
+        } => agent::run(
+            config,
+            message,
+            provider,
+            model,
+            temperature,
+            peripheral,
+            session,
+        )
+        .await
+        .map(|_| ()),
 
         Commands::Gateway { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
@@ -839,7 +1098,7 @@ async fn main() -> Result<()> {
             } else {
                 info!("🧠 Starting ZeroClaw Daemon on {host}:{port}");
             }
-            daemon::run(config, host, port).await
+            Box::pin(daemon::run(config, host, port)).await
         }
 
         Commands::Status => {
@@ -1008,6 +1267,12 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::Eval { eval_command } => eval::handle_command(eval_command, &config).await,
+
+        Commands::Sessions { sessions_command } => {
+            sessions::handle_command(sessions_command, &config)
+        }
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
@@ -1016,6 +1281,14 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Secrets { secrets_command } => {
+            security::secrets::handle_command(secrets_command, &config)
+        }
+
+        Commands::Backup { backup_command } => {
+            Box::pin(backup::handle_command(backup_command, &config)).await
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1035,6 +1308,60 @@ async fn main() -> Result<()> {
                 );
                 Ok(())
             }
+            ConfigCommands::Show { resolved } => {
+                if resolved {
+                    let parsed = Config::parse_file(&config.config_path).await?;
+                    let table = |config: &Config| -> Result<toml::Table> {
+                        Ok(toml::Table::try_from(config)?)
+                    };
+                    print!(
+                        "{}",
+                        config::layers::render_resolved(
+                            &table(&config)?,
+                            &table(&parsed)?,
+                            &config.sources,
+                            &config.secret_refs,
+                        )
+                    );
+                } else {
+                    println!("Config:  {}", config.config_path.display());
+                    println!(
+                        "Profile: {}",
+                        config.sources.profile.as_deref().unwrap_or("(none)")
+                    );
+                    println!("Files (merge order, later wins):");
+                    for file in &config.sources.files {
+                        println!("  {}", file.display());
+                    }
+                    if !config.sources.interpolated.is_empty() {
+                        println!("Values from ${{ENV}}:");
+                        for path in &config.sources.interpolated {
+                            println!("  {path}");
+                        }
+                    }
+                }
+                Ok(())
+            }
+            ConfigCommands::Validate => {
+                let diagnostics = config::validate::validate(&config);
+                for diagnostic in &diagnostics {
+                    println!("{diagnostic}");
+                }
+                let errors = diagnostics
+                    .iter()
+                    .filter(|d| d.severity == config::validate::Severity::Error)
+                    .count();
+                let warnings = diagnostics.len() - errors;
+                if diagnostics.is_empty() {
+                    println!("✅ {} is valid", config.config_path.display());
+                    Ok(())
+                } else if errors == 0 {
+                    println!("⚠️  {warnings} warning(s)");
+                    Ok(())
+                } else {
+                    bail!("{errors} error(s), {warnings} warning(s)")
+                }
+            }
         },
     }
 }
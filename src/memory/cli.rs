@@ -290,7 +290,7 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
     Ok(())
 }
 
-fn parse_category(s: &str) -> MemoryCategory {
+pub(crate) fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
         "daily" => MemoryCategory::Daily,
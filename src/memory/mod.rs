@@ -8,6 +8,7 @@ pub mod markdown;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
+pub mod scoped;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
@@ -23,6 +24,7 @@ pub use markdown::MarkdownMemory;
 pub use none::NoneMemory;
 #[cfg(feature = "memory-postgres")]
 pub use postgres::PostgresMemory;
+pub use scoped::ScopedMemory;
 pub use sqlite::SqliteMemory;
 pub use traits::{Memory, MemoryCategory};
 #[allow(unused_imports)]
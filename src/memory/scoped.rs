@@ -0,0 +1,189 @@
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Recall over-fetch factor for isolated views, since entries from other
+/// namespaces are filtered out after the backend ranks them.
+const RECALL_OVERFETCH: usize = 4;
+
+/// Restricted view of another memory backend, used for delegate sub-agents.
+///
+/// An isolated view prefixes every key with `<namespace>:` and only sees
+/// keys under that prefix; a read-only view sees everything but rejects
+/// `store` and `forget`.
+pub struct ScopedMemory {
+    inner: Arc<dyn Memory>,
+    prefix: Option<String>,
+    read_only: bool,
+}
+
+impl ScopedMemory {
+    pub fn isolated(inner: Arc<dyn Memory>, namespace: &str) -> Self {
+        Self {
+            inner,
+            prefix: Some(format!("{}:", namespace.trim())),
+            read_only: false,
+        }
+    }
+
+    pub fn read_only(inner: Arc<dyn Memory>) -> Self {
+        Self {
+            inner,
+            prefix: None,
+            read_only: true,
+        }
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}{key}"),
+            None => key.to_string(),
+        }
+    }
+
+    /// Keep entries inside the namespace, with the prefix stripped.
+    fn visible(&self, entries: Vec<MemoryEntry>) -> Vec<MemoryEntry> {
+        let Some(prefix) = &self.prefix else {
+            return entries;
+        };
+        entries
+            .into_iter()
+            .filter_map(|mut entry| {
+                entry.key = entry.key.strip_prefix(prefix.as_str())?.to_string();
+                Some(entry)
+            })
+            .collect()
+    }
+
+    fn ensure_writable(&self) -> anyhow::Result<()> {
+        if self.read_only {
+            anyhow::bail!("Memory is read-only for this agent");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Memory for ScopedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.ensure_writable()?;
+        self.inner
+            .store(&self.scoped_key(key), content, category, session_id)
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let fetch = if self.prefix.is_some() {
+            limit.saturating_mul(RECALL_OVERFETCH)
+        } else {
+            limit
+        };
+        let mut entries = self.visible(self.inner.recall(query, fetch, session_id).await?);
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entry = self.inner.get(&self.scoped_key(key)).await?;
+        Ok(self.visible(entry.into_iter().collect()).pop())
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        Ok(self.visible(self.inner.list(category, session_id).await?))
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.ensure_writable()?;
+        self.inner.forget(&self.scoped_key(key)).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        if self.prefix.is_some() {
+            Ok(self.list(None, None).await?.len())
+        } else {
+            self.inner.count().await
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MarkdownMemory;
+
+    #[tokio::test]
+    async fn isolated_view_prefixes_keys_and_hides_other_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let parent: Arc<dyn Memory> =
+            Arc::new(crate::memory::SqliteMemory::new(tmp.path()).unwrap());
+        parent
+            .store(
+                "shared_fact",
+                "parent knows rust",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let scoped = ScopedMemory::isolated(parent.clone(), "researcher");
+        scoped
+            .store("finding", "rust is fast", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(parent.get("researcher:finding").await.unwrap().is_some());
+        assert_eq!(scoped.get("finding").await.unwrap().unwrap().key, "finding");
+        assert!(scoped.get("shared_fact").await.unwrap().is_none());
+
+        let recalled = scoped.recall("rust", 10, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].key, "finding");
+        assert_eq!(scoped.count().await.unwrap(), 1);
+
+        assert!(scoped.forget("finding").await.unwrap());
+        assert!(parent.get("researcher:finding").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn read_only_view_recalls_but_rejects_writes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let parent: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        parent
+            .store("fact", "the sky is blue", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let scoped = ScopedMemory::read_only(parent);
+        assert!(!scoped.recall("sky", 5, None).await.unwrap().is_empty());
+        let err = scoped
+            .store("x", "y", MemoryCategory::Core, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"));
+        assert!(scoped.forget("fact").await.is_err());
+    }
+}
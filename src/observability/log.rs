@@ -79,6 +79,19 @@ impl Observer for LogObserver {
                     "llm.response"
                 );
             }
+            ObserverEvent::LlmReasoning {
+                provider,
+                model,
+                content,
+            } => {
+                // Log size only; reasoning text can contain sensitive context.
+                info!(
+                    provider = %provider,
+                    model = %model,
+                    chars = content.chars().count(),
+                    "llm.reasoning"
+                );
+            }
         }
     }
 
@@ -4,6 +4,7 @@ pub mod noop;
 #[cfg(feature = "observability-otel")]
 pub mod otel;
 pub mod prometheus;
+pub mod tail;
 pub mod traits;
 pub mod verbose;
 
@@ -15,14 +16,19 @@ pub use noop::NoopObserver;
 #[cfg(feature = "observability-otel")]
 pub use otel::OtelObserver;
 pub use prometheus::PrometheusObserver;
+pub use tail::TailObserver;
 pub use traits::{Observer, ObserverEvent};
 #[allow(unused_imports)]
 pub use verbose::VerboseObserver;
 
 use crate::config::ObservabilityConfig;
 
-/// Factory: create the right observer from config
+/// Factory: create the right observer from config, mirrored to the admin event tail
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
+    Box::new(TailObserver::new(create_backend(config)))
+}
+
+fn create_backend(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
         "prometheus" => Box::new(PrometheusObserver::new()),
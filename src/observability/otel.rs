@@ -185,6 +185,7 @@ impl Observer for OtelObserver {
                 );
             }
             ObserverEvent::LlmRequest { .. }
+            | ObserverEvent::LlmReasoning { .. }
             | ObserverEvent::ToolCallStart { .. }
             | ObserverEvent::TurnComplete => {}
             ObserverEvent::LlmResponse {
@@ -0,0 +1,211 @@
+use super::traits::{Observer, ObserverEvent, ObserverMetric};
+use parking_lot::Mutex;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+/// Events kept for clients that connect after they were recorded.
+const RECENT_CAPACITY: usize = 200;
+
+struct EventTail {
+    sender: broadcast::Sender<serde_json::Value>,
+    recent: Mutex<VecDeque<serde_json::Value>>,
+}
+
+static TAIL: OnceLock<EventTail> = OnceLock::new();
+
+fn tail() -> &'static EventTail {
+    TAIL.get_or_init(|| EventTail {
+        sender: broadcast::channel(RECENT_CAPACITY).0,
+        recent: Mutex::new(VecDeque::with_capacity(RECENT_CAPACITY)),
+    })
+}
+
+/// Subscribe to events recorded by any observer in this process.
+pub fn subscribe() -> broadcast::Receiver<serde_json::Value> {
+    tail().sender.subscribe()
+}
+
+/// The most recent events, oldest first.
+pub fn recent() -> Vec<serde_json::Value> {
+    tail().recent.lock().iter().cloned().collect()
+}
+
+fn publish(event: serde_json::Value) {
+    let tail = tail();
+    {
+        let mut recent = tail.recent.lock();
+        if recent.len() == RECENT_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(event.clone());
+    }
+    let _ = tail.sender.send(event);
+}
+
+/// JSON form of an event for the admin event tail. Reasoning content is
+/// left out, like every non-interactive observer.
+pub fn event_json(event: &ObserverEvent) -> serde_json::Value {
+    let at = chrono::Utc::now().to_rfc3339();
+    let mut value = match event {
+        ObserverEvent::AgentStart { provider, model } => {
+            serde_json::json!({"type": "agent_start", "provider": provider, "model": model})
+        }
+        ObserverEvent::LlmRequest {
+            provider,
+            model,
+            messages_count,
+        } => serde_json::json!({
+            "type": "llm_request",
+            "provider": provider,
+            "model": model,
+            "messages_count": messages_count,
+        }),
+        ObserverEvent::LlmResponse {
+            provider,
+            model,
+            duration,
+            success,
+            error_message,
+        } => serde_json::json!({
+            "type": "llm_response",
+            "provider": provider,
+            "model": model,
+            "duration_ms": duration.as_millis(),
+            "success": success,
+            "error": error_message,
+        }),
+        ObserverEvent::LlmReasoning {
+            provider, model, ..
+        } => {
+            serde_json::json!({"type": "llm_reasoning", "provider": provider, "model": model})
+        }
+        ObserverEvent::AgentEnd {
+            provider,
+            model,
+            duration,
+            tokens_used,
+            cost_usd,
+        } => serde_json::json!({
+            "type": "agent_end",
+            "provider": provider,
+            "model": model,
+            "duration_ms": duration.as_millis(),
+            "tokens_used": tokens_used,
+            "cost_usd": cost_usd,
+        }),
+        ObserverEvent::ToolCallStart { tool } => {
+            serde_json::json!({"type": "tool_call_start", "tool": tool})
+        }
+        ObserverEvent::ToolCall {
+            tool,
+            duration,
+            success,
+        } => serde_json::json!({
+            "type": "tool_call",
+            "tool": tool,
+            "duration_ms": duration.as_millis(),
+            "success": success,
+        }),
+        ObserverEvent::TurnComplete => serde_json::json!({"type": "turn_complete"}),
+        ObserverEvent::ChannelMessage { channel, direction } => serde_json::json!({
+            "type": "channel_message",
+            "channel": channel,
+            "direction": direction,
+        }),
+        ObserverEvent::HeartbeatTick => serde_json::json!({"type": "heartbeat_tick"}),
+        ObserverEvent::Error { component, message } => serde_json::json!({
+            "type": "error",
+            "component": component,
+            "message": message,
+        }),
+    };
+    value["at"] = serde_json::Value::String(at);
+    value
+}
+
+/// Wraps an observer and mirrors its events to the process-wide event tail
+/// read by the admin dashboard. Name and downcasting pass through to `inner`.
+pub struct TailObserver {
+    inner: Box<dyn Observer>,
+}
+
+impl TailObserver {
+    pub fn new(inner: Box<dyn Observer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Observer for TailObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        publish(event_json(event));
+        self.inner.record_event(event);
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.inner.as_any()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::{NoopObserver, PrometheusObserver};
+    use std::time::Duration;
+
+    #[test]
+    fn tail_observer_passes_name_and_downcast_through() {
+        let observer = TailObserver::new(Box::new(PrometheusObserver::new()));
+        assert_eq!(observer.name(), "prometheus");
+        assert!(observer
+            .as_any()
+            .downcast_ref::<PrometheusObserver>()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn recorded_events_reach_subscribers_and_recent_buffer() {
+        let mut receiver = subscribe();
+        let observer = TailObserver::new(Box::new(NoopObserver));
+        observer.record_event(&ObserverEvent::ToolCall {
+            tool: "tail_test_tool".into(),
+            duration: Duration::from_millis(12),
+            success: true,
+        });
+
+        let event = loop {
+            match receiver.recv().await {
+                Ok(event) if event["tool"] == "tail_test_tool" => break event,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(e) => panic!("event tail closed: {e}"),
+            }
+        };
+        assert_eq!(event["type"], "tool_call");
+        assert_eq!(event["duration_ms"], 12);
+        assert!(recent().iter().any(|e| e["tool"] == "tail_test_tool"));
+    }
+
+    #[test]
+    fn reasoning_content_is_not_exposed() {
+        let event = event_json(&ObserverEvent::LlmReasoning {
+            provider: "p".into(),
+            model: "m".into(),
+            content: "private chain of thought".into(),
+        });
+        assert_eq!(event["type"], "llm_reasoning");
+        assert!(!event.to_string().contains("private chain of thought"));
+    }
+}
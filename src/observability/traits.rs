@@ -27,6 +27,15 @@ pub enum ObserverEvent {
         success: bool,
         error_message: Option<String>,
     },
+    /// Reasoning ("thinking") content returned alongside an LLM response.
+    ///
+    /// Only interactive observers should print `content`; it is never part of
+    /// the reply delivered to users or channels.
+    LlmReasoning {
+        provider: String,
+        model: String,
+        content: String,
+    },
     /// The agent session has finished.
     ///
     /// Carries aggregate usage data (tokens, cost) when the provider reports it.
@@ -33,6 +33,12 @@ impl Observer for VerboseObserver {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 eprintln!("< Receive (success={success}, duration_ms={ms})");
             }
+            ObserverEvent::LlmReasoning { content, .. } => {
+                eprintln!("< Reasoning (chars={})", content.chars().count());
+                for line in content.lines() {
+                    eprintln!("  | {line}");
+                }
+            }
             ObserverEvent::ToolCallStart { tool } => {
                 eprintln!("> Tool {tool}");
             }
@@ -88,6 +94,11 @@ mod tests {
             success: true,
             error_message: None,
         });
+        obs.record_event(&ObserverEvent::LlmReasoning {
+            provider: "openrouter".into(),
+            model: "claude".into(),
+            content: "step one\nstep two".into(),
+        });
         obs.record_event(&ObserverEvent::ToolCallStart {
             tool: "shell".into(),
         });
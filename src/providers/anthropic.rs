@@ -1,6 +1,7 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, Reasoning, ReasoningOptions, ResponseSchema, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -51,8 +52,21 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<serde_json::Value>,
 }
 
+/// Default `max_tokens` for native chat requests.
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+/// Smallest extended-thinking budget the Messages API accepts.
+const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
+/// Description attached to the forced tool that carries a structured response.
+const STRUCTURED_RESPONSE_TOOL_DESCRIPTION: &str =
+    "Return the final answer as structured data matching this input schema.";
+
 #[derive(Debug, Serialize)]
 struct NativeMessage {
     role: String,
@@ -62,6 +76,10 @@ struct NativeMessage {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
 enum NativeContentOut {
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     #[serde(rename = "text")]
     Text {
         text: String,
@@ -128,6 +146,16 @@ struct SystemBlock {
 struct NativeChatResponse {
     #[serde(default)]
     content: Vec<NativeContentIn>,
+    #[serde(default)]
+    usage: Option<NativeUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -142,6 +170,12 @@ struct NativeContentIn {
     name: Option<String>,
     #[serde(default)]
     input: Option<serde_json::Value>,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    data: Option<String>,
 }
 
 impl AnthropicProvider {
@@ -200,7 +234,9 @@ impl AnthropicProvider {
                     | NativeContentOut::ToolResult { cache_control, .. } => {
                         *cache_control = Some(CacheControl::ephemeral());
                     }
-                    NativeContentOut::ToolUse { .. } => {}
+                    NativeContentOut::ToolUse { .. }
+                    | NativeContentOut::Thinking { .. }
+                    | NativeContentOut::RedactedThinking { .. } => {}
                 }
             }
         }
@@ -229,6 +265,34 @@ impl AnthropicProvider {
         Some(native_tools)
     }
 
+    /// Append the forced structured-response tool (Anthropic has no native JSON
+    /// mode, so the schema is enforced through `tool_choice`).
+    fn apply_response_schema<'a>(
+        tools: &mut Option<Vec<NativeToolSpec<'a>>>,
+        schema: &'a ResponseSchema,
+        tool_name: &'a str,
+    ) -> serde_json::Value {
+        tools.get_or_insert_with(Vec::new).push(NativeToolSpec {
+            name: tool_name,
+            description: STRUCTURED_RESPONSE_TOOL_DESCRIPTION,
+            input_schema: &schema.schema,
+            cache_control: None,
+        });
+        serde_json::json!({ "type": "tool", "name": tool_name })
+    }
+
+    /// Move the forced structured-response tool call into the response text.
+    fn take_structured_response(response: &mut ProviderChatResponse, tool_name: &str) {
+        if let Some(pos) = response
+            .tool_calls
+            .iter()
+            .position(|call| call.name == tool_name)
+        {
+            let call = response.tool_calls.remove(pos);
+            response.text = Some(call.arguments);
+        }
+    }
+
     fn parse_assistant_tool_call_message(content: &str) -> Option<Vec<NativeContentOut>> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_calls = value
@@ -236,6 +300,14 @@ impl AnthropicProvider {
             .and_then(|v| serde_json::from_value::<Vec<ProviderToolCall>>(v.clone()).ok())?;
 
         let mut blocks = Vec::new();
+        // Signed thinking blocks must precede tool_use blocks when replaying a
+        // tool-call turn with extended thinking enabled.
+        if let Some(reasoning) = value
+            .get("reasoning")
+            .and_then(|v| serde_json::from_value::<Reasoning>(v.clone()).ok())
+        {
+            blocks.extend(reasoning.blocks.iter().filter_map(Self::thinking_block_out));
+        }
         if let Some(text) = value
             .get("content")
             .and_then(serde_json::Value::as_str)
@@ -260,6 +332,45 @@ impl AnthropicProvider {
         Some(blocks)
     }
 
+    fn thinking_block_out(block: &serde_json::Value) -> Option<NativeContentOut> {
+        let field = |name: &str| {
+            block
+                .get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)
+        };
+        match block.get("type").and_then(serde_json::Value::as_str)? {
+            "thinking" => Some(NativeContentOut::Thinking {
+                thinking: field("thinking").unwrap_or_default(),
+                signature: field("signature")?,
+            }),
+            "redacted_thinking" => Some(NativeContentOut::RedactedThinking {
+                data: field("data")?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Translate reasoning controls into the `thinking` request parameter.
+    ///
+    /// Extended thinking cannot be combined with a forced `tool_choice`, so it
+    /// is skipped for structured-output requests.
+    fn thinking_config(
+        reasoning: Option<&ReasoningOptions>,
+        forced_tool: bool,
+    ) -> Option<(serde_json::Value, u32)> {
+        if forced_tool {
+            return None;
+        }
+        let budget = reasoning?
+            .effective_budget()?
+            .max(MIN_THINKING_BUDGET_TOKENS);
+        Some((
+            serde_json::json!({ "type": "enabled", "budget_tokens": budget }),
+            budget,
+        ))
+    }
+
     fn parse_tool_result_message(content: &str) -> Option<NativeMessage> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_use_id = value
@@ -361,9 +472,32 @@ impl AnthropicProvider {
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
+        let mut thinking_parts = Vec::new();
+        let mut thinking_blocks = Vec::new();
 
         for block in response.content {
             match block.kind.as_str() {
+                "thinking" => {
+                    let thinking = block.thinking.unwrap_or_default();
+                    if let Some(signature) = block.signature {
+                        thinking_blocks.push(serde_json::json!({
+                            "type": "thinking",
+                            "thinking": thinking,
+                            "signature": signature,
+                        }));
+                    }
+                    if !thinking.trim().is_empty() {
+                        thinking_parts.push(thinking);
+                    }
+                }
+                "redacted_thinking" => {
+                    if let Some(data) = block.data {
+                        thinking_blocks.push(serde_json::json!({
+                            "type": "redacted_thinking",
+                            "data": data,
+                        }));
+                    }
+                }
                 "text" => {
                     if let Some(text) = block.text.map(|t| t.trim().to_string()) {
                         if !text.is_empty() {
@@ -396,6 +530,18 @@ impl AnthropicProvider {
                 Some(text_parts.join("\n"))
             },
             tool_calls,
+            reasoning: if thinking_parts.is_empty() && thinking_blocks.is_empty() {
+                None
+            } else {
+                Some(Reasoning {
+                    text: thinking_parts.join("\n"),
+                    blocks: thinking_blocks,
+                })
+            },
+            usage: response.usage.map(|usage| TokenUsage {
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+            }),
         }
     }
 
@@ -468,13 +614,26 @@ impl Provider for AnthropicProvider {
             Self::apply_cache_to_last_message(&mut messages);
         }
 
+        let mut tools = Self::convert_tools(request.tools);
+        let structured_tool_name = request.response_schema.map(ResponseSchema::wire_name);
+        let tool_choice = request
+            .response_schema
+            .zip(structured_tool_name.as_deref())
+            .map(|(schema, name)| Self::apply_response_schema(&mut tools, schema, name));
+
+        let thinking = Self::thinking_config(request.reasoning, tool_choice.is_some());
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: thinking.as_ref().map_or(DEFAULT_MAX_TOKENS, |(_, budget)| {
+                budget.saturating_add(DEFAULT_MAX_TOKENS)
+            }),
             system: system_prompt,
             messages,
-            temperature,
-            tools: Self::convert_tools(request.tools),
+            // Extended thinking requires the default temperature of 1.0.
+            temperature: if thinking.is_some() { 1.0 } else { temperature },
+            tools,
+            tool_choice,
+            thinking: thinking.map(|(config, _)| config),
         };
 
         let req = self
@@ -490,7 +649,19 @@ impl Provider for AnthropicProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let mut parsed = Self::parse_native_response(native_response);
+        if let Some(name) = structured_tool_name.as_deref() {
+            Self::take_structured_response(&mut parsed, name);
+        }
+        Ok(parsed)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+            structured_output: true,
+        }
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -540,6 +711,8 @@ impl Provider for AnthropicProvider {
             } else {
                 Some(&tool_specs)
             },
+            response_schema: None,
+            reasoning: None,
         };
         self.chat(request, model, temperature).await
     }
@@ -1123,6 +1296,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_native_response_separates_signed_thinking() {
+        let response: NativeChatResponse = serde_json::from_value(serde_json::json!({
+            "content": [
+                {"type": "thinking", "thinking": "Check the file first.", "signature": "sig-1"},
+                {"type": "redacted_thinking", "data": "opaque"},
+                {"type": "text", "text": "Reading it now."},
+                {"type": "tool_use", "id": "toolu_1", "name": "file_read", "input": {"path": "a"}}
+            ],
+            "usage": {"input_tokens": 120, "output_tokens": 30}
+        }))
+        .unwrap();
+
+        let parsed = AnthropicProvider::parse_native_response(response);
+        assert_eq!(parsed.text.as_deref(), Some("Reading it now."));
+        let reasoning = parsed.reasoning.expect("reasoning should be captured");
+        assert_eq!(reasoning.text, "Check the file first.");
+        assert_eq!(reasoning.blocks.len(), 2);
+        assert_eq!(reasoning.blocks[0]["signature"], "sig-1");
+        assert_eq!(reasoning.blocks[1]["type"], "redacted_thinking");
+        assert_eq!(
+            parsed.usage,
+            Some(TokenUsage {
+                input_tokens: 120,
+                output_tokens: 30
+            })
+        );
+    }
+
+    #[test]
+    fn convert_messages_replays_thinking_blocks_before_tool_use() {
+        let assistant = serde_json::json!({
+            "content": null,
+            "tool_calls": [{"id": "toolu_1", "name": "file_read", "arguments": "{}"}],
+            "reasoning": {
+                "text": "Check the file first.",
+                "blocks": [{"type": "thinking", "thinking": "Check the file first.", "signature": "sig-1"}]
+            }
+        });
+        let messages = vec![
+            ChatMessage::user("read a"),
+            ChatMessage::assistant(assistant.to_string()),
+        ];
+
+        let (_, native) = AnthropicProvider::convert_messages(&messages);
+        let json = serde_json::to_value(&native[1]).unwrap();
+        assert_eq!(json["content"][0]["type"], "thinking");
+        assert_eq!(json["content"][0]["signature"], "sig-1");
+        assert_eq!(json["content"][1]["type"], "tool_use");
+    }
+
+    #[test]
+    fn thinking_config_respects_budget_and_forced_tools() {
+        let options = ReasoningOptions {
+            budget_tokens: Some(200),
+            effort: None,
+        };
+        let (config, budget) = AnthropicProvider::thinking_config(Some(&options), false).unwrap();
+        assert_eq!(budget, MIN_THINKING_BUDGET_TOKENS);
+        assert_eq!(config["type"], "enabled");
+        assert!(AnthropicProvider::thinking_config(Some(&options), true).is_none());
+        assert!(AnthropicProvider::thinking_config(None, false).is_none());
+
+        let effort = ReasoningOptions {
+            budget_tokens: None,
+            effort: Some("high".into()),
+        };
+        let (_, budget) = AnthropicProvider::thinking_config(Some(&effort), false).unwrap();
+        assert_eq!(budget, 16_384);
+    }
+
     #[test]
     fn backward_compatibility_native_chat_request() {
         // Test that requests without cache_control serialize identically to old format
@@ -1139,10 +1383,13 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            tool_choice: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("cache_control"));
+        assert!(!json.contains("thinking"));
         assert!(json.contains(r#""system":"System""#));
     }
 
@@ -1315,4 +1562,78 @@ mod tests {
 
         server_handle.abort();
     }
+
+    #[tokio::test]
+    async fn chat_with_response_schema_forces_tool_and_returns_json_text() {
+        use axum::{routing::post, Json, Router};
+        use std::sync::{Arc, Mutex};
+        use tokio::net::TcpListener;
+
+        let captured: Arc<Mutex<Option<serde_json::Value>>> = Arc::new(Mutex::new(None));
+        let captured_clone = captured.clone();
+
+        let app = Router::new().route(
+            "/v1/messages",
+            post(move |Json(body): Json<serde_json::Value>| {
+                let cap = captured_clone.clone();
+                async move {
+                    *cap.lock().unwrap() = Some(body);
+                    Json(serde_json::json!({
+                        "content": [{
+                            "type": "tool_use",
+                            "id": "toolu_1",
+                            "name": "weather_report",
+                            "input": {"city": "Oslo", "temp_c": 4}
+                        }]
+                    }))
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let provider = AnthropicProvider {
+            credential: Some("test-key".to_string()),
+            base_url: format!("http://{addr}"),
+        };
+        let schema = ResponseSchema::new(
+            "weather report",
+            serde_json::json!({
+                "type": "object",
+                "properties": {"city": {"type": "string"}, "temp_c": {"type": "number"}},
+                "required": ["city", "temp_c"]
+            }),
+        );
+        let messages = vec![ChatMessage::user("Weather in Oslo?")];
+
+        let response = provider
+            .chat(
+                ProviderChatRequest {
+                    messages: &messages,
+                    tools: None,
+                    response_schema: Some(&schema),
+                    reasoning: None,
+                },
+                "claude-sonnet-4",
+                0.0,
+            )
+            .await
+            .unwrap();
+
+        assert!(response.tool_calls.is_empty());
+        let parsed: serde_json::Value =
+            serde_json::from_str(response.text.as_deref().unwrap()).unwrap();
+        assert_eq!(parsed["city"], "Oslo");
+
+        let body = captured.lock().unwrap().take().unwrap();
+        assert_eq!(body["tool_choice"]["type"], "tool");
+        assert_eq!(body["tool_choice"]["name"], "weather_report");
+        assert_eq!(body["tools"][0]["name"], "weather_report");
+
+        server_handle.abort();
+    }
 }
@@ -0,0 +1,950 @@
+//! Provider record/replay harness.
+//!
+//! [`RecordingProvider`] wraps a live provider and writes every exchange
+//! (request, response, tool calls) to a JSON cassette file.
+//! [`ReplayProvider`] serves a cassette back offline, keyed by a hash of the
+//! request, so agent-loop and tool-parser regressions can run deterministically
+//! without network access.
+//!
+//! Selected via `[runtime.provider_cassette]` or `ZEROCLAW_PROVIDER_CASSETTE`
+//! and applied by the top-level provider factories through [`apply`].
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, Reasoning, ResponseSchema,
+    TokenUsage, ToolCall,
+};
+use super::Provider;
+use crate::config::{CassetteMatching, CassetteMode, ProviderCassetteConfig};
+use anyhow::Context;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// Current cassette file format version.
+pub const CASSETTE_VERSION: u32 = 1;
+
+/// System prompt sections whose content changes between otherwise identical
+/// runs (clock, host). They are left out of the request hash.
+const VOLATILE_SECTIONS: &[&str] = &["## Current Date & Time", "## Runtime"];
+
+/// Cassettes this process is recording, by absolute path. Every provider a
+/// run builds (routes, fallbacks, delegates) appends to the same recording.
+static RECORDINGS: LazyLock<Mutex<HashMap<PathBuf, Arc<Mutex<Cassette>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Which provider entry point produced an exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeKind {
+    ChatWithSystem,
+    ChatWithHistory,
+    Chat,
+    ChatWithTools,
+}
+
+impl ExchangeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ChatWithSystem => "chat_with_system",
+            Self::ChatWithHistory => "chat_with_history",
+            Self::Chat => "chat",
+            Self::ChatWithTools => "chat_with_tools",
+        }
+    }
+}
+
+/// Request side of a recorded exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub kind: ExchangeKind,
+    pub model: String,
+    pub temperature: f64,
+    pub messages: Vec<ChatMessage>,
+    /// Tool definitions (`ToolSpec` or provider-native JSON).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
+}
+
+impl RecordedRequest {
+    fn new(kind: ExchangeKind, messages: Vec<ChatMessage>, model: &str, temperature: f64) -> Self {
+        Self {
+            kind,
+            model: model.to_string(),
+            temperature,
+            messages,
+            tools: Vec::new(),
+            response_schema: None,
+        }
+    }
+
+    fn from_system(system_prompt: Option<&str>, message: &str, model: &str, temp: f64) -> Self {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        Self::new(ExchangeKind::ChatWithSystem, messages, model, temp)
+    }
+
+    fn from_chat(request: &ChatRequest<'_>, model: &str, temperature: f64) -> Self {
+        let mut recorded = Self::new(
+            ExchangeKind::Chat,
+            request.messages.to_vec(),
+            model,
+            temperature,
+        );
+        recorded.tools = request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|spec| serde_json::to_value(spec).ok())
+            .collect();
+        recorded.response_schema = request.response_schema.cloned();
+        recorded
+    }
+
+    /// Stable SHA-256 over everything that identifies the request except the
+    /// sampling temperature and the [`VOLATILE_SECTIONS`] of system prompts.
+    pub fn hash(&self) -> String {
+        let messages: Vec<ChatMessage> = self
+            .messages
+            .iter()
+            .map(|message| {
+                let mut message = message.clone();
+                if message.role == "system" {
+                    message.content = strip_volatile_sections(&message.content);
+                }
+                message
+            })
+            .collect();
+        let canonical = serde_json::json!({
+            "kind": self.kind,
+            "model": self.model,
+            "messages": messages,
+            "tools": self.tools,
+            "response_schema": self.response_schema,
+        });
+        let bytes = serde_json::to_vec(&canonical).unwrap_or_default();
+        hex::encode(Sha256::digest(&bytes))
+    }
+}
+
+/// Drop every `#`/`##` section listed in [`VOLATILE_SECTIONS`], up to the
+/// next heading of the same or a higher level.
+fn strip_volatile_sections(prompt: &str) -> String {
+    let mut stable = String::with_capacity(prompt.len());
+    let mut skipping = false;
+    for line in prompt.split_inclusive('\n') {
+        if line.starts_with("# ") || line.starts_with("## ") {
+            skipping = VOLATILE_SECTIONS.contains(&line.trim_end());
+        }
+        if !skipping {
+            stable.push_str(line);
+        }
+    }
+    stable
+}
+
+/// Response side of a recorded exchange.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<Reasoning>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+impl From<&ChatResponse> for RecordedResponse {
+    fn from(response: &ChatResponse) -> Self {
+        Self {
+            text: response.text.clone(),
+            tool_calls: response.tool_calls.clone(),
+            reasoning: response.reasoning.clone(),
+            usage: response.usage,
+        }
+    }
+}
+
+impl From<RecordedResponse> for ChatResponse {
+    fn from(response: RecordedResponse) -> Self {
+        Self {
+            text: response.text,
+            tool_calls: response.tool_calls,
+            reasoning: response.reasoning,
+            usage: response.usage,
+        }
+    }
+}
+
+/// One request/response pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub hash: String,
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// Capabilities of the recorded provider, replayed so the agent loop picks
+/// the same tool-calling path offline.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RecordedCapabilities {
+    #[serde(default)]
+    pub native_tool_calling: bool,
+    #[serde(default)]
+    pub vision: bool,
+    #[serde(default)]
+    pub structured_output: bool,
+}
+
+/// On-disk cassette.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    pub version: u32,
+    #[serde(default)]
+    pub capabilities: RecordedCapabilities,
+    #[serde(default)]
+    pub interactions: Vec<Interaction>,
+}
+
+impl Default for Cassette {
+    fn default() -> Self {
+        Self {
+            version: CASSETTE_VERSION,
+            capabilities: RecordedCapabilities::default(),
+            interactions: Vec::new(),
+        }
+    }
+}
+
+impl Cassette {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let cassette: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse cassette {}", path.display()))?;
+        if cassette.version > CASSETTE_VERSION {
+            anyhow::bail!(
+                "Cassette {} has unsupported version {} (max {CASSETTE_VERSION})",
+                path.display(),
+                cassette.version
+            );
+        }
+        Ok(cassette)
+    }
+
+    /// Write the cassette via a temp file + rename so readers never see a
+    /// partially written file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .with_context(|| format!("Failed to write cassette {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write cassette {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Wrap the provider produced by `build` according to the cassette settings.
+///
+/// Replay never calls `build`, so no credentials or network are needed.
+pub fn apply(
+    settings: Option<&ProviderCassetteConfig>,
+    build: impl FnOnce() -> anyhow::Result<Box<dyn Provider>>,
+) -> anyhow::Result<Box<dyn Provider>> {
+    let Some(settings) = settings else {
+        return build();
+    };
+    let path = PathBuf::from(shellexpand::tilde(settings.path.trim()).into_owned());
+    match settings.mode {
+        CassetteMode::Record => {
+            tracing::info!(path = %path.display(), "Recording provider exchanges to cassette");
+            Ok(Box::new(RecordingProvider::new(build()?, path)?))
+        }
+        CassetteMode::Replay => {
+            tracing::info!(path = %path.display(), "Replaying provider exchanges from cassette");
+            Ok(Box::new(ReplayProvider::from_file(
+                &path,
+                settings.matching,
+            )?))
+        }
+    }
+}
+
+/// Provider wrapper that records every successful exchange to a cassette.
+pub struct RecordingProvider {
+    inner: Box<dyn Provider>,
+    path: PathBuf,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl RecordingProvider {
+    /// Record to `path`. The first recorder for a path in this process starts
+    /// a fresh cassette, replacing any previous recording; later ones append
+    /// to it.
+    pub fn new(inner: Box<dyn Provider>, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let key = std::path::absolute(&path).unwrap_or_else(|_| path.clone());
+        let mut recordings = RECORDINGS.lock();
+        let cassette = match recordings.get(&key) {
+            Some(cassette) => Arc::clone(cassette),
+            None => {
+                let cassette = Cassette {
+                    capabilities: RecordedCapabilities {
+                        native_tool_calling: inner.supports_native_tools(),
+                        vision: inner.supports_vision(),
+                        structured_output: inner.supports_structured_output(),
+                    },
+                    ..Cassette::default()
+                };
+                cassette.save(&path)?;
+                let cassette = Arc::new(Mutex::new(cassette));
+                recordings.insert(key, Arc::clone(&cassette));
+                cassette
+            }
+        };
+        Ok(Self {
+            inner,
+            path,
+            cassette,
+        })
+    }
+
+    fn record(&self, request: RecordedRequest, response: RecordedResponse) -> anyhow::Result<()> {
+        let mut cassette = self.cassette.lock();
+        cassette.interactions.push(Interaction {
+            hash: request.hash(),
+            request,
+            response,
+        });
+        cassette.save(&self.path)
+    }
+
+    fn record_text(&self, request: RecordedRequest, text: &str) -> anyhow::Result<()> {
+        let response = RecordedResponse {
+            text: Some(text.to_string()),
+            ..RecordedResponse::default()
+        };
+        self.record(request, response)
+    }
+}
+
+#[async_trait]
+impl Provider for RecordingProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let text = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        let request = RecordedRequest::from_system(system_prompt, message, model, temperature);
+        self.record_text(request, &text)?;
+        Ok(text)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let text = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await?;
+        let request = RecordedRequest::new(
+            ExchangeKind::ChatWithHistory,
+            messages.to_vec(),
+            model,
+            temperature,
+        );
+        self.record_text(request, &text)?;
+        Ok(text)
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self.inner.chat(request, model, temperature).await?;
+        let recorded = RecordedRequest::from_chat(&request, model, temperature);
+        self.record(recorded, RecordedResponse::from(&response))?;
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await?;
+        let mut recorded = RecordedRequest::new(
+            ExchangeKind::ChatWithTools,
+            messages.to_vec(),
+            model,
+            temperature,
+        );
+        recorded.tools = tools.to_vec();
+        self.record(recorded, RecordedResponse::from(&response))?;
+        Ok(response)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+}
+
+/// Provider that serves responses from a recorded cassette.
+///
+/// Each interaction is served at most once. `Strict` matching requires the
+/// request hash to match; `Lenient` falls back to the next unused interaction
+/// of the same kind, in recorded order. Hashes are recomputed from the
+/// recorded requests, so cassettes follow changes to the hashing rules.
+pub struct ReplayProvider {
+    cassette: Cassette,
+    hashes: Vec<String>,
+    matching: CassetteMatching,
+    source: String,
+    used: Mutex<Vec<bool>>,
+}
+
+impl ReplayProvider {
+    pub fn new(cassette: Cassette, matching: CassetteMatching) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        let hashes = cassette
+            .interactions
+            .iter()
+            .map(|interaction| interaction.request.hash())
+            .collect();
+        Self {
+            cassette,
+            hashes,
+            matching,
+            source: "in-memory cassette".into(),
+            used: Mutex::new(used),
+        }
+    }
+
+    pub fn from_file(path: &Path, matching: CassetteMatching) -> anyhow::Result<Self> {
+        let mut provider = Self::new(Cassette::load(path)?, matching);
+        provider.source = path.display().to_string();
+        Ok(provider)
+    }
+
+    /// Number of interactions not served yet.
+    pub fn remaining(&self) -> usize {
+        self.used.lock().iter().filter(|used| !**used).count()
+    }
+
+    fn serve(&self, request: &RecordedRequest) -> anyhow::Result<RecordedResponse> {
+        let hash = request.hash();
+        let mut used = self.used.lock();
+        let interactions = &self.cassette.interactions;
+        let unused = |i: &usize| !used[*i];
+
+        let exact = (0..interactions.len())
+            .filter(unused)
+            .find(|&i| self.hashes[i] == hash);
+        let index = match (exact, self.matching) {
+            (Some(i), _) => Some(i),
+            (None, CassetteMatching::Lenient) => (0..interactions.len())
+                .filter(unused)
+                .find(|&i| interactions[i].request.kind == request.kind),
+            (None, CassetteMatching::Strict) => None,
+        };
+
+        let Some(index) = index else {
+            let remaining = used.iter().filter(|used| !**used).count();
+            anyhow::bail!(
+                "Cassette miss: no recorded {} exchange for model '{}' (request hash {}); \
+                 {remaining} unused interaction(s) in {}",
+                request.kind.as_str(),
+                request.model,
+                &hash[..12],
+                self.source
+            );
+        };
+        used[index] = true;
+        Ok(interactions[index].response.clone())
+    }
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        let caps = self.cassette.capabilities;
+        ProviderCapabilities {
+            native_tool_calling: caps.native_tool_calling,
+            vision: caps.vision,
+            structured_output: caps.structured_output,
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = RecordedRequest::from_system(system_prompt, message, model, temperature);
+        Ok(self.serve(&request)?.text.unwrap_or_default())
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = RecordedRequest::new(
+            ExchangeKind::ChatWithHistory,
+            messages.to_vec(),
+            model,
+            temperature,
+        );
+        Ok(self.serve(&request)?.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let recorded = RecordedRequest::from_chat(&request, model, temperature);
+        Ok(self.serve(&recorded)?.into())
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let mut recorded = RecordedRequest::new(
+            ExchangeKind::ChatWithTools,
+            messages.to_vec(),
+            model,
+            temperature,
+        );
+        recorded.tools = tools.to_vec();
+        Ok(self.serve(&recorded)?.into())
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.cassette.capabilities.native_tool_calling
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.cassette.capabilities.vision
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.cassette.capabilities.structured_output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolSpec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fake live provider: answers with a tool call first, then text.
+    struct LiveProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Provider for LiveProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                ..ProviderCapabilities::default()
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("echo: {message}"))
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Ok(ChatResponse {
+                    text: None,
+                    tool_calls: vec![ToolCall {
+                        id: "call_1".into(),
+                        name: "shell".into(),
+                        arguments: r#"{"command":"ls"}"#.into(),
+                    }],
+                    reasoning: None,
+                    usage: None,
+                })
+            } else {
+                Ok(ChatResponse {
+                    text: Some("done".into()),
+                    tool_calls: vec![],
+                    reasoning: Some(Reasoning {
+                        text: "thought".into(),
+                        blocks: vec![],
+                    }),
+                    usage: None,
+                })
+            }
+        }
+    }
+
+    fn tool_specs() -> Vec<ToolSpec> {
+        vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }]
+    }
+
+    async fn record_session(path: &Path) {
+        let recorder = RecordingProvider::new(
+            Box::new(LiveProvider {
+                calls: AtomicUsize::new(0),
+            }),
+            path,
+        )
+        .unwrap();
+        let tools = tool_specs();
+        let first = [ChatMessage::user("list files")];
+        let request = ChatRequest {
+            messages: &first,
+            tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
+        };
+        recorder.chat(request, "model-a", 0.7).await.unwrap();
+
+        let second = [
+            ChatMessage::user("list files"),
+            ChatMessage::assistant("calling shell"),
+        ];
+        let request = ChatRequest {
+            messages: &second,
+            tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
+        };
+        recorder.chat(request, "model-a", 0.7).await.unwrap();
+        recorder
+            .chat_with_system(Some("be brief"), "hi", "model-a", 0.7)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn recording_then_strict_replay_round_trips() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cassettes/session.json");
+        record_session(&path).await;
+
+        let cassette = Cassette::load(&path).unwrap();
+        assert_eq!(cassette.interactions.len(), 3);
+        assert!(cassette.capabilities.native_tool_calling);
+
+        let replay = ReplayProvider::from_file(&path, CassetteMatching::Strict).unwrap();
+        assert!(replay.supports_native_tools());
+
+        let tools = tool_specs();
+        let second = [
+            ChatMessage::user("list files"),
+            ChatMessage::assistant("calling shell"),
+        ];
+        let request = ChatRequest {
+            messages: &second,
+            tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
+        };
+        // Served by hash, not by position.
+        let response = replay.chat(request, "model-a", 0.2).await.unwrap();
+        assert_eq!(response.text.as_deref(), Some("done"));
+        assert_eq!(response.reasoning.unwrap().text, "thought");
+
+        let first = [ChatMessage::user("list files")];
+        let request = ChatRequest {
+            messages: &first,
+            tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
+        };
+        let response = replay.chat(request, "model-a", 0.7).await.unwrap();
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].name, "shell");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+
+        let text = replay
+            .chat_with_system(Some("be brief"), "hi", "model-a", 0.7)
+            .await
+            .unwrap();
+        assert_eq!(text, "echo: hi");
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn strict_replay_rejects_unrecorded_request() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("session.json");
+        record_session(&path).await;
+
+        let replay = ReplayProvider::from_file(&path, CassetteMatching::Strict).unwrap();
+        let err = replay
+            .chat_with_system(Some("be brief"), "something else", "model-a", 0.7)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Cassette miss"));
+        assert_eq!(replay.remaining(), 3);
+    }
+
+    #[tokio::test]
+    async fn lenient_replay_falls_back_to_next_interaction_of_same_kind() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("session.json");
+        record_session(&path).await;
+
+        let replay = ReplayProvider::from_file(&path, CassetteMatching::Lenient).unwrap();
+        let drifted = [ChatMessage::user("list files (at 12:01)")];
+        let request = ChatRequest {
+            messages: &drifted,
+            tools: None,
+            response_schema: None,
+            reasoning: None,
+        };
+        let response = replay.chat(request, "model-b", 0.7).await.unwrap();
+        assert_eq!(response.tool_calls[0].name, "shell");
+        let response = replay.chat(request, "model-b", 0.7).await.unwrap();
+        assert_eq!(response.text.as_deref(), Some("done"));
+        assert!(replay.chat(request, "model-b", 0.7).await.is_err());
+    }
+
+    #[test]
+    fn request_hash_ignores_temperature_but_not_messages() {
+        let a = RecordedRequest::from_system(None, "hi", "m", 0.1);
+        let b = RecordedRequest::from_system(None, "hi", "m", 0.9);
+        let c = RecordedRequest::from_system(None, "hello", "m", 0.1);
+        assert_eq!(a.hash(), b.hash());
+        assert_ne!(a.hash(), c.hash());
+    }
+
+    #[test]
+    fn request_hash_ignores_volatile_prompt_sections() {
+        let prompt = |time: &str, host: &str| {
+            format!(
+                "## Tools\n\nshell\n\n## Current Date & Time\n\n{time} (UTC)\n\n\
+                 ## Runtime\n\nHost: {host} | OS: linux | Model: m\n\n## Safety\n\nBe careful\n"
+            )
+        };
+        let a = RecordedRequest::from_system(
+            Some(&prompt("2026-01-01 10:00:00", "ci")),
+            "hi",
+            "m",
+            0.1,
+        );
+        let b = RecordedRequest::from_system(
+            Some(&prompt("2026-03-04 18:30:12", "laptop")),
+            "hi",
+            "m",
+            0.1,
+        );
+        assert_eq!(a.hash(), b.hash());
+
+        let changed = prompt("2026-01-01 10:00:00", "ci").replace("Be careful", "Be bold");
+        let c = RecordedRequest::from_system(Some(&changed), "hi", "m", 0.1);
+        assert_ne!(a.hash(), c.hash());
+    }
+
+    #[tokio::test]
+    async fn recorders_for_the_same_path_share_one_cassette() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("shared.json");
+        std::fs::write(&path, "stale recording").unwrap();
+
+        let live = || {
+            Box::new(LiveProvider {
+                calls: AtomicUsize::new(0),
+            })
+        };
+        let main = RecordingProvider::new(live(), &path).unwrap();
+        let route = RecordingProvider::new(live(), &path).unwrap();
+        main.chat_with_system(None, "one", "main-model", 0.7)
+            .await
+            .unwrap();
+        route
+            .chat_with_system(None, "two", "route-model", 0.7)
+            .await
+            .unwrap();
+
+        let cassette = Cassette::load(&path).unwrap();
+        let models: Vec<&str> = cassette
+            .interactions
+            .iter()
+            .map(|interaction| interaction.request.model.as_str())
+            .collect();
+        assert_eq!(models, ["main-model", "route-model"]);
+    }
+
+    /// Tool standing in for `shell` in the agent-loop replay test.
+    struct ListTool;
+
+    #[async_trait]
+    impl crate::tools::Tool for ListTool {
+        fn name(&self) -> &str {
+            "shell"
+        }
+
+        fn description(&self) -> &str {
+            "Run a command"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+        ) -> anyhow::Result<crate::tools::ToolResult> {
+            Ok(crate::tools::ToolResult {
+                success: true,
+                output: "notes.txt".into(),
+                error: None,
+            })
+        }
+    }
+
+    async fn run_agent_loop(provider: &dyn Provider, started_at: &str) -> anyhow::Result<String> {
+        let tools: Vec<Box<dyn crate::tools::Tool>> = vec![Box::new(ListTool)];
+        let mut history = vec![
+            ChatMessage::system(format!(
+                "You are an agent.\n\n## Current Date & Time\n\n{started_at} (UTC)\n\n\
+                 ## Runtime\n\nHost: box | OS: linux | Model: model-a\n"
+            )),
+            ChatMessage::user("list files"),
+        ];
+        crate::agent::loop_::run_tool_call_loop(
+            provider,
+            &mut history,
+            &tools,
+            &crate::observability::NoopObserver,
+            "live",
+            "model-a",
+            0.7,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            5,
+            None,
+            None,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn agent_loop_replays_strictly_at_a_later_time() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("agent.json");
+        let recorder = RecordingProvider::new(
+            Box::new(LiveProvider {
+                calls: AtomicUsize::new(0),
+            }),
+            &path,
+        )
+        .unwrap();
+        let recorded = run_agent_loop(&recorder, "2026-01-01 10:00:00")
+            .await
+            .unwrap();
+        assert_eq!(recorded, "done");
+
+        let replay = ReplayProvider::from_file(&path, CassetteMatching::Strict).unwrap();
+        assert_eq!(replay.remaining(), 2);
+        let replayed = run_agent_loop(&replay, "2026-02-03 08:15:42")
+            .await
+            .unwrap();
+        assert_eq!(replayed, recorded);
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn apply_replay_skips_building_live_provider() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("empty.json");
+        Cassette::default().save(&path).unwrap();
+
+        let settings = ProviderCassetteConfig {
+            mode: CassetteMode::Replay,
+            path: path.display().to_string(),
+            matching: CassetteMatching::Strict,
+        };
+        let provider = apply(Some(&settings), || {
+            anyhow::bail!("live provider must not be built during replay")
+        });
+        assert!(provider.is_ok());
+
+        let missing = ProviderCassetteConfig {
+            path: tmp.path().join("missing.json").display().to_string(),
+            ..settings
+        };
+        assert!(apply(Some(&missing), || anyhow::bail!("unused")).is_err());
+    }
+}
@@ -2,10 +2,11 @@
 //! Most LLM APIs follow the same `/v1/chat/completions` format.
 //! This module provides a single implementation that works for all of them.
 
-use crate::providers::openai_wire::{NativeContent, build_vision_content};
+use crate::providers::openai_wire::{build_vision_content, NativeContent, NativeUsage};
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, ToolCall as ProviderToolCall,
+    Provider, Reasoning, StreamChunk, StreamError, StreamOptions, StreamResult,
+    ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -273,6 +274,8 @@ struct Message {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<NativeUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -304,6 +307,26 @@ fn strip_think_tags(s: &str) -> String {
     result.trim().to_string()
 }
 
+/// Collect the contents of `<think>...</think>` blocks (the inverse of
+/// [`strip_think_tags`]). An unclosed block contributes its remaining text.
+fn extract_think_blocks(s: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("<think>") {
+        let body = &rest[start + "<think>".len()..];
+        let (inner, next) = match body.find("</think>") {
+            Some(end) => (&body[..end], &body[end + "</think>".len()..]),
+            None => (body, ""),
+        };
+        let inner = inner.trim();
+        if !inner.is_empty() {
+            blocks.push(inner.to_string());
+        }
+        rest = next;
+    }
+    blocks
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ResponseMessage {
     #[serde(default)]
@@ -337,6 +360,19 @@ impl ResponseMessage {
             .unwrap_or_default()
     }
 
+    /// Reasoning kept apart from the answer: inline `<think>` blocks plus
+    /// `reasoning_content`, unless the latter already stands in for the answer.
+    fn reasoning(&self) -> Option<Reasoning> {
+        let content = self.content.as_deref().unwrap_or_default();
+        let mut parts = extract_think_blocks(content);
+        if !strip_think_tags(content).is_empty() {
+            if let Some(reasoning) = self.reasoning_content.as_deref() {
+                parts.push(reasoning.trim().to_string());
+            }
+        }
+        Reasoning::from_text(parts.join("\n\n"))
+    }
+
     fn effective_content_optional(&self) -> Option<String> {
         if let Some(content) = self.content.as_ref().filter(|c| !c.is_empty()) {
             let stripped = strip_think_tags(content);
@@ -425,6 +461,8 @@ struct NativeChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -912,6 +950,7 @@ impl OpenAiCompatibleProvider {
 
     fn parse_native_response(message: ResponseMessage) -> ProviderChatResponse {
         let text = message.effective_content_optional();
+        let reasoning = message.reasoning();
         let tool_calls = message
             .tool_calls
             .unwrap_or_default()
@@ -938,7 +977,12 @@ impl OpenAiCompatibleProvider {
             })
             .collect::<Vec<_>>();
 
-        ProviderChatResponse { text, tool_calls }
+        ProviderChatResponse {
+            text,
+            tool_calls,
+            reasoning,
+            usage: None,
+        }
     }
 
     fn is_native_tool_schema_unsupported(status: reqwest::StatusCode, error: &str) -> bool {
@@ -969,6 +1013,7 @@ impl Provider for OpenAiCompatibleProvider {
         crate::providers::traits::ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: false,
         }
     }
 
@@ -1267,6 +1312,8 @@ impl Provider for OpenAiCompatibleProvider {
                 return Ok(ProviderChatResponse {
                     text: Some(text),
                     tool_calls: vec![],
+                    reasoning: None,
+                    usage: None,
                 });
             }
         };
@@ -1277,6 +1324,7 @@ impl Provider for OpenAiCompatibleProvider {
 
         let body = response.text().await?;
         let chat_response = parse_chat_response_body(&self.name, &body)?;
+        let usage = chat_response.usage.map(Into::into);
         let choice = chat_response
             .choices
             .into_iter()
@@ -1284,6 +1332,7 @@ impl Provider for OpenAiCompatibleProvider {
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
 
         let text = choice.message.effective_content_optional();
+        let reasoning = choice.message.reasoning();
         let tool_calls = choice
             .message
             .tool_calls
@@ -1301,7 +1350,12 @@ impl Provider for OpenAiCompatibleProvider {
             })
             .collect::<Vec<_>>();
 
-        Ok(ProviderChatResponse { text, tool_calls })
+        Ok(ProviderChatResponse {
+            text,
+            tool_calls,
+            reasoning,
+            usage,
+        })
     }
 
     async fn chat(
@@ -1330,6 +1384,7 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(false),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            reasoning_effort: request.reasoning.and_then(|r| r.effort.clone()),
         };
 
         let url = self.chat_completions_url();
@@ -1351,6 +1406,8 @@ impl Provider for OpenAiCompatibleProvider {
                         .map(|text| ProviderChatResponse {
                             text: Some(text),
                             tool_calls: vec![],
+                            reasoning: None,
+                            usage: None,
                         })
                         .map_err(|responses_err| {
                             anyhow::anyhow!(
@@ -1378,6 +1435,8 @@ impl Provider for OpenAiCompatibleProvider {
                 return Ok(ProviderChatResponse {
                     text: Some(text),
                     tool_calls: vec![],
+                    reasoning: None,
+                    usage: None,
                 });
             }
 
@@ -1388,6 +1447,8 @@ impl Provider for OpenAiCompatibleProvider {
                     .map(|text| ProviderChatResponse {
                         text: Some(text),
                         tool_calls: vec![],
+                        reasoning: None,
+                        usage: None,
                     })
                     .map_err(|responses_err| {
                         anyhow::anyhow!(
@@ -1401,6 +1462,7 @@ impl Provider for OpenAiCompatibleProvider {
         }
 
         let native_response: ApiChatResponse = response.json().await?;
+        let usage = native_response.usage.map(Into::into);
         let message = native_response
             .choices
             .into_iter()
@@ -1408,7 +1470,10 @@ impl Provider for OpenAiCompatibleProvider {
             .map(|choice| choice.message)
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
 
-        Ok(Self::parse_native_response(message))
+        Ok(ProviderChatResponse {
+            usage,
+            ..Self::parse_native_response(message)
+        })
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -2035,7 +2100,10 @@ mod tests {
         assert_eq!(converted.len(), 1);
         assert_eq!(converted[0].role, "tool");
         assert_eq!(converted[0].tool_call_id.as_deref(), Some("call_abc"));
-        assert_eq!(converted[0].content.as_ref().and_then(|c| c.as_text_str()), Some("done"));
+        assert_eq!(
+            converted[0].content.as_ref().and_then(|c| c.as_text_str()),
+            Some("done")
+        );
     }
 
     #[test]
@@ -2421,6 +2489,32 @@ mod tests {
         assert_eq!(msg.effective_content(), "Hello from Venice!");
     }
 
+    #[test]
+    fn reasoning_separates_think_blocks_and_reasoning_content() {
+        let json = r#"{"choices":[{"message":{"content":"<think>plan</think>Answer","reasoning_content":"more"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let msg = &resp.choices[0].message;
+        assert_eq!(msg.effective_content(), "Answer");
+        assert_eq!(msg.reasoning().unwrap().text, "plan\n\nmore");
+    }
+
+    #[test]
+    fn reasoning_none_when_reasoning_content_is_the_answer() {
+        let json =
+            r#"{"choices":[{"message":{"content":"","reasoning_content":"Only reasoning"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        assert!(resp.choices[0].message.reasoning().is_none());
+    }
+
+    #[test]
+    fn extract_think_blocks_handles_multiple_and_unclosed() {
+        assert_eq!(
+            extract_think_blocks("<think>a</think>x<think> b </think>y<think>c"),
+            vec!["a", "b", "c"]
+        );
+        assert!(extract_think_blocks("no tags").is_empty());
+    }
+
     // ----------------------------------------------------------
     // SSE streaming reasoning_content fallback tests
     // ----------------------------------------------------------
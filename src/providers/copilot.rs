@@ -11,6 +11,7 @@
 //! GitHub could change or revoke this at any time, which would break all
 //! third-party integrations simultaneously.
 
+use crate::providers::openai_wire::NativeUsage;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
     Provider, ToolCall as ProviderToolCall,
@@ -134,6 +135,8 @@ struct NativeFunctionCall {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<NativeUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -340,6 +343,7 @@ impl CopilotProvider {
         }
 
         let api_response: ApiChatResponse = response.json().await?;
+        let usage = api_response.usage.map(Into::into);
         let choice = api_response
             .choices
             .into_iter()
@@ -363,6 +367,8 @@ impl CopilotProvider {
         Ok(ProviderChatResponse {
             text: choice.message.content,
             tool_calls,
+            reasoning: None,
+            usage,
         })
     }
 
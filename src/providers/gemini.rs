@@ -3,7 +3,10 @@
 //! - Gemini CLI OAuth tokens (reuse existing ~/.gemini/ authentication)
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
-use crate::providers::traits::{ChatMessage, Provider};
+use crate::providers::traits::{
+    prompt_guided_messages, ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities,
+    ReasoningOptions, ResponseSchema, TokenUsage,
+};
 use async_trait::async_trait;
 use directories::UserDirs;
 use reqwest::Client;
@@ -106,6 +109,44 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+    #[serde(rename = "thinkingConfig", skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<ThinkingConfig>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct ThinkingConfig {
+    #[serde(rename = "thinkingBudget")]
+    thinking_budget: u32,
+}
+
+/// JSON Schema keywords that Gemini's OpenAPI-subset `responseSchema` rejects.
+const GEMINI_UNSUPPORTED_SCHEMA_KEYS: &[&str] = &[
+    "additionalProperties",
+    "$schema",
+    "$id",
+    "$defs",
+    "definitions",
+    "const",
+];
+
+/// Strip JSON Schema keywords Gemini does not accept in `responseSchema`.
+fn sanitize_gemini_schema(schema: &serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .filter(|(key, _)| !GEMINI_UNSUPPORTED_SCHEMA_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), sanitize_gemini_schema(value)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(sanitize_gemini_schema).collect())
+        }
+        other => other.clone(),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -114,6 +155,28 @@ struct GenerateContentResponse {
     error: Option<ApiError>,
     #[serde(default)]
     response: Option<Box<GenerateContentResponse>>,
+    #[serde(default, rename = "usageMetadata")]
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+    #[serde(default)]
+    thoughts_token_count: u64,
+}
+
+impl From<UsageMetadata> for TokenUsage {
+    fn from(usage: UsageMetadata) -> Self {
+        Self {
+            input_tokens: usage.prompt_token_count,
+            output_tokens: usage.candidates_token_count + usage.thoughts_token_count,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -337,6 +400,51 @@ impl GeminiProvider {
         }
     }
 
+    /// Split chat history into Gemini `contents` and an optional system instruction.
+    fn convert_messages(messages: &[ChatMessage]) -> (Vec<Content>, Option<Content>) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in messages {
+            match msg.role.as_str() {
+                "system" => {
+                    system_parts.push(&msg.content);
+                }
+                "user" => {
+                    contents.push(Content {
+                        role: Some("user".to_string()),
+                        parts: vec![Part {
+                            text: msg.content.clone(),
+                        }],
+                    });
+                }
+                "assistant" => {
+                    // Gemini API uses "model" role instead of "assistant"
+                    contents.push(Content {
+                        role: Some("model".to_string()),
+                        parts: vec![Part {
+                            text: msg.content.clone(),
+                        }],
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part {
+                    text: system_parts.join("\n\n"),
+                }],
+            })
+        };
+
+        (contents, system_instruction)
+    }
+
     fn resolve_oauth_project_id() -> Option<String> {
         for key in [
             "GEMINI_CODE_ASSIST_PROJECT",
@@ -361,7 +469,9 @@ impl GeminiProvider {
         system_instruction: Option<Content>,
         model: &str,
         temperature: f64,
-    ) -> anyhow::Result<String> {
+        response_schema: Option<&ResponseSchema>,
+        reasoning: Option<&ReasoningOptions>,
+    ) -> anyhow::Result<(String, Option<TokenUsage>)> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Gemini API key not found. Options:\n\
@@ -378,6 +488,11 @@ impl GeminiProvider {
             generation_config: GenerationConfig {
                 temperature,
                 max_output_tokens: 8192,
+                response_mime_type: response_schema.map(|_| "application/json".to_string()),
+                response_schema: response_schema.map(|s| sanitize_gemini_schema(&s.schema)),
+                thinking_config: reasoning
+                    .and_then(ReasoningOptions::effective_budget)
+                    .map(|thinking_budget| ThinkingConfig { thinking_budget }),
             },
         };
 
@@ -403,17 +518,27 @@ impl GeminiProvider {
             anyhow::bail!("Gemini API error: {}", err.message);
         }
 
-        result
+        let usage = result.usage_metadata.map(Into::into);
+        let text = result
             .candidates
             .and_then(|c| c.into_iter().next())
             .and_then(|c| c.content.parts.into_iter().next())
             .and_then(|p| p.text)
-            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))
+            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))?;
+        Ok((text, usage))
     }
 }
 
 #[async_trait]
 impl Provider for GeminiProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: false,
+            vision: false,
+            structured_output: true,
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -435,8 +560,10 @@ impl Provider for GeminiProvider {
             }],
         }];
 
-        self.send_generate_content(contents, system_instruction, model, temperature)
-            .await
+        let (text, _) = self
+            .send_generate_content(contents, system_instruction, model, temperature, None, None)
+            .await?;
+        Ok(text)
     }
 
     async fn chat_with_history(
@@ -445,48 +572,42 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in messages {
-            match msg.role.as_str() {
-                "system" => {
-                    system_parts.push(&msg.content);
-                }
-                "user" => {
-                    contents.push(Content {
-                        role: Some("user".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                "assistant" => {
-                    // Gemini API uses "model" role instead of "assistant"
-                    contents.push(Content {
-                        role: Some("model".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                _ => {}
-            }
-        }
+        let (contents, system_instruction) = Self::convert_messages(messages);
+        let (text, _) = self
+            .send_generate_content(contents, system_instruction, model, temperature, None, None)
+            .await?;
+        Ok(text)
+    }
 
-        let system_instruction = if system_parts.is_empty() {
-            None
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let reasoning = request.reasoning.filter(|options| options.is_enabled());
+        let messages = if request.response_schema.is_some() {
+            request.messages.to_vec()
         } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
+            prompt_guided_messages(self, &request)?
         };
-
-        self.send_generate_content(contents, system_instruction, model, temperature)
-            .await
+        let (contents, system_instruction) = Self::convert_messages(&messages);
+        let (text, usage) = self
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                temperature,
+                request.response_schema,
+                reasoning,
+            )
+            .await?;
+        Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: Vec::new(),
+            reasoning: None,
+            usage,
+        })
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -648,6 +769,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -683,6 +807,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -721,6 +848,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -750,6 +880,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -762,6 +895,25 @@ mod tests {
         assert!(json.contains("\"maxOutputTokens\":8192"));
     }
 
+    #[test]
+    fn thinking_budget_serializes_into_generation_config() {
+        let config = GenerationConfig {
+            temperature: 0.7,
+            max_output_tokens: 8192,
+            response_mime_type: None,
+            response_schema: None,
+            thinking_config: ReasoningOptions {
+                budget_tokens: None,
+                effort: Some("high".into()),
+            }
+            .effective_budget()
+            .map(|thinking_budget| ThinkingConfig { thinking_budget }),
+        };
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["thinkingConfig"]["thinkingBudget"], 16_384);
+    }
+
     #[test]
     fn internal_request_includes_model() {
         let request = InternalGenerateContentEnvelope {
@@ -779,6 +931,9 @@ mod tests {
                 generation_config: GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
+                    response_mime_type: None,
+                    response_schema: None,
+                    thinking_config: None,
                 },
             },
         };
@@ -0,0 +1,225 @@
+//! Provider wrapper that totals the token usage providers report.
+//!
+//! Used wherever a caller needs to know what a run consumed — eval task
+//! reports and per-user spend quotas — without threading usage through the
+//! agent loop.
+
+use crate::providers::traits::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities, StreamChunk,
+    StreamOptions, StreamResult, TokenUsage, ToolsPayload,
+};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// Calls and tokens counted by a [`MeteredProvider`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub calls: usize,
+    pub tokens: TokenUsage,
+    /// Calls whose provider reported no token counts. Text-only and
+    /// streaming calls never carry usage.
+    pub unmetered_calls: usize,
+}
+
+impl UsageTotals {
+    fn add(&mut self, reported: Option<TokenUsage>) {
+        self.calls += 1;
+        match reported {
+            Some(tokens) => {
+                self.tokens.input_tokens += tokens.input_tokens;
+                self.tokens.output_tokens += tokens.output_tokens;
+            }
+            None => self.unmetered_calls += 1,
+        }
+    }
+}
+
+/// Forwards every call to `inner` and adds the reported usage to a shared
+/// [`UsageTotals`].
+pub struct MeteredProvider {
+    inner: Arc<dyn Provider>,
+    totals: Arc<Mutex<UsageTotals>>,
+}
+
+impl MeteredProvider {
+    pub fn new(inner: Arc<dyn Provider>) -> Self {
+        Self {
+            inner,
+            totals: Arc::new(Mutex::new(UsageTotals::default())),
+        }
+    }
+
+    /// Handle to the running totals; stays valid after the wrapper is moved.
+    pub fn totals_handle(&self) -> Arc<Mutex<UsageTotals>> {
+        Arc::clone(&self.totals)
+    }
+
+    pub fn totals(&self) -> UsageTotals {
+        *self.totals.lock()
+    }
+}
+
+#[async_trait]
+impl Provider for MeteredProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let text = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        self.totals.lock().add(None);
+        Ok(text)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let text = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await?;
+        self.totals.lock().add(None);
+        Ok(text)
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self.inner.chat(request, model, temperature).await?;
+        self.totals.lock().add(response.usage);
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let response = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await?;
+        self.totals.lock().add(response.usage);
+        Ok(response)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.totals.lock().add(None);
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.totals.lock().add(None);
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedUsageProvider;
+
+    #[async_trait]
+    impl Provider for FixedUsageProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("text".into())
+        }
+
+        async fn chat_with_tools(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: &[serde_json::Value],
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            Ok(ChatResponse {
+                text: Some("ok".into()),
+                tool_calls: vec![],
+                reasoning: None,
+                usage: Some(TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 3,
+                }),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn totals_reported_and_unmetered_calls() {
+        let metered = MeteredProvider::new(Arc::new(FixedUsageProvider));
+        metered.chat_with_tools(&[], &[], "m", 0.0).await.unwrap();
+        metered.chat_with_tools(&[], &[], "m", 0.0).await.unwrap();
+        metered.simple_chat("hi", "m", 0.0).await.unwrap();
+
+        let totals = metered.totals();
+        assert_eq!(totals.calls, 3);
+        assert_eq!(totals.unmetered_calls, 1);
+        assert_eq!(totals.tokens.total(), 26);
+    }
+}
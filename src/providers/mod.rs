@@ -18,9 +18,11 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod cassette;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
+pub mod metered;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
@@ -28,12 +30,13 @@ pub mod openai_wire;
 pub mod openrouter;
 pub mod reliable;
 pub mod router;
+pub mod structured;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    Reasoning, ReasoningOptions, ResponseSchema, ToolCall, ToolResultMessage,
 };
 
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
@@ -685,6 +688,8 @@ pub struct ProviderRuntimeOptions {
     pub zeroclaw_dir: Option<PathBuf>,
     pub secrets_encrypt: bool,
     pub reasoning_enabled: Option<bool>,
+    /// Record/replay cassette applied by the top-level provider factories.
+    pub cassette: Option<crate::config::ProviderCassetteConfig>,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -694,6 +699,7 @@ impl Default for ProviderRuntimeOptions {
             zeroclaw_dir: None,
             secrets_encrypt: true,
             reasoning_enabled: None,
+            cassette: None,
         }
     }
 }
@@ -1196,6 +1202,18 @@ pub fn create_resilient_provider_with_options(
     api_url: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
     options: &ProviderRuntimeOptions,
+) -> anyhow::Result<Box<dyn Provider>> {
+    cassette::apply(options.cassette.as_ref(), || {
+        build_resilient_provider(primary_name, api_key, api_url, reliability, options)
+    })
+}
+
+fn build_resilient_provider(
+    primary_name: &str,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    options: &ProviderRuntimeOptions,
 ) -> anyhow::Result<Box<dyn Provider>> {
     let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
 
@@ -1285,6 +1303,28 @@ pub fn create_routed_provider_with_options(
         );
     }
 
+    cassette::apply(options.cassette.as_ref(), || {
+        build_routed_provider(
+            primary_name,
+            api_key,
+            api_url,
+            reliability,
+            model_routes,
+            default_model,
+            options,
+        )
+    })
+}
+
+fn build_routed_provider(
+    primary_name: &str,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    model_routes: &[crate::config::ModelRouteConfig],
+    default_model: &str,
+    options: &ProviderRuntimeOptions,
+) -> anyhow::Result<Box<dyn Provider>> {
     // Collect unique provider names needed
     let mut needed: Vec<String> = vec![primary_name.to_string()];
     for route in model_routes {
@@ -1308,7 +1348,7 @@ pub fn create_routed_provider_with_options(
         let key = routed_credential.or(api_key);
         // Only use api_url for the primary provider
         let url = if name == primary_name { api_url } else { None };
-        match create_resilient_provider_with_options(name, key, url, reliability, options) {
+        match build_resilient_provider(name, key, url, reliability, options) {
             Ok(provider) => providers.push((name.clone(), provider)),
             Err(e) => {
                 if name == primary_name {
@@ -1331,6 +1371,10 @@ pub fn create_routed_provider_with_options(
                 router::Route {
                     provider_name: r.provider.clone(),
                     model: r.model.clone(),
+                    reasoning: Some(traits::ReasoningOptions {
+                        budget_tokens: r.thinking_budget,
+                        effort: r.reasoning_effort.clone(),
+                    }),
                 },
             )
         })
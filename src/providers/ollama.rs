@@ -1,6 +1,6 @@
 use crate::multimodal;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, ToolCall,
+    ChatMessage, ChatResponse, Provider, ProviderCapabilities, Reasoning, TokenUsage, ToolCall,
 };
 use async_trait::async_trait;
 use reqwest::Client;
@@ -25,6 +25,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// JSON schema constraining the response (Ollama structured outputs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,6 +66,22 @@ struct Options {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     message: ResponseMessage,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+impl ApiChatResponse {
+    fn usage(&self) -> Option<TokenUsage> {
+        match (self.prompt_eval_count, self.eval_count) {
+            (None, None) => None,
+            (input, output) => Some(TokenUsage {
+                input_tokens: input.unwrap_or(0),
+                output_tokens: output.unwrap_or(0),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -170,14 +189,17 @@ impl OllamaProvider {
         model: &str,
         temperature: f64,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
+        think: Option<bool>,
     ) -> ChatRequest {
         ChatRequest {
             model: model.to_string(),
             messages,
             stream: false,
             options: Options { temperature },
-            think: self.reasoning_enabled,
+            think: think.or(self.reasoning_enabled),
             tools: tools.map(|t| t.to_vec()),
+            format: format.cloned(),
         }
     }
 
@@ -310,6 +332,8 @@ impl OllamaProvider {
 
     /// Send a request to Ollama and get the parsed response.
     /// Pass `tools` to enable native function-calling for models that support it.
+    /// `think` overrides the provider-level reasoning setting for this request.
+    #[allow(clippy::too_many_arguments)]
     async fn send_request(
         &self,
         messages: Vec<Message>,
@@ -317,8 +341,10 @@ impl OllamaProvider {
         temperature: f64,
         should_auth: bool,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
+        think: Option<bool>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
+        let request = self.build_chat_request(messages, model, temperature, tools, format, think);
 
         let url = format!("{}/api/chat", self.base_url);
 
@@ -378,6 +404,103 @@ impl OllamaProvider {
         Ok(chat_response)
     }
 
+    /// Native chat with optional tools; `think` overrides the provider-level
+    /// reasoning setting, e.g. from a model route's reasoning options.
+    async fn chat_with_think(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+        think: Option<bool>,
+    ) -> anyhow::Result<ChatResponse> {
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+
+        let api_messages = self.convert_messages(messages);
+
+        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
+        // tools_to_openai_format() in loop_.rs — pass them through directly.
+        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
+
+        let response = self
+            .send_request(
+                api_messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                tools_opt,
+                None,
+                think,
+            )
+            .await?;
+
+        let reasoning = response
+            .message
+            .thinking
+            .clone()
+            .and_then(Reasoning::from_text);
+        let usage = response.usage();
+
+        // Native tool calls returned by the model.
+        if !response.message.tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = response
+                .message
+                .tool_calls
+                .iter()
+                .map(|tc| {
+                    let (name, args) = self.extract_tool_name_and_args(tc);
+                    ToolCall {
+                        id: tc
+                            .id
+                            .clone()
+                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                        name,
+                        arguments: serde_json::to_string(&args)
+                            .unwrap_or_else(|_| "{}".to_string()),
+                    }
+                })
+                .collect();
+            let text = if response.message.content.is_empty() {
+                None
+            } else {
+                Some(response.message.content)
+            };
+            return Ok(ChatResponse {
+                text,
+                tool_calls,
+                reasoning,
+                usage,
+            });
+        }
+
+        // Plain text response.
+        let content = response.message.content;
+        if content.is_empty() {
+            if let Some(thinking) = &response.message.thinking {
+                tracing::warn!(
+                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
+                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
+                );
+                return Ok(ChatResponse {
+                    text: Some(format!(
+                        "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
+                        if thinking.len() > 200 { &thinking[..200] } else { thinking }
+                    )),
+                    tool_calls: vec![],
+                    reasoning: None,
+                    usage,
+                });
+            }
+            tracing::warn!("Ollama returned empty content with no tool calls");
+        }
+        Ok(ChatResponse {
+            text: Some(content),
+            tool_calls: vec![],
+            reasoning,
+            usage,
+        })
+    }
+
     /// Convert Ollama tool calls to the JSON format expected by parse_tool_calls in loop_.rs
     ///
     /// Handles quirky model behavior where tool calls are wrapped:
@@ -456,6 +579,7 @@ impl Provider for OllamaProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -490,7 +614,15 @@ impl Provider for OllamaProvider {
         });
 
         let response = self
-            .send_request(messages, &normalized_model, temperature, should_auth, None)
+            .send_request(
+                messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                None,
+                None,
+                None,
+            )
             .await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
@@ -540,6 +672,8 @@ impl Provider for OllamaProvider {
                 temperature,
                 should_auth,
                 None,
+                None,
+                None,
             )
             .await?;
 
@@ -582,73 +716,8 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
-
-        let api_messages = self.convert_messages(messages);
-
-        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
-        // tools_to_openai_format() in loop_.rs — pass them through directly.
-        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
-
-        let response = self
-            .send_request(
-                api_messages,
-                &normalized_model,
-                temperature,
-                should_auth,
-                tools_opt,
-            )
-            .await?;
-
-        // Native tool calls returned by the model.
-        if !response.message.tool_calls.is_empty() {
-            let tool_calls: Vec<ToolCall> = response
-                .message
-                .tool_calls
-                .iter()
-                .map(|tc| {
-                    let (name, args) = self.extract_tool_name_and_args(tc);
-                    ToolCall {
-                        id: tc
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                        name,
-                        arguments: serde_json::to_string(&args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                    }
-                })
-                .collect();
-            let text = if response.message.content.is_empty() {
-                None
-            } else {
-                Some(response.message.content)
-            };
-            return Ok(ChatResponse { text, tool_calls });
-        }
-
-        // Plain text response.
-        let content = response.message.content;
-        if content.is_empty() {
-            if let Some(thinking) = &response.message.thinking {
-                tracing::warn!(
-                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
-                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
-                );
-                return Ok(ChatResponse {
-                    text: Some(format!(
-                        "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
-                        if thinking.len() > 200 { &thinking[..200] } else { thinking }
-                    )),
-                    tool_calls: vec![],
-                });
-            }
-            tracing::warn!("Ollama returned empty content with no tool calls");
-        }
-        Ok(ChatResponse {
-            text: Some(content),
-            tool_calls: vec![],
-        })
+        self.chat_with_think(messages, tools, model, temperature, None)
+            .await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -664,6 +733,34 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        let think = request
+            .reasoning
+            .filter(|options| options.is_enabled())
+            .map(|_| true);
+
+        // Structured output: Ollama constrains decoding via the `format` field.
+        if let Some(schema) = request.response_schema {
+            let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+            let api_messages = self.convert_messages(request.messages);
+            let response = self
+                .send_request(
+                    api_messages,
+                    &normalized_model,
+                    temperature,
+                    should_auth,
+                    None,
+                    Some(&schema.schema),
+                    think,
+                )
+                .await?;
+            return Ok(ChatResponse {
+                text: Some(response.message.content),
+                tool_calls: vec![],
+                reasoning: None,
+                usage: None,
+            });
+        }
+
         // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
         if let Some(specs) = request.tools {
             if !specs.is_empty() {
@@ -681,19 +778,15 @@ impl Provider for OllamaProvider {
                     })
                     .collect();
                 return self
-                    .chat_with_tools(request.messages, &tools, model, temperature)
+                    .chat_with_think(request.messages, &tools, model, temperature, think)
                     .await;
             }
         }
 
-        // No tools — fall back to plain text chat.
-        let text = self
-            .chat_with_history(request.messages, model, temperature)
-            .await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: vec![],
-        })
+        // No tools — plain chat through the same path so `thinking` is kept
+        // apart from the answer.
+        self.chat_with_think(request.messages, &[], model, temperature, think)
+            .await
     }
 }
 
@@ -799,6 +892,8 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
@@ -819,12 +914,59 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
         assert_eq!(json.get("think"), Some(&serde_json::json!(false)));
     }
 
+    #[test]
+    fn request_think_override_takes_precedence() {
+        let provider = OllamaProvider::new_with_reasoning(None, None, Some(false));
+        let request = provider.build_chat_request(
+            vec![Message {
+                role: "user".to_string(),
+                content: Some("hello".to_string()),
+                images: None,
+                tool_calls: None,
+                tool_name: None,
+            }],
+            "llama3",
+            0.7,
+            None,
+            None,
+            Some(true),
+        );
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json.get("think"), Some(&serde_json::json!(true)));
+    }
+
+    #[test]
+    fn request_includes_format_when_schema_given() {
+        let provider = OllamaProvider::new(None, None);
+        let schema = serde_json::json!({"type": "object"});
+        let request = provider.build_chat_request(
+            vec![Message {
+                role: "user".to_string(),
+                content: Some("hello".to_string()),
+                images: None,
+                tool_calls: None,
+                tool_name: None,
+            }],
+            "llama3",
+            0.7,
+            None,
+            Some(&schema),
+            None,
+        );
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json.get("format"), Some(&schema));
+    }
+
     #[test]
     fn response_deserializes() {
         let json = r#"{"message":{"role":"assistant","content":"Hello from Ollama!"}}"#;
@@ -1009,5 +1151,6 @@ mod tests {
         let caps = <OllamaProvider as Provider>::capabilities(&provider);
         assert!(caps.native_tool_calling);
         assert!(caps.vision);
+        assert!(caps.structured_output);
     }
 }
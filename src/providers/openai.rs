@@ -1,9 +1,10 @@
 use crate::providers::openai_wire::{
-    convert_messages, NativeChatRequest, NativeToolFunctionSpec, NativeToolSpec, NativeToolCall,
+    convert_messages, json_schema_response_format, NativeChatRequest, NativeToolCall,
+    NativeToolFunctionSpec, NativeToolSpec, NativeUsage,
 };
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
-    ToolCall as ProviderToolCall,
+    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
+    Provider, ProviderCapabilities, Reasoning, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -73,6 +74,8 @@ fn parse_native_tool_spec(value: serde_json::Value) -> anyhow::Result<NativeTool
 #[derive(Debug, Deserialize)]
 struct NativeChatResponse {
     choices: Vec<NativeChoice>,
+    #[serde(default)]
+    usage: Option<NativeUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -98,6 +101,15 @@ impl NativeResponseMessage {
             _ => self.reasoning_content.clone(),
         }
     }
+
+    /// `reasoning_content` reported separately, unless it already stands in
+    /// for an empty `content`.
+    fn reasoning(&self) -> Option<Reasoning> {
+        match (&self.content, &self.reasoning_content) {
+            (Some(c), Some(r)) if !c.is_empty() => Reasoning::from_text(r.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl OpenAiProvider {
@@ -134,6 +146,7 @@ impl OpenAiProvider {
 
     fn parse_native_response(message: NativeResponseMessage) -> ProviderChatResponse {
         let text = message.effective_content();
+        let reasoning = message.reasoning();
         let tool_calls = message
             .tool_calls
             .unwrap_or_default()
@@ -145,7 +158,12 @@ impl OpenAiProvider {
             })
             .collect::<Vec<_>>();
 
-        ProviderChatResponse { text, tool_calls }
+        ProviderChatResponse {
+            text,
+            tool_calls,
+            reasoning,
+            usage: None,
+        }
     }
 
     fn http_client(&self) -> Client {
@@ -155,6 +173,14 @@ impl OpenAiProvider {
 
 #[async_trait]
 impl Provider for OpenAiProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+            structured_output: true,
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -225,6 +251,8 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request.response_schema.map(json_schema_response_format),
+            reasoning_effort: request.reasoning.and_then(|r| r.effort.clone()),
         };
 
         let response = self
@@ -240,13 +268,17 @@ impl Provider for OpenAiProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(Into::into);
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(ProviderChatResponse {
+            usage,
+            ..Self::parse_native_response(message)
+        })
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -282,6 +314,8 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            reasoning_effort: None,
         };
 
         let response = self
@@ -297,13 +331,17 @@ impl Provider for OpenAiProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(Into::into);
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(ProviderChatResponse {
+            usage,
+            ..Self::parse_native_response(message)
+        })
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -490,6 +528,24 @@ mod tests {
         assert_eq!(msg.effective_content(), Some("Real answer".to_string()));
     }
 
+    #[test]
+    fn native_response_reports_reasoning_separately() {
+        let json =
+            r#"{"choices":[{"message":{"content":"Answer","reasoning_content":"Because"}}]}"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let parsed =
+            OpenAiProvider::parse_native_response(resp.choices.into_iter().next().unwrap().message);
+        assert_eq!(parsed.text.as_deref(), Some("Answer"));
+        assert_eq!(parsed.reasoning.unwrap().text, "Because");
+
+        let json = r#"{"choices":[{"message":{"content":"","reasoning_content":"Only"}}]}"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let parsed =
+            OpenAiProvider::parse_native_response(resp.choices.into_iter().next().unwrap().message);
+        assert_eq!(parsed.text.as_deref(), Some("Only"));
+        assert!(parsed.reasoning.is_none());
+    }
+
     #[tokio::test]
     async fn chat_with_tools_fails_without_key() {
         let p = OpenAiProvider::new(None);
@@ -3,7 +3,9 @@
 //! Used by [`openai`] and [`openrouter`] to avoid duplicating the request/response
 //! struct definitions and message-conversion logic.
 
-use crate::providers::traits::{ChatMessage, ToolCall as ProviderToolCall};
+use crate::providers::traits::{
+    ChatMessage, ResponseSchema, TokenUsage, ToolCall as ProviderToolCall,
+};
 use serde::{Deserialize, Serialize};
 
 const IMAGE_MARKER_PREFIX: &str = "[IMAGE:";
@@ -18,6 +20,40 @@ pub struct NativeChatRequest {
     pub tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+    /// Reasoning effort for o-series / reasoning models (`low`, `medium`, `high`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+}
+
+/// Build an OpenAI `response_format` payload for a structured-output schema.
+pub fn json_schema_response_format(schema: &ResponseSchema) -> serde_json::Value {
+    serde_json::json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": schema.wire_name(),
+            "schema": schema.schema,
+        }
+    })
+}
+
+/// Token counts from an OpenAI-compatible `usage` block.
+#[derive(Debug, Deserialize)]
+pub struct NativeUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+}
+
+impl From<NativeUsage> for TokenUsage {
+    fn from(usage: NativeUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        }
+    }
 }
 
 /// Message content: either a plain string or an array of content parts (vision).
@@ -341,6 +377,7 @@ mod tests {
     // ── Vision conversion tests ─────────────────────────────────────
 
     #[test]
+    #[allow(clippy::match_wildcard_for_single_variants)]
     fn convert_messages_with_vision_creates_multipart_content() {
         let messages = vec![ChatMessage::user(
             "Process this receipt [IMAGE:data:image/png;base64,abc123]".to_string(),
@@ -435,6 +472,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::match_wildcard_for_single_variants)]
     fn build_vision_content_image_only() {
         let content = "[IMAGE:data:image/png;base64,abc123]";
         let result = build_vision_content(content).unwrap();
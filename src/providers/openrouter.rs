@@ -1,5 +1,6 @@
 use crate::providers::openai_wire::{
     convert_messages, NativeChatRequest, NativeToolCall, NativeToolFunctionSpec, NativeToolSpec,
+    NativeUsage,
 };
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
@@ -45,6 +46,8 @@ struct ResponseMessage {
 #[derive(Debug, Deserialize)]
 struct NativeChatResponse {
     choices: Vec<NativeChoice>,
+    #[serde(default)]
+    usage: Option<NativeUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -102,6 +105,8 @@ impl OpenRouterProvider {
         ProviderChatResponse {
             text: message.content,
             tool_calls,
+            reasoning: None,
+            usage: None,
         }
     }
 
@@ -252,6 +257,8 @@ impl Provider for OpenRouterProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: None,
+            reasoning_effort: None,
         };
 
         let response = self
@@ -272,13 +279,17 @@ impl Provider for OpenRouterProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(Into::into);
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(ProviderChatResponse {
+            usage,
+            ..Self::parse_native_response(message)
+        })
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -340,6 +351,8 @@ impl Provider for OpenRouterProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            reasoning_effort: None,
         };
 
         let response = self
@@ -360,13 +373,17 @@ impl Provider for OpenRouterProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(Into::into);
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(ProviderChatResponse {
+            usage,
+            ..Self::parse_native_response(message)
+        })
     }
 }
 
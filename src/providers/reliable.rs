@@ -554,6 +554,8 @@ impl Provider for ReliableProvider {
                     let req = ChatRequest {
                         messages: request.messages,
                         tools: request.tools,
+                        response_schema: request.response_schema,
+                        reasoning: request.reasoning,
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
@@ -659,6 +661,16 @@ impl Provider for ReliableProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn supports_structured_output(&self) -> bool {
+        // A fallback provider without schema support would silently drop the
+        // schema, so every provider in the chain has to support it.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, provider)| provider.supports_structured_output())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -1659,6 +1671,8 @@ mod tests {
             Ok(ChatResponse {
                 text: Some(self.response_text.to_string()),
                 tool_calls: self.tool_calls.clone(),
+                reasoning: None,
+                usage: None,
             })
         }
     }
@@ -1690,6 +1704,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1726,6 +1742,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1760,6 +1778,60 @@ mod tests {
         );
     }
 
+    struct StructuredOutputMock(bool);
+
+    #[async_trait]
+    impl Provider for StructuredOutputMock {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("ok".to_string())
+        }
+
+        fn supports_structured_output(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn structured_output_requires_support_across_fallback_chain() {
+        let all = ReliableProvider::new(
+            vec![
+                (
+                    "a".into(),
+                    Box::new(StructuredOutputMock(true)) as Box<dyn Provider>,
+                ),
+                (
+                    "b".into(),
+                    Box::new(StructuredOutputMock(true)) as Box<dyn Provider>,
+                ),
+            ],
+            1,
+            1,
+        );
+        assert!(all.supports_structured_output());
+
+        let mixed = ReliableProvider::new(
+            vec![
+                (
+                    "a".into(),
+                    Box::new(StructuredOutputMock(true)) as Box<dyn Provider>,
+                ),
+                (
+                    "b".into(),
+                    Box::new(StructuredOutputMock(false)) as Box<dyn Provider>,
+                ),
+            ],
+            1,
+            1,
+        );
+        assert!(!mixed.supports_structured_output());
+    }
+
     // ── Gap 2-4: Parity tests for chat() ────────────────────────
 
     /// Gap 2: `chat()` returns an aggregated error when all providers fail,
@@ -1797,6 +1869,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
         let err = provider
             .chat(request, "test", 0.0)
@@ -1850,6 +1924,8 @@ mod tests {
             Ok(ChatResponse {
                 text: Some(self.response_text.to_string()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             })
         }
     }
@@ -1911,6 +1987,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
         let result = provider.chat(request, "claude-opus", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("ok from sonnet"));
@@ -1959,6 +2037,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
         let result = provider.chat(request, "test", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("from fallback"));
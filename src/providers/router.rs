@@ -1,4 +1,4 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{ChatMessage, ChatRequest, ChatResponse, ReasoningOptions};
 use super::Provider;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -8,6 +8,8 @@ use std::collections::HashMap;
 pub struct Route {
     pub provider_name: String,
     pub model: String,
+    /// Reasoning controls applied to requests dispatched through this route.
+    pub reasoning: Option<ReasoningOptions>,
 }
 
 /// Multi-model router — routes requests to different provider+model combos
@@ -20,6 +22,7 @@ pub struct Route {
 /// This wraps multiple pre-created providers and selects the right one per request.
 pub struct RouterProvider {
     routes: HashMap<String, (usize, String)>, // hint → (provider_index, model)
+    route_reasoning: HashMap<String, ReasoningOptions>,
     providers: Vec<(String, Box<dyn Provider>)>,
     default_index: usize,
     default_model: String,
@@ -42,6 +45,17 @@ impl RouterProvider {
             .map(|(i, (name, _))| (name.as_str(), i))
             .collect();
 
+        let route_reasoning: HashMap<String, ReasoningOptions> = routes
+            .iter()
+            .filter_map(|(hint, route)| {
+                route
+                    .reasoning
+                    .clone()
+                    .filter(ReasoningOptions::is_enabled)
+                    .map(|reasoning| (hint.clone(), reasoning))
+            })
+            .collect();
+
         // Resolve routes to provider indices
         let resolved_routes: HashMap<String, (usize, String)> = routes
             .into_iter()
@@ -63,6 +77,7 @@ impl RouterProvider {
 
         Self {
             routes: resolved_routes,
+            route_reasoning,
             providers,
             default_index: 0,
             default_model,
@@ -134,6 +149,12 @@ impl Provider for RouterProvider {
     ) -> anyhow::Result<ChatResponse> {
         let (provider_idx, resolved_model) = self.resolve(model);
         let (_, provider) = &self.providers[provider_idx];
+        let mut request = request;
+        if request.reasoning.is_none() {
+            request.reasoning = model
+                .strip_prefix("hint:")
+                .and_then(|hint| self.route_reasoning.get(hint));
+        }
         provider.chat(request, &resolved_model, temperature).await
     }
 
@@ -164,6 +185,13 @@ impl Provider for RouterProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn supports_structured_output(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .map(|(_, p)| p.supports_structured_output())
+            .unwrap_or(false)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -248,6 +276,7 @@ mod tests {
                     Route {
                         provider_name: provider_name.to_string(),
                         model: model.to_string(),
+                        reasoning: None,
                     },
                 )
             })
@@ -0,0 +1,653 @@
+//! Structured (JSON-schema) response mode with a validate-and-retry fallback.
+//!
+//! Providers that declare `structured_output` in their capabilities receive the
+//! schema natively through [`ChatRequest::response_schema`]. Every other
+//! provider gets prompt-guided instructions instead. In both cases the answer
+//! is parsed and validated locally, and the model is asked to correct itself
+//! when the response does not satisfy the schema.
+
+use crate::providers::traits::{ChatMessage, ChatRequest, Provider, ResponseSchema};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Default number of model calls before giving up on a schema-conforming answer.
+pub const DEFAULT_STRUCTURED_ATTEMPTS: usize = 3;
+
+/// Maximum number of validation errors reported back to the model per attempt.
+const MAX_REPORTED_ERRORS: usize = 8;
+
+/// Build the prompt-guided instructions used when the provider cannot enforce
+/// the schema natively.
+pub fn schema_instructions(schema: &ResponseSchema) -> String {
+    let rendered =
+        serde_json::to_string_pretty(&schema.schema).unwrap_or_else(|_| "{}".to_string());
+    let mut instructions = String::new();
+    instructions.push_str("## Response Format\n\n");
+    instructions.push_str(
+        "Respond with a single JSON value that conforms to the JSON Schema below. \
+         Output only the JSON — no prose, no markdown fences.\n\n",
+    );
+    let _ = writeln!(instructions, "Schema `{}`:", schema.name);
+    instructions.push_str(&rendered);
+    instructions.push('\n');
+    instructions
+}
+
+/// Extract the first JSON object or array from a model response.
+///
+/// Accepts raw JSON, JSON wrapped in markdown code fences, or JSON embedded in
+/// surrounding prose.
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+        return Some(value);
+    }
+
+    if let Some(fenced) = strip_code_fence(trimmed) {
+        if let Ok(value) = serde_json::from_str::<Value>(fenced) {
+            return Some(value);
+        }
+    }
+
+    for (idx, ch) in trimmed.char_indices() {
+        if ch != '{' && ch != '[' {
+            continue;
+        }
+        let mut stream = serde_json::Deserializer::from_str(&trimmed[idx..]).into_iter::<Value>();
+        if let Some(Ok(value)) = stream.next() {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+fn strip_code_fence(text: &str) -> Option<&str> {
+    let start = text.find("```")?;
+    let after_open = &text[start + 3..];
+    let body_start = after_open.find('\n')? + 1;
+    let body = &after_open[body_start..];
+    let end = body.find("```")?;
+    Some(body[..end].trim())
+}
+
+/// Validate `value` against a JSON Schema.
+///
+/// Supports the subset of JSON Schema that response schemas use in practice:
+/// `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+/// `items`, `minItems`/`maxItems`, `minLength`/`maxLength`,
+/// `minimum`/`maximum`, and `anyOf`/`oneOf`. Unknown keywords are ignored.
+pub fn validate_json(value: &Value, schema: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(value, schema, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        // `true` / missing schema accepts everything; `false` rejects.
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: no value is allowed here"));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| matches_type(value, name)) {
+            errors.push(format!(
+                "{path}: expected {}, got {}",
+                allowed.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{path}: must be one of {}",
+                Value::Array(options.clone())
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{path}: must equal {expected}"));
+        }
+    }
+
+    let matching = |variants: &[Value]| {
+        variants
+            .iter()
+            .filter(|variant| {
+                let mut scratch = Vec::new();
+                validate_at(value, variant, path, &mut scratch);
+                scratch.is_empty()
+            })
+            .count()
+    };
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+        if matching(variants) == 0 {
+            errors.push(format!("{path}: does not match any allowed variant"));
+        }
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        match matching(variants) {
+            0 => errors.push(format!("{path}: does not match any allowed variant")),
+            1 => {}
+            n => errors.push(format!(
+                "{path}: matches {n} variants but must match exactly one"
+            )),
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{path}: missing required property '{key}'"));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, item) in map {
+                let child_path = format!("{path}.{key}");
+                match properties.and_then(|props| props.get(key)) {
+                    Some(child_schema) => validate_at(item, child_schema, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{path}: unexpected property '{key}'"));
+                        }
+                        Some(extra @ Value::Object(_)) => {
+                            validate_at(item, extra, &child_path, errors);
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{path}: expected at least {min} item(s)"));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if (items.len() as u64) > max {
+                    errors.push(format!("{path}: expected at most {max} item(s)"));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    validate_at(item, item_schema, &format!("{path}[{idx}]"), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let len = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!("{path}: shorter than {min} character(s)"));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!("{path}: longer than {max} character(s)"));
+                }
+            }
+        }
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                    if number < min {
+                        errors.push(format!("{path}: must be >= {min}"));
+                    }
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                    if number > max {
+                        errors.push(format!("{path}: must be <= {max}"));
+                    }
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn matches_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value
+                    .as_f64()
+                    .is_some_and(|n| n.is_finite() && n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Ask the provider for a response that conforms to `schema`.
+///
+/// Uses native structured output when the provider supports it and
+/// prompt-guided instructions otherwise. The parsed response is validated
+/// locally; on failure the model is shown the validation errors and asked to
+/// try again, up to `max_attempts` calls in total.
+pub async fn chat_structured(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    schema: &ResponseSchema,
+    model: &str,
+    temperature: f64,
+    max_attempts: usize,
+) -> anyhow::Result<Value> {
+    let native = provider.supports_structured_output();
+    let mut history = messages.to_vec();
+
+    if !native {
+        let instructions = schema_instructions(schema);
+        if let Some(system_message) = history.iter_mut().find(|m| m.role == "system") {
+            if !system_message.content.is_empty() {
+                system_message.content.push_str("\n\n");
+            }
+            system_message.content.push_str(&instructions);
+        } else {
+            history.insert(0, ChatMessage::system(instructions));
+        }
+    }
+
+    let max_attempts = max_attempts.max(1);
+    let mut last_error = String::new();
+
+    for attempt in 1..=max_attempts {
+        let response = provider
+            .chat(
+                ChatRequest {
+                    messages: &history,
+                    tools: None,
+                    response_schema: native.then_some(schema),
+                    reasoning: None,
+                },
+                model,
+                temperature,
+            )
+            .await?;
+        let text = response.text_or_empty().to_string();
+
+        let problems = match extract_json(&text) {
+            Some(value) => match validate_json(&value, &schema.schema) {
+                Ok(()) => return Ok(value),
+                Err(errors) => errors,
+            },
+            None => vec!["response is not valid JSON".to_string()],
+        };
+
+        last_error = problems
+            .iter()
+            .take(MAX_REPORTED_ERRORS)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("; ");
+        tracing::debug!(
+            schema = schema.name.as_str(),
+            attempt,
+            error = last_error.as_str(),
+            "Structured response failed validation"
+        );
+
+        if attempt < max_attempts {
+            history.push(ChatMessage::assistant(text));
+            history.push(ChatMessage::user(format!(
+                "Your previous response did not satisfy the required JSON schema: {last_error}. \
+                 Reply again with only a JSON value that satisfies the schema."
+            )));
+        }
+    }
+
+    anyhow::bail!(
+        "Structured response did not match schema '{}' after {max_attempts} attempt(s): {last_error}",
+        schema.name
+    )
+}
+
+/// Coerce free-form agent output into a schema-conforming JSON value.
+///
+/// Returns the output as-is when it already contains valid JSON for the schema;
+/// otherwise runs a single structured conversion pass over the text.
+pub async fn coerce_to_schema(
+    provider: &dyn Provider,
+    output: &str,
+    schema: &ResponseSchema,
+    model: &str,
+    temperature: f64,
+) -> anyhow::Result<Value> {
+    if let Some(value) = extract_json(output) {
+        if validate_json(&value, &schema.schema).is_ok() {
+            return Ok(value);
+        }
+    }
+
+    let messages = [
+        ChatMessage::system(
+            "Convert the result below into JSON that matches the required schema. \
+             Use only facts present in the result; use null or empty values for missing data.",
+        ),
+        ChatMessage::user(output),
+    ];
+    chat_structured(
+        provider,
+        &messages,
+        schema,
+        model,
+        temperature,
+        DEFAULT_STRUCTURED_ATTEMPTS,
+    )
+    .await
+}
+
+/// Parse a response schema supplied by a user or tool call.
+///
+/// Accepts either a bare JSON Schema object or a `{ "name", "schema" }` pair.
+pub fn parse_response_schema(raw: &Value, default_name: &str) -> anyhow::Result<ResponseSchema> {
+    let Some(object) = raw.as_object() else {
+        anyhow::bail!("output schema must be a JSON object");
+    };
+
+    if let (Some(Value::String(name)), Some(schema @ Value::Object(_))) =
+        (object.get("name"), object.get("schema"))
+    {
+        return Ok(ResponseSchema::new(name.clone(), schema.clone()));
+    }
+
+    Ok(ResponseSchema::new(default_name, raw.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::{ChatResponse, ProviderCapabilities};
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    fn person_schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "person",
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "age": {"type": "integer", "minimum": 0}
+                },
+                "required": ["name", "age"],
+                "additionalProperties": false
+            }),
+        )
+    }
+
+    struct ScriptedProvider {
+        native: bool,
+        responses: Mutex<Vec<String>>,
+        requests: Mutex<Vec<(Vec<ChatMessage>, Option<ResponseSchema>)>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(native: bool, responses: &[&str]) -> Self {
+            Self {
+                native,
+                responses: Mutex::new(responses.iter().rev().map(|s| s.to_string()).collect()),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: false,
+                vision: false,
+                structured_output: self.native,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.requests
+                .lock()
+                .push((request.messages.to_vec(), request.response_schema.cloned()));
+            Ok(ChatResponse {
+                text: self.responses.lock().pop(),
+                tool_calls: Vec::new(),
+                reasoning: None,
+                usage: None,
+            })
+        }
+    }
+
+    #[test]
+    fn extract_json_handles_fences_and_prose() {
+        assert_eq!(extract_json(r#"{"a":1}"#), Some(json!({"a": 1})));
+        assert_eq!(
+            extract_json("```json\n{\"a\": 2}\n```"),
+            Some(json!({"a": 2}))
+        );
+        assert_eq!(
+            extract_json("Here you go: [1, 2, 3] — done."),
+            Some(json!([1, 2, 3]))
+        );
+        assert_eq!(extract_json("no json here"), None);
+    }
+
+    #[test]
+    fn validate_json_reports_paths() {
+        let schema = person_schema();
+        assert!(validate_json(&json!({"name": "Ada", "age": 36}), &schema.schema).is_ok());
+
+        let errors = validate_json(&json!({"name": 7, "extra": true}), &schema.schema)
+            .expect_err("invalid value should fail");
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("missing required property 'age'")));
+        assert!(errors.iter().any(|e| e.contains("$.name: expected string")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("unexpected property 'extra'")));
+    }
+
+    #[test]
+    fn validate_json_checks_arrays_and_enums() {
+        let schema = json!({
+            "type": "array",
+            "minItems": 1,
+            "items": {"type": "string", "enum": ["ok", "warn"]}
+        });
+        assert!(validate_json(&json!(["ok", "warn"]), &schema).is_ok());
+        assert!(validate_json(&json!([]), &schema).is_err());
+        let errors = validate_json(&json!(["ok", "bad"]), &schema).unwrap_err();
+        assert!(errors[0].starts_with("$[1]"));
+    }
+
+    #[test]
+    fn integer_type_rejects_fractions() {
+        let schema = json!({"type": "integer"});
+        assert!(validate_json(&json!(3), &schema).is_ok());
+        assert!(validate_json(&json!(3.5), &schema).is_err());
+    }
+
+    #[test]
+    fn one_of_requires_exactly_one_match_and_any_of_at_least_one() {
+        let variants = json!([{"type": "integer"}, {"type": "number", "minimum": 10}]);
+        let one_of = json!({"oneOf": variants});
+        let any_of = json!({"anyOf": variants});
+
+        assert!(validate_json(&json!(3), &one_of).is_ok());
+        assert!(validate_json(&json!(10.5), &one_of).is_ok());
+        let errors = validate_json(&json!(12), &one_of).unwrap_err();
+        assert!(errors[0].contains("exactly one"));
+        assert!(validate_json(&json!("x"), &one_of).is_err());
+
+        assert!(validate_json(&json!(12), &any_of).is_ok());
+        assert!(validate_json(&json!("x"), &any_of).is_err());
+    }
+
+    #[tokio::test]
+    async fn fallback_injects_instructions_and_retries_until_valid() {
+        let provider = ScriptedProvider::new(
+            false,
+            &[
+                "not json",
+                r#"{"name": "Ada"}"#,
+                r#"{"name": "Ada", "age": 36}"#,
+            ],
+        );
+        let schema = person_schema();
+        let value = chat_structured(
+            &provider,
+            &[ChatMessage::user("who?")],
+            &schema,
+            "model",
+            0.0,
+            3,
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, json!({"name": "Ada", "age": 36}));
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].1.is_none());
+        assert_eq!(requests[0].0[0].role, "system");
+        assert!(requests[0].0[0].content.contains("Response Format"));
+        let last_user = requests[2].0.last().unwrap();
+        assert!(last_user
+            .content
+            .contains("missing required property 'age'"));
+    }
+
+    #[tokio::test]
+    async fn native_provider_receives_schema() {
+        let provider = ScriptedProvider::new(true, &[r#"{"name": "Ada", "age": 36}"#]);
+        let schema = person_schema();
+        chat_structured(
+            &provider,
+            &[ChatMessage::user("who?")],
+            &schema,
+            "model",
+            0.0,
+            1,
+        )
+        .await
+        .unwrap();
+
+        let requests = provider.requests.lock();
+        assert_eq!(requests[0].1.as_ref(), Some(&schema));
+        assert!(requests[0].0.iter().all(|m| m.role != "system"));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let provider = ScriptedProvider::new(false, &["{}", "{}"]);
+        let err = chat_structured(
+            &provider,
+            &[ChatMessage::user("who?")],
+            &person_schema(),
+            "model",
+            0.0,
+            2,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("after 2 attempt(s)"));
+    }
+
+    #[tokio::test]
+    async fn coerce_to_schema_skips_llm_when_output_already_valid() {
+        let provider = ScriptedProvider::new(false, &[]);
+        let value = coerce_to_schema(
+            &provider,
+            "Result:\n```json\n{\"name\": \"Ada\", \"age\": 36}\n```",
+            &person_schema(),
+            "model",
+            0.0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(value["age"], 36);
+        assert!(provider.requests.lock().is_empty());
+    }
+
+    #[test]
+    fn parse_response_schema_accepts_bare_and_named_forms() {
+        let bare = parse_response_schema(&json!({"type": "object"}), "cron_output").unwrap();
+        assert_eq!(bare.name, "cron_output");
+
+        let named = parse_response_schema(
+            &json!({"name": "report", "schema": {"type": "object"}}),
+            "cron_output",
+        )
+        .unwrap();
+        assert_eq!(named.name, "report");
+        assert_eq!(named.schema, json!({"type": "object"}));
+
+        assert!(parse_response_schema(&json!("nope"), "x").is_err());
+    }
+
+    #[test]
+    fn wire_name_is_sanitized() {
+        let schema = ResponseSchema::new("daily report!", json!({}));
+        assert_eq!(schema.wire_name(), "daily_report_");
+        assert_eq!(
+            ResponseSchema::new("", json!({})).wire_name(),
+            "structured_response"
+        );
+    }
+}
@@ -42,7 +42,7 @@ impl ChatMessage {
 }
 
 /// A tool call requested by the LLM.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ToolCall {
     pub id: String,
     pub name: String,
@@ -56,6 +56,54 @@ pub struct ChatResponse {
     pub text: Option<String>,
     /// Tool calls requested by the LLM.
     pub tool_calls: Vec<ToolCall>,
+    /// Reasoning / extended-thinking content, kept apart from `text`.
+    pub reasoning: Option<Reasoning>,
+    /// Token counts, when the provider reports them.
+    pub usage: Option<TokenUsage>,
+}
+
+/// Tokens consumed by one provider call, as reported by the provider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total(self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// Reasoning ("thinking") content returned alongside an answer.
+///
+/// Never shown to channel users by default; the agent loop forwards it to the
+/// observer and, when `blocks` is non-empty, replays it in history because some
+/// APIs require it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reasoning {
+    /// Plain reasoning text (may be a provider-generated summary).
+    pub text: String,
+    /// Provider-native blocks that must be sent back verbatim on the next
+    /// request of a tool-call turn (Anthropic signed `thinking` /
+    /// `redacted_thinking` blocks).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<serde_json::Value>,
+}
+
+impl Reasoning {
+    /// Wrap plain reasoning text, returning `None` when it is blank.
+    pub fn from_text(text: impl Into<String>) -> Option<Self> {
+        let text = text.into();
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(Self {
+                text,
+                blocks: Vec::new(),
+            })
+        }
+    }
 }
 
 impl ChatResponse {
@@ -70,11 +118,95 @@ impl ChatResponse {
     }
 }
 
+/// JSON schema the final answer must conform to (structured output mode).
+///
+/// Providers with native support map this to their API primitive (OpenAI
+/// `response_format: json_schema`, Anthropic forced tool use, Gemini
+/// `responseSchema`, Ollama `format`). Other providers ignore it; use
+/// [`crate::providers::structured::chat_structured`] to get a prompt-guided,
+/// validate-and-retry fallback that works everywhere.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Short identifier for the schema (used as the OpenAI schema name and the
+    /// Anthropic forced tool name).
+    pub name: String,
+    /// JSON Schema describing the expected response object.
+    pub schema: serde_json::Value,
+}
+
+impl ResponseSchema {
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+        }
+    }
+
+    /// Schema name restricted to `[A-Za-z0-9_-]{1,64}`, as required by
+    /// OpenAI `json_schema.name` and Anthropic tool names.
+    pub fn wire_name(&self) -> String {
+        let sanitized: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(64)
+            .collect();
+        if sanitized.is_empty() {
+            "structured_response".to_string()
+        } else {
+            sanitized
+        }
+    }
+}
+
 /// Request payload for provider chat calls.
 #[derive(Debug, Clone, Copy)]
 pub struct ChatRequest<'a> {
     pub messages: &'a [ChatMessage],
     pub tools: Option<&'a [ToolSpec]>,
+    /// Optional structured-output schema for the final answer.
+    pub response_schema: Option<&'a ResponseSchema>,
+    /// Optional per-request reasoning controls (thinking budget / effort).
+    pub reasoning: Option<&'a ReasoningOptions>,
+}
+
+/// Per-request reasoning controls, usually sourced from a model route.
+///
+/// Providers map these to their own knobs: Anthropic and Gemini take a token
+/// budget, OpenAI o-series models take an effort level, Ollama a `think` flag.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReasoningOptions {
+    /// Maximum tokens the model may spend thinking.
+    pub budget_tokens: Option<u32>,
+    /// Reasoning effort hint (`"low"`, `"medium"`, `"high"`).
+    pub effort: Option<String>,
+}
+
+impl ReasoningOptions {
+    /// Whether any reasoning control is set.
+    pub fn is_enabled(&self) -> bool {
+        self.budget_tokens.is_some() || self.effort.is_some()
+    }
+
+    /// Thinking budget in tokens, derived from `effort` when no explicit budget
+    /// is configured.
+    pub fn effective_budget(&self) -> Option<u32> {
+        self.budget_tokens.or_else(|| {
+            self.effort
+                .as_deref()
+                .map(|effort| match effort.to_ascii_lowercase().as_str() {
+                    "low" | "minimal" => 1024,
+                    "high" => 16_384,
+                    _ => 4096,
+                })
+        })
+    }
 }
 
 /// A tool result to feed back to the LLM.
@@ -94,6 +226,10 @@ pub enum ConversationMessage {
     AssistantToolCalls {
         text: Option<String>,
         tool_calls: Vec<ToolCall>,
+        /// Reasoning returned with the tool calls; replayed when the provider
+        /// requires it (see [`Reasoning::blocks`]).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reasoning: Option<Reasoning>,
     },
     /// Results of tool executions, fed back to the LLM.
     ToolResults(Vec<ToolResultMessage>),
@@ -216,6 +352,9 @@ pub struct ProviderCapabilities {
     pub native_tool_calling: bool,
     /// Whether the provider supports vision / image inputs.
     pub vision: bool,
+    /// Whether the provider can enforce a JSON schema on the response natively
+    /// (see [`ResponseSchema`]).
+    pub structured_output: bool,
 }
 
 /// Provider-specific tool payload formats.
@@ -311,50 +450,7 @@ pub trait Provider: Send + Sync {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        // If tools are provided but provider doesn't support native tools,
-        // inject tool instructions into system prompt as fallback.
-        if let Some(tools) = request.tools {
-            if !tools.is_empty() && !self.supports_native_tools() {
-                let tool_instructions = match self.convert_tools(tools) {
-                    ToolsPayload::PromptGuided { instructions } => instructions,
-                    payload => {
-                        anyhow::bail!(
-                            "Provider returned non-prompt-guided tools payload ({payload:?}) while supports_native_tools() is false"
-                        )
-                    }
-                };
-                let mut modified_messages = request.messages.to_vec();
-
-                // Inject tool instructions into an existing system message.
-                // If none exists, prepend one to the conversation.
-                if let Some(system_message) =
-                    modified_messages.iter_mut().find(|m| m.role == "system")
-                {
-                    if !system_message.content.is_empty() {
-                        system_message.content.push_str("\n\n");
-                    }
-                    system_message.content.push_str(&tool_instructions);
-                } else {
-                    modified_messages.insert(0, ChatMessage::system(tool_instructions));
-                }
-
-                let text = self
-                    .chat_with_history(&modified_messages, model, temperature)
-                    .await?;
-                return Ok(ChatResponse {
-                    text: Some(text),
-                    tool_calls: Vec::new(),
-                });
-            }
-        }
-
-        let text = self
-            .chat_with_history(request.messages, model, temperature)
-            .await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: Vec::new(),
-        })
+        prompt_guided_chat(self, request, model, temperature).await
     }
 
     /// Whether provider supports native tool calls over API.
@@ -367,6 +463,11 @@ pub trait Provider: Send + Sync {
         self.capabilities().vision
     }
 
+    /// Whether provider honors `ChatRequest::response_schema` natively.
+    fn supports_structured_output(&self) -> bool {
+        self.capabilities().structured_output
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -387,6 +488,8 @@ pub trait Provider: Send + Sync {
         Ok(ChatResponse {
             text: Some(text),
             tool_calls: Vec::new(),
+            reasoning: None,
+            usage: None,
         })
     }
 
@@ -430,6 +533,66 @@ pub trait Provider: Send + Sync {
     }
 }
 
+/// Default `Provider::chat` behavior: inject prompt-guided tool instructions
+/// when the provider lacks native tool calling, then delegate to
+/// `chat_with_history`.
+///
+/// Exposed so providers that override `chat` for one feature (for example
+/// native structured output) can fall back to the stock behavior otherwise.
+pub async fn prompt_guided_chat<P: Provider + ?Sized>(
+    provider: &P,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+) -> anyhow::Result<ChatResponse> {
+    let messages = prompt_guided_messages(provider, &request)?;
+    let text = provider
+        .chat_with_history(&messages, model, temperature)
+        .await?;
+    Ok(ChatResponse {
+        text: Some(text),
+        tool_calls: Vec::new(),
+        reasoning: None,
+        usage: None,
+    })
+}
+
+/// The request's messages with tool instructions injected into the system
+/// prompt when tools are provided but the provider has no native tool calling.
+pub fn prompt_guided_messages<P: Provider + ?Sized>(
+    provider: &P,
+    request: &ChatRequest<'_>,
+) -> anyhow::Result<Vec<ChatMessage>> {
+    let mut messages = request.messages.to_vec();
+    let Some(tools) = request.tools else {
+        return Ok(messages);
+    };
+    if tools.is_empty() || provider.supports_native_tools() {
+        return Ok(messages);
+    }
+
+    let tool_instructions = match provider.convert_tools(tools) {
+        ToolsPayload::PromptGuided { instructions } => instructions,
+        payload => {
+            anyhow::bail!(
+                "Provider returned non-prompt-guided tools payload ({payload:?}) while supports_native_tools() is false"
+            )
+        }
+    };
+
+    // Inject tool instructions into an existing system message.
+    // If none exists, prepend one to the conversation.
+    if let Some(system_message) = messages.iter_mut().find(|m| m.role == "system") {
+        if !system_message.content.is_empty() {
+            system_message.content.push_str("\n\n");
+        }
+        system_message.content.push_str(&tool_instructions);
+    } else {
+        messages.insert(0, ChatMessage::system(tool_instructions));
+    }
+    Ok(messages)
+}
+
 /// Build tool instructions text for prompt-guided tool calling.
 ///
 /// Generates a formatted text block describing available tools and how to
@@ -475,6 +638,7 @@ mod tests {
             ProviderCapabilities {
                 native_tool_calling: true,
                 vision: true,
+                structured_output: false,
             }
         }
 
@@ -510,6 +674,8 @@ mod tests {
         let empty = ChatResponse {
             text: None,
             tool_calls: vec![],
+            reasoning: None,
+            usage: None,
         };
         assert!(!empty.has_tool_calls());
         assert_eq!(empty.text_or_empty(), "");
@@ -521,6 +687,8 @@ mod tests {
                 name: "shell".into(),
                 arguments: "{}".into(),
             }],
+            reasoning: None,
+            usage: None,
         };
         assert!(with_tools.has_tool_calls());
         assert_eq!(with_tools.text_or_empty(), "Let me check");
@@ -557,6 +725,7 @@ mod tests {
         let caps = ProviderCapabilities::default();
         assert!(!caps.native_tool_calling);
         assert!(!caps.vision);
+        assert!(!caps.structured_output);
     }
 
     #[test]
@@ -564,14 +733,17 @@ mod tests {
         let caps1 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps2 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps3 = ProviderCapabilities {
             native_tool_calling: false,
             vision: false,
+            structured_output: false,
         };
 
         assert_eq!(caps1, caps2);
@@ -731,6 +903,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -748,6 +922,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: None,
+            response_schema: None,
+            reasoning: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -848,6 +1024,8 @@ mod tests {
                 ChatMessage::system("BASE_SYSTEM_PROMPT"),
             ],
             tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -870,6 +1048,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -892,6 +1072,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
+            reasoning: None,
         };
 
         let err = provider.chat(request, "model", 0.7).await.unwrap_err();
@@ -899,4 +1081,28 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    #[test]
+    fn reasoning_from_text_ignores_blank() {
+        assert!(Reasoning::from_text("  \n").is_none());
+        assert_eq!(Reasoning::from_text("why").unwrap().text, "why");
+    }
+
+    #[test]
+    fn reasoning_options_budget_prefers_explicit_then_effort() {
+        let explicit = ReasoningOptions {
+            budget_tokens: Some(2000),
+            effort: Some("high".into()),
+        };
+        assert_eq!(explicit.effective_budget(), Some(2000));
+
+        let effort = ReasoningOptions {
+            budget_tokens: None,
+            effort: Some("high".into()),
+        };
+        assert_eq!(effort.effective_budget(), Some(16_384));
+
+        assert!(!ReasoningOptions::default().is_enabled());
+        assert_eq!(ReasoningOptions::default().effective_budget(), None);
+    }
 }
@@ -4,7 +4,8 @@
 //! [`SecurityPolicy`] defines autonomy levels, workspace boundaries, and
 //! access-control rules that are enforced across the tool and runtime subsystems.
 //! [`PairingGuard`] implements device pairing for channel authentication, and
-//! [`SecretStore`] handles encrypted credential storage.
+//! [`SecretStore`] handles encrypted credential storage, and [`secret_refs`]
+//! resolves `secret://` references to external secret backends.
 //!
 //! OS-level isolation is provided through the [`Sandbox`] trait defined in
 //! [`traits`], with pluggable backends including Docker, Firejail, Bubblewrap,
@@ -29,6 +30,9 @@ pub mod firejail;
 pub mod landlock;
 pub mod pairing;
 pub mod policy;
+pub mod quota;
+pub mod rbac;
+pub mod secret_refs;
 pub mod secrets;
 pub mod traits;
 
@@ -39,6 +43,7 @@ pub use detect::create_sandbox;
 #[allow(unused_imports)]
 pub use pairing::PairingGuard;
 pub use policy::{AutonomyLevel, SecurityPolicy};
+pub use quota::DailyQuota;
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
 #[allow(unused_imports)]
@@ -1,7 +1,10 @@
+use super::quota::DailyQuota;
+use super::rbac;
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
 /// How much autonomy the agent has
@@ -90,6 +93,8 @@ pub struct SecurityPolicy {
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub tracker: ActionTracker,
+    /// Per-user daily budget, set for registered users in multi-user setups.
+    pub quota: Option<Arc<DailyQuota>>,
 }
 
 impl Default for SecurityPolicy {
@@ -140,6 +145,7 @@ impl Default for SecurityPolicy {
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             tracker: ActionTracker::new(),
+            quota: None,
         }
     }
 }
@@ -509,16 +515,21 @@ impl SecurityPolicy {
         approved: bool,
     ) -> Result<CommandRiskLevel, String> {
         if !self.is_command_allowed(command) {
-            return Err(format!("Command not allowed by security policy: {command}"));
+            let reason = format!("Command not allowed by security policy: {command}");
+            if let Some(role) = rbac::current() {
+                return Err(role.deny("shell", &reason));
+            }
+            return Err(reason);
         }
 
+        let autonomy = self.effective_autonomy();
         let risk = self.command_risk_level(command);
 
         if risk == CommandRiskLevel::High {
             if self.block_high_risk_commands {
                 return Err("Command blocked: high-risk command is disallowed by policy".into());
             }
-            if self.autonomy == AutonomyLevel::Supervised && !approved {
+            if autonomy == AutonomyLevel::Supervised && !approved {
                 return Err(
                     "Command requires explicit approval (approved=true): high-risk operation"
                         .into(),
@@ -527,7 +538,7 @@ impl SecurityPolicy {
         }
 
         if risk == CommandRiskLevel::Medium
-            && self.autonomy == AutonomyLevel::Supervised
+            && autonomy == AutonomyLevel::Supervised
             && self.require_approval_for_medium_risk
             && !approved
         {
@@ -554,9 +565,10 @@ impl SecurityPolicy {
     /// - Blocks output redirections (`>`, `>>`) that could write outside workspace
     /// - Blocks dangerous arguments (e.g. `find -exec`, `git config`)
     pub fn is_command_allowed(&self, command: &str) -> bool {
-        if self.autonomy == AutonomyLevel::ReadOnly {
+        if self.effective_autonomy() == AutonomyLevel::ReadOnly {
             return false;
         }
+        let role = rbac::current();
 
         // Block subshell/expansion operators — these allow hiding arbitrary
         // commands inside an allowed command (e.g. `echo $(rm -rf /)`)
@@ -611,6 +623,12 @@ impl SecurityPolicy {
             {
                 return false;
             }
+            if role
+                .as_ref()
+                .is_some_and(|role| !role.permissions.allows_command(base_cmd))
+            {
+                return false;
+            }
 
             // Validate arguments for the command
             let args: Vec<String> = words.map(|w| w.to_ascii_lowercase()).collect();
@@ -730,7 +748,14 @@ impl SecurityPolicy {
 
     /// Check if autonomy level permits any action at all
     pub fn can_act(&self) -> bool {
-        self.autonomy != AutonomyLevel::ReadOnly
+        self.effective_autonomy() != AutonomyLevel::ReadOnly
+    }
+
+    /// Configured autonomy, lowered to the active gateway role's ceiling.
+    pub fn effective_autonomy(&self) -> AutonomyLevel {
+        rbac::current().map_or(self.autonomy, |role| {
+            role.permissions.cap_autonomy(self.autonomy)
+        })
     }
 
     // ── Tool Operation Gating ──────────────────────────────────────────────
@@ -770,11 +795,19 @@ impl SecurityPolicy {
     pub fn record_action(&self) -> bool {
         let count = self.tracker.record();
         count <= self.max_actions_per_hour as usize
+            && self
+                .quota
+                .as_ref()
+                .is_none_or(|quota| quota.record_action())
     }
 
     /// Check if the rate limit would be exceeded without recording.
     pub fn is_rate_limited(&self) -> bool {
         self.tracker.count() >= self.max_actions_per_hour as usize
+            || self
+                .quota
+                .as_ref()
+                .is_some_and(|quota| quota.actions_exhausted())
     }
 
     /// Build from config sections
@@ -793,6 +826,7 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             tracker: ActionTracker::new(),
+            quota: None,
         }
     }
 }
@@ -1211,6 +1245,23 @@ mod tests {
         assert!(p.is_rate_limited());
     }
 
+    #[test]
+    fn daily_quota_limits_actions_across_policies() {
+        let quota = Arc::new(DailyQuota::new(Some(2), None));
+        let first = SecurityPolicy {
+            quota: Some(quota.clone()),
+            ..SecurityPolicy::default()
+        };
+        let second = SecurityPolicy {
+            quota: Some(quota),
+            ..SecurityPolicy::default()
+        };
+        assert!(first.record_action());
+        assert!(second.record_action());
+        assert!(first.is_rate_limited());
+        assert!(!second.record_action());
+    }
+
     #[test]
     fn action_tracker_clone_is_independent() {
         let tracker = ActionTracker::new();
@@ -1744,4 +1795,51 @@ mod tests {
             "URL-encoded parent dir traversal must be blocked"
         );
     }
+
+    #[tokio::test]
+    async fn active_role_caps_autonomy_and_commands() {
+        use crate::config::{GatewayRbacConfig, GatewayRoleConfig};
+
+        let config = GatewayRbacConfig {
+            roles: vec![
+                GatewayRoleConfig {
+                    name: "ops".into(),
+                    allowed_commands: Some(vec!["ls".into()]),
+                    autonomy: Some(AutonomyLevel::Supervised),
+                    ..GatewayRoleConfig::default()
+                },
+                GatewayRoleConfig {
+                    name: "viewer".into(),
+                    autonomy: Some(AutonomyLevel::ReadOnly),
+                    ..GatewayRoleConfig::default()
+                },
+            ],
+            ..GatewayRbacConfig::default()
+        };
+        let rbac = rbac::RbacPolicy::from_config(&config, None);
+        let policy = full_policy();
+        assert!(policy.is_command_allowed("git status"));
+
+        let claims = serde_json::json!({ "realm_access": { "roles": ["ops"] } });
+        let ops = rbac.scope_for(rbac.resolve(&claims), None);
+        rbac::scope(ops, async {
+            assert_eq!(policy.effective_autonomy(), AutonomyLevel::Supervised);
+            assert!(policy.is_command_allowed("ls -la"));
+            assert!(!policy.is_command_allowed("git status"));
+            assert!(!policy.is_command_allowed("ls | git status"));
+            let err = policy
+                .validate_command_execution("git status", true)
+                .unwrap_err();
+            assert!(err.contains("Denied by role 'ops'"));
+        })
+        .await;
+
+        let viewer = rbac.scope_for(rbac.resolve(&serde_json::json!({})), None);
+        rbac::scope(viewer, async {
+            assert!(!policy.can_act());
+            assert!(!policy.is_command_allowed("ls"));
+        })
+        .await;
+        assert!(policy.can_act());
+    }
 }
@@ -0,0 +1,176 @@
+//! Per-user daily quotas for tool actions and estimated model spend.
+//!
+//! Counters reset at local midnight. A quota built with
+//! [`DailyQuota::persistent`] saves its counters after every change, so a
+//! daemon restart keeps the day's usage.
+
+use chrono::{Local, NaiveDate};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct QuotaState {
+    day: NaiveDate,
+    actions: u32,
+    cost_usd: f64,
+}
+
+/// Daily action and cost budget shared by every tool and LLM call made on
+/// behalf of one user.
+#[derive(Debug)]
+pub struct DailyQuota {
+    max_actions: Option<u32>,
+    max_cost_usd: Option<f64>,
+    state: Mutex<QuotaState>,
+    path: Option<PathBuf>,
+}
+
+impl DailyQuota {
+    pub fn new(max_actions: Option<u32>, max_cost_usd: Option<f64>) -> Self {
+        Self {
+            max_actions,
+            max_cost_usd,
+            state: Mutex::new(QuotaState {
+                day: Local::now().date_naive(),
+                actions: 0,
+                cost_usd: 0.0,
+            }),
+            path: None,
+        }
+    }
+
+    /// A quota whose counters are kept in `path`. Today's saved counters are
+    /// picked up; a missing, stale or unreadable file starts from zero.
+    pub fn persistent(max_actions: Option<u32>, max_cost_usd: Option<f64>, path: &Path) -> Self {
+        let mut quota = Self::new(max_actions, max_cost_usd);
+        if let Some(saved) = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<QuotaState>(&raw).ok())
+        {
+            *quota.state.get_mut() = saved;
+        }
+        quota.path = Some(path.to_path_buf());
+        quota
+    }
+
+    fn save(&self, state: &QuotaState) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, serde_json::to_vec(state).unwrap_or_default()));
+        if let Err(e) = result {
+            tracing::warn!("Failed to save quota {}: {e}", path.display());
+        }
+    }
+
+    fn today(&self) -> parking_lot::MutexGuard<'_, QuotaState> {
+        let mut state = self.state.lock();
+        let today = Local::now().date_naive();
+        if state.day != today {
+            *state = QuotaState {
+                day: today,
+                actions: 0,
+                cost_usd: 0.0,
+            };
+        }
+        state
+    }
+
+    /// Count one action. Returns `false` once today's action budget is spent.
+    pub fn record_action(&self) -> bool {
+        let mut state = self.today();
+        if self.max_actions.is_some_and(|max| state.actions >= max) {
+            return false;
+        }
+        state.actions += 1;
+        self.save(&state);
+        true
+    }
+
+    pub fn record_cost(&self, cost_usd: f64) {
+        let mut state = self.today();
+        state.cost_usd += cost_usd.max(0.0);
+        self.save(&state);
+    }
+
+    pub fn actions_exhausted(&self) -> bool {
+        let state = self.today();
+        self.max_actions.is_some_and(|max| state.actions >= max)
+    }
+
+    pub fn cost_exhausted(&self) -> bool {
+        let state = self.today();
+        self.max_cost_usd.is_some_and(|max| state.cost_usd >= max)
+    }
+
+    /// Today's `(actions, cost_usd)`.
+    pub fn usage(&self) -> (u32, f64) {
+        let state = self.today();
+        (state.actions, state.cost_usd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_action_and_cost_limits() {
+        let quota = DailyQuota::new(Some(2), Some(1.0));
+        assert!(quota.record_action());
+        assert!(quota.record_action());
+        assert!(quota.actions_exhausted());
+        assert!(!quota.record_action());
+        assert_eq!(quota.usage().0, 2);
+
+        assert!(!quota.cost_exhausted());
+        quota.record_cost(0.6);
+        quota.record_cost(0.5);
+        assert!(quota.cost_exhausted());
+
+        let unlimited = DailyQuota::new(None, None);
+        for _ in 0..100 {
+            assert!(unlimited.record_action());
+        }
+        unlimited.record_cost(1_000.0);
+        assert!(!unlimited.cost_exhausted());
+    }
+
+    #[test]
+    fn counters_reset_on_a_new_day() {
+        let quota = DailyQuota::new(Some(1), None);
+        assert!(quota.record_action());
+        assert!(!quota.record_action());
+        quota.state.lock().day = Local::now().date_naive() - chrono::Duration::days(1);
+        assert!(quota.record_action());
+    }
+
+    #[test]
+    fn persistent_quota_survives_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("state/quota/alice.json");
+
+        let quota = DailyQuota::persistent(Some(2), Some(1.0), &path);
+        assert!(quota.record_action());
+        quota.record_cost(0.75);
+        drop(quota);
+
+        let restarted = DailyQuota::persistent(Some(2), Some(1.0), &path);
+        assert_eq!(restarted.usage(), (1, 0.75));
+        assert!(restarted.record_action());
+        assert!(!restarted.record_action());
+
+        let mut stale: QuotaState =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        stale.day -= chrono::Duration::days(1);
+        std::fs::write(&path, serde_json::to_vec(&stale).unwrap()).unwrap();
+        assert_eq!(
+            DailyQuota::persistent(Some(2), None, &path).usage(),
+            (0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,501 @@
+//! Role-based access control for authenticated gateway requests.
+//!
+//! The gateway resolves a role from the caller's JWT and runs the agent loop
+//! inside [`scope`]. While a role is active, [`SecurityPolicy`] caps autonomy
+//! and the shell allowlist, and the tool loop calls
+//! [`RoleScope::check_tool_call`] before every tool runs. Denials are written
+//! to the audit log.
+//!
+//! [`SecurityPolicy`]: super::SecurityPolicy
+
+use super::audit::{AuditEvent, AuditEventType, AuditLogger};
+use super::policy::AutonomyLevel;
+use crate::config::{Config, GatewayRbacConfig, GatewayRoleConfig};
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static ACTIVE_ROLE: Arc<RoleScope>;
+}
+
+/// What one role may do. `None` fields add no restriction.
+#[derive(Debug, Clone)]
+pub struct RolePermissions {
+    pub name: String,
+    claim_values: Vec<String>,
+    allowed_tools: Option<Vec<String>>,
+    allowed_commands: Option<Vec<String>>,
+    http_methods: Option<Vec<String>>,
+    autonomy: Option<AutonomyLevel>,
+    memory_categories: Option<Vec<String>>,
+}
+
+impl RolePermissions {
+    pub fn from_config(role: &GatewayRoleConfig) -> Self {
+        let claim_values = if role.claim_values.is_empty() {
+            vec![role.name.clone()]
+        } else {
+            role.claim_values.clone()
+        };
+        Self {
+            name: role.name.clone(),
+            claim_values,
+            allowed_tools: role.allowed_tools.clone(),
+            allowed_commands: role.allowed_commands.clone(),
+            http_methods: role
+                .http_methods
+                .as_ref()
+                .map(|methods| methods.iter().map(|m| m.to_ascii_uppercase()).collect()),
+            autonomy: role.autonomy,
+            memory_categories: role.memory_categories.clone(),
+        }
+    }
+
+    /// Tool allowlist, or `None` when every tool is allowed.
+    pub fn allowed_tools(&self) -> Option<&[String]> {
+        self.allowed_tools.as_deref()
+    }
+
+    pub fn allows_tool(&self, tool: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == tool))
+    }
+
+    pub fn allows_command(&self, base_command: &str) -> bool {
+        self.allowed_commands
+            .as_ref()
+            .is_none_or(|commands| commands.iter().any(|c| c == base_command))
+    }
+
+    pub fn allows_http_method(&self, method: &str) -> bool {
+        self.http_methods
+            .as_ref()
+            .is_none_or(|methods| methods.iter().any(|m| m.eq_ignore_ascii_case(method)))
+    }
+
+    pub fn allows_memory_category(&self, category: &str) -> bool {
+        self.memory_categories
+            .as_ref()
+            .is_none_or(|categories| categories.iter().any(|c| c == category))
+    }
+
+    /// System note describing this role's configured limits, so the model's
+    /// picture of the user matches what the tool loop enforces.
+    pub fn prompt_note(&self) -> String {
+        let mut note = format!("[System: The current user has role '{}'.", self.name);
+        match self.autonomy {
+            Some(AutonomyLevel::ReadOnly) => note.push_str(
+                " The role is read-only: do not create, change or delete anything. If the user \
+                 asks for a modification, explain that their role does not allow it.",
+            ),
+            Some(AutonomyLevel::Supervised) => {
+                note.push_str(" Actions that change anything need approval.");
+            }
+            Some(AutonomyLevel::Full) | None => {}
+        }
+        let limits = [
+            ("Only these tools are available", &self.allowed_tools),
+            ("Shell commands are limited to", &self.allowed_commands),
+            ("HTTP requests are limited to", &self.http_methods),
+            ("Memory access is limited to", &self.memory_categories),
+        ];
+        for (label, values) in limits {
+            if let Some(values) = values {
+                let listed = if values.is_empty() {
+                    "none".to_string()
+                } else {
+                    values.join(", ")
+                };
+                let _ = write!(note, " {label}: {listed}.");
+            }
+        }
+        if self.autonomy.is_none() && limits.iter().all(|(_, values)| values.is_none()) {
+            note.push_str(" All operations are available.");
+        }
+        note.push(']');
+        note
+    }
+
+    /// The lower of `level` and this role's autonomy ceiling.
+    pub fn cap_autonomy(&self, level: AutonomyLevel) -> AutonomyLevel {
+        match (self.autonomy, level) {
+            (Some(AutonomyLevel::ReadOnly), _) | (_, AutonomyLevel::ReadOnly) => {
+                AutonomyLevel::ReadOnly
+            }
+            (Some(AutonomyLevel::Supervised), _) | (_, AutonomyLevel::Supervised) => {
+                AutonomyLevel::Supervised
+            }
+            _ => AutonomyLevel::Full,
+        }
+    }
+}
+
+/// Roles in priority order plus the claim they are read from.
+pub struct RbacPolicy {
+    claim: String,
+    default_role: Arc<RolePermissions>,
+    roles: Vec<Arc<RolePermissions>>,
+    audit: Option<Arc<AuditLogger>>,
+}
+
+impl Default for RbacPolicy {
+    fn default() -> Self {
+        Self::from_config(&GatewayRbacConfig::default(), None)
+    }
+}
+
+impl RbacPolicy {
+    pub fn from_config(config: &GatewayRbacConfig, audit: Option<Arc<AuditLogger>>) -> Self {
+        let roles: Vec<Arc<RolePermissions>> = config
+            .effective_roles()
+            .iter()
+            .map(|role| Arc::new(RolePermissions::from_config(role)))
+            .collect();
+        // Validation guarantees the default role exists; fall back to the
+        // lowest-priority role rather than granting anything extra.
+        let default_role = roles
+            .iter()
+            .find(|role| role.name == config.default_role)
+            .or_else(|| roles.last())
+            .cloned()
+            .unwrap_or_else(|| {
+                Arc::new(RolePermissions::from_config(&GatewayRoleConfig {
+                    name: config.default_role.clone(),
+                    allowed_tools: Some(Vec::new()),
+                    ..GatewayRoleConfig::default()
+                }))
+            });
+        Self {
+            claim: config.claim.clone(),
+            default_role,
+            roles,
+            audit,
+        }
+    }
+
+    /// The configured policy, with denials written to the `[security.audit]`
+    /// log next to the config file.
+    pub fn for_config(config: &Config) -> Self {
+        let audit = config.config_path.parent().and_then(|dir| {
+            AuditLogger::new(config.security.audit.clone(), dir.to_path_buf())
+                .map(Arc::new)
+                .ok()
+        });
+        Self::from_config(&config.gateway.rbac, audit)
+    }
+
+    /// Role with `name`, or the default role when it no longer exists.
+    pub fn role_named(&self, name: &str) -> Arc<RolePermissions> {
+        self.roles
+            .iter()
+            .find(|role| role.name == name)
+            .cloned()
+            .unwrap_or_else(|| Arc::clone(&self.default_role))
+    }
+
+    /// Highest-priority role whose claim values appear in `claims`.
+    pub fn resolve(&self, claims: &serde_json::Value) -> Arc<RolePermissions> {
+        let values = claim_values(claims, &self.claim);
+        self.roles
+            .iter()
+            .find(|role| role.claim_values.iter().any(|v| values.contains(v)))
+            .cloned()
+            .unwrap_or_else(|| Arc::clone(&self.default_role))
+    }
+
+    pub fn scope_for(&self, role: Arc<RolePermissions>, subject: Option<String>) -> RoleScope {
+        RoleScope {
+            permissions: role,
+            subject,
+            audit: self.audit.clone(),
+        }
+    }
+}
+
+/// Strings found at a dot-separated claim path (a string or array of strings).
+pub fn claim_values(claims: &serde_json::Value, path: &str) -> Vec<String> {
+    let mut node = claims;
+    for part in path.split('.') {
+        match node.get(part) {
+            Some(next) => node = next,
+            None => return Vec::new(),
+        }
+    }
+    match node {
+        serde_json::Value::String(value) => vec![value.clone()],
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The role active for the current request.
+#[derive(Clone)]
+pub struct RoleScope {
+    pub permissions: Arc<RolePermissions>,
+    pub subject: Option<String>,
+    audit: Option<Arc<AuditLogger>>,
+}
+
+impl RoleScope {
+    /// Check a tool call against the role before it runs.
+    pub fn check_tool_call(&self, tool: &str, args: &serde_json::Value) -> Result<(), String> {
+        let role = &self.permissions;
+        if !role.allows_tool(tool) {
+            return Err(self.deny(tool, &format!("tool '{tool}' is not allowed")));
+        }
+        match tool {
+            "http_request" => {
+                let method = args.get("method").and_then(|m| m.as_str()).unwrap_or("GET");
+                if !role.allows_http_method(method) {
+                    return Err(self.deny(
+                        tool,
+                        &format!("HTTP method {} is not allowed", method.to_ascii_uppercase()),
+                    ));
+                }
+            }
+            "memory_store" => {
+                let category = args
+                    .get("category")
+                    .and_then(|c| c.as_str())
+                    .unwrap_or("core");
+                if !role.allows_memory_category(category) {
+                    return Err(self.deny(
+                        tool,
+                        &format!("memory category '{category}' is not allowed"),
+                    ));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Record a denial and return the message shown to the model.
+    pub fn deny(&self, action: &str, reason: &str) -> String {
+        let message = format!("Denied by role '{}': {reason}", self.permissions.name);
+        tracing::warn!(
+            role = %self.permissions.name,
+            subject = self.subject.as_deref().unwrap_or("unknown"),
+            "RBAC denial for {action}: {reason}"
+        );
+        if let Some(audit) = &self.audit {
+            let mut event = AuditEvent::new(AuditEventType::PolicyViolation)
+                .with_actor(
+                    "gateway".into(),
+                    self.subject.clone(),
+                    Some(self.permissions.name.clone()),
+                )
+                .with_action(format!("{action}: {reason}"), "rbac".into(), false, false);
+            event.security.policy_violation = true;
+            if let Err(e) = audit.log(&event) {
+                tracing::warn!("Failed to write RBAC audit event: {e}");
+            }
+        }
+        message
+    }
+}
+
+/// Run `f` with `role` active.
+pub async fn scope<F: Future>(role: RoleScope, f: F) -> F::Output {
+    ACTIVE_ROLE.scope(Arc::new(role), f).await
+}
+
+/// The role active for the current task, if any.
+pub fn current() -> Option<Arc<RoleScope>> {
+    ACTIVE_ROLE.try_with(Arc::clone).ok()
+}
+
+/// Carry the role active here into `f`, for work handed to `tokio::spawn`,
+/// which does not inherit task-locals.
+pub fn propagate<F: Future>(f: F) -> impl Future<Output = F::Output> {
+    let role = current();
+    async move {
+        match role {
+            Some(role) => ACTIVE_ROLE.scope(role, f).await,
+            None => f.await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuditConfig;
+    use serde_json::json;
+
+    fn keycloak_claims(roles: &[&str]) -> serde_json::Value {
+        json!({ "sub": "u1", "realm_access": { "roles": roles } })
+    }
+
+    #[test]
+    fn builtin_roles_pick_highest_priority() {
+        let rbac = RbacPolicy::default();
+        assert_eq!(
+            rbac.resolve(&keycloak_claims(&["viewer", "admin", "accountant"]))
+                .name,
+            "admin"
+        );
+        assert_eq!(
+            rbac.resolve(&keycloak_claims(&["viewer", "accountant"]))
+                .name,
+            "accountant"
+        );
+        assert_eq!(
+            rbac.resolve(&keycloak_claims(&["uma_authorization"])).name,
+            "viewer"
+        );
+        assert_eq!(rbac.resolve(&json!({ "sub": "u4" })).name, "viewer");
+    }
+
+    #[test]
+    fn builtin_viewer_is_read_only() {
+        let viewer = RbacPolicy::default().role_named("viewer");
+        assert_eq!(
+            viewer.cap_autonomy(AutonomyLevel::Full),
+            AutonomyLevel::ReadOnly
+        );
+        assert!(viewer.allows_tool("file_read"));
+        assert!(!viewer.allows_tool("shell"));
+        assert!(!viewer.allows_tool("file_write"));
+        assert!(!viewer.allows_http_method("POST"));
+        assert_eq!(RbacPolicy::default().role_named("gone").name, "viewer");
+    }
+
+    #[test]
+    fn prompt_note_follows_configured_permissions() {
+        let rbac = RbacPolicy::default();
+        let viewer = rbac.role_named("viewer").prompt_note();
+        assert!(viewer.contains("role 'viewer'"));
+        assert!(viewer.contains("read-only"));
+        assert!(viewer.contains("HTTP requests are limited to: GET."));
+        assert!(rbac
+            .role_named("admin")
+            .prompt_note()
+            .contains("All operations are available"));
+
+        // A custom role reusing a built-in name is described by its own rules.
+        let config = GatewayRbacConfig {
+            roles: vec![GatewayRoleConfig {
+                name: "viewer".into(),
+                allowed_tools: Some(vec!["file_write".into()]),
+                ..GatewayRoleConfig::default()
+            }],
+            ..GatewayRbacConfig::default()
+        };
+        let custom = RbacPolicy::from_config(&config, None)
+            .role_named("viewer")
+            .prompt_note();
+        assert!(!custom.contains("read-only"));
+        assert!(custom.contains("Only these tools are available: file_write."));
+    }
+
+    #[test]
+    fn custom_claim_path_and_values() {
+        let config = GatewayRbacConfig {
+            claim: "resource_access.zeroclaw.roles".into(),
+            default_role: "guest".into(),
+            roles: vec![
+                GatewayRoleConfig {
+                    name: "operator".into(),
+                    claim_values: vec!["ops".into()],
+                    ..GatewayRoleConfig::default()
+                },
+                GatewayRoleConfig {
+                    name: "guest".into(),
+                    allowed_tools: Some(vec![]),
+                    ..GatewayRoleConfig::default()
+                },
+            ],
+        };
+        let rbac = RbacPolicy::from_config(&config, None);
+        let claims = json!({ "resource_access": { "zeroclaw": { "roles": ["ops"] } } });
+        assert_eq!(rbac.resolve(&claims).name, "operator");
+        assert_eq!(rbac.resolve(&keycloak_claims(&["ops"])).name, "guest");
+        assert_eq!(claim_values(&json!({ "role": "ops" }), "role"), vec!["ops"]);
+    }
+
+    #[test]
+    fn tool_calls_are_checked_and_denials_audited() {
+        let tmp = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            AuditLogger::new(
+                AuditConfig {
+                    enabled: true,
+                    ..AuditConfig::default()
+                },
+                tmp.path().to_path_buf(),
+            )
+            .unwrap(),
+        );
+        let config = GatewayRbacConfig {
+            roles: vec![GatewayRoleConfig {
+                name: "viewer".into(),
+                allowed_tools: Some(vec!["http_request".into(), "memory_store".into()]),
+                http_methods: Some(vec!["get".into()]),
+                memory_categories: Some(vec!["conversation".into()]),
+                autonomy: Some(AutonomyLevel::ReadOnly),
+                ..GatewayRoleConfig::default()
+            }],
+            ..GatewayRbacConfig::default()
+        };
+        let rbac = RbacPolicy::from_config(&config, Some(audit));
+        let role = rbac.resolve(&keycloak_claims(&["viewer"]));
+        assert_eq!(
+            role.cap_autonomy(AutonomyLevel::Full),
+            AutonomyLevel::ReadOnly
+        );
+        let scope = rbac.scope_for(role, Some("u1".into()));
+
+        assert!(scope
+            .check_tool_call("http_request", &json!({"url": "https://x"}))
+            .is_ok());
+        let denied = scope
+            .check_tool_call("http_request", &json!({"method": "DELETE"}))
+            .unwrap_err();
+        assert!(denied.contains("Denied by role 'viewer'"));
+        assert!(scope.check_tool_call("shell", &json!({})).is_err());
+        assert!(scope
+            .check_tool_call("memory_store", &json!({"category": "conversation"}))
+            .is_ok());
+        assert!(scope.check_tool_call("memory_store", &json!({})).is_err());
+
+        let log =
+            std::fs::read_to_string(tmp.path().join(AuditConfig::default().log_path)).unwrap();
+        assert_eq!(log.lines().count(), 3);
+        assert!(log.contains("policy_violation"));
+    }
+
+    #[tokio::test]
+    async fn scope_is_visible_only_inside() {
+        assert!(current().is_none());
+        let rbac = RbacPolicy::default();
+        let role = rbac.resolve(&keycloak_claims(&["viewer"]));
+        let name = scope(rbac.scope_for(role, None), async {
+            current().map(|scope| scope.permissions.name.clone())
+        })
+        .await;
+        assert_eq!(name.as_deref(), Some("viewer"));
+        assert!(current().is_none());
+    }
+
+    #[tokio::test]
+    async fn propagate_carries_role_into_spawned_tasks() {
+        let rbac = RbacPolicy::default();
+        let role = rbac.resolve(&keycloak_claims(&["viewer"]));
+        let (plain, propagated) = scope(rbac.scope_for(role, None), async {
+            let plain = tokio::spawn(async { current().is_some() });
+            let propagated = tokio::spawn(propagate(async {
+                current().map(|scope| scope.permissions.name.clone())
+            }));
+            (plain.await.unwrap(), propagated.await.unwrap())
+        })
+        .await;
+        assert!(!plain);
+        assert_eq!(propagated.as_deref(), Some("viewer"));
+    }
+}
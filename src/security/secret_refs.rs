@@ -0,0 +1,612 @@
+//! `secret://` references in config values.
+//!
+//! Any string in `config.toml` of the form `secret://<backend>/<locator>` is
+//! replaced at load time by the value fetched from that backend, so the
+//! config directory never has to hold the credential itself:
+//!
+//! | Reference | Source |
+//! |---|---|
+//! | `secret://env/NAME` | environment variable |
+//! | `secret://file/path` | file contents (relative to the config directory) |
+//! | `secret://cmd/<command>` | stdout of a shell command |
+//! | `secret://keyring/<name>` | OS keyring entry under service `zeroclaw` |
+//! | `secret://vault/<path>#field` | HashiCorp Vault KV v2 (`[secrets.vault]`) |
+//!
+//! [`Config::save`](crate::config::Config::save) writes the references back
+//! instead of the resolved values.
+
+use crate::config::VaultConfig;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// Prefix that marks a config string as a secret reference.
+pub const SECRET_REF_PREFIX: &str = "secret://";
+
+/// Keyring service name used for `secret://keyring/<name>`.
+const KEYRING_SERVICE: &str = "zeroclaw";
+
+/// Upper bound for `secret://cmd/...` helpers (password managers may prompt).
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+type VaultData = serde_json::Map<String, serde_json::Value>;
+
+/// A parsed `secret://` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    Env(String),
+    File(String),
+    Cmd(String),
+    Keyring(String),
+    Vault { path: String, field: String },
+}
+
+impl SecretRef {
+    /// Parse `value`, returning `Ok(None)` when it is not a reference.
+    pub fn parse(value: &str) -> Result<Option<Self>> {
+        let Some(rest) = value.strip_prefix(SECRET_REF_PREFIX) else {
+            return Ok(None);
+        };
+        let (backend, locator) = rest
+            .split_once('/')
+            .filter(|(_, locator)| !locator.is_empty())
+            .with_context(|| {
+                format!("Invalid secret reference '{value}': expected secret://<backend>/<name>")
+            })?;
+        let reference = match backend {
+            "env" => Self::Env(locator.to_string()),
+            "file" => Self::File(locator.to_string()),
+            "cmd" => Self::Cmd(locator.to_string()),
+            "keyring" => Self::Keyring(locator.to_string()),
+            "vault" => {
+                let (path, field) = locator
+                    .rsplit_once('#')
+                    .map(|(path, field)| (path.trim_matches('/'), field))
+                    .filter(|(path, field)| !path.is_empty() && !field.is_empty())
+                    .with_context(|| {
+                        format!(
+                            "Invalid secret reference '{value}': \
+                             expected secret://vault/<path>#<field>"
+                        )
+                    })?;
+                Self::Vault {
+                    path: path.to_string(),
+                    field: field.to_string(),
+                }
+            }
+            other => bail!(
+                "Unknown secret backend '{other}' in '{value}' \
+                 (expected env, file, cmd, keyring or vault)"
+            ),
+        };
+        Ok(Some(reference))
+    }
+}
+
+/// A config value that was loaded from a reference.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolvedSecret {
+    /// Table keys (and array indices) leading to the value.
+    pub path: Vec<String>,
+    /// The original `secret://` string.
+    pub reference: String,
+    value: String,
+}
+
+impl fmt::Debug for ResolvedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedSecret")
+            .field("path", &self.path.join("."))
+            .field("reference", &self.reference)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Minimal HashiCorp Vault KV v2 reader.
+pub struct VaultClient {
+    address: String,
+    mount: String,
+    namespace: Option<String>,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl VaultClient {
+    pub fn new(address: &str, mount: &str, namespace: Option<String>, token: String) -> Self {
+        Self {
+            address: address.trim_end_matches('/').to_string(),
+            mount: mount.trim_matches('/').to_string(),
+            namespace,
+            token,
+            client: crate::config::build_runtime_proxy_client_with_timeouts(
+                "secrets.vault",
+                30,
+                10,
+            ),
+        }
+    }
+
+    /// Build a client from `[secrets.vault]`, falling back to `VAULT_ADDR`,
+    /// `VAULT_NAMESPACE`, `VAULT_TOKEN` and `~/.vault-token`.
+    pub fn from_config(config: &VaultConfig) -> Result<Self> {
+        let env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+        let address = config
+            .address
+            .clone()
+            .or_else(|| env("VAULT_ADDR"))
+            .context("Vault address not configured: set [secrets.vault].address or VAULT_ADDR")?;
+        let token = match env("VAULT_TOKEN") {
+            Some(token) => token,
+            None => directories::UserDirs::new()
+                .map(|dirs| dirs.home_dir().join(".vault-token"))
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty())
+                .context("No Vault token: set VAULT_TOKEN or run `vault login`")?,
+        };
+        Ok(Self::new(
+            &address,
+            &config.mount,
+            config.namespace.clone().or_else(|| env("VAULT_NAMESPACE")),
+            token,
+        ))
+    }
+
+    /// Read the latest version of the secret at `path` under the mount.
+    pub async fn read(&self, path: &str) -> Result<VaultData> {
+        let url = format!("{}/v1/{}/data/{path}", self.address, self.mount);
+        let mut request = self.client.get(&url).header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Vault at {}", self.address))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("Vault returned {status} for '{}/{path}'", self.mount);
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("Vault returned invalid JSON")?;
+        body.pointer("/data/data")
+            .and_then(serde_json::Value::as_object)
+            .cloned()
+            .with_context(|| {
+                format!(
+                    "Vault response for '{}/{path}' has no data (is '{}' a KV v2 mount?)",
+                    self.mount, self.mount
+                )
+            })
+    }
+}
+
+/// Fetches referenced secrets, caching Vault reads for the duration of a load.
+pub struct SecretResolver {
+    base_dir: PathBuf,
+    vault_config: VaultConfig,
+    vault: Option<VaultClient>,
+    vault_cache: HashMap<String, VaultData>,
+}
+
+impl SecretResolver {
+    /// `base_dir` anchors relative `secret://file/...` paths (the config directory).
+    pub fn new(base_dir: &Path, vault_config: VaultConfig) -> Self {
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            vault_config,
+            vault: None,
+            vault_cache: HashMap::new(),
+        }
+    }
+
+    /// Use `client` instead of building one from `[secrets.vault]`.
+    pub fn with_vault_client(mut self, client: VaultClient) -> Self {
+        self.vault = Some(client);
+        self
+    }
+
+    pub async fn resolve(&mut self, reference: &SecretRef) -> Result<String> {
+        match reference {
+            SecretRef::Env(name) => std::env::var(name)
+                .with_context(|| format!("Environment variable {name} is not set")),
+            SecretRef::File(path) => {
+                let path = self.base_dir.join(shellexpand::tilde(path).as_ref());
+                let contents = tokio::fs::read_to_string(&path)
+                    .await
+                    .with_context(|| format!("Failed to read secret file {}", path.display()))?;
+                Ok(trim_line_end(&contents))
+            }
+            SecretRef::Cmd(command) => run_secret_command(command).await,
+            SecretRef::Keyring(name) => read_keyring(name).await,
+            SecretRef::Vault { path, field } => self.read_vault(path, field).await,
+        }
+    }
+
+    async fn read_vault(&mut self, path: &str, field: &str) -> Result<String> {
+        if !self.vault_cache.contains_key(path) {
+            if self.vault.is_none() {
+                self.vault = Some(VaultClient::from_config(&self.vault_config)?);
+            }
+            let client = self.vault.as_ref().context("Vault client unavailable")?;
+            let data = client.read(path).await?;
+            self.vault_cache.insert(path.to_string(), data);
+        }
+        let value = self
+            .vault_cache
+            .get(path)
+            .and_then(|data| data.get(field))
+            .with_context(|| format!("Vault secret '{path}' has no field '{field}'"))?;
+        Ok(match value {
+            serde_json::Value::String(value) => value.clone(),
+            other => other.to_string(),
+        })
+    }
+}
+
+/// Replace every `secret://` string in `root` with its resolved value.
+pub async fn resolve_toml_refs(
+    root: &mut toml::Value,
+    resolver: &mut SecretResolver,
+) -> Result<Vec<ResolvedSecret>> {
+    let mut pending = Vec::new();
+    collect_refs(root, &mut Vec::new(), &mut pending);
+
+    let mut resolved = Vec::with_capacity(pending.len());
+    for (path, reference) in pending {
+        let Some(parsed) = SecretRef::parse(&reference)? else {
+            continue;
+        };
+        let value = resolver
+            .resolve(&parsed)
+            .await
+            .with_context(|| format!("Failed to resolve {} ({reference})", path.join(".")))?;
+        if let Some(slot) = lookup_mut(root, &path) {
+            *slot = toml::Value::String(value.clone());
+        }
+        resolved.push(ResolvedSecret {
+            path,
+            reference,
+            value,
+        });
+    }
+    Ok(resolved)
+}
+
+/// Put references back into `doc` before it is written to disk. A value that
+/// was changed in memory since loading (`current` differs from what the
+/// reference resolved to) is kept as-is.
+pub fn restore_toml_refs(doc: &mut toml::Value, current: &toml::Value, refs: &[ResolvedSecret]) {
+    for secret in refs {
+        let unchanged = lookup(current, &secret.path)
+            .and_then(toml::Value::as_str)
+            .is_some_and(|value| value == secret.value);
+        if !unchanged {
+            continue;
+        }
+        if let Some(slot) = lookup_mut(doc, &secret.path) {
+            *slot = toml::Value::String(secret.reference.clone());
+        }
+    }
+}
+
+fn collect_refs(node: &toml::Value, path: &mut Vec<String>, out: &mut Vec<(Vec<String>, String)>) {
+    match node {
+        toml::Value::String(value) if value.starts_with(SECRET_REF_PREFIX) => {
+            out.push((path.clone(), value.clone()));
+        }
+        toml::Value::Table(table) => {
+            for (key, child) in table {
+                path.push(key.clone());
+                collect_refs(child, path, out);
+                path.pop();
+            }
+        }
+        toml::Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                path.push(index.to_string());
+                collect_refs(child, path, out);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lookup<'a>(mut node: &'a toml::Value, path: &[String]) -> Option<&'a toml::Value> {
+    for part in path {
+        node = match node {
+            toml::Value::Table(table) => table.get(part)?,
+            toml::Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(node)
+}
+
+fn lookup_mut<'a>(mut node: &'a mut toml::Value, path: &[String]) -> Option<&'a mut toml::Value> {
+    for part in path {
+        node = match node {
+            toml::Value::Table(table) => table.get_mut(part)?,
+            toml::Value::Array(items) => items.get_mut(part.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(node)
+}
+
+fn trim_line_end(value: &str) -> String {
+    value.trim_end_matches(['\r', '\n']).to_string()
+}
+
+async fn run_captured(program: &str, args: &[&str]) -> Result<String> {
+    let output = tokio::time::timeout(
+        COMMAND_TIMEOUT,
+        tokio::process::Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .with_context(|| format!("{program} timed out after {}s", COMMAND_TIMEOUT.as_secs()))?
+    .with_context(|| format!("Failed to run {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let stdout = String::from_utf8(output.stdout).context("Secret output is not valid UTF-8")?;
+    Ok(trim_line_end(&stdout))
+}
+
+async fn run_secret_command(command: &str) -> Result<String> {
+    if cfg!(windows) {
+        run_captured("cmd", &["/C", command]).await
+    } else {
+        run_captured("sh", &["-c", command]).await
+    }
+}
+
+async fn read_keyring(name: &str) -> Result<String> {
+    let result = if cfg!(target_os = "macos") {
+        run_captured(
+            "security",
+            &[
+                "find-generic-password",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                name,
+                "-w",
+            ],
+        )
+        .await
+    } else if cfg!(target_os = "linux") {
+        run_captured(
+            "secret-tool",
+            &["lookup", "service", KEYRING_SERVICE, "account", name],
+        )
+        .await
+    } else {
+        bail!("secret://keyring is supported on Linux (secret-tool) and macOS only");
+    };
+    let value = result
+        .with_context(|| format!("No keyring entry '{name}' for service '{KEYRING_SERVICE}'"))?;
+    anyhow::ensure!(
+        !value.is_empty(),
+        "Keyring entry '{name}' for service '{KEYRING_SERVICE}' is empty"
+    );
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Path as AxumPath, http::HeaderMap, routing::get, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn parses_every_backend() {
+        assert_eq!(SecretRef::parse("sk-plain").unwrap(), None);
+        assert_eq!(
+            SecretRef::parse("secret://env/OPENAI_API_KEY").unwrap(),
+            Some(SecretRef::Env("OPENAI_API_KEY".into()))
+        );
+        assert_eq!(
+            SecretRef::parse("secret://file//run/secrets/token").unwrap(),
+            Some(SecretRef::File("/run/secrets/token".into()))
+        );
+        assert_eq!(
+            SecretRef::parse("secret://cmd/pass show zeroclaw/openai").unwrap(),
+            Some(SecretRef::Cmd("pass show zeroclaw/openai".into()))
+        );
+        assert_eq!(
+            SecretRef::parse("secret://keyring/telegram").unwrap(),
+            Some(SecretRef::Keyring("telegram".into()))
+        );
+        assert_eq!(
+            SecretRef::parse("secret://vault/zeroclaw/providers#openai").unwrap(),
+            Some(SecretRef::Vault {
+                path: "zeroclaw/providers".into(),
+                field: "openai".into()
+            })
+        );
+        assert!(SecretRef::parse("secret://vault/zeroclaw").is_err());
+        assert!(SecretRef::parse("secret://aws/key").is_err());
+        assert!(SecretRef::parse("secret://env/").is_err());
+    }
+
+    #[tokio::test]
+    async fn resolves_refs_in_place_and_restores_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("bot.token"), "123:abc\n").unwrap();
+        std::env::set_var("ZEROCLAW_TEST_SECRET_REF_KEY", "sk-from-env");
+
+        let mut doc: toml::Value = toml::from_str(
+            r#"
+api_key = "secret://env/ZEROCLAW_TEST_SECRET_REF_KEY"
+default_model = "gpt"
+
+[channels_config.telegram]
+bot_token = "secret://file/bot.token"
+allowed_users = ["secret://env/ZEROCLAW_TEST_SECRET_REF_KEY"]
+"#,
+        )
+        .unwrap();
+        let original = doc.clone();
+        let mut resolver = SecretResolver::new(tmp.path(), VaultConfig::default());
+        let refs = resolve_toml_refs(&mut doc, &mut resolver).await.unwrap();
+
+        assert_eq!(refs.len(), 3);
+        assert_eq!(doc["api_key"].as_str(), Some("sk-from-env"));
+        assert_eq!(
+            doc["channels_config"]["telegram"]["bot_token"].as_str(),
+            Some("123:abc")
+        );
+        assert_eq!(
+            doc["channels_config"]["telegram"]["allowed_users"][0].as_str(),
+            Some("sk-from-env")
+        );
+        assert!(!format!("{refs:?}").contains("sk-from-env"));
+
+        let mut saved = doc.clone();
+        restore_toml_refs(&mut saved, &doc, &refs);
+        assert_eq!(saved, original);
+
+        let mut edited = doc.clone();
+        edited["api_key"] = toml::Value::String("sk-typed-in".into());
+        let mut saved = edited.clone();
+        restore_toml_refs(&mut saved, &edited, &refs);
+        assert_eq!(saved["api_key"].as_str(), Some("sk-typed-in"));
+
+        let mut missing: toml::Value =
+            toml::from_str(r#"api_key = "secret://env/ZEROCLAW_TEST_SECRET_REF_UNSET""#).unwrap();
+        let err = resolve_toml_refs(&mut missing, &mut resolver)
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("api_key"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_backend_uses_stdout_and_fails_on_non_zero_exit() {
+        let mut resolver = SecretResolver::new(Path::new("."), VaultConfig::default());
+        let value = resolver
+            .resolve(&SecretRef::Cmd("printf 'hunter2\\n'".into()))
+            .await
+            .unwrap();
+        assert_eq!(value, "hunter2");
+        assert!(resolver
+            .resolve(&SecretRef::Cmd("echo nope >&2; exit 3".into()))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn vault_kv2_fields_are_read_once_per_path() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        let app = Router::new().route(
+            "/v1/kv/data/{*path}",
+            get(
+                move |AxumPath(path): AxumPath<String>, headers: HeaderMap| {
+                    let counter = Arc::clone(&counter);
+                    async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        let authorized = headers
+                            .get("x-vault-token")
+                            .is_some_and(|token| token == "dev-root");
+                        if !authorized || path != "zeroclaw/providers" {
+                            return (
+                                axum::http::StatusCode::FORBIDDEN,
+                                Json(serde_json::json!({ "errors": ["permission denied"] })),
+                            );
+                        }
+                        (
+                            axum::http::StatusCode::OK,
+                            Json(serde_json::json!({
+                                "data": {
+                                    "data": { "openai": "sk-vault", "port": 8200 },
+                                    "metadata": { "version": 3 }
+                                }
+                            })),
+                        )
+                    }
+                },
+            ),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let client = VaultClient::new(&format!("http://{addr}/"), "kv", None, "dev-root".into());
+        let mut resolver =
+            SecretResolver::new(Path::new("."), VaultConfig::default()).with_vault_client(client);
+        let vault = |field: &str| SecretRef::Vault {
+            path: "zeroclaw/providers".into(),
+            field: field.into(),
+        };
+        assert_eq!(
+            resolver.resolve(&vault("openai")).await.unwrap(),
+            "sk-vault"
+        );
+        assert_eq!(resolver.resolve(&vault("port")).await.unwrap(), "8200");
+        assert!(resolver.resolve(&vault("missing")).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let denied = resolver
+            .resolve(&SecretRef::Vault {
+                path: "other".into(),
+                field: "x".into(),
+            })
+            .await
+            .unwrap_err();
+        assert!(denied.to_string().contains("403"));
+    }
+
+    /// Run against a local dev server:
+    /// `vault server -dev -dev-root-token-id=root` then
+    /// `VAULT_ADDR=http://127.0.0.1:8200 VAULT_TOKEN=root cargo test -- --ignored vault_dev_server`.
+    #[tokio::test]
+    #[ignore = "requires a local Vault dev server (VAULT_ADDR, VAULT_TOKEN)"]
+    async fn vault_dev_server_roundtrip() {
+        let client = VaultClient::from_config(&VaultConfig::default()).unwrap();
+        let address = std::env::var("VAULT_ADDR").unwrap();
+        let token = std::env::var("VAULT_TOKEN").unwrap();
+        reqwest::Client::new()
+            .post(format!(
+                "{}/v1/secret/data/zeroclaw-test",
+                address.trim_end_matches('/')
+            ))
+            .header("X-Vault-Token", token)
+            .json(&serde_json::json!({ "data": { "api_key": "sk-dev-server" } }))
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        let mut resolver =
+            SecretResolver::new(Path::new("."), VaultConfig::default()).with_vault_client(client);
+        let reference = SecretRef::parse("secret://vault/zeroclaw-test#api_key")
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolver.resolve(&reference).await.unwrap(), "sk-dev-server");
+    }
+}
@@ -59,20 +59,7 @@ impl SecretStore {
         }
 
         let key_bytes = self.load_or_create_key()?;
-        let key = Key::from_slice(&key_bytes);
-        let cipher = ChaCha20Poly1305::new(key);
-
-        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
-        let ciphertext = cipher
-            .encrypt(&nonce, plaintext.as_bytes())
-            .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
-
-        // Prepend nonce to ciphertext for storage
-        let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
-        blob.extend_from_slice(&nonce);
-        blob.extend_from_slice(&ciphertext);
-
-        Ok(format!("enc2:{}", hex_encode(&blob)))
+        encrypt_with_key(&key_bytes, plaintext)
     }
 
     /// Decrypt a secret.
@@ -175,53 +162,295 @@ impl SecretStore {
             hex_decode(hex_key.trim()).context("Secret key file is corrupt")
         } else {
             let key = generate_random_key();
-            if let Some(parent) = self.key_path.parent() {
-                fs::create_dir_all(parent)?;
+            write_key_file(&self.key_path, &key)?;
+            Ok(key)
+        }
+    }
+}
+
+/// Write `key` hex-encoded to `key_path`, readable only by the current user.
+fn write_key_file(key_path: &Path, key: &[u8]) -> Result<()> {
+    if let Some(parent) = key_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(key_path, hex_encode(key)).context("Failed to write secret key file")?;
+
+    // Set restrictive permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(key_path, fs::Permissions::from_mode(0o600))
+            .context("Failed to set key file permissions")?;
+    }
+    #[cfg(windows)]
+    {
+        // On Windows, use icacls to restrict permissions to current user only
+        let username = std::env::var("USERNAME").unwrap_or_default();
+        let Some(grant_arg) = build_windows_icacls_grant_arg(&username) else {
+            tracing::warn!(
+                "USERNAME environment variable is empty; \
+                 cannot restrict key file permissions via icacls"
+            );
+            return Ok(());
+        };
+
+        match std::process::Command::new("icacls")
+            .arg(key_path)
+            .args(["/inheritance:r", "/grant:r"])
+            .arg(grant_arg)
+            .output()
+        {
+            Ok(o) if !o.status.success() => {
+                tracing::warn!(
+                    "Failed to set key file permissions via icacls (exit code {:?})",
+                    o.status.code()
+                );
+            }
+            Err(e) => {
+                tracing::warn!("Could not set key file permissions: {e}");
+            }
+            _ => {
+                tracing::debug!("Key file permissions restricted via icacls");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypt `plaintext` under `key_bytes` into the `enc2:` format.
+fn encrypt_with_key(key_bytes: &[u8], plaintext: &str) -> Result<String> {
+    let key = Key::from_slice(key_bytes);
+    let cipher = ChaCha20Poly1305::new(key);
+
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
+
+    // Prepend nonce to ciphertext for storage
+    let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+
+    Ok(format!("enc2:{}", hex_encode(&blob)))
+}
+
+/// Outcome of [`rotate_key`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct KeyRotation {
+    /// Files rewritten, with the number of values re-encrypted in each.
+    pub rotated: Vec<(PathBuf, usize)>,
+    /// Paths that could not be scanned, with the reason. Secrets in them stay
+    /// under the old key and can no longer be decrypted.
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// A rewritten file waiting to replace `path`.
+struct StagedFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    count: usize,
+    original: String,
+}
+
+/// Files that hold `enc:`/`enc2:` secrets for `config`: `config.toml`, the
+/// files it was composed from (includes, profile and override files) and the
+/// auth-profile store. Nothing under the workspace is included.
+pub fn secret_files(config: &crate::config::Config) -> Vec<PathBuf> {
+    let state_dir = crate::auth::state_dir_from_config(config);
+    let mut files = vec![config.config_path.clone()];
+    files.extend(config.sources.files.iter().cloned());
+    files.push(
+        crate::auth::profiles::AuthProfilesStore::new(&state_dir, false)
+            .path()
+            .to_path_buf(),
+    );
+    files.retain(|path| !path.starts_with(&config.workspace_dir));
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Re-encrypt every `enc:`/`enc2:` value in `files` (see [`secret_files`])
+/// with a freshly generated key, then replace `.secret_key` in
+/// `zeroclaw_dir`. Files that do not exist are ignored.
+///
+/// Every value is decrypted and every rewritten file staged before the key is
+/// swapped, so a value that fails to decrypt aborts without touching anything.
+/// The old key is kept as `.secret_key.old` until every file is replaced; if a
+/// replacement fails, rewritten files and the key are restored. Legacy `enc:`
+/// values are upgraded to `enc2:`.
+pub fn rotate_key(zeroclaw_dir: &Path, files: &[PathBuf]) -> Result<KeyRotation> {
+    let old = SecretStore::new(zeroclaw_dir, true);
+    anyhow::ensure!(
+        old.key_path.exists(),
+        "No secret key at {}; nothing to rotate",
+        old.key_path.display()
+    );
+    let backup_key_path = old.key_path.with_extension("old");
+    anyhow::ensure!(
+        !backup_key_path.exists(),
+        "{} is left from an interrupted rotation; restore or remove it first",
+        backup_key_path.display()
+    );
+    let new_key = generate_random_key();
+    let pattern = regex::Regex::new(r#"(["'])(enc2?:[0-9a-fA-F]+)(["'])"#)?;
+
+    let mut skipped = Vec::new();
+    let mut staged: Vec<StagedFile> = Vec::new();
+    for path in files {
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                skipped.push((path.clone(), "symbolic link not followed".into()));
+                continue;
             }
-            fs::write(&self.key_path, hex_encode(&key))
-                .context("Failed to write secret key file")?;
-
-            // Set restrictive permissions
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                fs::set_permissions(&self.key_path, fs::Permissions::from_mode(0o600))
-                    .context("Failed to set key file permissions")?;
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                skipped.push((path.clone(), e.to_string()));
+                continue;
             }
-            #[cfg(windows)]
-            {
-                // On Windows, use icacls to restrict permissions to current user only
-                let username = std::env::var("USERNAME").unwrap_or_default();
-                let Some(grant_arg) = build_windows_icacls_grant_arg(&username) else {
-                    tracing::warn!(
-                        "USERNAME environment variable is empty; \
-                         cannot restrict key file permissions via icacls"
-                    );
-                    return Ok(key);
-                };
-
-                match std::process::Command::new("icacls")
-                    .arg(&self.key_path)
-                    .args(["/inheritance:r", "/grant:r"])
-                    .arg(grant_arg)
-                    .output()
-                {
-                    Ok(o) if !o.status.success() => {
-                        tracing::warn!(
-                            "Failed to set key file permissions via icacls (exit code {:?})",
-                            o.status.code()
-                        );
-                    }
-                    Err(e) => {
-                        tracing::warn!("Could not set key file permissions: {e}");
-                    }
-                    _ => {
-                        tracing::debug!("Key file permissions restricted via icacls");
-                    }
+        }
+        let path = path.clone();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                skipped.push((path, e.to_string()));
+                continue;
+            }
+        };
+        let mut rotated = std::collections::HashMap::new();
+        for captures in pattern.captures_iter(&contents) {
+            let ciphertext = &captures[2];
+            let reencrypted = old
+                .decrypt(ciphertext)
+                .with_context(|| format!("Failed to decrypt a secret in {}", path.display()))
+                .and_then(|plaintext| encrypt_with_key(&new_key, &plaintext));
+            match reencrypted {
+                Ok(value) => {
+                    rotated.insert(ciphertext.to_string(), value);
+                }
+                Err(e) => {
+                    discard_staged(&staged);
+                    return Err(e);
                 }
             }
+        }
+        if rotated.is_empty() {
+            continue;
+        }
+        let count = rotated.len();
+        let updated = pattern.replace_all(&contents, |captures: &regex::Captures<'_>| {
+            format!("{}{}{}", &captures[1], rotated[&captures[2]], &captures[3])
+        });
+        let temp_path = path.with_extension(format!("rotate-{}", uuid::Uuid::new_v4()));
+        if let Err(e) = fs::write(&temp_path, updated.as_bytes()) {
+            let _ = fs::remove_file(&temp_path);
+            discard_staged(&staged);
+            return Err(e).with_context(|| format!("Failed to stage {}", path.display()));
+        }
+        staged.push(StagedFile {
+            path,
+            temp_path,
+            count,
+            original: contents,
+        });
+    }
 
-            Ok(key)
+    let new_key_path = old.key_path.with_extension("new");
+    let swapped = fs::copy(&old.key_path, &backup_key_path)
+        .context("Failed to back up secret key file")
+        .and_then(|_| write_key_file(&new_key_path, &new_key))
+        .and_then(|()| {
+            fs::rename(&new_key_path, &old.key_path).context("Failed to replace secret key file")
+        });
+    if let Err(e) = swapped {
+        let _ = fs::remove_file(&new_key_path);
+        let _ = fs::remove_file(&backup_key_path);
+        discard_staged(&staged);
+        return Err(e);
+    }
+
+    let mut rotated = Vec::with_capacity(staged.len());
+    for (index, file) in staged.iter().enumerate() {
+        if let Err(e) = fs::rename(&file.temp_path, &file.path) {
+            let error = anyhow::Error::new(e).context(format!(
+                "Failed to replace {}; rotation rolled back",
+                file.path.display()
+            ));
+            return Err(
+                match roll_back(
+                    &staged[..index],
+                    &staged[index..],
+                    &backup_key_path,
+                    &old.key_path,
+                ) {
+                    Ok(()) => error,
+                    Err(rollback) => error.context(format!(
+                        "Rollback failed ({rollback:#}); the old key is kept at {}",
+                        backup_key_path.display()
+                    )),
+                },
+            );
+        }
+        rotated.push((file.path.clone(), file.count));
+    }
+    let _ = fs::remove_file(&backup_key_path);
+    Ok(KeyRotation { rotated, skipped })
+}
+
+fn discard_staged(staged: &[StagedFile]) {
+    for file in staged {
+        let _ = fs::remove_file(&file.temp_path);
+    }
+}
+
+/// Undo a partly applied rotation: restore the files already replaced, drop
+/// the staged rewrites not yet applied and put the old key back.
+fn roll_back(
+    replaced: &[StagedFile],
+    pending: &[StagedFile],
+    backup_key_path: &Path,
+    key_path: &Path,
+) -> Result<()> {
+    discard_staged(pending);
+    for file in replaced {
+        fs::write(&file.path, &file.original)
+            .with_context(|| format!("Failed to restore {}", file.path.display()))?;
+    }
+    fs::rename(backup_key_path, key_path).context("Failed to restore the old secret key")
+}
+
+/// Handle `zeroclaw secrets` subcommands.
+pub fn handle_command(
+    command: crate::SecretsCommands,
+    config: &crate::config::Config,
+) -> Result<()> {
+    match command {
+        crate::SecretsCommands::RotateKey => {
+            let zeroclaw_dir = config
+                .config_path
+                .parent()
+                .context("Config path must have a parent directory")?;
+            let report = rotate_key(zeroclaw_dir, &secret_files(config))?;
+            for (path, count) in &report.rotated {
+                println!("  {} — {count} secret(s) re-encrypted", path.display());
+            }
+            if report.rotated.is_empty() {
+                println!("No encrypted values found.");
+            }
+            for (path, reason) in &report.skipped {
+                println!(
+                    "  ⚠️  {} not rotated ({reason}); secrets in it can no longer be decrypted",
+                    path.display()
+                );
+            }
+            println!(
+                "✅ Rotated {}. Older backups (e.g. config.toml.bak) can no longer be decrypted.",
+                zeroclaw_dir.join(".secret_key").display()
+            );
+            Ok(())
         }
     }
 }
@@ -724,6 +953,221 @@ mod tests {
 
     // ── Low-level helpers ───────────────────────────────────────
 
+    // ── Key rotation ───────────────────────────────────────────
+
+    fn rotation_config(dir: &Path) -> crate::config::Config {
+        crate::config::Config {
+            config_path: dir.join("config.toml"),
+            workspace_dir: dir.join("workspace"),
+            ..crate::config::Config::default()
+        }
+    }
+
+    #[test]
+    fn secret_files_cover_config_sources_and_profiles_outside_the_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = rotation_config(tmp.path());
+        config.sources.files = vec![
+            tmp.path().join("channels.toml"),
+            tmp.path().join("config.toml"),
+            tmp.path().join("workspace").join("models.toml"),
+        ];
+        assert_eq!(
+            secret_files(&config),
+            vec![
+                tmp.path().join("auth-profiles.json"),
+                tmp.path().join("channels.toml"),
+                tmp.path().join("config.toml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rotate_key_reencrypts_config_and_profiles() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true);
+        let api_key = store.encrypt("sk-config").unwrap();
+        let token = store.encrypt("oauth-token").unwrap();
+        let legacy = format!(
+            "enc:{}",
+            hex_encode(&xor_cipher(b"legacy", &store.load_or_create_key().unwrap()))
+        );
+        let old_key = fs::read_to_string(tmp.path().join(".secret_key")).unwrap();
+
+        let config =
+            format!("# keep me\napi_key = \"{api_key}\"\n\n[composio]\napi_key = '{legacy}'\n");
+        fs::write(tmp.path().join("config.toml"), &config).unwrap();
+        fs::write(
+            tmp.path().join("auth-profiles.json"),
+            format!("{{\"token\": \"{token}\", \"plain\": \"x\"}}"),
+        )
+        .unwrap();
+        fs::write(tmp.path().join("config.toml.bak"), &config).unwrap();
+
+        let files = secret_files(&rotation_config(tmp.path()));
+        let report = rotate_key(tmp.path(), &files).unwrap();
+        assert_eq!(
+            report.rotated,
+            vec![
+                (tmp.path().join("auth-profiles.json"), 1),
+                (tmp.path().join("config.toml"), 2),
+            ]
+        );
+        assert!(report.skipped.is_empty());
+        assert!(!tmp.path().join(".secret_key.old").exists());
+        assert_ne!(
+            fs::read_to_string(tmp.path().join(".secret_key")).unwrap(),
+            old_key
+        );
+
+        let rotated = fs::read_to_string(tmp.path().join("config.toml")).unwrap();
+        assert!(rotated.starts_with("# keep me\n"));
+        assert!(!rotated.contains(&api_key));
+        assert!(!rotated.contains("enc:"));
+        let parsed: toml::Table = toml::from_str(&rotated).unwrap();
+        let fresh = SecretStore::new(tmp.path(), true);
+        assert_eq!(
+            fresh.decrypt(parsed["api_key"].as_str().unwrap()).unwrap(),
+            "sk-config"
+        );
+        assert_eq!(
+            fresh
+                .decrypt(parsed["composio"]["api_key"].as_str().unwrap())
+                .unwrap(),
+            "legacy"
+        );
+        let profiles: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(tmp.path().join("auth-profiles.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            fresh.decrypt(profiles["token"].as_str().unwrap()).unwrap(),
+            "oauth-token"
+        );
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("config.toml.bak")).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn roll_back_restores_replaced_files_and_old_key() {
+        let tmp = TempDir::new().unwrap();
+        let key_path = tmp.path().join(".secret_key");
+        let backup_key_path = tmp.path().join(".secret_key.old");
+        fs::write(&key_path, "new").unwrap();
+        fs::write(&backup_key_path, "old").unwrap();
+        let staged = |name: &str, current: &str| {
+            let path = tmp.path().join(name);
+            let temp_path = tmp.path().join(format!("{name}.rotate"));
+            fs::write(&path, current).unwrap();
+            fs::write(&temp_path, "rewritten").unwrap();
+            StagedFile {
+                path,
+                temp_path,
+                count: 1,
+                original: format!("original {name}"),
+            }
+        };
+        let replaced = staged("a.toml", "rewritten");
+        let pending = staged("b.toml", "original b.toml");
+
+        roll_back(
+            std::slice::from_ref(&replaced),
+            std::slice::from_ref(&pending),
+            &backup_key_path,
+            &key_path,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&replaced.path).unwrap(),
+            "original a.toml"
+        );
+        assert_eq!(
+            fs::read_to_string(&pending.path).unwrap(),
+            "original b.toml"
+        );
+        assert!(!pending.temp_path.exists());
+        assert_eq!(fs::read_to_string(&key_path).unwrap(), "old");
+        assert!(!backup_key_path.exists());
+    }
+
+    #[test]
+    fn rotate_key_refuses_to_overwrite_an_old_key_backup() {
+        let tmp = TempDir::new().unwrap();
+        SecretStore::new(tmp.path(), true).encrypt("x").unwrap();
+        fs::write(tmp.path().join(".secret_key.old"), "kept").unwrap();
+        assert!(rotate_key(tmp.path(), &[]).is_err());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join(".secret_key.old")).unwrap(),
+            "kept"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rotate_key_reports_paths_it_does_not_follow() {
+        let tmp = TempDir::new().unwrap();
+        SecretStore::new(tmp.path(), true).encrypt("x").unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("channels.toml"), "").unwrap();
+        let linked = tmp.path().join("channels.toml");
+        std::os::unix::fs::symlink(outside.path().join("channels.toml"), &linked).unwrap();
+
+        let report = rotate_key(tmp.path(), std::slice::from_ref(&linked)).unwrap();
+        assert!(report.rotated.is_empty());
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].0, linked);
+    }
+
+    #[test]
+    fn rotate_key_leaves_workspace_files_alone() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true);
+        let api_key = store.encrypt("sk-config").unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            format!("api_key = \"{api_key}\"\n"),
+        )
+        .unwrap();
+        let notes = tmp.path().join("workspace").join("notes.toml");
+        fs::create_dir_all(notes.parent().unwrap()).unwrap();
+        let literal = "example = \"enc:deadbeef\"\n";
+        fs::write(&notes, literal).unwrap();
+
+        let files = secret_files(&rotation_config(tmp.path()));
+        let report = rotate_key(tmp.path(), &files).unwrap();
+        assert_eq!(report.rotated, vec![(tmp.path().join("config.toml"), 1)]);
+        assert_eq!(fs::read_to_string(&notes).unwrap(), literal);
+    }
+
+    #[test]
+    fn rotate_key_aborts_without_changes_on_undecryptable_value() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true);
+        let good = store.encrypt("sk-good").unwrap();
+        let foreign = SecretStore::new(TempDir::new().unwrap().path(), true)
+            .encrypt("sk-foreign")
+            .unwrap();
+        let config = format!("a = \"{good}\"\nb = \"{foreign}\"\n");
+        fs::write(tmp.path().join("config.toml"), &config).unwrap();
+        let old_key = fs::read_to_string(tmp.path().join(".secret_key")).unwrap();
+
+        let files = secret_files(&rotation_config(tmp.path()));
+        assert!(rotate_key(tmp.path(), &files).is_err());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("config.toml")).unwrap(),
+            config
+        );
+        assert_eq!(
+            fs::read_to_string(tmp.path().join(".secret_key")).unwrap(),
+            old_key
+        );
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 2);
+
+        assert!(rotate_key(TempDir::new().unwrap().path(), &files).is_err());
+    }
+
     #[test]
     fn xor_cipher_roundtrip() {
         let key = b"testkey123";
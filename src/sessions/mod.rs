@@ -0,0 +1,500 @@
+//! Persistent conversation sessions (`zeroclaw sessions`).
+//!
+//! CLI and channel conversations are appended to
+//! `<workspace>/sessions/<id>.jsonl`, one message per line, with credentials
+//! scrubbed before anything touches disk. Sessions can be exported as
+//! Markdown, OpenAI-format JSONL or HTML, and OpenAI/Anthropic transcripts can
+//! be imported to seed a new session that `zeroclaw agent --session` resumes.
+
+pub mod transcript;
+
+use crate::agent::loop_::{
+    build_assistant_history_with_tool_calls, build_native_assistant_history, scrub_credentials,
+};
+use crate::config::Config;
+use crate::providers::{ChatMessage, ToolCall};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+tokio::task_local! {
+    static CURRENT_SESSION: String;
+}
+
+/// Run `f` as part of conversation `session_id`. Tools shared by several
+/// conversations (one registry per channel daemon or gateway) key their
+/// per-conversation state on [`current_session_id`].
+pub async fn scope<F: Future>(session_id: String, f: F) -> F::Output {
+    CURRENT_SESSION.scope(session_id, f).await
+}
+
+/// The conversation the current task belongs to, if any.
+pub fn current_session_id() -> Option<String> {
+    CURRENT_SESSION.try_with(Clone::clone).ok()
+}
+
+/// One recorded message. Tool calls and tool results are kept structured so
+/// exports can reproduce them faithfully.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionMessage {
+    /// `system`, `user`, `assistant` or `tool`.
+    pub role: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// Set on `tool` messages: the call this result answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// RFC 3339 time the message was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+}
+
+impl SessionMessage {
+    pub fn new(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+            timestamp: None,
+        }
+    }
+
+    /// Decode an agent-loop history entry. Native tool-calling history stores
+    /// assistant tool calls and tool results as JSON payloads; those are
+    /// unpacked here. Prompt-mode (`<tool_call>` XML) entries stay as text.
+    pub fn from_history(message: &ChatMessage) -> Self {
+        let mut decoded = Self::new(&message.role, message.content.clone());
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&message.content) else {
+            return decoded;
+        };
+        match message.role.as_str() {
+            "assistant" => {
+                if let Some(calls) = value.get("tool_calls").and_then(|v| v.as_array()) {
+                    decoded.content = value
+                        .get("content")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    decoded.tool_calls = calls
+                        .iter()
+                        .filter_map(|call| serde_json::from_value(call.clone()).ok())
+                        .collect();
+                }
+            }
+            "tool" => {
+                if let Some(id) = value.get("tool_call_id").and_then(|v| v.as_str()) {
+                    decoded.tool_call_id = Some(id.to_string());
+                    decoded.content = match value.get("content") {
+                        Some(serde_json::Value::String(text)) => text.clone(),
+                        Some(other) => other.to_string(),
+                        None => String::new(),
+                    };
+                }
+            }
+            _ => {}
+        }
+        decoded
+    }
+
+    fn scrubbed(mut self) -> Self {
+        self.content = scrub_credentials(&self.content);
+        for call in &mut self.tool_calls {
+            call.arguments = scrub_credentials(&call.arguments);
+        }
+        self
+    }
+}
+
+/// Rebuild agent-loop history from recorded messages, using the encoding the
+/// active provider expects. System messages are dropped; the caller supplies
+/// its own system prompt.
+pub fn to_history(messages: &[SessionMessage], native_tools: bool) -> Vec<ChatMessage> {
+    let mut history = Vec::new();
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    let mut pending_results = String::new();
+
+    for message in messages {
+        if !native_tools && message.role != "tool" && !pending_results.is_empty() {
+            history.push(ChatMessage::user(format!(
+                "[Tool results]\n{}",
+                std::mem::take(&mut pending_results)
+            )));
+        }
+        match message.role.as_str() {
+            "user" => history.push(ChatMessage::user(&message.content)),
+            "assistant" if message.tool_calls.is_empty() => {
+                history.push(ChatMessage::assistant(&message.content));
+            }
+            "assistant" => {
+                for call in &message.tool_calls {
+                    tool_names.insert(&call.id, &call.name);
+                }
+                history.push(ChatMessage::assistant(if native_tools {
+                    build_native_assistant_history(&message.content, &message.tool_calls, None)
+                } else {
+                    build_assistant_history_with_tool_calls(&message.content, &message.tool_calls)
+                }));
+            }
+            "tool" => {
+                let id = message.tool_call_id.as_deref().unwrap_or_default();
+                if native_tools {
+                    let payload = serde_json::json!({
+                        "tool_call_id": id,
+                        "content": message.content,
+                    });
+                    history.push(ChatMessage::tool(payload.to_string()));
+                } else {
+                    let name = tool_names.get(id).copied().unwrap_or("tool");
+                    let _ = writeln!(
+                        pending_results,
+                        "<tool_result name=\"{name}\">\n{}\n</tool_result>",
+                        message.content
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    if !pending_results.is_empty() {
+        history.push(ChatMessage::user(format!(
+            "[Tool results]\n{pending_results}"
+        )));
+    }
+    history
+}
+
+/// Listing entry for `zeroclaw sessions list`.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub id: String,
+    pub messages: usize,
+    pub updated: Option<chrono::DateTime<chrono::Local>>,
+}
+
+/// JSONL session files under `<workspace>/sessions/`.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            dir: workspace_dir.join("sessions"),
+        }
+    }
+
+    pub fn path(&self, id: &str) -> Result<PathBuf> {
+        validate_id(id)?;
+        Ok(self.dir.join(format!("{id}.jsonl")))
+    }
+
+    pub fn exists(&self, id: &str) -> bool {
+        self.path(id).is_ok_and(|path| path.is_file())
+    }
+
+    /// Append messages, scrubbing credentials and stamping the current time.
+    pub fn append(&self, id: &str, messages: &[SessionMessage]) -> Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let path = self.path(id)?;
+        std::fs::create_dir_all(&self.dir)?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut lines = String::new();
+        for message in messages {
+            let mut message = message.clone().scrubbed();
+            message.timestamp.get_or_insert_with(|| now.clone());
+            lines.push_str(&serde_json::to_string(&message)?);
+            lines.push('\n');
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open session {}", path.display()))?;
+        file.write_all(lines.as_bytes())
+            .with_context(|| format!("Failed to write session {}", path.display()))
+    }
+
+    /// Create a new session from messages; fails if the id is taken.
+    pub fn create(&self, id: &str, messages: &[SessionMessage]) -> Result<()> {
+        if self.exists(id) {
+            bail!("Session '{id}' already exists");
+        }
+        self.append(id, messages)
+    }
+
+    pub fn load(&self, id: &str) -> Result<Vec<SessionMessage>> {
+        let path = self.path(id)?;
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Session '{id}' not found at {}", path.display()))?;
+        raw.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!("Invalid session line {} in {}", index + 1, path.display())
+                })
+            })
+            .collect()
+    }
+
+    /// All sessions, most recently updated first.
+    pub fn list(&self) -> Result<Vec<SessionSummary>> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut sessions = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let messages = std::fs::read_to_string(&path)
+                .map(|raw| raw.lines().filter(|l| !l.trim().is_empty()).count())
+                .unwrap_or(0);
+            let updated = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(chrono::DateTime::<chrono::Local>::from);
+            sessions.push(SessionSummary {
+                id: id.to_string(),
+                messages,
+                updated,
+            });
+        }
+        sessions.sort_by(|a, b| b.updated.cmp(&a.updated).then_with(|| a.id.cmp(&b.id)));
+        Ok(sessions)
+    }
+}
+
+fn validate_id(id: &str) -> Result<()> {
+    if id.is_empty()
+        || id.starts_with('.')
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!("Invalid session id '{id}': use letters, digits, '-', '_' or '.'");
+    }
+    Ok(())
+}
+
+/// Fresh id for a new session, e.g. `cli-20260101-120000-1a2b3c4d`. The
+/// random suffix keeps sessions started in the same second apart.
+pub fn new_session_id(prefix: &str) -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{prefix}-{}-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        &suffix[..8]
+    )
+}
+
+/// Session id for a channel conversation key (`<channel>_<sender>`).
+///
+/// Keys that are already valid ids are used as-is. Otherwise unsupported
+/// characters become `_` and a hash of the original key is appended after a
+/// `.`, which never occurs in an unchanged key, so distinct keys never share
+/// a session.
+pub fn channel_session_id(history_key: &str) -> String {
+    let mut sanitized: String = history_key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized == history_key && !sanitized.is_empty() {
+        return sanitized;
+    }
+    if sanitized.is_empty() {
+        sanitized.push('_');
+    }
+    let digest = hex::encode(Sha256::digest(history_key.as_bytes()));
+    format!("{sanitized}.{}", &digest[..16])
+}
+
+/// Handle `zeroclaw sessions <subcommand>` CLI commands.
+pub fn handle_command(command: crate::SessionsCommands, config: &Config) -> Result<()> {
+    let store = SessionStore::new(&config.workspace_dir);
+    match command {
+        crate::SessionsCommands::List => {
+            let sessions = store.list()?;
+            if sessions.is_empty() {
+                println!("No sessions recorded yet.");
+                return Ok(());
+            }
+            println!("Sessions ({}):", sessions.len());
+            for session in sessions {
+                let updated = session
+                    .updated
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                println!(
+                    "  {:<40} {:>5} messages  {updated}",
+                    session.id, session.messages
+                );
+            }
+            Ok(())
+        }
+        crate::SessionsCommands::Export { id, format, output } => {
+            let messages = store.load(&id)?;
+            let rendered = match format.as_str() {
+                "jsonl" => transcript::to_openai_jsonl(&messages)?,
+                "html" => transcript::to_html(&id, &messages),
+                _ => transcript::to_markdown(&id, &messages),
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!("Exported session '{id}' to {}", path.display());
+                }
+                None => print!("{rendered}"),
+            }
+            Ok(())
+        }
+        crate::SessionsCommands::Import { file, id } => {
+            let raw = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let messages = transcript::parse_transcript(&raw)?;
+            if messages.is_empty() {
+                bail!("No messages found in {}", file.display());
+            }
+            let id = id.unwrap_or_else(|| new_session_id("import"));
+            store.create(&id, &messages)?;
+            println!(
+                "Imported {} messages into session '{id}'. Resume with: zeroclaw agent --session {id}",
+                messages.len()
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(id: &str, name: &str) -> ToolCall {
+        ToolCall {
+            id: id.into(),
+            name: name.into(),
+            arguments: r#"{"command":"ls"}"#.into(),
+        }
+    }
+
+    #[test]
+    fn store_appends_scrubs_and_lists() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = SessionStore::new(tmp.path());
+
+        store
+            .append(
+                "cli-1",
+                &[
+                    SessionMessage::new("user", "my api_key=sk-abcdefghijklmnop"),
+                    SessionMessage::new("assistant", "noted"),
+                ],
+            )
+            .unwrap();
+        store
+            .append("cli-1", &[SessionMessage::new("user", "again")])
+            .unwrap();
+
+        let loaded = store.load("cli-1").unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert!(loaded[0].content.contains("[REDACTED]"));
+        assert!(!loaded[0].content.contains("abcdefghijklmnop"));
+        assert!(loaded.iter().all(|m| m.timestamp.is_some()));
+
+        let listed = store.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].messages, 3);
+
+        assert!(store.create("cli-1", &loaded).is_err());
+        assert!(store.path("../escape").is_err());
+    }
+
+    #[test]
+    fn session_ids_do_not_collide() {
+        assert_ne!(new_session_id("cli"), new_session_id("cli"));
+
+        assert_eq!(channel_session_id("slack_U1"), "slack_U1");
+        let slash = channel_session_id("slack_U1/x");
+        let colon = channel_session_id("slack_U1:x");
+        assert!(slash.starts_with("slack_U1_x."));
+        assert_ne!(slash, colon);
+        assert_ne!(slash, channel_session_id("slack_U1_x"));
+        assert!(validate_id(&slash).is_ok());
+        assert!(validate_id(&channel_session_id("")).is_ok());
+    }
+
+    #[test]
+    fn native_history_round_trips() {
+        let history = [
+            ChatMessage::user("list files"),
+            ChatMessage::assistant(build_native_assistant_history(
+                "checking",
+                &[call("c1", "shell")],
+                None,
+            )),
+            ChatMessage::tool(r#"{"tool_call_id":"c1","content":"a.txt"}"#),
+            ChatMessage::assistant("a.txt"),
+        ];
+        let messages: Vec<_> = history.iter().map(SessionMessage::from_history).collect();
+        assert_eq!(messages[1].content, "checking");
+        assert_eq!(messages[1].tool_calls[0].name, "shell");
+        assert_eq!(messages[2].tool_call_id.as_deref(), Some("c1"));
+        assert_eq!(messages[2].content, "a.txt");
+
+        let rebuilt = to_history(&messages, true);
+        assert_eq!(rebuilt.len(), 4);
+        assert_eq!(rebuilt[1].content, history[1].content);
+        assert_eq!(SessionMessage::from_history(&rebuilt[2]), messages[2]);
+    }
+
+    #[test]
+    fn prompt_mode_history_groups_tool_results() {
+        let mut assistant = SessionMessage::new("assistant", "");
+        assistant.tool_calls = vec![call("c1", "shell"), call("c2", "file_read")];
+        let mut first = SessionMessage::new("tool", "out1");
+        first.tool_call_id = Some("c1".into());
+        let mut second = SessionMessage::new("tool", "out2");
+        second.tool_call_id = Some("c2".into());
+        let messages = vec![
+            SessionMessage::new("system", "ignored"),
+            SessionMessage::new("user", "go"),
+            assistant,
+            first,
+            second,
+            SessionMessage::new("assistant", "done"),
+        ];
+
+        let history = to_history(&messages, false);
+        assert_eq!(history.len(), 4);
+        assert!(history[1].content.contains("<tool_call>"));
+        assert_eq!(history[2].role, "user");
+        assert!(history[2].content.starts_with("[Tool results]"));
+        assert!(history[2]
+            .content
+            .contains("<tool_result name=\"file_read\">\nout2"));
+        assert_eq!(history[3].content, "done");
+    }
+}
@@ -0,0 +1,349 @@
+use super::SessionMessage;
+use crate::providers::ToolCall;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::fmt::Write;
+
+/// Render a session as a Markdown transcript.
+pub fn to_markdown(id: &str, messages: &[SessionMessage]) -> String {
+    let mut out = format!("# Session `{id}`\n");
+    for message in messages {
+        match message.role.as_str() {
+            "tool" => {
+                let id = message.tool_call_id.as_deref().unwrap_or("?");
+                let _ = write!(out, "\n### Tool result (`{id}`)\n\n");
+                out.push_str(&fenced("", &message.content));
+            }
+            role => {
+                let _ = write!(out, "\n### {}\n", title_case(role));
+                if !message.content.is_empty() {
+                    let _ = write!(out, "\n{}\n", message.content.trim_end());
+                }
+                for call in &message.tool_calls {
+                    let _ = write!(out, "\n**Tool call** `{}` (`{}`)\n\n", call.name, call.id);
+                    out.push_str(&fenced("json", &pretty_arguments(&call.arguments)));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Render a session as JSONL, one OpenAI chat-completions message per line.
+pub fn to_openai_jsonl(messages: &[SessionMessage]) -> Result<String> {
+    let mut out = String::new();
+    for message in messages {
+        let value = match message.role.as_str() {
+            "tool" => json!({
+                "role": "tool",
+                "tool_call_id": message.tool_call_id.as_deref().unwrap_or_default(),
+                "content": message.content,
+            }),
+            "assistant" if !message.tool_calls.is_empty() => {
+                let calls: Vec<Value> = message
+                    .tool_calls
+                    .iter()
+                    .map(|call| {
+                        json!({
+                            "id": call.id,
+                            "type": "function",
+                            "function": { "name": call.name, "arguments": call.arguments },
+                        })
+                    })
+                    .collect();
+                json!({
+                    "role": "assistant",
+                    "content": (!message.content.is_empty()).then_some(&message.content),
+                    "tool_calls": calls,
+                })
+            }
+            role => json!({ "role": role, "content": message.content }),
+        };
+        out.push_str(&serde_json::to_string(&value)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Render a session as a standalone HTML page.
+pub fn to_html(id: &str, messages: &[SessionMessage]) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Session {title}</title>\n<style>\n\
+         body {{ font-family: system-ui, sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; }}\n\
+         .msg {{ border-radius: 8px; padding: 0.75rem 1rem; margin: 0.75rem 0; }}\n\
+         .role {{ font-weight: 600; font-size: 0.85rem; text-transform: uppercase; color: #555; }}\n\
+         .user {{ background: #eef4ff; }} .assistant {{ background: #f4f4f4; }}\n\
+         .system {{ background: #fff8e6; }} .tool {{ background: #eefaf0; }}\n\
+         pre {{ white-space: pre-wrap; word-break: break-word; margin: 0.5rem 0 0; }}\n\
+         </style>\n</head>\n<body>\n<h1>Session {title}</h1>\n",
+        title = escape_html(id)
+    );
+    for message in messages {
+        let role = escape_html(&message.role);
+        let _ = write!(out, "<div class=\"msg {role}\">\n<div class=\"role\">");
+        match message.tool_call_id.as_deref() {
+            Some(call_id) if message.role == "tool" => {
+                let _ = write!(out, "tool result <code>{}</code>", escape_html(call_id));
+            }
+            _ => out.push_str(&role),
+        }
+        if let Some(timestamp) = &message.timestamp {
+            let _ = write!(out, " <small>{}</small>", escape_html(timestamp));
+        }
+        out.push_str("</div>\n");
+        if !message.content.is_empty() {
+            let _ = writeln!(out, "<pre>{}</pre>", escape_html(&message.content));
+        }
+        for call in &message.tool_calls {
+            let _ = writeln!(
+                out,
+                "<div>tool call <code>{}</code> <small>{}</small></div>\n<pre>{}</pre>",
+                escape_html(&call.name),
+                escape_html(&call.id),
+                escape_html(&pretty_arguments(&call.arguments))
+            );
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Parse an OpenAI or Anthropic transcript. Accepts JSONL with one message
+/// per line, JSONL with one `{"messages": [...]}` conversation per line, or a
+/// single JSON array/object of the same shapes.
+pub fn parse_transcript(raw: &str) -> Result<Vec<SessionMessage>> {
+    if let Ok(value) = serde_json::from_str::<Value>(raw.trim()) {
+        return parse_value(&value);
+    }
+    let mut messages = Vec::new();
+    for (index, line) in raw.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(line)
+            .with_context(|| format!("Line {}: invalid JSON", index + 1))?;
+        messages.extend(parse_value(&value).with_context(|| format!("Line {}", index + 1))?);
+    }
+    Ok(messages)
+}
+
+fn parse_value(value: &Value) -> Result<Vec<SessionMessage>> {
+    if let Some(items) = value.as_array() {
+        let mut messages = Vec::new();
+        for item in items {
+            messages.extend(parse_value(item)?);
+        }
+        return Ok(messages);
+    }
+    if let Some(conversation) = value.get("messages").and_then(Value::as_array) {
+        // Anthropic requests carry the system prompt beside the messages.
+        let mut messages = Vec::new();
+        let system = block_text(value.get("system"));
+        if !system.is_empty() {
+            messages.push(SessionMessage::new("system", system));
+        }
+        for item in conversation {
+            messages.extend(parse_message(item)?);
+        }
+        return Ok(messages);
+    }
+    parse_message(value)
+}
+
+fn parse_message(value: &Value) -> Result<Vec<SessionMessage>> {
+    let role = match value.get("role").and_then(Value::as_str) {
+        Some("developer" | "system") => "system",
+        Some(role @ ("user" | "assistant" | "tool")) => role,
+        Some(other) => bail!("Unsupported message role '{other}'"),
+        None => bail!("Message has no role"),
+    };
+
+    let mut message = SessionMessage::new(role, String::new());
+    let mut results = Vec::new();
+    match value.get("content") {
+        Some(Value::Array(blocks)) => {
+            let mut text = Vec::new();
+            for block in blocks {
+                match block.get("type").and_then(Value::as_str) {
+                    Some("text" | "input_text" | "output_text") => {
+                        text.push(block_text(Some(block)));
+                    }
+                    Some("tool_use") => message.tool_calls.push(ToolCall {
+                        id: string_field(block, "id"),
+                        name: string_field(block, "name"),
+                        arguments: block
+                            .get("input")
+                            .map_or_else(|| "{}".to_string(), Value::to_string),
+                    }),
+                    Some("tool_result") => {
+                        let mut result =
+                            SessionMessage::new("tool", block_text(block.get("content")));
+                        result.tool_call_id = Some(string_field(block, "tool_use_id"));
+                        results.push(result);
+                    }
+                    Some(other) => text.push(format!("[{other} omitted]")),
+                    None => {}
+                }
+            }
+            message.content = text.join("\n");
+        }
+        other => message.content = block_text(other),
+    }
+
+    if let Some(calls) = value.get("tool_calls").and_then(Value::as_array) {
+        for call in calls {
+            let function = call.get("function").unwrap_or(call);
+            message.tool_calls.push(ToolCall {
+                id: string_field(call, "id"),
+                name: string_field(function, "name"),
+                arguments: match function.get("arguments") {
+                    Some(Value::String(arguments)) => arguments.clone(),
+                    Some(other) => other.to_string(),
+                    None => "{}".to_string(),
+                },
+            });
+        }
+    }
+    if role == "tool" {
+        message.tool_call_id = Some(string_field(value, "tool_call_id"));
+    }
+
+    // An Anthropic user turn that only carries tool results becomes plain
+    // tool messages.
+    let mut messages = Vec::new();
+    if results.is_empty() || !message.content.is_empty() || !message.tool_calls.is_empty() {
+        messages.push(message);
+    }
+    messages.extend(results);
+    Ok(messages)
+}
+
+/// Text of a string, a text block, or a list of text blocks.
+fn block_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .map(|block| block_text(Some(block)))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(Value::Object(block)) => block
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn pretty_arguments(arguments: &str) -> String {
+    serde_json::from_str::<Value>(arguments)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| arguments.to_string())
+}
+
+fn fenced(lang: &str, body: &str) -> String {
+    let mut fence = "```".to_string();
+    while body.contains(&fence) {
+        fence.push('`');
+    }
+    format!("{fence}{lang}\n{}\n{fence}\n", body.trim_end())
+}
+
+fn title_case(role: &str) -> String {
+    let mut chars = role.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().collect::<String>() + chars.as_str()
+    })
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<SessionMessage> {
+        let mut assistant = SessionMessage::new("assistant", "");
+        assistant.tool_calls = vec![ToolCall {
+            id: "c1".into(),
+            name: "shell".into(),
+            arguments: r#"{"command":"ls"}"#.into(),
+        }];
+        let mut result = SessionMessage::new("tool", "a.txt <b>");
+        result.tool_call_id = Some("c1".into());
+        vec![
+            SessionMessage::new("user", "list files"),
+            assistant,
+            result,
+            SessionMessage::new("assistant", "Found a.txt"),
+        ]
+    }
+
+    #[test]
+    fn openai_jsonl_export_round_trips() {
+        let exported = to_openai_jsonl(&sample()).unwrap();
+        let lines: Vec<Value> = exported
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1]["content"].is_null());
+        assert_eq!(lines[1]["tool_calls"][0]["function"]["name"], "shell");
+        assert_eq!(lines[2]["role"], "tool");
+        assert_eq!(lines[2]["tool_call_id"], "c1");
+
+        assert_eq!(parse_transcript(&exported).unwrap(), sample());
+    }
+
+    #[test]
+    fn markdown_and_html_render_tool_calls() {
+        let markdown = to_markdown("s1", &sample());
+        assert!(markdown.contains("### User\n\nlist files"));
+        assert!(markdown.contains("**Tool call** `shell` (`c1`)"));
+        assert!(markdown.contains("### Tool result (`c1`)"));
+
+        let html = to_html("s1", &sample());
+        assert!(html.contains("a.txt &lt;b&gt;"));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn parses_anthropic_conversation() {
+        let raw = r#"{"system":"Be brief","messages":[
+            {"role":"user","content":"list files"},
+            {"role":"assistant","content":[{"type":"text","text":"checking"},{"type":"tool_use","id":"tu1","name":"shell","input":{"command":"ls"}}]},
+            {"role":"user","content":[{"type":"tool_result","tool_use_id":"tu1","content":[{"type":"text","text":"a.txt"}]}]},
+            {"role":"assistant","content":"Found a.txt"}
+        ]}"#;
+        let messages = parse_transcript(raw).unwrap();
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[2].content, "checking");
+        assert_eq!(messages[2].tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(messages[3].role, "tool");
+        assert_eq!(messages[3].tool_call_id.as_deref(), Some("tu1"));
+        assert_eq!(messages[3].content, "a.txt");
+
+        assert!(parse_transcript(r#"{"role":"narrator","content":"x"}"#).is_err());
+    }
+}
@@ -0,0 +1,661 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use glob::{MatchOptions, Pattern};
+use regex::{Regex, RegexBuilder};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DEFAULT_MAX_RESULTS: usize = 100;
+const MAX_RESULTS: usize = 1000;
+const MAX_CONTEXT_LINES: usize = 10;
+const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+/// Matched lines longer than this are cut in the output.
+const MAX_LINE_CHARS: usize = 300;
+/// Bytes inspected for NUL when deciding whether a file is binary.
+const BINARY_PROBE_BYTES: usize = 8192;
+
+/// Search file contents by regex or literal text within the workspace.
+///
+/// Walks the workspace natively (no shell), skips `.git`, binary files and
+/// anything matched by `.gitignore`, and never follows symlinks, so results
+/// cannot escape the workspace.
+pub struct ContentSearchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ContentSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for ContentSearchTool {
+    fn name(&self) -> &str {
+        "content_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents in the workspace by regex (default) or literal text. \
+         Respects .gitignore and skips binary files. Results are 'path:line:text', with \
+         context lines as 'path-line-text'. Filter files with include/exclude globs, \
+         e.g. include=['*.rs'], exclude=['tests/**']."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regex (or literal text when literal=true) to search for"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory or file to search, relative to the workspace (default: workspace root)"
+                },
+                "literal": {
+                    "type": "boolean",
+                    "description": "Treat pattern as literal text instead of a regex",
+                    "default": false
+                },
+                "case_sensitive": {
+                    "type": "boolean",
+                    "description": "Match case exactly",
+                    "default": true
+                },
+                "include": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only search files matching these globs; globs without '/' match the file name"
+                },
+                "exclude": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Skip files and directories matching these globs"
+                },
+                "context": {
+                    "type": "integer",
+                    "description": "Lines of context before and after each match (max 10)",
+                    "default": 0
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matching lines to return (max 1000)",
+                    "default": DEFAULT_MAX_RESULTS
+                },
+                "respect_gitignore": {
+                    "type": "boolean",
+                    "description": "Skip files ignored by .gitignore",
+                    "default": true
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter"))?;
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let flag = |key: &str, default: bool| {
+            args.get(key)
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(default)
+        };
+        let number = |key: &str, default: usize| {
+            args.get(key)
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|n| usize::try_from(n).ok())
+                .unwrap_or(default)
+        };
+        let globs = |key: &str| -> Vec<String> {
+            match args.get(key) {
+                Some(serde_json::Value::String(s)) => vec![s.clone()],
+                Some(serde_json::Value::Array(items)) => items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        };
+
+        // Rate limit check (fast path)
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult::err(format!(
+                "Path not allowed by security policy: {path}"
+            )));
+        }
+
+        let source = if flag("literal", false) {
+            regex::escape(pattern)
+        } else {
+            pattern.to_string()
+        };
+        let regex = match RegexBuilder::new(&source)
+            .case_insensitive(!flag("case_sensitive", true))
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => return Ok(ToolResult::err(format!("Invalid regex: {e}"))),
+        };
+        let include = match compile_globs(&globs("include")) {
+            Ok(globs) => globs,
+            Err(e) => return Ok(ToolResult::err(e)),
+        };
+        let exclude = match compile_globs(&globs("exclude")) {
+            Ok(globs) => globs,
+            Err(e) => return Ok(ToolResult::err(e)),
+        };
+
+        let workspace_canon = match std::fs::canonicalize(&self.security.workspace_dir) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Cannot resolve workspace directory: {e}"
+                )))
+            }
+        };
+        let root = match std::fs::canonicalize(workspace_canon.join(path)) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Failed to resolve search path: {e}"
+                )))
+            }
+        };
+        if !self.security.is_resolved_path_allowed(&root) {
+            return Ok(ToolResult::err(format!(
+                "Resolved path escapes workspace: {}",
+                root.display()
+            )));
+        }
+
+        // Record action to consume rate limit budget
+        if !self.security.record_action() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        let search = Search {
+            regex,
+            include,
+            exclude,
+            context: number("context", 0).min(MAX_CONTEXT_LINES),
+            max_results: number("max_results", DEFAULT_MAX_RESULTS).clamp(1, MAX_RESULTS),
+            respect_gitignore: flag("respect_gitignore", true),
+            workspace: workspace_canon,
+        };
+        let report = tokio::task::spawn_blocking(move || search.run(&root)).await?;
+
+        let output = if report.matches == 0 {
+            format!("No matches for '{pattern}' in {path}.")
+        } else {
+            let mut buf = report.output;
+            if report.truncated {
+                let _ = write!(
+                    buf,
+                    "\n[Results truncated at {} matches; narrow the pattern or path]",
+                    report.matches
+                );
+            }
+            let _ = write!(
+                buf,
+                "\nTotal: {} matches in {} files",
+                report.matches, report.files
+            );
+            buf
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+fn compile_globs(raw: &[String]) -> Result<Vec<Pattern>, String> {
+    raw.iter()
+        .map(|glob| {
+            Pattern::new(glob.trim_start_matches("./"))
+                .map_err(|e| format!("Invalid glob '{glob}': {e}"))
+        })
+        .collect()
+}
+
+/// Globs without a `/` match the file name; others match the whole
+/// workspace-relative path.
+fn glob_matches(pattern: &Pattern, rel: &str) -> bool {
+    if pattern.as_str().contains('/') {
+        pattern.matches(rel)
+    } else {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        pattern.matches(name)
+    }
+}
+
+/// One `.gitignore` line, scoped to the directory that holds the file.
+struct IgnoreRule {
+    /// Workspace-relative directory of the `.gitignore` (empty for the root).
+    base: String,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Whether the pattern is matched against the path below `base` rather
+    /// than only the entry name.
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    fn parse(base: &str, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let pattern = Pattern::new(line.trim_start_matches('/')).ok()?;
+        Some(Self {
+            base: base.to_string(),
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, rel: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let below = if self.base.is_empty() {
+            rel
+        } else {
+            match rel
+                .strip_prefix(self.base.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(below) => below,
+                None => return false,
+            }
+        };
+        if self.anchored {
+            let options = MatchOptions {
+                require_literal_separator: true,
+                ..MatchOptions::default()
+            };
+            self.pattern.matches_with(below, options)
+        } else {
+            let name = below.rsplit('/').next().unwrap_or(below);
+            self.pattern.matches(name)
+        }
+    }
+}
+
+/// Last matching rule wins, as in git.
+fn is_ignored(rules: &[IgnoreRule], rel: &str, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(rel, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+struct Search {
+    regex: Regex,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    context: usize,
+    max_results: usize,
+    respect_gitignore: bool,
+    workspace: PathBuf,
+}
+
+#[derive(Default)]
+struct Report {
+    output: String,
+    matches: usize,
+    files: usize,
+    truncated: bool,
+}
+
+impl Search {
+    fn run(&self, root: &Path) -> Report {
+        let mut report = Report::default();
+        let mut rules = Vec::new();
+        if self.respect_gitignore {
+            // Pick up .gitignore files between the workspace root and the search root.
+            let mut dir = self.workspace.clone();
+            self.load_gitignore(&dir, &mut rules);
+            if let Ok(rel) = root.strip_prefix(&self.workspace) {
+                for component in rel.parent().into_iter().flat_map(Path::components) {
+                    dir.push(component);
+                    self.load_gitignore(&dir, &mut rules);
+                }
+            }
+        }
+        if root.is_dir() {
+            self.walk(root, &mut rules, &mut report);
+        } else {
+            self.search_file(root, &mut report);
+        }
+        report
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    fn load_gitignore(&self, dir: &Path, rules: &mut Vec<IgnoreRule>) {
+        if let Ok(raw) = std::fs::read_to_string(dir.join(".gitignore")) {
+            let base = self.relative(dir);
+            rules.extend(
+                raw.lines()
+                    .filter_map(|line| IgnoreRule::parse(&base, line)),
+            );
+        }
+    }
+
+    fn walk(&self, dir: &Path, rules: &mut Vec<IgnoreRule>, report: &mut Report) {
+        let inherited = rules.len();
+        if self.respect_gitignore && dir != self.workspace {
+            self.load_gitignore(dir, rules);
+        }
+
+        let mut entries: Vec<_> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(Result::ok).collect(),
+            Err(_) => return,
+        };
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+
+        for entry in entries {
+            if report.truncated {
+                break;
+            }
+            // Never follow symlinks: they are the only way out of the workspace.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            let rel = self.relative(&path);
+            if file_type.is_dir() {
+                if entry.file_name() == ".git"
+                    || self.exclude.iter().any(|glob| glob_matches(glob, &rel))
+                    || (self.respect_gitignore && is_ignored(rules, &rel, true))
+                {
+                    continue;
+                }
+                self.walk(&path, rules, report);
+            } else if file_type.is_file() {
+                if self.exclude.iter().any(|glob| glob_matches(glob, &rel))
+                    || (!self.include.is_empty()
+                        && !self.include.iter().any(|glob| glob_matches(glob, &rel)))
+                    || (self.respect_gitignore && is_ignored(rules, &rel, false))
+                {
+                    continue;
+                }
+                self.search_file(&path, report);
+            }
+        }
+        rules.truncate(inherited);
+    }
+
+    fn search_file(&self, path: &Path, report: &mut Report) {
+        if std::fs::metadata(path).map_or(true, |meta| meta.len() > MAX_FILE_SIZE_BYTES) {
+            return;
+        }
+        let Ok(bytes) = std::fs::read(path) else {
+            return;
+        };
+        if bytes[..bytes.len().min(BINARY_PROBE_BYTES)].contains(&0) {
+            return;
+        }
+        let content = String::from_utf8_lossy(&bytes);
+        let lines: Vec<&str> = content.lines().collect();
+        let hits: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| self.regex.is_match(line))
+            .map(|(index, _)| index)
+            .collect();
+        if hits.is_empty() {
+            return;
+        }
+
+        let rel = self.relative(path);
+        report.files += 1;
+        // Index of the last line written for this file, to merge overlapping context.
+        let mut printed: Option<usize> = None;
+        for &hit in &hits {
+            if report.matches >= self.max_results {
+                report.truncated = true;
+                break;
+            }
+            let from = hit.saturating_sub(self.context);
+            let from = printed.map_or(from, |last| from.max(last + 1));
+            if printed.is_some_and(|last| from > last + 1) {
+                report.output.push_str("--\n");
+            }
+            let to = (hit + self.context).min(lines.len() - 1);
+            for (index, line) in lines.iter().enumerate().take(to + 1).skip(from) {
+                let separator = if hits.binary_search(&index).is_ok() {
+                    ':'
+                } else {
+                    '-'
+                };
+                let _ = writeln!(
+                    report.output,
+                    "{rel}{separator}{}{separator}{}",
+                    index + 1,
+                    truncate_line(line)
+                );
+            }
+            printed = Some(to);
+            report.matches += 1;
+        }
+        if self.context > 0 {
+            report.output.push_str("--\n");
+        }
+    }
+}
+
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use tempfile::TempDir;
+
+    fn test_security(workspace: PathBuf, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn workspace() -> (TempDir, ContentSearchTool) {
+        let dir = TempDir::new().unwrap();
+        let write = |rel: &str, content: &[u8]| {
+            let path = dir.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "src/main.rs",
+            b"fn main() {\n    helper();\n}\n\nfn helper() {}\n",
+        );
+        write("src/lib.rs", b"pub fn Helper() {}\n");
+        write("docs/notes.md", b"call helper() here\n");
+        write("target/debug/out.rs", b"fn helper() {}\n");
+        write("blob.bin", b"helper\0\x01");
+        write(".gitignore", b"target/\n*.log\n");
+        write("run.log", b"helper failed\n");
+        let tool = ContentSearchTool::new(test_security(
+            dir.path().to_path_buf(),
+            AutonomyLevel::ReadOnly,
+        ));
+        (dir, tool)
+    }
+
+    #[test]
+    fn content_search_name_and_schema() {
+        let tool = ContentSearchTool::new(test_security(
+            std::env::temp_dir(),
+            AutonomyLevel::Supervised,
+        ));
+        assert_eq!(tool.name(), "content_search");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["include"].is_object());
+        assert_eq!(schema["required"], json!(["pattern"]));
+    }
+
+    #[tokio::test]
+    async fn content_search_respects_gitignore_and_skips_binary_files() {
+        let (_dir, tool) = workspace();
+        let result = tool.execute(json!({"pattern": "helper"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("src/main.rs:2:    helper();"));
+        assert!(result.output.contains("src/main.rs:5:fn helper() {}"));
+        assert!(result.output.contains("docs/notes.md:1:"));
+        assert!(!result.output.contains("target/"));
+        assert!(!result.output.contains("run.log"));
+        assert!(!result.output.contains("blob.bin"));
+        assert!(!result.output.contains("src/lib.rs"));
+        assert!(result.output.contains("Total: 3 matches in 2 files"));
+
+        let result = tool
+            .execute(json!({"pattern": "helper", "respect_gitignore": false}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("target/debug/out.rs:1:"));
+        assert!(result.output.contains("run.log:1:"));
+    }
+
+    #[tokio::test]
+    async fn content_search_literal_case_and_globs() {
+        let (_dir, tool) = workspace();
+        let result = tool
+            .execute(json!({"pattern": "helper()", "literal": true, "include": ["*.md"]}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("docs/notes.md:1:call helper() here"));
+        assert!(result.output.contains("Total: 1 matches in 1 files"));
+
+        let result = tool
+            .execute(json!({"pattern": "fn helper", "case_sensitive": false, "path": "src", "exclude": ["src/main.rs"]}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("src/lib.rs:1:pub fn Helper() {}"));
+        assert!(!result.output.contains("src/main.rs"));
+    }
+
+    #[tokio::test]
+    async fn content_search_context_and_result_cap() {
+        let (_dir, tool) = workspace();
+        let result = tool
+            .execute(json!({"pattern": "helper", "path": "src/main.rs", "context": 1}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with(
+            "src/main.rs-1-fn main() {\nsrc/main.rs:2:    helper();\nsrc/main.rs-3-}\nsrc/main.rs-4-\nsrc/main.rs:5:"
+        ));
+
+        let result = tool
+            .execute(json!({"pattern": "helper", "max_results": 1}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("[Results truncated at 1 matches"));
+    }
+
+    #[tokio::test]
+    async fn content_search_rejects_bad_input_and_escapes() {
+        let (_dir, tool) = workspace();
+        assert!(tool.execute(json!({})).await.is_err());
+
+        let result = tool.execute(json!({"pattern": "(unclosed"})).await.unwrap();
+        assert!(result.error.unwrap().contains("Invalid regex"));
+
+        let result = tool
+            .execute(json!({"pattern": "x", "path": "../.."}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let result = tool
+            .execute(json!({"pattern": "x", "path": "/etc"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn content_search_does_not_follow_symlinks() {
+        let root = TempDir::new().unwrap();
+        let workspace = root.path().join("workspace");
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "token=abc\n").unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.join("escape")).unwrap();
+
+        let tool = ContentSearchTool::new(test_security(workspace, AutonomyLevel::Supervised));
+        let result = tool.execute(json!({"pattern": "token"})).await.unwrap();
+        assert!(result.output.contains("No matches"));
+
+        let result = tool
+            .execute(json!({"pattern": "token", "path": "escape"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("escapes workspace"));
+    }
+
+    #[test]
+    fn gitignore_rules_support_negation_and_anchoring() {
+        let rules: Vec<IgnoreRule> = ["*.log", "!keep.log", "/build", "docs/*.tmp"]
+            .iter()
+            .filter_map(|line| IgnoreRule::parse("", line))
+            .collect();
+        assert!(is_ignored(&rules, "a/b/debug.log", false));
+        assert!(!is_ignored(&rules, "a/keep.log", false));
+        assert!(is_ignored(&rules, "build", true));
+        assert!(!is_ignored(&rules, "src/build", true));
+        assert!(is_ignored(&rules, "docs/x.tmp", false));
+        assert!(!is_ignored(&rules, "docs/sub/x.tmp", false));
+
+        let nested: Vec<IgnoreRule> = IgnoreRule::parse("pkg", "out/").into_iter().collect();
+        assert!(is_ignored(&nested, "pkg/out", true));
+        assert!(!is_ignored(&nested, "pkg/out", false));
+        assert!(!is_ignored(&nested, "other/out", true));
+    }
+}
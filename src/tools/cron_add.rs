@@ -1,6 +1,8 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{
+    self, CronJobPatch, DeliveryConfig, JobDependency, JobType, Schedule, SessionTarget,
+};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -38,7 +40,7 @@ impl Tool for CronAddTool {
                 "name": { "type": "string" },
                 "schedule": {
                     "type": "object",
-                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
+                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms} | {kind:'watch',path,glob?} | {kind:'webhook',name} | {kind:'channel',pattern,channel?} | {kind:'after'} (run when the `after` jobs finish)"
                 },
                 "job_type": { "type": "string", "enum": ["shell", "agent"] },
                 "command": { "type": "string" },
@@ -47,6 +49,15 @@ impl Tool for CronAddTool {
                 "model": { "type": "string" },
                 "delivery": { "type": "object" },
                 "delete_after_run": { "type": "boolean" },
+                "after": {
+                    "type": "array",
+                    "description": "Upstream jobs to run after; requires schedule {kind:'after'}: [{job_id, condition?: 'success'|'failure'|'always'}]. The upstream output is appended to agent prompts and exposed to shell jobs as ZEROCLAW_UPSTREAM_OUTPUT",
+                    "items": { "type": "object" }
+                },
+                "output_schema": {
+                    "type": "object",
+                    "description": "Agent jobs only: JSON Schema the final output must conform to; the result is stored as JSON"
+                },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -120,7 +131,29 @@ impl Tool for CronAddTool {
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
 
+        let after = match args.get("after") {
+            Some(v) => match serde_json::from_value::<Vec<JobDependency>>(v.clone()) {
+                Ok(after) => after,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid after: {e}")),
+                    });
+                }
+            },
+            None => Vec::new(),
+        };
+        if let Err(e) = cron::validate_dependencies(&self.config, None, &after) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            });
+        }
+
         let result = match job_type {
+            JobType::Backup => unreachable!("job_type is parsed as shell or agent"),
             JobType::Shell => {
                 let command = match args.get("command").and_then(serde_json::Value::as_str) {
                     Some(command) if !command.trim().is_empty() => command,
@@ -141,11 +174,29 @@ impl Tool for CronAddTool {
                     });
                 }
 
+                if args.get("output_schema").is_some() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("output_schema is only supported for agent jobs".to_string()),
+                    });
+                }
+
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
                     return Ok(blocked);
                 }
 
-                cron::add_shell_job(&self.config, name, schedule, command)
+                cron::add_job_with(
+                    &self.config,
+                    JobType::Shell,
+                    name,
+                    schedule,
+                    CronJobPatch {
+                        command: Some(command.to_string()),
+                        after: Some(after),
+                        ..CronJobPatch::default()
+                    },
+                )
             }
             JobType::Agent => {
                 let prompt = match args.get("prompt").and_then(serde_json::Value::as_str) {
@@ -192,19 +243,41 @@ impl Tool for CronAddTool {
                     None => None,
                 };
 
+                let output_schema = match args.get("output_schema") {
+                    Some(v) => {
+                        if let Err(e) =
+                            crate::providers::structured::parse_response_schema(v, "cron_output")
+                        {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid output_schema: {e}")),
+                            });
+                        }
+                        Some(v.clone())
+                    }
+                    None => None,
+                };
+
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
                     return Ok(blocked);
                 }
 
-                cron::add_agent_job(
+                cron::add_job_with(
                     &self.config,
+                    JobType::Agent,
                     name,
                     schedule,
-                    prompt,
-                    session_target,
-                    model,
-                    delivery,
-                    delete_after_run,
+                    CronJobPatch {
+                        prompt: Some(prompt.to_string()),
+                        session_target: Some(session_target),
+                        model,
+                        delivery,
+                        delete_after_run: Some(delete_after_run),
+                        output_schema,
+                        after: Some(after),
+                        ..CronJobPatch::default()
+                    },
                 )
             }
         };
@@ -218,7 +291,8 @@ impl Tool for CronAddTool {
                     "job_type": job.job_type,
                     "schedule": job.schedule,
                     "next_run": job.next_run,
-                    "enabled": job.enabled
+                    "enabled": job.enabled,
+                    "after": job.after
                 }))?,
                 error: None,
             }),
@@ -275,6 +349,73 @@ mod tests {
         assert!(result.output.contains("next_run"));
     }
 
+    #[tokio::test]
+    async fn agent_job_persists_output_schema() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let schema = json!({
+            "type": "object",
+            "properties": { "status": { "type": "string" } },
+            "required": ["status"]
+        });
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "job_type": "agent",
+                "prompt": "check status",
+                "output_schema": schema
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].output_schema, Some(schema));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_object_output_schema() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "job_type": "agent",
+                "prompt": "check status",
+                "output_schema": "not a schema"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid output_schema"));
+        assert!(cron::list_jobs(&cfg).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_output_schema_on_shell_job() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "job_type": "shell",
+                "command": "echo ok",
+                "output_schema": { "type": "object" }
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("only supported for agent jobs"));
+        assert!(cron::list_jobs(&cfg).unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn blocks_disallowed_shell_command() {
         let tmp = TempDir::new().unwrap();
@@ -412,4 +553,50 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn adds_job_chained_after_upstream() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let upstream = cron::add_job(&cfg, "0 1 * * *", "echo backup").unwrap();
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "after" },
+                "job_type": "shell",
+                "command": "echo summary",
+                "after": [{ "job_id": upstream.id, "condition": "failure" }]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(output["after"][0]["job_id"], upstream.id.as_str());
+        assert_eq!(output["after"][0]["condition"], "failure");
+
+        let missing = tool
+            .execute(json!({
+                "schedule": { "kind": "after" },
+                "job_type": "shell",
+                "command": "echo summary",
+                "after": [{ "job_id": "missing" }]
+            }))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+
+        let scheduled = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 2 * * *" },
+                "job_type": "shell",
+                "command": "echo summary",
+                "after": [{ "job_id": upstream.id }]
+            }))
+            .await
+            .unwrap();
+        assert!(!scheduled.success);
+        assert!(scheduled.error.unwrap().contains("schedule kind 'after'"));
+        assert_eq!(cron::list_jobs(&cfg).unwrap().len(), 2);
+    }
 }
@@ -28,7 +28,7 @@ impl Tool for CronUpdateTool {
     }
 
     fn description(&self) -> &str {
-        "Patch an existing cron job (schedule, command, prompt, enabled, delivery, model, etc.)"
+        "Patch an existing cron job (schedule, command, prompt, enabled, delivery, model, overlap, catch_up, jitter_secs, etc.)"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
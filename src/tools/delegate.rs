@@ -1,13 +1,17 @@
+use super::delegate_background::DelegationRegistry;
 use super::traits::{Tool, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
-use crate::config::DelegateAgentConfig;
+use crate::config::{Config, DelegateAgentConfig, DelegateMemoryMode};
+use crate::memory::{Memory, ScopedMemory};
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
+use crate::runtime::{NativeRuntime, RuntimeAdapter};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{AutonomyLevel, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -15,11 +19,20 @@ use std::time::Duration;
 const DELEGATE_TIMEOUT_SECS: u64 = 120;
 /// Default timeout for agentic sub-agent runs.
 const DELEGATE_AGENTIC_TIMEOUT_SECS: u64 = 300;
+/// Timeout for sub-agents started with `background=true`, which do not block
+/// the calling conversation.
+const DELEGATE_BACKGROUND_TIMEOUT_SECS: u64 = 1800;
+/// Maximum number of sub-agents a single delegate call may fan out to.
+const MAX_PARALLEL_DELEGATIONS: usize = 8;
 
 /// Tool that delegates a subtask to a named agent with a different
 /// provider/model configuration. Enables multi-agent workflows where
 /// a primary agent can hand off specialized work (research, coding,
 /// summarization) to purpose-built sub-agents.
+///
+/// One call can fan out to several agents or tasks concurrently, and with
+/// `background=true` returns a handle tracked by a [`DelegationRegistry`].
+#[derive(Clone)]
 pub struct DelegateTool {
     agents: Arc<HashMap<String, DelegateAgentConfig>>,
     security: Arc<SecurityPolicy>,
@@ -33,6 +46,12 @@ pub struct DelegateTool {
     parent_tools: Arc<Vec<Arc<dyn Tool>>>,
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
+    /// Registry for background runs; background mode is refused without one.
+    background: Option<Arc<DelegationRegistry>>,
+    /// Handles used to rebuild tools for agents with their own profile.
+    scope: DelegateScope,
+    timeout_secs: u64,
+    agentic_timeout_secs: u64,
 }
 
 impl DelegateTool {
@@ -63,6 +82,10 @@ impl DelegateTool {
             depth: 0,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            background: None,
+            scope: DelegateScope::default(),
+            timeout_secs: DELEGATE_TIMEOUT_SECS,
+            agentic_timeout_secs: DELEGATE_AGENTIC_TIMEOUT_SECS,
         }
     }
 
@@ -99,6 +122,10 @@ impl DelegateTool {
             depth,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            background: None,
+            scope: DelegateScope::default(),
+            timeout_secs: DELEGATE_TIMEOUT_SECS,
+            agentic_timeout_secs: DELEGATE_AGENTIC_TIMEOUT_SECS,
         }
     }
 
@@ -113,6 +140,28 @@ impl DelegateTool {
         self.multimodal_config = config;
         self
     }
+
+    /// Provide the parent's memory and runtime so agents that declare a
+    /// memory mode or security profile get their own memory and shell tools.
+    pub fn with_scope(
+        mut self,
+        memory: Arc<dyn Memory>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        config: Arc<Config>,
+    ) -> Self {
+        self.scope = DelegateScope {
+            memory: Some(memory),
+            runtime: Some(runtime),
+            config: Some(config),
+        };
+        self
+    }
+
+    /// Enable `background=true` runs, tracked in `registry`.
+    pub fn with_background_registry(mut self, registry: Arc<DelegationRegistry>) -> Self {
+        self.background = Some(registry);
+        self
+    }
 }
 
 #[async_trait]
@@ -124,7 +173,10 @@ impl Tool for DelegateTool {
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
          (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt by default; with agentic=true it can iterate with a filtered tool-call loop."
+         prompt by default; with agentic=true it can iterate with a filtered tool-call loop. \
+         Pass agents=[...] to send one prompt to several agents, or tasks=[{agent, prompt}] to run \
+         several tasks concurrently. With background=true the call returns an id immediately; \
+         poll it with delegate_status/delegate_result or stop it with delegate_cancel."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -153,18 +205,304 @@ impl Tool for DelegateTool {
                 "context": {
                     "type": "string",
                     "description": "Optional context to prepend (e.g. relevant code, prior findings)"
+                },
+                "output_schema": {
+                    "type": "object",
+                    "description": "Optional JSON Schema the sub-agent's answer must conform to; the result is returned as JSON"
+                },
+                "agents": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "maxItems": MAX_PARALLEL_DELEGATIONS,
+                    "description": "Send the prompt to each of these agents concurrently (instead of 'agent')"
+                },
+                "tasks": {
+                    "type": "array",
+                    "maxItems": MAX_PARALLEL_DELEGATIONS,
+                    "description": "Run several tasks concurrently; each task's agent defaults to 'agent'",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "agent": { "type": "string" },
+                            "prompt": { "type": "string" },
+                            "context": { "type": "string" }
+                        },
+                        "required": ["prompt"]
+                    }
+                },
+                "background": {
+                    "type": "boolean",
+                    "description": "Return a delegation id immediately and keep the sub-agent(s) running",
+                    "default": false
                 }
-            },
-            "required": ["agent", "prompt"]
+            }
         })
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        let agent_name = args
-            .get("agent")
+        let tasks = parse_tasks(&args)?;
+
+        if tasks.len() > MAX_PARALLEL_DELEGATIONS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Too many delegation tasks ({}); at most {MAX_PARALLEL_DELEGATIONS} run per call",
+                    tasks.len()
+                )),
+            });
+        }
+
+        let output_schema = match args.get("output_schema") {
+            Some(raw) => match providers::structured::parse_response_schema(raw, "delegate_output")
+            {
+                Ok(schema) => Some(schema),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid output_schema: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        if args.get("background").and_then(serde_json::Value::as_bool) != Some(true) {
+            return Ok(self.run_tasks(tasks, output_schema).await);
+        }
+
+        let Some(registry) = self.background.clone() else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Background delegation is not available in this context".into()),
+            });
+        };
+        let label = tasks
+            .iter()
+            .map(|task| task.agent.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let summary = truncate_chars(&tasks[0].prompt, 80);
+        let mut runner = self.clone();
+        runner.timeout_secs = DELEGATE_BACKGROUND_TIMEOUT_SECS;
+        runner.agentic_timeout_secs = DELEGATE_BACKGROUND_TIMEOUT_SECS;
+        match registry.spawn(format!("{label}: {summary}"), async move {
+            runner.run_tasks(tasks, output_schema).await
+        }) {
+            Ok(id) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Started background delegation {id} ({label}). \
+                     Use delegate_status, delegate_result or delegate_cancel with id=\"{id}\"."
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct DelegateScope {
+    memory: Option<Arc<dyn Memory>>,
+    runtime: Option<Arc<dyn RuntimeAdapter>>,
+    config: Option<Arc<Config>>,
+}
+
+/// One sub-agent invocation within a (possibly fanned-out) delegate call.
+#[derive(Debug, Clone)]
+struct DelegationTask {
+    agent: String,
+    prompt: String,
+    context: String,
+}
+
+/// Read the call's tasks: `tasks` (each with its own prompt and optional
+/// agent), `agents` (one prompt to several agents), or a single
+/// `agent`/`prompt` pair.
+fn parse_tasks(args: &serde_json::Value) -> anyhow::Result<Vec<DelegationTask>> {
+    let text = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
             .and_then(|v| v.as_str())
-            .map(str::trim)
-            .ok_or_else(|| anyhow::anyhow!("Missing 'agent' parameter"))?;
+            .map(|s| s.trim().to_string())
+    };
+    let context = text(args, "context").unwrap_or_default();
+
+    if let Some(items) = args.get("tasks").and_then(|v| v.as_array()) {
+        if items.is_empty() {
+            anyhow::bail!("'tasks' must not be empty");
+        }
+        return items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                Ok(DelegationTask {
+                    agent: text(item, "agent")
+                        .or_else(|| text(args, "agent"))
+                        .ok_or_else(|| anyhow::anyhow!("Task {} is missing 'agent'", index + 1))?,
+                    prompt: text(item, "prompt")
+                        .ok_or_else(|| anyhow::anyhow!("Task {} is missing 'prompt'", index + 1))?,
+                    context: text(item, "context").unwrap_or_else(|| context.clone()),
+                })
+            })
+            .collect();
+    }
+
+    let prompt =
+        text(args, "prompt").ok_or_else(|| anyhow::anyhow!("Missing 'prompt' parameter"))?;
+    if let Some(agents) = args.get("agents").and_then(|v| v.as_array()) {
+        if agents.is_empty() {
+            anyhow::bail!("'agents' must not be empty");
+        }
+        return agents
+            .iter()
+            .map(|agent| {
+                Ok(DelegationTask {
+                    agent: agent
+                        .as_str()
+                        .map(|s| s.trim().to_string())
+                        .ok_or_else(|| anyhow::anyhow!("'agents' must contain agent names"))?,
+                    prompt: prompt.clone(),
+                    context: context.clone(),
+                })
+            })
+            .collect();
+    }
+
+    let agent = text(args, "agent").ok_or_else(|| anyhow::anyhow!("Missing 'agent' parameter"))?;
+    Ok(vec![DelegationTask {
+        agent,
+        prompt,
+        context,
+    }])
+}
+
+/// The parent's policy narrowed by the agent's profile. Autonomy and the
+/// command allowlist can only be restricted, never widened.
+fn delegate_security(
+    parent: &SecurityPolicy,
+    agent_config: &DelegateAgentConfig,
+) -> anyhow::Result<SecurityPolicy> {
+    fn rank(level: AutonomyLevel) -> u8 {
+        match level {
+            AutonomyLevel::ReadOnly => 0,
+            AutonomyLevel::Supervised => 1,
+            AutonomyLevel::Full => 2,
+        }
+    }
+
+    let mut policy = parent.clone();
+    if let Some(level) = agent_config.autonomy {
+        if rank(level) < rank(parent.autonomy) {
+            policy.autonomy = level;
+        }
+    }
+    if let Some(commands) = &agent_config.allowed_commands {
+        policy.allowed_commands = commands
+            .iter()
+            .filter(|command| parent.allowed_commands.contains(command))
+            .cloned()
+            .collect();
+    }
+    if let Some(subdir) = &agent_config.workspace {
+        if !parent.is_path_allowed(subdir) {
+            anyhow::bail!("workspace '{subdir}' is not allowed by the parent security policy");
+        }
+        let workspace = parent.workspace_dir.join(subdir);
+        std::fs::create_dir_all(&workspace)?;
+        policy.workspace_dir = workspace;
+    }
+    Ok(policy)
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+impl DelegateTool {
+    /// Run every task concurrently. A single task returns its own result
+    /// unchanged; several are aggregated into one report that succeeds when
+    /// at least one sub-agent did.
+    async fn run_tasks(
+        &self,
+        tasks: Vec<DelegationTask>,
+        output_schema: Option<providers::ResponseSchema>,
+    ) -> ToolResult {
+        let schema = output_schema.as_ref();
+        if let [task] = tasks.as_slice() {
+            return self.run_task(task, schema).await;
+        }
+
+        let results =
+            futures_util::future::join_all(tasks.iter().map(|task| self.run_task(task, schema)))
+                .await;
+
+        let succeeded = results.iter().filter(|result| result.success).count();
+        let mut output = format!(
+            "[Delegated {} tasks: {succeeded} succeeded, {} failed]",
+            tasks.len(),
+            tasks.len() - succeeded
+        );
+        for (index, (task, result)) in tasks.iter().zip(&results).enumerate() {
+            let body = if result.success {
+                result.output.clone()
+            } else {
+                format!(
+                    "[Agent '{}' failed] {}",
+                    task.agent,
+                    result.error.as_deref().unwrap_or("unknown error")
+                )
+            };
+            let _ = write!(output, "\n\n--- Task {} ---\n{body}", index + 1);
+        }
+
+        if succeeded == 0 {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(output),
+            };
+        }
+        ToolResult {
+            success: true,
+            output,
+            error: None,
+        }
+    }
+
+    async fn run_task(
+        &self,
+        task: &DelegationTask,
+        output_schema: Option<&providers::ResponseSchema>,
+    ) -> ToolResult {
+        match self.run_task_inner(task, output_schema).await {
+            Ok(result) => result,
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Agent '{}' failed: {e}", task.agent)),
+            },
+        }
+    }
+
+    async fn run_task_inner(
+        &self,
+        task: &DelegationTask,
+        output_schema: Option<&providers::ResponseSchema>,
+    ) -> anyhow::Result<ToolResult> {
+        let agent_name = task.agent.as_str();
+        let prompt = task.prompt.as_str();
+        let context = task.context.as_str();
 
         if agent_name.is_empty() {
             return Ok(ToolResult {
@@ -174,12 +512,6 @@ impl Tool for DelegateTool {
             });
         }
 
-        let prompt = args
-            .get("prompt")
-            .and_then(|v| v.as_str())
-            .map(str::trim)
-            .ok_or_else(|| anyhow::anyhow!("Missing 'prompt' parameter"))?;
-
         if prompt.is_empty() {
             return Ok(ToolResult {
                 success: false,
@@ -188,12 +520,6 @@ impl Tool for DelegateTool {
             });
         }
 
-        let context = args
-            .get("context")
-            .and_then(|v| v.as_str())
-            .map(str::trim)
-            .unwrap_or("");
-
         // Look up agent config
         let agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg,
@@ -248,10 +574,16 @@ impl Tool for DelegateTool {
         #[allow(clippy::option_as_ref_deref)]
         let provider_credential = provider_credential_owned.as_ref().map(String::as_str);
 
-        let provider: Box<dyn Provider> = match providers::create_provider_with_options(
-            &agent_config.provider,
-            provider_credential,
-            &self.provider_runtime_options,
+        // Sub-agents append to the parent's cassette when one is configured.
+        let provider: Box<dyn Provider> = match providers::cassette::apply(
+            self.provider_runtime_options.cassette.as_ref(),
+            || {
+                providers::create_provider_with_options(
+                    &agent_config.provider,
+                    provider_credential,
+                    &self.provider_runtime_options,
+                )
+            },
         ) {
             Ok(p) => p,
             Err(e) => {
@@ -284,20 +616,43 @@ impl Tool for DelegateTool {
                     &*provider,
                     &full_prompt,
                     temperature,
+                    output_schema,
                 )
                 .await;
         }
 
         // Wrap the provider call in a timeout to prevent indefinite blocking
-        let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                agent_config.system_prompt.as_deref(),
-                &full_prompt,
-                &agent_config.model,
-                temperature,
-            ),
-        )
+        let result = tokio::time::timeout(Duration::from_secs(self.timeout_secs), async {
+            match output_schema {
+                Some(schema) => {
+                    let mut messages = Vec::new();
+                    if let Some(system_prompt) = agent_config.system_prompt.as_ref() {
+                        messages.push(ChatMessage::system(system_prompt.clone()));
+                    }
+                    messages.push(ChatMessage::user(full_prompt.clone()));
+                    providers::structured::chat_structured(
+                        &*provider,
+                        &messages,
+                        schema,
+                        &agent_config.model,
+                        temperature,
+                        providers::structured::DEFAULT_STRUCTURED_ATTEMPTS,
+                    )
+                    .await
+                    .map(|value| value.to_string())
+                }
+                None => {
+                    provider
+                        .chat_with_system(
+                            agent_config.system_prompt.as_deref(),
+                            &full_prompt,
+                            &agent_config.model,
+                            temperature,
+                        )
+                        .await
+                }
+            }
+        })
         .await;
 
         let result = match result {
@@ -307,7 +662,8 @@ impl Tool for DelegateTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!(
-                        "Agent '{agent_name}' timed out after {DELEGATE_TIMEOUT_SECS}s"
+                        "Agent '{agent_name}' timed out after {}s",
+                        self.timeout_secs
                     )),
                 });
             }
@@ -337,9 +693,53 @@ impl Tool for DelegateTool {
             }),
         }
     }
-}
 
-impl DelegateTool {
+    /// Tools offered to an agentic sub-agent before allowlist filtering.
+    ///
+    /// Agents without a profile get the parent's tools. Agents that declare
+    /// a memory mode, autonomy, command allowlist or workspace get the
+    /// security- and memory-bound tools rebuilt under their own policy, plus
+    /// the parent's [`POLICY_FREE_TOOLS`](super::POLICY_FREE_TOOLS). Any other
+    /// parent tool would act under the parent's policy and is left out.
+    fn profile_tools(
+        &self,
+        agent_name: &str,
+        agent_config: &DelegateAgentConfig,
+    ) -> anyhow::Result<Vec<Arc<dyn Tool>>> {
+        if !agent_config.has_scoped_profile() {
+            return Ok(self.parent_tools.to_vec());
+        }
+
+        let security = Arc::new(delegate_security(&self.security, agent_config)?);
+        let memory = self.scope.memory.as_ref().map(|parent| -> Arc<dyn Memory> {
+            match agent_config.memory {
+                DelegateMemoryMode::Shared => parent.clone(),
+                DelegateMemoryMode::Isolated => Arc::new(ScopedMemory::isolated(
+                    parent.clone(),
+                    agent_config
+                        .memory_namespace
+                        .as_deref()
+                        .unwrap_or(agent_name),
+                )),
+                DelegateMemoryMode::ReadOnly => Arc::new(ScopedMemory::read_only(parent.clone())),
+            }
+        });
+        let runtime = self
+            .scope
+            .runtime
+            .clone()
+            .unwrap_or_else(|| Arc::new(NativeRuntime::new()));
+
+        let mut tools = super::scoped_tools(&security, runtime, memory, self.scope.config.as_ref());
+        tools.extend(
+            self.parent_tools
+                .iter()
+                .filter(|tool| super::POLICY_FREE_TOOLS.contains(&tool.name()))
+                .cloned(),
+        );
+        Ok(tools)
+    }
+
     async fn execute_agentic(
         &self,
         agent_name: &str,
@@ -347,6 +747,7 @@ impl DelegateTool {
         provider: &dyn Provider,
         full_prompt: &str,
         temperature: f64,
+        output_schema: Option<&providers::ResponseSchema>,
     ) -> anyhow::Result<ToolResult> {
         if agent_config.allowed_tools.is_empty() {
             return Ok(ToolResult {
@@ -365,8 +766,20 @@ impl DelegateTool {
             .filter(|name| !name.is_empty())
             .collect::<std::collections::HashSet<_>>();
 
-        let sub_tools: Vec<Box<dyn Tool>> = self
-            .parent_tools
+        let candidates = match self.profile_tools(agent_name, agent_config) {
+            Ok(tools) => tools,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Failed to prepare profile for agent '{agent_name}': {e}"
+                    )),
+                });
+            }
+        };
+
+        let sub_tools: Vec<Box<dyn Tool>> = candidates
             .iter()
             .filter(|tool| allowed.contains(tool.name()))
             .filter(|tool| tool.name() != "delegate")
@@ -393,7 +806,7 @@ impl DelegateTool {
         let noop_observer = NoopObserver;
 
         let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_AGENTIC_TIMEOUT_SECS),
+            Duration::from_secs(self.agentic_timeout_secs),
             run_tool_call_loop(
                 provider,
                 &mut history,
@@ -413,6 +826,20 @@ impl DelegateTool {
         )
         .await;
 
+        // Structured mode: convert the loop's final answer into schema-conforming JSON.
+        let result = match (result, output_schema) {
+            (Ok(Ok(response)), Some(schema)) => Ok(providers::structured::coerce_to_schema(
+                provider,
+                &response,
+                schema,
+                &agent_config.model,
+                temperature,
+            )
+            .await
+            .map(|value| value.to_string())),
+            (result, _) => result,
+        };
+
         match result {
             Ok(Ok(response)) => {
                 let rendered = if response.trim().is_empty() {
@@ -440,7 +867,8 @@ impl DelegateTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Agent '{agent_name}' timed out after {DELEGATE_AGENTIC_TIMEOUT_SECS}s"
+                    "Agent '{agent_name}' timed out after {}s",
+                    self.agentic_timeout_secs
                 )),
             }),
         }
@@ -517,6 +945,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         agents.insert(
@@ -531,6 +964,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         agents
@@ -598,6 +1036,8 @@ mod tests {
                 Ok(ChatResponse {
                     text: Some("done".to_string()),
                     tool_calls: Vec::new(),
+                    reasoning: None,
+                    usage: None,
                 })
             } else {
                 Ok(ChatResponse {
@@ -607,6 +1047,8 @@ mod tests {
                         name: "echo_tool".to_string(),
                         arguments: "{\"value\":\"ping\"}".to_string(),
                     }],
+                    reasoning: None,
+                    usage: None,
                 })
             }
         }
@@ -639,6 +1081,8 @@ mod tests {
                     name: "echo_tool".to_string(),
                     arguments: "{\"value\":\"x\"}".to_string(),
                 }],
+                reasoning: None,
+                usage: None,
             })
         }
     }
@@ -678,6 +1122,11 @@ mod tests {
             agentic: true,
             allowed_tools,
             max_iterations,
+            memory: crate::config::DelegateMemoryMode::Shared,
+            memory_namespace: None,
+            autonomy: None,
+            allowed_commands: None,
+            workspace: None,
         }
     }
 
@@ -689,9 +1138,13 @@ mod tests {
         assert!(schema["properties"]["agent"].is_object());
         assert!(schema["properties"]["prompt"].is_object());
         assert!(schema["properties"]["context"].is_object());
-        let required = schema["required"].as_array().unwrap();
-        assert!(required.contains(&json!("agent")));
-        assert!(required.contains(&json!("prompt")));
+        assert!(schema["properties"]["agents"].is_object());
+        assert!(schema["properties"]["tasks"].is_object());
+        assert!(schema["properties"]["background"].is_object());
+        // agent/prompt are optional at the schema level because fan-out calls
+        // use `agents` or `tasks` instead; execute() still rejects calls
+        // that provide neither.
+        assert!(schema.get("required").is_none());
         assert_eq!(schema["additionalProperties"], json!(false));
         assert_eq!(schema["properties"]["agent"]["minLength"], json!(1));
         assert_eq!(schema["properties"]["prompt"]["minLength"], json!(1));
@@ -786,6 +1239,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -892,6 +1350,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -927,6 +1390,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1018,7 +1486,7 @@ mod tests {
 
         let provider = OneToolThenFinalProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic("agentic", &config, &provider, "run", 0.2, None)
             .await
             .unwrap();
 
@@ -1027,6 +1495,46 @@ mod tests {
         assert!(result.output.contains("done"));
     }
 
+    #[tokio::test]
+    async fn execute_agentic_returns_schema_conforming_json() {
+        let config = agentic_config(vec!["echo_tool".to_string()], 10);
+        let tool = DelegateTool::new(HashMap::new(), None, test_security())
+            .with_parent_tools(Arc::new(vec![Arc::new(EchoTool)]));
+        let schema = providers::ResponseSchema::new(
+            "status",
+            json!({
+                "type": "object",
+                "properties": {"status": {"type": "string"}},
+                "required": ["status"]
+            }),
+        );
+
+        let provider = OneToolThenFinalProvider;
+        let result = tool
+            .execute_agentic("agentic", &config, &provider, "run", 0.2, Some(&schema))
+            .await
+            .unwrap();
+
+        // "done" is not JSON, and the provider cannot produce a conforming answer.
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("did not match schema"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_output_schema() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({
+                "agent": "researcher",
+                "prompt": "test",
+                "output_schema": "not an object"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid output_schema"));
+    }
+
     #[tokio::test]
     async fn execute_agentic_excludes_delegate_even_if_allowlisted() {
         let config = agentic_config(vec!["delegate".to_string()], 10);
@@ -1040,7 +1548,7 @@ mod tests {
 
         let provider = OneToolThenFinalProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic("agentic", &config, &provider, "run", 0.2, None)
             .await
             .unwrap();
 
@@ -1060,7 +1568,7 @@ mod tests {
 
         let provider = InfiniteToolCallProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic("agentic", &config, &provider, "run", 0.2, None)
             .await
             .unwrap();
 
@@ -1080,7 +1588,7 @@ mod tests {
 
         let provider = FailingProvider;
         let result = tool
-            .execute_agentic("agentic", &config, &provider, "run", 0.2)
+            .execute_agentic("agentic", &config, &provider, "run", 0.2, None)
             .await
             .unwrap();
 
@@ -1091,4 +1599,231 @@ mod tests {
             .unwrap_or("")
             .contains("provider boom"));
     }
+
+    #[tokio::test]
+    async fn fan_out_aggregates_results_per_task() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({"agents": ["nonexistent", "ghost"], "prompt": "test"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let report = result.error.unwrap();
+        assert!(report.contains("[Delegated 2 tasks: 0 succeeded, 2 failed]"));
+        assert!(report.contains("--- Task 1 ---\n[Agent 'nonexistent' failed] Unknown agent"));
+        assert!(report.contains("--- Task 2 ---\n[Agent 'ghost' failed]"));
+    }
+
+    #[test]
+    fn parse_tasks_supports_tasks_agents_and_single_forms() {
+        let tasks = parse_tasks(&json!({
+            "agent": "researcher",
+            "context": "shared",
+            "tasks": [
+                {"prompt": "a"},
+                {"agent": "coder", "prompt": "b", "context": "own"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(
+            (tasks[0].agent.as_str(), tasks[0].context.as_str()),
+            ("researcher", "shared")
+        );
+        assert_eq!(
+            (tasks[1].agent.as_str(), tasks[1].context.as_str()),
+            ("coder", "own")
+        );
+
+        let tasks = parse_tasks(&json!({"agents": ["a", "b"], "prompt": "p"})).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks.iter().all(|task| task.prompt == "p"));
+
+        assert!(parse_tasks(&json!({"tasks": [{"prompt": "no agent"}]})).is_err());
+        assert!(parse_tasks(&json!({"agents": [], "prompt": "p"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn fan_out_rejects_too_many_tasks() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let agents: Vec<String> = (0..=MAX_PARALLEL_DELEGATIONS)
+            .map(|i| format!("agent{i}"))
+            .collect();
+        let result = tool
+            .execute(json!({"agents": agents, "prompt": "test"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Too many delegation tasks"));
+    }
+
+    #[tokio::test]
+    async fn background_requires_registry_and_returns_handle() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({"agent": "nonexistent", "prompt": "test", "background": true}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not available"));
+
+        let registry = Arc::new(DelegationRegistry::new());
+        let tool = tool.with_background_registry(registry.clone());
+        let result = tool
+            .execute(json!({"agent": "nonexistent", "prompt": "test", "background": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let id = registry.list()[0].id.clone();
+        assert!(result.output.contains(&id));
+
+        let status = registry.wait(&id, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(
+            status.state,
+            crate::tools::delegate_background::DelegationState::Failed
+        );
+        assert!(status.label.starts_with("nonexistent: test"));
+        assert!(status
+            .result
+            .unwrap()
+            .error
+            .unwrap()
+            .contains("Unknown agent"));
+    }
+
+    fn find_tool<'a>(tools: &'a [Arc<dyn Tool>], name: &str) -> &'a Arc<dyn Tool> {
+        tools.iter().find(|tool| tool.name() == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn profile_restricts_autonomy_workspace_and_memory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let parent_security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let memory: Arc<dyn Memory> =
+            Arc::new(crate::memory::SqliteMemory::new(tmp.path()).unwrap());
+        let tool = DelegateTool::new(HashMap::new(), None, parent_security)
+            .with_parent_tools(Arc::new(vec![Arc::new(EchoTool)]))
+            .with_scope(
+                memory.clone(),
+                Arc::new(NativeRuntime::new()),
+                Arc::new(Config::default()),
+            );
+
+        let mut config = agentic_config(vec!["file_write".into()], 5);
+        config.autonomy = Some(AutonomyLevel::ReadOnly);
+        config.workspace = Some("research".into());
+        config.memory = DelegateMemoryMode::Isolated;
+
+        let tools = tool.profile_tools("researcher", &config).unwrap();
+        // Parent tools bound to the parent's policy are not passed through.
+        assert!(!tools.iter().any(|tool| tool.name() == "echo_tool"));
+        assert!(tmp.path().join("research").is_dir());
+
+        let result = find_tool(&tools, "file_write")
+            .execute(json!({"path": "notes.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+
+        // Raising autonomy above the parent's is ignored; the workspace is
+        // still the subdirectory.
+        config.autonomy = Some(AutonomyLevel::Full);
+        let tools = tool.profile_tools("researcher", &config).unwrap();
+        let result = find_tool(&tools, "file_write")
+            .execute(json!({"path": "notes.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(tmp.path().join("research/notes.txt").exists());
+
+        find_tool(&tools, "memory_store")
+            .execute(json!({"key": "lead", "content": "check arxiv"}))
+            .await
+            .unwrap();
+        assert!(memory.get("researcher:lead").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn readonly_delegate_cannot_create_shell_job() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Arc::new(Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        });
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let parent_security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: config.workspace_dir.clone(),
+            ..SecurityPolicy::default()
+        });
+        let memory: Arc<dyn Memory> =
+            Arc::new(crate::memory::SqliteMemory::new(tmp.path()).unwrap());
+        let parent_tools: Vec<Arc<dyn Tool>> = vec![
+            Arc::new(super::super::CronAddTool::new(
+                config.clone(),
+                parent_security.clone(),
+            )),
+            Arc::new(super::super::CronListTool::new(config.clone())),
+        ];
+        let tool = DelegateTool::new(HashMap::new(), None, parent_security)
+            .with_parent_tools(Arc::new(parent_tools))
+            .with_scope(memory, Arc::new(NativeRuntime::new()), config.clone());
+
+        let mut agent = agentic_config(vec!["cron_add".into()], 5);
+        agent.autonomy = Some(AutonomyLevel::ReadOnly);
+        let tools = tool.profile_tools("auditor", &agent).unwrap();
+        assert_eq!(
+            tools
+                .iter()
+                .filter(|tool| tool.name() == "cron_add")
+                .count(),
+            1
+        );
+        assert!(tools.iter().any(|tool| tool.name() == "cron_list"));
+
+        let job = json!({
+            "schedule": {"kind": "cron", "expr": "*/5 * * * *"},
+            "job_type": "shell",
+            "command": "ls"
+        });
+        let result = find_tool(&tools, "cron_add")
+            .execute(job.clone())
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(crate::cron::list_jobs(&config).unwrap().is_empty());
+
+        // The same request succeeds under the parent's policy.
+        let result = find_tool(&tool.parent_tools, "cron_add")
+            .execute(job)
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+    }
+
+    #[test]
+    fn delegate_security_only_narrows_command_allowlist() {
+        let parent = SecurityPolicy::default();
+        let mut config = agentic_config(vec!["shell".into()], 5);
+        config.allowed_commands = Some(vec!["ls".into(), "curl".into()]);
+        let policy = delegate_security(&parent, &config).unwrap();
+        assert_eq!(policy.allowed_commands, vec!["ls".to_string()]);
+        assert_eq!(policy.autonomy, parent.autonomy);
+
+        config.workspace = Some("../outside".into());
+        assert!(delegate_security(&parent, &config).is_err());
+    }
+
+    #[test]
+    fn agents_without_profile_use_parent_tools() {
+        let tool = DelegateTool::new(HashMap::new(), None, test_security())
+            .with_parent_tools(Arc::new(vec![Arc::new(EchoTool)]));
+        let config = agentic_config(vec!["echo_tool".into()], 5);
+        let tools = tool.profile_tools("plain", &config).unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "echo_tool");
+    }
 }
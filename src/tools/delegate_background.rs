@@ -0,0 +1,616 @@
+//! Background delegation handles and the `delegate_status`,
+//! `delegate_result` and `delegate_cancel` tools that inspect them.
+//!
+//! [`DelegateTool`](super::DelegateTool) with `background=true` spawns its
+//! sub-agent run on the tokio runtime and registers it here; the companion
+//! tools share the same [`DelegationRegistry`]. A registry serves every
+//! conversation of a daemon, so each delegation belongs to the session that
+//! started it ([`crate::sessions::current_session_id`]) and is invisible to
+//! the others. Ids are random, not sequential.
+
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Background delegations allowed to run at the same time.
+const MAX_RUNNING_DELEGATIONS: usize = 8;
+/// Finished delegations a session keeps for `delegate_result` before its
+/// oldest is dropped.
+const MAX_FINISHED_DELEGATIONS: usize = 32;
+/// Finished delegations kept across all sessions of the registry.
+const MAX_FINISHED_DELEGATIONS_TOTAL: usize = 256;
+/// Upper bound for `delegate_result`'s `wait_secs`.
+const MAX_WAIT_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegationState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl DelegationState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Point-in-time view of one background delegation.
+#[derive(Debug, Clone)]
+pub struct DelegationStatus {
+    pub id: String,
+    pub label: String,
+    pub state: DelegationState,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub result: Option<ToolResult>,
+}
+
+impl DelegationStatus {
+    fn elapsed_secs(&self) -> i64 {
+        (self.finished_at.unwrap_or_else(Utc::now) - self.started_at).num_seconds()
+    }
+}
+
+type Outcome = Option<(ToolResult, DateTime<Utc>)>;
+
+struct Delegation {
+    /// Session that started the delegation.
+    owner: String,
+    label: String,
+    started_at: DateTime<Utc>,
+    outcome: watch::Receiver<Outcome>,
+    abort: tokio::task::AbortHandle,
+    cancelled_at: Option<DateTime<Utc>>,
+}
+
+impl Delegation {
+    fn status(&self, id: &str) -> DelegationStatus {
+        let outcome = self.outcome.borrow().clone();
+        let (state, finished_at, result) = match (outcome, self.cancelled_at) {
+            (Some((result, at)), _) => {
+                let state = if result.success {
+                    DelegationState::Completed
+                } else {
+                    DelegationState::Failed
+                };
+                (state, Some(at), Some(result))
+            }
+            (None, Some(at)) => (DelegationState::Cancelled, Some(at), None),
+            // The task ended without reporting, i.e. it panicked.
+            (None, None) if self.abort.is_finished() => (
+                DelegationState::Failed,
+                Some(Utc::now()),
+                Some(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Delegation task terminated unexpectedly".into()),
+                }),
+            ),
+            (None, None) => (DelegationState::Running, None, None),
+        };
+        DelegationStatus {
+            id: id.to_string(),
+            label: self.label.clone(),
+            state,
+            started_at: self.started_at,
+            finished_at,
+            result,
+        }
+    }
+}
+
+/// Table of background delegations, scoped per conversation.
+#[derive(Default)]
+pub struct DelegationRegistry {
+    entries: Mutex<HashMap<String, Delegation>>,
+}
+
+/// Session of the calling task; unscoped callers (CLI) share one owner.
+fn current_owner() -> String {
+    crate::sessions::current_session_id().unwrap_or_default()
+}
+
+impl DelegationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `work` on behalf of the current session and return its
+    /// delegation id. `work` keeps running inside that session.
+    pub fn spawn<F>(&self, label: String, work: F) -> Result<String, String>
+    where
+        F: Future<Output = ToolResult> + Send + 'static,
+    {
+        let owner = current_owner();
+        let mut entries = self.entries.lock();
+        let statuses: Vec<DelegationStatus> =
+            entries.iter().map(|(id, entry)| entry.status(id)).collect();
+        let running = statuses
+            .iter()
+            .filter(|status| status.state == DelegationState::Running)
+            .count();
+        if running >= MAX_RUNNING_DELEGATIONS {
+            return Err(format!(
+                "Too many background delegations running ({running}); wait for one to finish or cancel it"
+            ));
+        }
+
+        // Drop the session's oldest finished entries beyond the retention
+        // limit, then the oldest of any session beyond the registry-wide one,
+        // so sessions that never spawn again do not pin their results.
+        let mut finished: Vec<&DelegationStatus> = statuses
+            .iter()
+            .filter(|status| status.state != DelegationState::Running)
+            .collect();
+        finished.sort_by_key(|status| status.finished_at);
+        let mut own = finished
+            .iter()
+            .filter(|status| {
+                entries
+                    .get(&status.id)
+                    .is_some_and(|entry| entry.owner == owner)
+            })
+            .count();
+        let mut total = finished.len();
+        for status in finished {
+            let own_entry = entries
+                .get(&status.id)
+                .is_some_and(|entry| entry.owner == owner);
+            if own_entry && own >= MAX_FINISHED_DELEGATIONS {
+                own -= 1;
+            } else if total < MAX_FINISHED_DELEGATIONS_TOTAL {
+                continue;
+            }
+            entries.remove(&status.id);
+            total -= 1;
+        }
+
+        let id = format!("dlg-{}", uuid::Uuid::new_v4().simple());
+        let (sender, outcome) = watch::channel(None);
+        // The sub-agent keeps the caller's gateway role, if any.
+        let handle = tokio::spawn(crate::security::rbac::propagate(crate::sessions::scope(
+            owner.clone(),
+            async move {
+                let result = work.await;
+                let _ = sender.send(Some((result, Utc::now())));
+            },
+        )));
+        entries.insert(
+            id.clone(),
+            Delegation {
+                owner,
+                label,
+                started_at: Utc::now(),
+                outcome,
+                abort: handle.abort_handle(),
+                cancelled_at: None,
+            },
+        );
+        Ok(id)
+    }
+
+    pub fn status(&self, id: &str) -> Option<DelegationStatus> {
+        let owner = current_owner();
+        self.entries
+            .lock()
+            .get(id)
+            .filter(|entry| entry.owner == owner)
+            .map(|entry| entry.status(id))
+    }
+
+    /// The current session's delegations, oldest first.
+    pub fn list(&self) -> Vec<DelegationStatus> {
+        let owner = current_owner();
+        let mut all: Vec<DelegationStatus> = self
+            .entries
+            .lock()
+            .iter()
+            .filter(|(_, entry)| entry.owner == owner)
+            .map(|(id, entry)| entry.status(id))
+            .collect();
+        all.sort_by_key(|status| status.started_at);
+        all
+    }
+
+    /// Wait up to `timeout` for the delegation to finish, then report it.
+    pub async fn wait(&self, id: &str, timeout: Duration) -> Option<DelegationStatus> {
+        let owner = current_owner();
+        let mut outcome = self
+            .entries
+            .lock()
+            .get(id)
+            .filter(|entry| entry.owner == owner)?
+            .outcome
+            .clone();
+        // A closed channel means the task was cancelled or died; either way
+        // there is nothing more to wait for.
+        let _ = tokio::time::timeout(timeout, outcome.wait_for(Option::is_some)).await;
+        self.status(id)
+    }
+
+    /// Abort a running delegation. `None` for an unknown id, `Err` with the
+    /// final state when it had already finished or been cancelled.
+    pub fn cancel(&self, id: &str) -> Option<Result<(), DelegationState>> {
+        let owner = current_owner();
+        let mut entries = self.entries.lock();
+        let entry = entries.get_mut(id).filter(|entry| entry.owner == owner)?;
+        match entry.status(id).state {
+            DelegationState::Running => {
+                entry.abort.abort();
+                entry.cancelled_at = Some(Utc::now());
+                Some(Ok(()))
+            }
+            state => Some(Err(state)),
+        }
+    }
+}
+
+fn unknown(id: &str) -> ToolResult {
+    ToolResult::err(format!("Unknown delegation '{id}'"))
+}
+
+fn required_id(args: &serde_json::Value) -> anyhow::Result<&str> {
+    args.get("id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))
+}
+
+fn status_line(status: &DelegationStatus) -> String {
+    format!(
+        "{}  {}  {}s  {}",
+        status.id,
+        status.state.as_str(),
+        status.elapsed_secs(),
+        status.label
+    )
+}
+
+/// Report the state of background delegations.
+pub struct DelegateStatusTool {
+    registry: Arc<DelegationRegistry>,
+}
+
+impl DelegateStatusTool {
+    pub fn new(registry: Arc<DelegationRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for DelegateStatusTool {
+    fn name(&self) -> &str {
+        "delegate_status"
+    }
+
+    fn description(&self) -> &str {
+        "Show the state (running, completed, failed, cancelled) of background delegations \
+         started with delegate background=true. Omit id to list all of them."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Delegation id returned by delegate"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let output = match args.get("id").and_then(|v| v.as_str()).map(str::trim) {
+            Some(id) => match self.registry.status(id) {
+                Some(status) => status_line(&status),
+                None => return Ok(unknown(id)),
+            },
+            None => {
+                let all = self.registry.list();
+                if all.is_empty() {
+                    "No background delegations.".to_string()
+                } else {
+                    all.iter().map(status_line).collect::<Vec<_>>().join("\n")
+                }
+            }
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Fetch the result of a background delegation, optionally waiting for it.
+pub struct DelegateResultTool {
+    registry: Arc<DelegationRegistry>,
+}
+
+impl DelegateResultTool {
+    pub fn new(registry: Arc<DelegationRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for DelegateResultTool {
+    fn name(&self) -> &str {
+        "delegate_result"
+    }
+
+    fn description(&self) -> &str {
+        "Get the result of a background delegation. Set wait_secs to wait for a running \
+         delegation to finish (max 300)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Delegation id returned by delegate"
+                },
+                "wait_secs": {
+                    "type": "integer",
+                    "description": "Seconds to wait for completion before returning (default 0, max 300)",
+                    "default": 0
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = required_id(&args)?;
+        let wait = args
+            .get("wait_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0)
+            .min(MAX_WAIT_SECS);
+
+        let Some(status) = self.registry.wait(id, Duration::from_secs(wait)).await else {
+            return Ok(unknown(id));
+        };
+        Ok(match (status.state, status.result) {
+            (DelegationState::Running, _) => ToolResult {
+                success: true,
+                output: format!(
+                    "Delegation {id} is still running ({}s elapsed); check again later.",
+                    (Utc::now() - status.started_at).num_seconds()
+                ),
+                error: None,
+            },
+            (DelegationState::Cancelled, _) => {
+                ToolResult::err(format!("Delegation {id} was cancelled"))
+            }
+            (_, Some(result)) => result,
+            (_, None) => ToolResult::err(format!("Delegation {id} produced no result")),
+        })
+    }
+}
+
+/// Cancel a running background delegation.
+pub struct DelegateCancelTool {
+    registry: Arc<DelegationRegistry>,
+}
+
+impl DelegateCancelTool {
+    pub fn new(registry: Arc<DelegationRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl Tool for DelegateCancelTool {
+    fn name(&self) -> &str {
+        "delegate_cancel"
+    }
+
+    fn description(&self) -> &str {
+        "Cancel a running background delegation by id."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Delegation id returned by delegate"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = required_id(&args)?;
+        Ok(match self.registry.cancel(id) {
+            None => unknown(id),
+            Some(Ok(())) => ToolResult {
+                success: true,
+                output: format!("Cancelled delegation {id}"),
+                error: None,
+            },
+            Some(Err(state)) => ToolResult::err(format!(
+                "Delegation {id} already finished ({})",
+                state.as_str()
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(output: &str) -> ToolResult {
+        ToolResult {
+            success: true,
+            output: output.into(),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn result_waits_for_completion() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let id = registry
+            .spawn("researcher: look".into(), async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                ok("found it")
+            })
+            .unwrap();
+        assert!(id.starts_with("dlg-"));
+
+        let status = DelegateStatusTool::new(registry.clone());
+        let listed = status.execute(json!({})).await.unwrap();
+        assert!(listed.output.contains(&format!("{id}  running")));
+
+        let result = DelegateResultTool::new(registry.clone())
+            .execute(json!({"id": id, "wait_secs": 5}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "found it");
+        assert_eq!(
+            registry.status(&id).unwrap().state,
+            DelegationState::Completed
+        );
+    }
+
+    #[tokio::test]
+    async fn result_reports_running_without_wait() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let id = registry
+            .spawn("slow".into(), async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                ok("late")
+            })
+            .unwrap();
+        let result = DelegateResultTool::new(registry)
+            .execute(json!({"id": id}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("still running"));
+    }
+
+    #[tokio::test]
+    async fn cancel_stops_running_delegation() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let id = registry
+            .spawn("slow".into(), async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                ok("late")
+            })
+            .unwrap();
+        let cancel = DelegateCancelTool::new(registry.clone());
+        let result = cancel.execute(json!({"id": id})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let again = cancel.execute(json!({"id": id})).await.unwrap();
+        assert!(again.error.unwrap().contains("already finished"));
+
+        let result = DelegateResultTool::new(registry)
+            .execute(json!({"id": id, "wait_secs": 1}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn unknown_ids_and_running_limit() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let result = DelegateStatusTool::new(registry.clone())
+            .execute(json!({"id": "dlg-9"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Unknown delegation"));
+        assert!(DelegateResultTool::new(registry.clone())
+            .execute(json!({}))
+            .await
+            .is_err());
+
+        for _ in 0..MAX_RUNNING_DELEGATIONS {
+            registry
+                .spawn("slow".into(), async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    ok("late")
+                })
+                .unwrap();
+        }
+        let err = registry
+            .spawn("one too many".into(), async { ok("x") })
+            .unwrap_err();
+        assert!(err.contains("Too many background delegations"));
+    }
+
+    #[tokio::test]
+    async fn delegations_are_private_to_their_session() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let id = crate::sessions::scope("alice".into(), async {
+            registry.spawn("slow".into(), async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                ok("late")
+            })
+        })
+        .await
+        .unwrap();
+        let other = registry.spawn("mine".into(), async { ok("x") }).unwrap();
+        assert_ne!(id, other);
+
+        crate::sessions::scope("bob".into(), async {
+            assert!(registry.list().is_empty());
+            assert!(registry.status(&id).is_none());
+            assert!(registry.cancel(&id).is_none());
+            assert!(registry.wait(&id, Duration::ZERO).await.is_none());
+        })
+        .await;
+
+        crate::sessions::scope("alice".into(), async {
+            let listed = registry.list();
+            assert_eq!(listed.len(), 1);
+            assert_eq!(listed[0].id, id);
+            assert_eq!(registry.cancel(&id), Some(Ok(())));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn finished_delegations_are_bounded_across_sessions() {
+        let registry = Arc::new(DelegationRegistry::new());
+        let mut ids = Vec::new();
+        for n in 0..MAX_FINISHED_DELEGATIONS_TOTAL + 10 {
+            let id = crate::sessions::scope(format!("session-{n}"), async {
+                let id = registry.spawn("quick".into(), async { ok("x") }).unwrap();
+                registry.wait(&id, Duration::from_secs(5)).await.unwrap();
+                id
+            })
+            .await;
+            ids.push(id);
+        }
+
+        let entries = registry.entries.lock();
+        assert_eq!(entries.len(), MAX_FINISHED_DELEGATIONS_TOTAL);
+        assert!(!entries.contains_key(&ids[0]));
+        assert!(entries.contains_key(ids.last().unwrap()));
+    }
+}
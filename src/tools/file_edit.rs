@@ -0,0 +1,952 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+/// Undo entries kept per file.
+const MAX_UNDO_DEPTH: usize = 20;
+/// Context lines around each hunk of the returned diff.
+const DIFF_CONTEXT: usize = 3;
+/// Above this many line comparisons the diff falls back to one replace block.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// One applied edit: the file content before and after it.
+struct UndoEntry {
+    before: String,
+    after: String,
+}
+
+/// Edit existing files in place with path sandboxing.
+///
+/// Supports exact search/replace, line-range replacement and unified-diff
+/// patches. Every edit returns a compact diff and can be reverted with
+/// `undo` from the same conversation ([`crate::sessions::current_session_id`])
+/// for as long as the tool instance lives.
+pub struct FileEditTool {
+    security: Arc<SecurityPolicy>,
+    /// Undo stacks keyed by session id and resolved file path.
+    history: Mutex<HashMap<(String, PathBuf), Vec<UndoEntry>>>,
+}
+
+impl FileEditTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn push_undo(&self, key: (String, PathBuf), entry: UndoEntry) {
+        let mut history = self.history.lock();
+        let stack = history.entry(key).or_default();
+        stack.push(entry);
+        if stack.len() > MAX_UNDO_DEPTH {
+            stack.remove(0);
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FileEditTool {
+    fn name(&self) -> &str {
+        "file_edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit an existing file in the workspace. Provide exactly one of: old_string/new_string \
+         (exact replacement; old_string must be unique unless replace_all is true), \
+         start_line/end_line/content (replace a 1-based inclusive line range), \
+         patch (a unified diff; context is matched fuzzily), or undo=true to revert the last edit. \
+         Returns a diff of what changed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                },
+                "old_string": {
+                    "type": "string",
+                    "description": "Exact text to replace"
+                },
+                "new_string": {
+                    "type": "string",
+                    "description": "Replacement text for old_string"
+                },
+                "replace_all": {
+                    "type": "boolean",
+                    "description": "Replace every occurrence of old_string instead of requiring a unique match",
+                    "default": false
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line (1-based) of the range to replace"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line (inclusive) of the range to replace; use start_line - 1 to insert before start_line"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Replacement lines for the start_line..end_line range"
+                },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff to apply to the file"
+                },
+                "undo": {
+                    "type": "boolean",
+                    "description": "Revert the most recent edit made to this file in this conversation"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let operation = EditOperation::from_args(&args)?;
+
+        if !self.security.can_act() {
+            return Ok(ToolResult::err("Action blocked: autonomy is read-only"));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult::err(format!(
+                "Path not allowed by security policy: {path}"
+            )));
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        // Refuse to edit through a symlink, even one pointing inside the workspace.
+        match tokio::fs::symlink_metadata(&full_path).await {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                return Ok(ToolResult::err(format!(
+                    "Refusing to edit through symlink: {}",
+                    full_path.display()
+                )));
+            }
+            Ok(meta) if !meta.is_file() => {
+                return Ok(ToolResult::err(format!("Not a regular file: {path}")));
+            }
+            Ok(meta) if meta.len() > MAX_FILE_SIZE_BYTES => {
+                return Ok(ToolResult::err(format!(
+                    "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
+                    meta.len()
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => return Ok(ToolResult::err(format!("Failed to read file: {e}"))),
+        }
+
+        let resolved = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(ToolResult::err(format!("Failed to resolve file path: {e}"))),
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(ToolResult::err(format!(
+                "Resolved path escapes workspace: {}",
+                resolved.display()
+            )));
+        }
+
+        let current = match tokio::fs::read_to_string(&resolved).await {
+            Ok(content) => content,
+            Err(e) => return Ok(ToolResult::err(format!("Failed to read file: {e}"))),
+        };
+
+        let key = (
+            crate::sessions::current_session_id().unwrap_or_default(),
+            resolved,
+        );
+        let updated = match &operation {
+            EditOperation::Undo => {
+                let history = self.history.lock();
+                match history.get(&key).and_then(|stack| stack.last()) {
+                    None => return Ok(ToolResult::err(format!("No edits to undo for {path}"))),
+                    Some(entry) if entry.after != current => {
+                        return Ok(ToolResult::err(format!(
+                            "Cannot undo: {path} changed since the last edit"
+                        )));
+                    }
+                    Some(entry) => entry.before.clone(),
+                }
+            }
+            operation => match operation.apply(&current) {
+                Ok(updated) => updated,
+                Err(e) => return Ok(ToolResult::err(e)),
+            },
+        };
+
+        if updated == current {
+            return Ok(ToolResult::err("Edit produced no changes"));
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        if let Err(e) = tokio::fs::write(&key.1, &updated).await {
+            return Ok(ToolResult::err(format!("Failed to write file: {e}")));
+        }
+
+        let summary = if matches!(operation, EditOperation::Undo) {
+            if let Some(stack) = self.history.lock().get_mut(&key) {
+                stack.pop();
+            }
+            format!("Reverted last edit to {path}")
+        } else {
+            self.push_undo(
+                key,
+                UndoEntry {
+                    before: current.clone(),
+                    after: updated.clone(),
+                },
+            );
+            format!("Edited {path}")
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("{summary}\n{}", unified_diff(path, &current, &updated)),
+            error: None,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum EditOperation {
+    Replace {
+        old: String,
+        new: String,
+        all: bool,
+    },
+    Lines {
+        start: usize,
+        end: usize,
+        content: String,
+    },
+    Patch(String),
+    Undo,
+}
+
+impl EditOperation {
+    fn from_args(args: &serde_json::Value) -> anyhow::Result<Self> {
+        let string = |key: &str| args.get(key).and_then(|v| v.as_str());
+        let line = |key: &str| -> anyhow::Result<Option<usize>> {
+            match args.get(key) {
+                None | Some(serde_json::Value::Null) => Ok(None),
+                Some(value) => value
+                    .as_u64()
+                    .and_then(|n| usize::try_from(n).ok())
+                    .map(Some)
+                    .ok_or_else(|| anyhow::anyhow!("'{key}' must be a non-negative integer")),
+            }
+        };
+
+        let mut operations = Vec::new();
+        if args.get("undo").and_then(|v| v.as_bool()) == Some(true) {
+            operations.push(Self::Undo);
+        }
+        if let Some(old) = string("old_string") {
+            let new = string("new_string")
+                .ok_or_else(|| anyhow::anyhow!("Missing 'new_string' parameter"))?;
+            operations.push(Self::Replace {
+                old: old.to_string(),
+                new: new.to_string(),
+                all: args
+                    .get("replace_all")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            });
+        }
+        if let Some(start) = line("start_line")? {
+            let end =
+                line("end_line")?.ok_or_else(|| anyhow::anyhow!("Missing 'end_line' parameter"))?;
+            let content =
+                string("content").ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
+            operations.push(Self::Lines {
+                start,
+                end,
+                content: content.to_string(),
+            });
+        }
+        if let Some(patch) = string("patch") {
+            operations.push(Self::Patch(patch.to_string()));
+        }
+
+        match operations.len() {
+            1 => Ok(operations.remove(0)),
+            0 => anyhow::bail!(
+                "Missing edit: provide old_string/new_string, start_line/end_line/content, patch, or undo"
+            ),
+            _ => anyhow::bail!("Provide exactly one edit mode per call"),
+        }
+    }
+
+    fn apply(&self, current: &str) -> Result<String, String> {
+        match self {
+            Self::Replace { old, new, all } => replace_string(current, old, new, *all),
+            Self::Lines {
+                start,
+                end,
+                content,
+            } => replace_lines(current, *start, *end, content),
+            Self::Patch(patch) => apply_patch(current, patch),
+            Self::Undo => Ok(current.to_string()),
+        }
+    }
+}
+
+fn replace_string(current: &str, old: &str, new: &str, all: bool) -> Result<String, String> {
+    if old.is_empty() {
+        return Err("old_string must not be empty".into());
+    }
+    if old == new {
+        return Err("old_string and new_string are identical".into());
+    }
+    match current.matches(old).count() {
+        0 => Err("old_string not found in file".into()),
+        1 => Ok(current.replacen(old, new, 1)),
+        _ if all => Ok(current.replace(old, new)),
+        n => Err(format!(
+            "old_string matches {n} times; include more surrounding context or set replace_all"
+        )),
+    }
+}
+
+/// A file split into lines, remembering its line ending and final newline so
+/// edits round-trip the file's formatting.
+struct Lines<'a> {
+    lines: Vec<&'a str>,
+    eol: &'static str,
+    trailing_newline: bool,
+}
+
+impl<'a> Lines<'a> {
+    fn split(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().collect(),
+            eol: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: content.ends_with('\n'),
+        }
+    }
+
+    fn join(&self, lines: &[&str]) -> String {
+        let mut out = lines.join(self.eol);
+        if !lines.is_empty() && (self.trailing_newline || self.lines.is_empty()) {
+            out.push_str(self.eol);
+        }
+        out
+    }
+}
+
+fn replace_lines(current: &str, start: usize, end: usize, content: &str) -> Result<String, String> {
+    let file = Lines::split(current);
+    let total = file.lines.len();
+    if start == 0 || start > total + 1 || end + 1 < start || end > total {
+        return Err(format!(
+            "Invalid line range {start}..{end}: file has {total} lines (ranges are 1-based and inclusive)"
+        ));
+    }
+    let mut lines = file.lines[..start - 1].to_vec();
+    lines.extend(content.lines());
+    lines.extend_from_slice(&file.lines[end..]);
+    Ok(file.join(&lines))
+}
+
+#[derive(Debug, Default)]
+struct Hunk<'a> {
+    /// 1-based start line from the `@@ -l,s` header.
+    old_start: usize,
+    old: Vec<&'a str>,
+    new: Vec<&'a str>,
+}
+
+fn parse_patch(patch: &str) -> Result<Vec<Hunk<'_>>, String> {
+    let mut hunks: Vec<Hunk<'_>> = Vec::new();
+    // Old/new lines the current hunk header still promises. While any are
+    // outstanding, `--- x` is a removed line rather than the next file header.
+    let mut remaining = (0usize, 0usize);
+    for raw in patch.lines() {
+        if let Some(header) = raw.strip_prefix("@@") {
+            let (old_start, old_count, new_count) =
+                parse_hunk_header(header).ok_or_else(|| format!("Invalid hunk header: {raw}"))?;
+            hunks.push(Hunk {
+                old_start,
+                ..Hunk::default()
+            });
+            remaining = (old_count, new_count);
+            continue;
+        }
+        if remaining == (0, 0)
+            && (raw.starts_with("diff ") || raw.starts_with("--- ") || raw.starts_with("+++ "))
+        {
+            if !hunks.is_empty() {
+                return Err(
+                    "Patch touches more than one file; send one file's patch per call".into(),
+                );
+            }
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            // Other preamble (`index ...`, commit messages) before the first hunk.
+            continue;
+        };
+        if remaining == (0, 0) {
+            // The hunk is complete; trailing blank lines or chatter before the
+            // next `@@` are not part of it.
+            continue;
+        }
+        if let Some(line) = raw.strip_prefix('+') {
+            hunk.new.push(line);
+            remaining.1 = remaining.1.saturating_sub(1);
+        } else if let Some(line) = raw.strip_prefix('-') {
+            hunk.old.push(line);
+            remaining.0 = remaining.0.saturating_sub(1);
+        } else if raw.starts_with('\\') {
+            // "\ No newline at end of file"
+        } else {
+            // Context; tolerate a missing leading space on blank lines.
+            let line = raw.strip_prefix(' ').unwrap_or(raw);
+            hunk.old.push(line);
+            hunk.new.push(line);
+            remaining = (remaining.0.saturating_sub(1), remaining.1.saturating_sub(1));
+        }
+    }
+    if hunks.is_empty() {
+        return Err("Patch contains no hunks".into());
+    }
+    Ok(hunks)
+}
+
+/// Parse the part of a hunk header after `@@`: ` -l,s +l,s @@`. Omitted
+/// counts default to 1, as in `diff -u`.
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize)> {
+    let mut ranges = header.split_whitespace();
+    let range = |part: &str| -> Option<(usize, usize)> {
+        match part.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((part.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(ranges.next()?.strip_prefix('-')?)?;
+    let new_count = ranges
+        .next()
+        .and_then(|part| part.strip_prefix('+'))
+        .and_then(range)
+        .map_or(old_count, |(_, count)| count);
+    Some((old_start, old_count, new_count))
+}
+
+/// Find `needle` in `haystack` at or after `from`, preferring the position
+/// closest to `hint`. Tries an exact match first, then ignores trailing and
+/// finally surrounding whitespace.
+fn locate(haystack: &[&str], needle: &[&str], from: usize, hint: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(hint.clamp(from, haystack.len()));
+    }
+    if haystack.len() < needle.len() {
+        return None;
+    }
+    let last = haystack.len() - needle.len();
+    if from > last {
+        return None;
+    }
+    let comparators: [fn(&str, &str) -> bool; 3] = [
+        |a, b| a == b,
+        |a, b| a.trim_end() == b.trim_end(),
+        |a, b| a.trim() == b.trim(),
+    ];
+    let hint = hint.clamp(from, last);
+    for matches in comparators {
+        let fits = |pos: usize| {
+            haystack[pos..pos + needle.len()]
+                .iter()
+                .zip(needle)
+                .all(|(a, b)| matches(a, b))
+        };
+        for distance in 0..=(last - from).max(hint - from) {
+            if hint + distance <= last && fits(hint + distance) {
+                return Some(hint + distance);
+            }
+            if distance > 0 && distance <= hint - from && fits(hint - distance) {
+                return Some(hint - distance);
+            }
+        }
+    }
+    None
+}
+
+fn apply_patch(current: &str, patch: &str) -> Result<String, String> {
+    let hunks = parse_patch(patch)?;
+    let file = Lines::split(current);
+    let mut lines: Vec<&str> = Vec::with_capacity(file.lines.len());
+    let mut cursor = 0;
+    // Line shift between the patch's numbering and where hunks actually matched.
+    let mut offset: isize = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let expected = hunk
+            .old_start
+            .saturating_sub(1)
+            .saturating_add_signed(offset);
+        let pos = locate(&file.lines, &hunk.old, cursor, expected).ok_or_else(|| {
+            format!(
+                "Hunk {} (@@ -{}) does not match the file content",
+                index + 1,
+                hunk.old_start
+            )
+        })?;
+        offset += pos as isize - expected as isize;
+        lines.extend_from_slice(&file.lines[cursor..pos]);
+        lines.extend_from_slice(&hunk.new);
+        cursor = pos + hunk.old.len();
+    }
+    lines.extend_from_slice(&file.lines[cursor..]);
+    Ok(file.join(&lines))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line diff of `old` against `new`: common prefix and suffix are matched
+/// directly, the middle with an LCS table when it is small enough.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<(DiffOp, usize, usize)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    // Each op carries the old and new line index it refers to.
+    let mut ops: Vec<(DiffOp, usize, usize)> = (0..prefix).map(|i| (DiffOp::Equal, i, i)).collect();
+    if a.len().saturating_mul(b.len()) <= MAX_DIFF_CELLS {
+        let mut table = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                table[i][j] = if a[i] == b[j] {
+                    table[i + 1][j + 1] + 1
+                } else {
+                    table[i + 1][j].max(table[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                ops.push((DiffOp::Equal, prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < a.len() && (j == b.len() || table[i + 1][j] >= table[i][j + 1]) {
+                ops.push((DiffOp::Delete, prefix + i, prefix + j));
+                i += 1;
+            } else {
+                ops.push((DiffOp::Insert, prefix + i, prefix + j));
+                j += 1;
+            }
+        }
+    } else {
+        ops.extend((0..a.len()).map(|i| (DiffOp::Delete, prefix + i, prefix)));
+        ops.extend((0..b.len()).map(|j| (DiffOp::Insert, prefix + a.len(), prefix + j)));
+    }
+    ops.extend((0..suffix).map(|k| {
+        (
+            DiffOp::Equal,
+            old.len() - suffix + k,
+            new.len() - suffix + k,
+        )
+    }));
+    ops
+}
+
+/// Render a unified diff with [`DIFF_CONTEXT`] lines of context per hunk.
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != DiffOp::Equal)
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        // Only line endings or the final newline changed.
+        out.push_str("(whitespace-only change)\n");
+        return out;
+    }
+
+    let mut group_start = 0;
+    while group_start < changes.len() {
+        // Merge changes whose context windows touch.
+        let mut group_end = group_start;
+        while group_end + 1 < changes.len()
+            && changes[group_end + 1] - changes[group_end] <= 2 * DIFF_CONTEXT + 1
+        {
+            group_end += 1;
+        }
+        let from = changes[group_start].saturating_sub(DIFF_CONTEXT);
+        let to = (changes[group_end] + DIFF_CONTEXT + 1).min(ops.len());
+        let hunk = &ops[from..to];
+
+        let old_count = hunk.iter().filter(|op| op.0 != DiffOp::Insert).count();
+        let new_count = hunk.iter().filter(|op| op.0 != DiffOp::Delete).count();
+        let (_, old_at, new_at) = hunk[0];
+        let _ = writeln!(
+            out,
+            "@@ -{},{old_count} +{},{new_count} @@",
+            old_at + usize::from(old_count > 0),
+            new_at + usize::from(new_count > 0)
+        );
+        for (op, i, j) in hunk {
+            let _ = match op {
+                DiffOp::Equal => writeln!(out, " {}", old_lines[*i]),
+                DiffOp::Delete => writeln!(out, "-{}", old_lines[*i]),
+                DiffOp::Insert => writeln!(out, "+{}", new_lines[*j]),
+            };
+        }
+        group_start = group_end + 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    async fn setup(name: &str, content: &str) -> (tempfile::TempDir, FileEditTool) {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join(name), content)
+            .await
+            .unwrap();
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()));
+        (dir, tool)
+    }
+
+    #[test]
+    fn file_edit_name_and_schema() {
+        let tool = FileEditTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "file_edit");
+        let schema = tool.parameters_schema();
+        for key in ["path", "old_string", "start_line", "patch", "undo"] {
+            assert!(schema["properties"][key].is_object(), "missing {key}");
+        }
+        assert_eq!(schema["required"], json!(["path"]));
+    }
+
+    #[tokio::test]
+    async fn replace_requires_unique_match_unless_replace_all() {
+        let (dir, tool) = setup("a.txt", "foo\nbar\nfoo\n").await;
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "old_string": "foo", "new_string": "baz"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("matches 2 times"));
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "old_string": "foo", "new_string": "baz", "replace_all": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("-foo\n+baz"));
+        let content = tokio::fs::read_to_string(dir.path().join("a.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "baz\nbar\nbaz\n");
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "old_string": "missing", "new_string": "x"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn line_range_replaces_inserts_and_deletes() {
+        let (dir, tool) = setup("a.txt", "one\ntwo\nthree\n").await;
+        let read = || tokio::fs::read_to_string(dir.path().join("a.txt"));
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "start_line": 2, "end_line": 2, "content": "TWO\n2b"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(read().await.unwrap(), "one\nTWO\n2b\nthree\n");
+
+        tool.execute(json!({"path": "a.txt", "start_line": 1, "end_line": 0, "content": "zero"}))
+            .await
+            .unwrap();
+        assert_eq!(read().await.unwrap(), "zero\none\nTWO\n2b\nthree\n");
+
+        tool.execute(json!({"path": "a.txt", "start_line": 3, "end_line": 4, "content": ""}))
+            .await
+            .unwrap();
+        assert_eq!(read().await.unwrap(), "zero\none\nthree\n");
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "start_line": 3, "end_line": 9, "content": "x"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Invalid line range"));
+    }
+
+    #[tokio::test]
+    async fn patch_applies_with_offset_and_whitespace_fuzz() {
+        let (dir, tool) = setup(
+            "main.rs",
+            "// header\n// more\nfn main() {\n    let x = 1;   \n    println!(\"{x}\");\n}\n",
+        )
+        .await;
+        // Line numbers are off by two and the context lacks trailing spaces.
+        let patch = "--- a/main.rs\n+++ b/main.rs\n@@ -1,4 +1,4 @@\n fn main() {\n-    let x = 1;\n+    let x = 2;\n     println!(\"{x}\");\n }\n";
+        let result = tool
+            .execute(json!({"path": "main.rs", "patch": patch}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let content = tokio::fs::read_to_string(dir.path().join("main.rs"))
+            .await
+            .unwrap();
+        assert_eq!(
+            content,
+            "// header\n// more\nfn main() {\n    let x = 2;\n    println!(\"{x}\");\n}\n"
+        );
+
+        let bad = "@@ -1,2 +1,2 @@\n-nothing like this\n+x\n";
+        let result = tool
+            .execute(json!({"path": "main.rs", "patch": bad}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("does not match"));
+    }
+
+    #[tokio::test]
+    async fn patch_ignores_lines_after_a_complete_hunk() {
+        let (dir, tool) = setup("a.txt", "one\ntwo\nthree\n").await;
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n one\n-two\n+2\n\n";
+        let result = tool
+            .execute(json!({"path": "a.txt", "patch": patch}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("a.txt"))
+                .await
+                .unwrap(),
+            "one\n2\nthree\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_reverts_edits_in_order_and_detects_external_changes() {
+        let (dir, tool) = setup("a.txt", "v1\n").await;
+        let path = dir.path().join("a.txt");
+
+        for (old, new) in [("v1", "v2"), ("v2", "v3")] {
+            let result = tool
+                .execute(json!({"path": "a.txt", "old_string": old, "new_string": new}))
+                .await
+                .unwrap();
+            assert!(result.success);
+        }
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "undo": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "v2\n");
+
+        tokio::fs::write(&path, "edited elsewhere\n").await.unwrap();
+        let result = tool
+            .execute(json!({"path": "a.txt", "undo": true}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("changed since"));
+
+        tokio::fs::write(&path, "v2\n").await.unwrap();
+        tool.execute(json!({"path": "a.txt", "undo": true}))
+            .await
+            .unwrap();
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "v1\n");
+        let result = tool
+            .execute(json!({"path": "a.txt", "undo": true}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("No edits to undo"));
+    }
+
+    #[tokio::test]
+    async fn patch_rejects_second_file_but_keeps_removed_dash_lines() {
+        let (dir, tool) = setup("q.sql", "-- old comment\nselect 1;\n").await;
+        let multi = "--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1,2 @@\n--- old comment\n+-- new comment\n select 1;\n--- a/other.sql\n+++ b/other.sql\n@@ -1 +1 @@\n-x\n+y\n";
+        let result = tool
+            .execute(json!({"path": "q.sql", "patch": multi}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("more than one file"));
+
+        let single = multi.split("--- a/other.sql").next().unwrap();
+        let result = tool
+            .execute(json!({"path": "q.sql", "patch": single}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("q.sql"))
+                .await
+                .unwrap(),
+            "-- new comment\nselect 1;\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_history_is_per_session() {
+        let (dir, tool) = setup("a.txt", "v1\n").await;
+        let edit = json!({"path": "a.txt", "old_string": "v1", "new_string": "v2"});
+        let undo = json!({"path": "a.txt", "undo": true});
+
+        let result = crate::sessions::scope("alice".into(), tool.execute(edit))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let result = crate::sessions::scope("bob".into(), tool.execute(undo.clone()))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("No edits to undo"));
+
+        let result = crate::sessions::scope("alice".into(), tool.execute(undo))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            tokio::fs::read_to_string(dir.path().join("a.txt"))
+                .await
+                .unwrap(),
+            "v1\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn file_edit_rejects_ambiguous_or_missing_modes() {
+        let (_dir, tool) = setup("a.txt", "x\n").await;
+        assert!(tool.execute(json!({"path": "a.txt"})).await.is_err());
+        assert!(tool
+            .execute(json!({"path": "a.txt", "old_string": "x", "new_string": "y", "patch": "@@ -1 +1 @@"}))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn file_edit_blocks_path_traversal_and_readonly() {
+        let (dir, tool) = setup("a.txt", "x\n").await;
+        let result = tool
+            .execute(json!({"path": "../../etc/passwd", "old_string": "x", "new_string": "y"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let readonly = FileEditTool::new(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: dir.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }));
+        let result = readonly
+            .execute(json!({"path": "a.txt", "old_string": "x", "new_string": "y"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_edit_refuses_symlinks() {
+        let root = tempfile::tempdir().unwrap();
+        let workspace = root.path().join("workspace");
+        tokio::fs::create_dir_all(&workspace).await.unwrap();
+        let outside = root.path().join("outside.txt");
+        tokio::fs::write(&outside, "secret\n").await.unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.join("link.txt")).unwrap();
+
+        let tool = FileEditTool::new(test_security(workspace));
+        let result = tool
+            .execute(json!({"path": "link.txt", "old_string": "secret", "new_string": "pwned"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("symlink"));
+        assert_eq!(
+            tokio::fs::read_to_string(&outside).await.unwrap(),
+            "secret\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_reports_separate_hunks() {
+        let old = (1..=20)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+        let diff = unified_diff("f.txt", &old, &new);
+        assert!(diff.starts_with("--- a/f.txt\n+++ b/f.txt\n"));
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n"));
+        assert!(diff.contains("@@ -15,6 +15,5 @@"));
+        assert!(diff.contains("-line 18\n"));
+    }
+}
@@ -58,6 +58,22 @@ impl Tool for MemoryForgetTool {
             });
         }
 
+        if let Some(role) = crate::security::rbac::current() {
+            if let Ok(Some(entry)) = self.memory.get(key).await {
+                let category = entry.category.to_string();
+                if !role.permissions.allows_memory_category(&category) {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(role.deny(
+                            "memory_forget",
+                            &format!("memory category '{category}' is not allowed"),
+                        )),
+                    });
+                }
+            }
+        }
+
         match self.memory.forget(key).await {
             Ok(true) => Ok(ToolResult {
                 success: true,
@@ -55,7 +55,22 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        let role = crate::security::rbac::current();
+        let recalled = self
+            .memory
+            .recall(query, limit, None)
+            .await
+            .map(|mut entries| {
+                if let Some(role) = &role {
+                    entries.retain(|entry| {
+                        role.permissions
+                            .allows_memory_category(&entry.category.to_string())
+                    });
+                }
+                entries
+            });
+
+        match recalled {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -5,9 +5,9 @@
 //! which requires a name, description, JSON parameter schema, and an async
 //! `execute` method returning a structured [`ToolResult`].
 //!
-//! Tools are assembled into registries by [`default_tools`] (shell, file read/write)
-//! and [`all_tools`] (full set including memory, browser, cron, HTTP, delegation,
-//! and optional integrations). Security policy enforcement is injected via
+//! Tools are assembled into registries by [`default_tools`] (shell, file
+//! read/write/edit, glob and content search) and [`all_tools`] (full set
+//! including memory, browser, cron, HTTP, delegation, and optional integrations). Security policy enforcement is injected via
 //! [`SecurityPolicy`](crate::security::SecurityPolicy) at construction time.
 //!
 //! # Extension
@@ -18,6 +18,7 @@
 pub mod browser;
 pub mod browser_open;
 pub mod composio;
+pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -25,6 +26,8 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod delegate_background;
+pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
@@ -43,6 +46,7 @@ pub mod pushover;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod send_message;
 pub mod shell;
 pub mod traits;
 pub mod web_search_tool;
@@ -50,6 +54,7 @@ pub mod web_search_tool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use composio::ComposioTool;
+pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -57,6 +62,10 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use delegate_background::{
+    DelegateCancelTool, DelegateResultTool, DelegateStatusTool, DelegationRegistry,
+};
+pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
@@ -77,6 +86,7 @@ pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use schedule::ScheduleTool;
 pub use screenshot::ScreenshotTool;
+pub use send_message::SendMessageTool;
 pub use shell::ShellTool;
 pub use traits::Tool;
 pub use traits::{ToolResult, ToolSpec};
@@ -162,10 +172,68 @@ pub fn default_tools_with_runtime(
         Box::new(ShellTool::new(security.clone(), runtime)),
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
-        Box::new(GlobSearchTool::new(security)),
+        Box::new(FileEditTool::new(security.clone())),
+        Box::new(GlobSearchTool::new(security.clone())),
+        Box::new(ContentSearchTool::new(security)),
     ]
 }
 
+/// Parent tools that carry no security policy of their own, so a delegate
+/// with its own profile may use the parent's instances unchanged.
+pub(crate) const POLICY_FREE_TOOLS: &[&str] = &["cron_list", "cron_runs", "web_search"];
+
+/// Create the tools bound to a security policy and memory backend.
+///
+/// Used to give delegate agents with their own profile (autonomy, command
+/// allowlist, workspace, memory view) tools that enforce it. With `config`,
+/// the cron and schedule tools are rebuilt under the same policy.
+pub(crate) fn scoped_tools(
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    memory: Option<Arc<dyn Memory>>,
+    config: Option<&Arc<Config>>,
+) -> Vec<Arc<dyn Tool>> {
+    let mut tools: Vec<Arc<dyn Tool>> = vec![
+        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(FileReadTool::new(security.clone())),
+        Arc::new(FileWriteTool::new(security.clone())),
+        Arc::new(FileEditTool::new(security.clone())),
+        Arc::new(GlobSearchTool::new(security.clone())),
+        Arc::new(ContentSearchTool::new(security.clone())),
+        Arc::new(GitOperationsTool::new(
+            security.clone(),
+            security.workspace_dir.clone(),
+        )),
+        Arc::new(PdfReadTool::new(security.clone())),
+        Arc::new(ImageInfoTool::new(security.clone())),
+    ];
+    if let Some(memory) = memory {
+        tools.push(Arc::new(MemoryStoreTool::new(
+            memory.clone(),
+            security.clone(),
+        )));
+        tools.push(Arc::new(MemoryRecallTool::new(memory.clone())));
+        tools.push(Arc::new(MemoryForgetTool::new(memory, security.clone())));
+    }
+    if let Some(config) = config {
+        tools.push(Arc::new(CronAddTool::new(config.clone(), security.clone())));
+        tools.push(Arc::new(CronRemoveTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+        tools.push(Arc::new(CronUpdateTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+        tools.push(Arc::new(CronRunTool::new(config.clone(), security.clone())));
+        tools.push(Arc::new(ScheduleTool::new(
+            security.clone(),
+            config.as_ref().clone(),
+        )));
+    }
+    tools
+}
+
 /// Create full tool registry including memory tools and optional Composio
 #[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
 pub fn all_tools(
@@ -214,10 +282,12 @@ pub fn all_tools_with_runtime(
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(ShellTool::new(security.clone(), runtime.clone())),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
+        Arc::new(FileEditTool::new(security.clone())),
         Arc::new(GlobSearchTool::new(security.clone())),
+        Arc::new(ContentSearchTool::new(security.clone())),
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
         Arc::new(CronListTool::new(config.clone())),
         Arc::new(CronRemoveTool::new(config.clone(), security.clone())),
@@ -226,7 +296,7 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ProxyConfigTool::new(config.clone(), security.clone())),
         Arc::new(GitOperationsTool::new(
@@ -275,6 +345,13 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    if root_config.send_message.enabled {
+        tool_arcs.push(Arc::new(SendMessageTool::running(
+            config.clone(),
+            security.clone(),
+        )));
+    }
+
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
         tool_arcs.push(Arc::new(WebSearchTool::new(
@@ -325,11 +402,19 @@ pub fn all_tools_with_runtime(
                     .map(std::path::PathBuf::from),
                 secrets_encrypt: root_config.secrets.encrypt,
                 reasoning_enabled: root_config.runtime.reasoning_enabled,
+                cassette: root_config.runtime.provider_cassette.clone(),
             },
         )
         .with_parent_tools(parent_tools)
-        .with_multimodal_config(root_config.multimodal.clone());
-        tool_arcs.push(Arc::new(delegate_tool));
+        .with_multimodal_config(root_config.multimodal.clone())
+        .with_scope(memory, runtime, config.clone());
+        let registry = Arc::new(DelegationRegistry::new());
+        tool_arcs.push(Arc::new(
+            delegate_tool.with_background_registry(registry.clone()),
+        ));
+        tool_arcs.push(Arc::new(DelegateStatusTool::new(registry.clone())));
+        tool_arcs.push(Arc::new(DelegateResultTool::new(registry.clone())));
+        tool_arcs.push(Arc::new(DelegateCancelTool::new(registry)));
     }
 
     boxed_registry_from_arcs(tool_arcs)
@@ -353,7 +438,7 @@ mod tests {
     fn default_tools_has_expected_count() {
         let security = Arc::new(SecurityPolicy::default());
         let tools = default_tools(security);
-        assert_eq!(tools.len(), 4);
+        assert_eq!(tools.len(), 6);
     }
 
     #[test]
@@ -443,7 +528,9 @@ mod tests {
         assert!(names.contains(&"shell"));
         assert!(names.contains(&"file_read"));
         assert!(names.contains(&"file_write"));
+        assert!(names.contains(&"file_edit"));
         assert!(names.contains(&"glob_search"));
+        assert!(names.contains(&"content_search"));
     }
 
     #[test]
@@ -558,6 +645,11 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                memory: crate::config::DelegateMemoryMode::Shared,
+                memory_namespace: None,
+                autonomy: None,
+                allowed_commands: None,
+                workspace: None,
             },
         );
 
@@ -576,6 +668,9 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"delegate"));
+        assert!(names.contains(&"delegate_status"));
+        assert!(names.contains(&"delegate_result"));
+        assert!(names.contains(&"delegate_cancel"));
     }
 
     #[test]
@@ -608,5 +703,6 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"delegate"));
+        assert!(!names.contains(&"delegate_status"));
     }
 }
@@ -0,0 +1,558 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::traits::{Channel, SendMessage};
+use crate::config::Config;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Send a message to a recipient on any configured channel.
+///
+/// Recipients are deny-by-default: a named contact from
+/// `[send_message.contacts]`, an entry in `send_message.allowed_recipients`,
+/// or an explicit (non-`*`) entry in the target channel's own allowlist.
+pub struct SendMessageTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+    /// Fixed channel handles; `None` uses the channels running in this
+    /// process at send time.
+    channels: Option<Vec<Arc<dyn Channel>>>,
+}
+
+/// Where a message goes after contact lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    channel: String,
+    to: String,
+    thread: Option<String>,
+    contact: bool,
+}
+
+impl SendMessageTool {
+    pub fn new(
+        config: Arc<Config>,
+        security: Arc<SecurityPolicy>,
+        channels: Vec<Arc<dyn Channel>>,
+    ) -> Self {
+        Self {
+            config,
+            security,
+            channels: Some(channels),
+        }
+    }
+
+    /// A tool that sends through the live handles of the channel pipeline
+    /// ([`crate::channels::running_channels`]), so it never opens a second
+    /// connection next to the daemon's.
+    pub fn running(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            security,
+            channels: None,
+        }
+    }
+
+    fn resolve_target(&self, to: &str, channel: Option<&str>) -> Result<Target, String> {
+        if let Some(contact) = self.config.send_message.contacts.get(to) {
+            if channel.is_none_or(|c| c.eq_ignore_ascii_case(&contact.channel)) {
+                return Ok(Target {
+                    channel: contact.channel.to_ascii_lowercase(),
+                    to: contact.to.clone(),
+                    thread: contact.thread.clone(),
+                    contact: true,
+                });
+            }
+        }
+        let channel =
+            channel.ok_or_else(|| format!("Missing 'channel': '{to}' is not a named contact"))?;
+        Ok(Target {
+            channel: channel.to_ascii_lowercase(),
+            to: to.to_string(),
+            thread: None,
+            contact: false,
+        })
+    }
+
+    fn is_recipient_allowed(&self, target: &Target) -> bool {
+        if target.contact {
+            return true;
+        }
+        let outbound = self
+            .config
+            .send_message
+            .allowed_recipients
+            .iter()
+            .filter_map(|entry| entry.split_once(':'))
+            .any(|(channel, to)| {
+                (channel == "*" || channel.eq_ignore_ascii_case(&target.channel))
+                    && (to == "*" || to == target.to)
+            });
+        // A `*` channel allowlist lets anyone talk to the bot; it is not
+        // permission to message anyone.
+        outbound
+            || channel_allowlist(&self.config, &target.channel)
+                .iter()
+                .any(|allowed| allowed != "*" && *allowed == target.to)
+    }
+
+    /// Attachment text for `channel`: Telegram media markers, or links for
+    /// the other channels, which cannot upload files.
+    async fn attachment_text(
+        &self,
+        channel: &str,
+        attachments: &[String],
+    ) -> Result<String, String> {
+        let mut text = String::new();
+        for attachment in attachments {
+            let attachment = attachment.trim();
+            let is_url = attachment.starts_with("https://") || attachment.starts_with("http://");
+            let target = if is_url {
+                attachment.to_string()
+            } else {
+                if channel != "telegram" {
+                    return Err(format!(
+                        "Channel '{channel}' cannot upload files; attach an http(s) URL instead of '{attachment}'"
+                    ));
+                }
+                if !self.security.is_path_allowed(attachment) {
+                    return Err(format!("Path not allowed by security policy: {attachment}"));
+                }
+                let resolved =
+                    tokio::fs::canonicalize(self.security.workspace_dir.join(attachment))
+                        .await
+                        .map_err(|e| format!("Failed to resolve attachment '{attachment}': {e}"))?;
+                if !self.security.is_resolved_path_allowed(&resolved) {
+                    return Err(format!(
+                        "Resolved path escapes workspace: {}",
+                        resolved.display()
+                    ));
+                }
+                resolved.display().to_string()
+            };
+            text.push('\n');
+            if channel == "telegram" {
+                let _ = write!(text, "[{}:{target}]", telegram_marker(&target));
+            } else {
+                text.push_str(&target);
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// The allowlist a channel applies to inbound senders.
+fn channel_allowlist<'a>(config: &'a Config, channel: &str) -> &'a [String] {
+    let channels = &config.channels_config;
+    let list = match channel {
+        "telegram" => channels.telegram.as_ref().map(|c| &c.allowed_users),
+        "discord" => channels.discord.as_ref().map(|c| &c.allowed_users),
+        "slack" => channels.slack.as_ref().map(|c| &c.allowed_users),
+        "mattermost" => channels.mattermost.as_ref().map(|c| &c.allowed_users),
+        "imessage" => channels.imessage.as_ref().map(|c| &c.allowed_contacts),
+        "matrix" => channels.matrix.as_ref().map(|c| &c.allowed_users),
+        "signal" => channels.signal.as_ref().map(|c| &c.allowed_from),
+        "whatsapp" => channels.whatsapp.as_ref().map(|c| &c.allowed_numbers),
+        "linq" => channels.linq.as_ref().map(|c| &c.allowed_senders),
+        "nextcloud_talk" => channels.nextcloud_talk.as_ref().map(|c| &c.allowed_users),
+        "email" => channels.email.as_ref().map(|c| &c.allowed_senders),
+        "irc" => channels.irc.as_ref().map(|c| &c.allowed_users),
+        "lark" => channels.lark.as_ref().map(|c| &c.allowed_users),
+        "dingtalk" => channels.dingtalk.as_ref().map(|c| &c.allowed_users),
+        "qq" => channels.qq.as_ref().map(|c| &c.allowed_users),
+        _ => None,
+    };
+    list.map_or(&[], Vec::as_slice)
+}
+
+/// Telegram media marker kind for `target`, by file extension.
+fn telegram_marker(target: &str) -> &'static str {
+    let extension = Path::new(target.split(['?', '#']).next().unwrap_or(target))
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" => "IMAGE",
+        "mp4" | "mov" | "mkv" | "avi" | "webm" => "VIDEO",
+        "mp3" | "m4a" | "wav" | "flac" => "AUDIO",
+        _ => "DOCUMENT",
+    }
+}
+
+#[async_trait]
+impl Tool for SendMessageTool {
+    fn name(&self) -> &str {
+        "send_message"
+    }
+
+    fn description(&self) -> &str {
+        "Send a message to a person, group or channel on any configured channel (Slack, \
+         Telegram, Discord, email, ...). Address a named contact with to='<name>', or give \
+         channel and a platform recipient ID. Supports threads. Attachments are uploaded on \
+         Telegram; other channels receive http(s) attachment URLs as links. Requires \
+         approved=true in supervised mode."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let mut contacts: Vec<&str> = self
+            .config
+            .send_message
+            .contacts
+            .keys()
+            .map(String::as_str)
+            .collect();
+        contacts.sort_unstable();
+        let contacts = if contacts.is_empty() {
+            "none".to_string()
+        } else {
+            contacts.join(", ")
+        };
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "type": "string",
+                    "description": format!(
+                        "Named contact or platform recipient (chat ID, channel ID, phone number, address). Contacts: {contacts}"
+                    )
+                },
+                "channel": {
+                    "type": "string",
+                    "description": "Channel to send through, e.g. 'slack', 'telegram'. Required unless 'to' is a contact"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Message text"
+                },
+                "thread": {
+                    "type": "string",
+                    "description": "Platform thread to reply in (e.g. Slack thread ts)"
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "Subject line, for email"
+                },
+                "attachments": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "http(s) URLs, sent as links except on Telegram. Workspace file paths are uploaded on Telegram only"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true to explicitly approve sending in supervised mode",
+                    "default": false
+                }
+            },
+            "required": ["to", "message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let Some(to) = str_arg("to") else {
+            return Ok(ToolResult::err("Missing 'to' parameter"));
+        };
+        let Some(message) = str_arg("message") else {
+            return Ok(ToolResult::err("Missing 'message' parameter"));
+        };
+        let attachments: Vec<String> = match args.get("attachments") {
+            Some(value) => match serde_json::from_value(value.clone()) {
+                Ok(attachments) => attachments,
+                Err(e) => return Ok(ToolResult::err(format!("Invalid 'attachments': {e}"))),
+            },
+            None => Vec::new(),
+        };
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let mut target = match self.resolve_target(to, str_arg("channel")) {
+            Ok(target) => target,
+            Err(e) => return Ok(ToolResult::err(e)),
+        };
+        if let Some(thread) = str_arg("thread") {
+            target.thread = Some(thread.to_string());
+        }
+
+        let channels = match &self.channels {
+            Some(channels) => channels.clone(),
+            None => crate::channels::running_channels(),
+        };
+        let Some(channel) = channels
+            .iter()
+            .find(|c| c.name().eq_ignore_ascii_case(&target.channel))
+        else {
+            if channels.is_empty() {
+                return Ok(ToolResult::err(
+                    "No channels are running; start them with `zeroclaw daemon` or \
+                     `zeroclaw channel start`",
+                ));
+            }
+            let mut available: Vec<&str> = channels.iter().map(|c| c.name()).collect();
+            available.sort_unstable();
+            return Ok(ToolResult::err(format!(
+                "Channel '{}' is not running (available: {})",
+                target.channel,
+                available.join(", ")
+            )));
+        };
+
+        if !self.is_recipient_allowed(&target) {
+            return Ok(ToolResult::err(format!(
+                "Recipient '{}' on {} is not allowed: add a [send_message.contacts] entry or \
+                 \"{}:{}\" to send_message.allowed_recipients",
+                target.to, target.channel, target.channel, target.to
+            )));
+        }
+
+        if self.security.effective_autonomy() == AutonomyLevel::Supervised && !approved {
+            return Ok(ToolResult::err(
+                "Sending a message requires explicit approval (approved=true) in supervised mode",
+            ));
+        }
+
+        let attachment_text = match self.attachment_text(&target.channel, &attachments).await {
+            Ok(text) => text,
+            Err(e) => return Ok(ToolResult::err(e)),
+        };
+
+        if let Some(blocked) = super::enforce_mutation(&self.security, "send_message") {
+            return Ok(blocked);
+        }
+
+        let content = format!("{message}{attachment_text}");
+        let outgoing = match str_arg("subject") {
+            Some(subject) => SendMessage::with_subject(content, &target.to, subject),
+            None => SendMessage::new(content, &target.to),
+        }
+        .in_thread(target.thread.clone());
+
+        match channel.send(&outgoing).await {
+            Ok(()) => Ok(ToolResult::ok(format!(
+                "Sent to {} on {}{}",
+                if target.contact { to } else { &target.to },
+                target.channel,
+                target
+                    .thread
+                    .as_deref()
+                    .map(|t| format!(" (thread {t})"))
+                    .unwrap_or_default()
+            ))),
+            Err(e) => Ok(ToolResult::err(format!(
+                "Failed to send via {}: {e}",
+                target.channel
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use crate::config::{MessageContactConfig, TelegramConfig};
+    use parking_lot::Mutex;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct RecordingChannel {
+        sent: Mutex<Vec<SendMessage>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "telegram"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent.lock().push(message.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn setup(autonomy: AutonomyLevel) -> (TempDir, Arc<RecordingChannel>, SendMessageTool) {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.channels_config.telegram = Some(TelegramConfig {
+            bot_token: "token".into(),
+            allowed_users: vec!["*".into(), "111".into()],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+        });
+        config.send_message.enabled = true;
+        config.send_message.allowed_recipients = vec!["telegram:-100ops".into()];
+        config.send_message.contacts.insert(
+            "me".into(),
+            MessageContactConfig {
+                channel: "telegram".into(),
+                to: "999".into(),
+                thread: Some("7".into()),
+            },
+        );
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let channel = Arc::new(RecordingChannel::default());
+        let tool = SendMessageTool::new(
+            Arc::new(config),
+            security,
+            vec![channel.clone() as Arc<dyn Channel>],
+        );
+        (tmp, channel, tool)
+    }
+
+    #[test]
+    fn send_message_name_and_schema() {
+        let (_tmp, _channel, tool) = setup(AutonomyLevel::Full);
+        assert_eq!(tool.name(), "send_message");
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["required"], json!(["to", "message"]));
+        assert!(schema["properties"]["to"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("Contacts: me"));
+    }
+
+    #[tokio::test]
+    async fn sends_to_contacts_and_allowlisted_recipients_only() {
+        let (_tmp, channel, tool) = setup(AutonomyLevel::Full);
+
+        let result = tool
+            .execute(json!({ "to": "me", "message": "deploy done" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Sent to me on telegram (thread 7)");
+
+        for to in ["111", "-100ops"] {
+            let result = tool
+                .execute(json!({ "to": to, "channel": "Telegram", "message": "hi" }))
+                .await
+                .unwrap();
+            assert!(result.success, "{to}: {:?}", result.error);
+        }
+
+        // The channel's `*` allowlist entry does not open outbound sends.
+        let result = tool
+            .execute(json!({ "to": "222", "channel": "telegram", "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let result = tool
+            .execute(json!({ "to": "someone", "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Missing 'channel'"));
+
+        let result = tool
+            .execute(json!({ "to": "x", "channel": "slack", "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(result
+            .error
+            .unwrap()
+            .contains("'slack' is not running (available: telegram)"));
+
+        let sent = channel.sent.lock();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0].recipient, "999");
+        assert_eq!(sent[0].thread_ts.as_deref(), Some("7"));
+        assert_eq!(sent[0].content, "deploy done");
+    }
+
+    #[tokio::test]
+    async fn supervised_mode_requires_approval() {
+        let (_tmp, channel, tool) = setup(AutonomyLevel::Supervised);
+        let args = json!({ "to": "me", "message": "hi" });
+
+        let result = tool.execute(args.clone()).await.unwrap();
+        assert!(result.error.unwrap().contains("approved=true"));
+        assert!(channel.sent.lock().is_empty());
+
+        let mut approved = args;
+        approved["approved"] = json!(true);
+        let result = tool.execute(approved).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let (_tmp, _channel, read_only) = setup(AutonomyLevel::ReadOnly);
+        let result = read_only
+            .execute(json!({ "to": "me", "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn running_tool_needs_a_channel_pipeline() {
+        let (tmp, _channel, _tool) = setup(AutonomyLevel::Full);
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let tool = SendMessageTool::running(Arc::new(config), Arc::new(SecurityPolicy::default()));
+        let result = tool
+            .execute(json!({ "to": "x", "channel": "telegram", "message": "hi" }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("No channels are running"));
+    }
+
+    #[tokio::test]
+    async fn attachments_become_telegram_markers() {
+        let (tmp, channel, tool) = setup(AutonomyLevel::Full);
+        std::fs::write(tmp.path().join("report.pdf"), b"%PDF").unwrap();
+
+        let result = tool
+            .execute(json!({
+                "to": "me",
+                "message": "report",
+                "attachments": ["report.pdf", "https://example.com/chart.png"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let resolved = tmp.path().canonicalize().unwrap().join("report.pdf");
+        assert_eq!(
+            channel.sent.lock()[0].content,
+            format!(
+                "report\n[DOCUMENT:{}]\n[IMAGE:https://example.com/chart.png]",
+                resolved.display()
+            )
+        );
+
+        let result = tool
+            .execute(json!({
+                "to": "me",
+                "message": "x",
+                "attachments": ["../etc/passwd"]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
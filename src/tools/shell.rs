@@ -405,6 +405,7 @@ mod tests {
     // ── §5.2 Shell timeout enforcement tests ─────────────────
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn shell_timeout_constant_is_reasonable() {
         assert!(
             SHELL_TIMEOUT_SECS >= 120 && SHELL_TIMEOUT_SECS <= 600,
@@ -12,12 +12,20 @@ pub struct ToolResult {
 impl ToolResult {
     /// Create a successful result with the given output.
     pub fn ok(output: impl Into<String>) -> Self {
-        Self { success: true, output: output.into(), error: None }
+        Self {
+            success: true,
+            output: output.into(),
+            error: None,
+        }
     }
 
     /// Create a failed result with the given error message.
     pub fn err(message: impl Into<String>) -> Self {
-        Self { success: false, output: String::new(), error: Some(message.into()) }
+        Self {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
     }
 }
 
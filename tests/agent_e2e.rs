@@ -14,10 +14,11 @@ use std::sync::{Arc, Mutex};
 use zeroclaw::agent::agent::Agent;
 use zeroclaw::agent::dispatcher::{NativeToolDispatcher, XmlToolDispatcher};
 use zeroclaw::agent::memory_loader::MemoryLoader;
-use zeroclaw::config::MemoryConfig;
+use zeroclaw::config::{CassetteMatching, MemoryConfig};
 use zeroclaw::memory;
 use zeroclaw::memory::Memory;
 use zeroclaw::observability::{NoopObserver, Observer};
+use zeroclaw::providers::cassette;
 use zeroclaw::providers::traits::ChatMessage;
 use zeroclaw::providers::{
     ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderRuntimeOptions, ToolCall,
@@ -64,6 +65,8 @@ impl Provider for MockProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -188,6 +191,8 @@ impl Provider for RecordingProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -235,6 +240,8 @@ fn text_response(text: &str) -> ChatResponse {
     ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     }
 }
 
@@ -242,6 +249,8 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
     ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
+        reasoning: None,
+        usage: None,
     }
 }
 
@@ -365,6 +374,8 @@ async fn e2e_xml_dispatcher_tool_call() {
                     .into(),
             ),
             tool_calls: vec![],
+            reasoning: None,
+            usage: None,
         },
         text_response("XML tool executed"),
     ]));
@@ -642,6 +653,40 @@ async fn e2e_empty_memory_context_passthrough() {
     );
 }
 
+// ═════════════════════════════════════════════════════════════════════════════
+// Cassette record/replay
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Records a tool-call session through `RecordingProvider`, then replays the
+/// cassette through a fresh agent with no live provider behind it.
+#[tokio::test]
+async fn e2e_cassette_record_then_replay() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("session.json");
+    let live = MockProvider::new(vec![
+        tool_response(vec![ToolCall {
+            id: "tc1".into(),
+            name: "counter".into(),
+            arguments: "{}".into(),
+        }]),
+        text_response("Counted once"),
+    ]);
+
+    let recorder = cassette::RecordingProvider::new(Box::new(live), &path).unwrap();
+    let (counting_tool, recorded_count) = CountingTool::new();
+    let mut agent = build_agent(Box::new(recorder), vec![Box::new(counting_tool)]);
+    let recorded = agent.turn("count please").await.unwrap();
+    assert_eq!(*recorded_count.lock().unwrap(), 1);
+
+    let replay = cassette::ReplayProvider::from_file(&path, CassetteMatching::Lenient).unwrap();
+    let (counting_tool, replayed_count) = CountingTool::new();
+    let mut agent = build_agent(Box::new(replay), vec![Box::new(counting_tool)]);
+    let replayed = agent.turn("count please").await.unwrap();
+
+    assert_eq!(replayed, recorded);
+    assert_eq!(*replayed_count.lock().unwrap(), 1);
+}
+
 // ═════════════════════════════════════════════════════════════════════════════
 // Live integration test — real OpenAI Codex API (requires credentials)
 // ═════════════════════════════════════════════════════════════════════════════
@@ -59,6 +59,8 @@ impl Provider for MockProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                reasoning: None,
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -179,6 +181,8 @@ fn text_response(text: &str) -> ChatResponse {
     ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     }
 }
 
@@ -186,6 +190,8 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
     ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
+        reasoning: None,
+        usage: None,
     }
 }
 
@@ -353,6 +359,8 @@ async fn agent_handles_empty_provider_response() {
     let provider = Box::new(MockProvider::new(vec![ChatResponse {
         text: Some(String::new()),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);
@@ -366,6 +374,8 @@ async fn agent_handles_none_text_response() {
     let provider = Box::new(MockProvider::new(vec![ChatResponse {
         text: None,
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);
@@ -153,6 +153,8 @@ fn chat_response_text_only() {
     let resp = ChatResponse {
         text: Some("Hello world".into()),
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     };
 
     assert_eq!(resp.text_or_empty(), "Hello world");
@@ -168,6 +170,8 @@ fn chat_response_with_tool_calls() {
             name: "echo".into(),
             arguments: "{}".into(),
         }],
+        reasoning: None,
+        usage: None,
     };
 
     assert!(resp.has_tool_calls());
@@ -180,6 +184,8 @@ fn chat_response_text_or_empty_handles_none() {
     let resp = ChatResponse {
         text: None,
         tool_calls: vec![],
+        reasoning: None,
+        usage: None,
     };
 
     assert_eq!(resp.text_or_empty(), "");
@@ -201,6 +207,8 @@ fn chat_response_multiple_tool_calls() {
                 arguments: r#"{"path": "test.txt"}"#.into(),
             },
         ],
+        reasoning: None,
+        usage: None,
     };
 
     assert!(resp.has_tool_calls());